@@ -21,6 +21,12 @@ class _SymbolsModule:
         max_read_bytes: int = 10_485_760,
         max_file_size: int = 104_857_600,
     ) -> tuple[list[str], list[str], list[str], list[str], list[str]]: ...
+    def list_symbols_detailed(
+        self,
+        path: str,
+        max_read_bytes: int = 10_485_760,
+        max_file_size: int = 104_857_600,
+    ) -> list[SymbolInfo]: ...
     def imphash(
         self,
         path: str,
@@ -40,6 +46,32 @@ class _SymbolsModule:
         max_file_size: int = 104_857_600,
     ) -> dict[str, object]: ...
 
+class SymbolType(enum.Enum):
+    Function: SymbolType
+    Data: SymbolType
+    Section: SymbolType
+    File: SymbolType
+    Object: SymbolType
+    Common: SymbolType
+    TLS: SymbolType
+    Unknown: SymbolType
+
+class SymbolBinding(enum.Enum):
+    Local: SymbolBinding
+    Global: SymbolBinding
+    Weak: SymbolBinding
+    Unknown: SymbolBinding
+
+class SymbolInfo:
+    name: str
+    address: int
+    size: int
+    symbol_type: SymbolType
+    binding: SymbolBinding
+    section: Optional[str]
+    is_imported: bool
+    is_exported: bool
+
 symbols: _SymbolsModule
 
 # ============================================================================
@@ -867,6 +899,7 @@ class BasicBlock:
     successor_ids: List[str]
     predecessor_ids: List[str]
     relationships_known: bool
+    structural_hash: Optional[str]
 
     def __init__(
         self,
@@ -1021,6 +1054,19 @@ class ControlFlowGraph:
     def compute_stats(self) -> ControlFlowGraphStats: ...
     def block_count(self) -> int: ...
     def edge_count(self) -> int: ...
+    def dominators(self, entry: str) -> Dict[str, str]: ...
+    def immediate_dominator(self, block_id: str) -> Optional[str]: ...
+    def natural_loops(self) -> List[Loop]: ...
+    def to_dot(self) -> str: ...
+
+class Loop:
+    """Natural loop in a control flow graph."""
+
+    header: str
+    latches: List[str]
+    blocks: List[str]
+
+    def contains(self, block_id: str) -> bool: ...
 
 class ControlFlowGraphStats:
     """Statistics for control flow graph."""
@@ -1078,6 +1124,10 @@ class CallGraph:
     def compute_stats(self) -> CallGraphStats: ...
     def function_count(self) -> int: ...
     def edge_count(self) -> int: ...
+    def strongly_connected_components(self) -> List[List[str]]: ...
+    def topo_order(self) -> List[str]: ...
+    def to_dot(self) -> str: ...
+    def to_graphml(self) -> str: ...
 
 class CallGraphStats:
     """Statistics for call graph."""
@@ -1454,6 +1504,7 @@ __all__ = [
     "ControlFlowEdge",
     "ControlFlowGraph",
     "ControlFlowGraphStats",
+    "Loop",
     "CallGraphEdge",
     "CallGraph",
     "CallGraphStats",
@@ -250,7 +250,10 @@ class TriageFormatter(BaseFormatter):
             top = art.verdicts[0]
             format_text = Text()
             format_text.append(f"{str(top.format).upper()}\n", style="bold yellow")
-            format_text.append(f"{top.arch} {top.bits}-bit\n", style="cyan")
+            if str(top.format) == "Unknown" and getattr(top, "label", None):
+                format_text.append(f"{top.label} ({top.mime})\n", style="cyan")
+            else:
+                format_text.append(f"{top.arch} {top.bits}-bit\n", style="cyan")
             conf_color = (
                 "green"
                 if top.confidence >= 0.9
@@ -548,6 +551,8 @@ class TriageFormatter(BaseFormatter):
                 f"format={top.format} arch={top.arch} {top.bits}-bit "
                 f"endianness={top.endianness} confidence={top.confidence:.2f}"
             )
+            if getattr(top, "mime", None):
+                lines.append(f"mime: {top.mime} label={top.label}")
 
         # Symbols
         symbols = getattr(art, "symbols", None)
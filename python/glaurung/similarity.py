@@ -13,6 +13,9 @@ ctph_similarity = _native.similarity.ctph_similarity
 ctph_recommended_params = _native.similarity.ctph_recommended_params
 ctph_pairwise_matrix = _native.similarity.ctph_pairwise_matrix
 ctph_top_k = _native.similarity.ctph_top_k
+compare_bytes = _native.similarity.compare_bytes
+compare_files = _native.similarity.compare_files
+CtphIndex = _native.similarity.CtphIndex
 
 
 def cluster_single_linkage(
@@ -19,6 +19,7 @@ EntropySummary = _native.triage.EntropySummary
 DetectedString = _native.triage.DetectedString
 StringsSummary = _native.triage.StringsSummary
 IocSample = _native.triage.IocSample
+UrlComponents = _native.triage.UrlComponents
 try:
     SymbolSummary = _native.triage.SymbolSummary  # type: ignore[attr-defined]
 except AttributeError:  # Backward-compatible with older native modules
@@ -26,6 +27,8 @@ except AttributeError:  # Backward-compatible with older native modules
 PackerMatch = _native.triage.PackerMatch
 ContainerChild = _native.triage.ContainerChild
 Budgets = _native.triage.Budgets
+PhaseTimings = _native.triage.PhaseTimings
+Indicators = _native.triage.Indicators
 TriageVerdict = _native.triage.TriageVerdict
 TriagedArtifact = _native.triage.TriagedArtifact
 # Overlay detection types
@@ -373,10 +376,13 @@ __all__ = [
     "DetectedString",
     "StringsSummary",
     "IocSample",
+    "UrlComponents",
     "SymbolSummary",
     "PackerMatch",
     "ContainerChild",
     "Budgets",
+    "PhaseTimings",
+    "Indicators",
     "TriageVerdict",
     "TriagedArtifact",
     "OverlayAnalysis",
@@ -35,10 +35,23 @@ class TriageErrorKind:
     def __str__(self) -> str: ...
     def __repr__(self) -> str: ...
 
+class Severity:
+    Info: Severity
+    Warning: Severity
+    Error: Severity
+    def __str__(self) -> str: ...
+    def __repr__(self) -> str: ...
+
 class TriageError:
     kind: TriageErrorKind
     message: Optional[str]
-    def __init__(self, kind: TriageErrorKind, message: Optional[str] = ...) -> None: ...
+    severity: Severity
+    def __init__(
+        self,
+        kind: TriageErrorKind,
+        message: Optional[str] = ...,
+        severity: Optional[Severity] = ...,
+    ) -> None: ...
     def __repr__(self) -> str: ...
 
 class ConfidenceSignal:
@@ -88,12 +101,18 @@ class EntropyAnomaly:
     to: float  # Also available for backward compatibility
     delta: float
 
+class EntropyOutlier:
+    index: int
+    entropy: float
+    z_score: float
+
 class PackedIndicators:
     has_low_entropy_header: bool
     has_high_entropy_body: bool
     entropy_cliff: Optional[int]
     verdict: float
 
+def detect_format(data: bytes) -> tuple[Format, float]: ...
 def entropy_of_bytes(data: bytes) -> float: ...
 def compute_entropy(
     data: bytes,
@@ -110,12 +129,14 @@ def analyze_entropy_bytes(
     max_windows: int = ...,
     header_size: int = ...,
 ) -> EntropyAnalysis: ...
+def compare_entropy_profiles(a: List[float], b: List[float]) -> float: ...
 
 class EntropyAnalysis:
     summary: EntropySummary
     classification: EntropyClass
     packed_indicators: PackedIndicators
     anomalies: list[EntropyAnomaly]
+    outliers: list[EntropyOutlier]
     classification_kind: str
     def __init__(self) -> None: ...
 
@@ -142,6 +163,62 @@ class SymbolSummary:
     relocations_present: Optional[bool]
     rpaths: Optional[List[str]]
     runpaths: Optional[List[str]]
+    wx_sections: Optional[List[str]]
+    def is_stripped(self) -> bool: ...
+
+class SourceLanguage:
+    C: SourceLanguage
+    Cpp: SourceLanguage
+    Rust: SourceLanguage
+    Go: SourceLanguage
+    Swift: SourceLanguage
+    ObjectiveC: SourceLanguage
+    D: SourceLanguage
+    Fortran: SourceLanguage
+    Pascal: SourceLanguage
+    Ada: SourceLanguage
+    Zig: SourceLanguage
+    Nim: SourceLanguage
+    Crystal: SourceLanguage
+    CSharp: SourceLanguage
+    Java: SourceLanguage
+    Python: SourceLanguage
+    JavaScript: SourceLanguage
+    TypeScript: SourceLanguage
+    Kotlin: SourceLanguage
+    Scala: SourceLanguage
+    Unknown: SourceLanguage
+
+class CompilerVendor:
+    Gnu: CompilerVendor
+    Llvm: CompilerVendor
+    Microsoft: CompilerVendor
+    Intel: CompilerVendor
+    Rustc: CompilerVendor
+    Go: CompilerVendor
+    Swift: CompilerVendor
+    MinGW: CompilerVendor
+    Borland: CompilerVendor
+    Watcom: CompilerVendor
+    Tcc: CompilerVendor
+    Pcc: CompilerVendor
+    Unknown: CompilerVendor
+
+class CompilerInfo:
+    vendor: CompilerVendor
+    product_name: str
+    version_major: Optional[int]
+    version_minor: Optional[int]
+    version_patch: Optional[int]
+    build_number: Optional[int]
+    target_triple: Optional[str]
+
+class LanguageDetectionResult:
+    language: SourceLanguage
+    compiler: Optional[CompilerInfo]
+    confidence: float
+    alternative_languages: List[tuple[SourceLanguage, float]]
+    evidence_summary: str
 
 class SimilaritySummary:
     imphash: Optional[str]
@@ -161,6 +238,8 @@ class OverlayFormat:
     AppImage: OverlayFormat
     SquashFS: OverlayFormat
     ISO9660: OverlayFormat
+    PE: OverlayFormat
+    ELF: OverlayFormat
     Unknown: OverlayFormat
     def __str__(self) -> str: ...
     def __repr__(self) -> str: ...
@@ -171,6 +250,7 @@ class OverlayAnalysis:
     entropy: float
     header: List[int]
     detected_format: Optional[OverlayFormat]
+    payload: Optional[str]
     has_signature: bool
     is_archive: bool
     sha256: str
@@ -204,6 +284,8 @@ class DetectedString:
     script: Optional[str]
     confidence: Optional[float]
     offset: Optional[int]
+    truncated: bool
+    count: int
     def __init__(
         self,
         text: str,
@@ -218,6 +300,14 @@ class IocSample:
     kind: str
     text: str
     offset: Optional[int]
+    truncated: bool
+    url: Optional[UrlComponents]
+
+class UrlComponents:
+    scheme: str
+    host: Optional[str]
+    port: Optional[int]
+    path: str
 
 class PackerMatch:
     name: str
@@ -235,6 +325,10 @@ class ContainerChild:
     type_name: str
     offset: int
     size: int
+    in_decompressed_stream: bool
+    name: Optional[str]
+    entropy: Optional[float]
+    ctph: Optional[str]
     def __init__(self, type_name: str, offset: int, size: int) -> None: ...
 
 class ContainerMetadata:
@@ -260,6 +354,26 @@ class Budgets:
     hit_byte_limit: bool
     def __init__(self, bytes_read: int, time_ms: int, recursion_depth: int) -> None: ...
 
+class Indicators:
+    is_packed: bool
+    is_signed: bool
+    is_stripped: bool
+    has_overlay: bool
+    high_entropy: bool
+    suspicious_imports: int
+    wx_sections: int
+
+class PhaseTimings:
+    sniff_ms: int
+    headers_ms: int
+    entropy_ms: int
+    strings_ms: int
+    parsers_ms: int
+    disasm_ms: int
+    format_ms: int
+    symbols_ms: int
+    scoring_ms: int
+
 # Language detection helpers
 def detect_language(
     text: str, min_size: int = 4, min_conf: float = 0.5, agree_conf: float = 0.4
@@ -274,13 +388,69 @@ def detect_languages(
     texts: list[str], min_size: int = 4, min_conf: float = 0.5, agree_conf: float = 0.4
 ) -> list[tuple[Optional[str], Optional[str], Optional[float]]]: ...
 
+class SnifferConfig:
+    """Content/extension sniffer configuration."""
+
+    extension_overrides: dict[str, str]
+    mismatch_severity: Severity
+    def __init__(self) -> None: ...
+    def add_magic(
+        self, label: str, magic: bytes, offset: int = 0, mime: Optional[str] = None
+    ) -> None: ...
+
+class OverlayConfig:
+    """Overlay payload classification configuration."""
+
+    marker_scan_bytes: int
+    @property
+    def signatures(self) -> list[tuple[str, bytes, int]]: ...
+    def __init__(self) -> None: ...
+    def add_signature(self, label: str, magic: bytes, offset: int = 0) -> None: ...
+
+class InputKind:
+    File: InputKind
+    MemoryDump: InputKind
+    Shellcode: InputKind
+
 class TriageConfig:
     """Configuration wrapper used by analyze_* to control behavior."""
     def __init__(self) -> None: ...
+    @staticmethod
+    def fast() -> TriageConfig: ...
+    @staticmethod
+    def balanced() -> TriageConfig: ...
+    @staticmethod
+    def deep() -> TriageConfig: ...
+    @staticmethod
+    def from_env() -> TriageConfig: ...
     @property
     def packers(self) -> PackerConfig: ...
     @packers.setter
     def packers(self, cfg: PackerConfig) -> None: ...
+    @property
+    def sniffer(self) -> SnifferConfig: ...
+    @sniffer.setter
+    def sniffer(self, cfg: SnifferConfig) -> None: ...
+    @property
+    def overlay(self) -> OverlayConfig: ...
+    @overlay.setter
+    def overlay(self, cfg: OverlayConfig) -> None: ...
+    @property
+    def known_good_sha256(self) -> set[str]: ...
+    @known_good_sha256.setter
+    def known_good_sha256(self, digests: set[str]) -> None: ...
+    @property
+    def hash_full_file(self) -> bool: ...
+    @hash_full_file.setter
+    def hash_full_file(self, enabled: bool) -> None: ...
+    @property
+    def input_kind(self) -> InputKind: ...
+    @input_kind.setter
+    def input_kind(self, kind: InputKind) -> None: ...
+    @property
+    def max_time_ms(self) -> Optional[int]: ...
+    @max_time_ms.setter
+    def max_time_ms(self, ms: Optional[int]) -> None: ...
 
 class TriageVerdict:
     from glaurung import Format, Arch, Endianness
@@ -291,6 +461,8 @@ class TriageVerdict:
     endianness: Endianness
     confidence: float
     signals: Optional[List[ConfidenceSignal]]
+    mime: Optional[str]
+    label: Optional[str]
     def __init__(
         self,
         format: Format,
@@ -299,6 +471,8 @@ class TriageVerdict:
         endianness: Endianness,
         confidence: float,
         signals: Optional[List[ConfidenceSignal]] = ...,
+        mime: Optional[str] = ...,
+        label: Optional[str] = ...,
     ) -> None: ...
 
 class TriagedArtifact:
@@ -312,6 +486,7 @@ class TriagedArtifact:
     entropy_analysis: Optional[EntropyAnalysis]
     strings: Optional[StringsSummary]
     symbols: Optional[SymbolSummary]
+    language: Optional[LanguageDetectionResult]
     similarity: Optional[SimilaritySummary]
     packers: Optional[List[PackerMatch]]
     containers: Optional[List[ContainerChild]]
@@ -319,6 +494,9 @@ class TriagedArtifact:
     parse_status: Optional[List[ParserResult]]
     budgets: Optional[Budgets]
     errors: Optional[List[TriageError]]
+    phase_timings: Optional[PhaseTimings]
+    known_good: bool
+    source_range: Optional[tuple[int, int]]
     def __init__(
         self,
         id: str,
@@ -339,9 +517,14 @@ class TriagedArtifact:
         errors: Optional[list[TriageError]] = ...,
     ) -> None: ...
     def to_json(self) -> str: ...
+    def canonical_json(self) -> str: ...
     @staticmethod
     def from_json(json_str: str) -> TriagedArtifact: ...
     def ctph_similarity(self, other: TriagedArtifact) -> Optional[float]: ...
+    def errors_at_least(self, min: Severity) -> List[TriageError]: ...
+    def has_fatal_errors(self) -> bool: ...
+    def is_stripped(self) -> Optional[bool]: ...
+    def indicators(self) -> Indicators: ...
 
 # Note: symbols API is now exposed at top-level: glaurung.symbols
 
@@ -13,3 +13,5 @@ cargo fmt --all -- --check
 # Lint with clippy and deny warnings
 cargo clippy --all-targets --all-features -- -D warnings
 
+# The default build without instruction decoding must stay warning-free too
+cargo clippy --all-targets --no-default-features --features triage-core,lang-detect -- -D warnings
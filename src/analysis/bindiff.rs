@@ -0,0 +1,187 @@
+//! Structural function matching between two binaries.
+//!
+//! [`hash_blocks`] stamps every basic block with a hash of its normalized
+//! instruction sequence (see [`crate::similarity::code`]), so a block that
+//! only moved or had its constants relocated keeps its hash. [`match_functions`]
+//! then pairs functions across two recovered CFGs by the overlap of their
+//! block-hash multisets, assigning greedily from the best score down so
+//! each function is matched at most once.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::core::function::Function;
+#[cfg(feature = "disasm")]
+use crate::{
+    analysis::memory::MemoryView,
+    core::binary::{Arch, Endianness},
+    similarity::code,
+};
+
+/// Pairs scoring below this are not reported.
+pub const MIN_MATCH_SCORE: f64 = 0.5;
+
+/// Block hashes shared by more functions than this (`ret`-only blocks,
+/// thunks) do not propose candidate pairs on their own; they still count
+/// towards the score of pairs proposed by rarer blocks.
+const MAX_POSTINGS: usize = 64;
+
+/// Set `structural_hash` on every block of `functions` that decodes to at
+/// least one instruction; blocks that do not decode keep `None`.
+#[cfg(feature = "disasm")]
+pub fn hash_blocks(
+    view: &dyn MemoryView,
+    functions: &mut [Function],
+    arch: Arch,
+    endianness: Endianness,
+) {
+    let Some(backend) = code::backend_for(arch, endianness) else {
+        return;
+    };
+    for bb in functions.iter_mut().flat_map(|f| f.basic_blocks.iter_mut()) {
+        let lines = code::normalized_block(view, bb, &backend);
+        bb.structural_hash = (!lines.is_empty()).then(|| {
+            blake3::hash(lines.join("\n").as_bytes())
+                .to_hex()
+                .to_string()
+        });
+    }
+}
+
+/// Block-hash multiset of one function.
+struct Bag<'a> {
+    counts: BTreeMap<&'a str, usize>,
+    total: usize,
+}
+
+impl<'a> Bag<'a> {
+    fn of(f: &'a Function) -> Self {
+        let mut counts = BTreeMap::new();
+        for h in f
+            .basic_blocks
+            .iter()
+            .filter_map(|b| b.structural_hash.as_deref())
+        {
+            *counts.entry(h).or_insert(0) += 1;
+        }
+        let total = counts.values().sum();
+        Self { counts, total }
+    }
+
+    /// Dice coefficient of the two multisets.
+    fn similarity(&self, other: &Bag) -> f64 {
+        let shared: usize = self
+            .counts
+            .iter()
+            .map(|(h, &n)| n.min(other.counts.get(h).copied().unwrap_or(0)))
+            .sum();
+        2.0 * shared as f64 / (self.total + other.total) as f64
+    }
+}
+
+/// `(name in cfg_a, name in cfg_b, score)` for each matched function pair,
+/// best matches first. Scores are the Dice coefficient of the functions'
+/// block-hash multisets, in `[MIN_MATCH_SCORE, 1.0]`; functions without
+/// hashed blocks are never matched. Run [`hash_blocks`] on both sides first.
+pub fn match_functions(cfg_a: &[Function], cfg_b: &[Function]) -> Vec<(String, String, f64)> {
+    let bags_a: Vec<Bag> = cfg_a.iter().map(Bag::of).collect();
+    let bags_b: Vec<Bag> = cfg_b.iter().map(Bag::of).collect();
+
+    // Identical multisets always pair up; otherwise candidates come from
+    // block hashes rare enough to be distinctive.
+    let mut exact: HashMap<Vec<(&str, usize)>, Vec<usize>> = HashMap::new();
+    let mut postings: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (j, bag) in bags_b.iter().enumerate().filter(|(_, b)| b.total > 0) {
+        exact
+            .entry(bag.counts.iter().map(|(&h, &n)| (h, n)).collect())
+            .or_default()
+            .push(j);
+        for &h in bag.counts.keys() {
+            postings.entry(h).or_default().push(j);
+        }
+    }
+
+    let mut candidates: HashSet<(usize, usize)> = HashSet::new();
+    for (i, bag) in bags_a.iter().enumerate().filter(|(_, b)| b.total > 0) {
+        let key: Vec<(&str, usize)> = bag.counts.iter().map(|(&h, &n)| (h, n)).collect();
+        if let Some(js) = exact.get(&key) {
+            candidates.extend(js.iter().map(|&j| (i, j)));
+        }
+        for h in bag.counts.keys() {
+            match postings.get(h) {
+                Some(js) if js.len() <= MAX_POSTINGS => {
+                    candidates.extend(js.iter().map(|&j| (i, j)))
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let mut scored: Vec<(f64, usize, usize)> = candidates
+        .into_iter()
+        .map(|(i, j)| (bags_a[i].similarity(&bags_b[j]), i, j))
+        .filter(|&(score, _, _)| score >= MIN_MATCH_SCORE)
+        .collect();
+    scored.sort_by(|x, y| y.0.total_cmp(&x.0).then((x.1, x.2).cmp(&(y.1, y.2))));
+
+    let mut used_a = vec![false; cfg_a.len()];
+    let mut used_b = vec![false; cfg_b.len()];
+    let mut out = Vec::new();
+    for (score, i, j) in scored {
+        if used_a[i] || used_b[j] {
+            continue;
+        }
+        used_a[i] = true;
+        used_b[j] = true;
+        out.push((cfg_a[i].name.clone(), cfg_b[j].name.clone(), score));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::address::{Address, AddressKind};
+    use crate::core::basic_block::BasicBlock;
+    use crate::core::function::FunctionKind;
+
+    fn function(name: &str, hashes: &[&str]) -> Function {
+        let at = |v: u64| Address::new(AddressKind::VA, v, 64, None, None).unwrap();
+        let mut f = Function::new(name.into(), at(0x1000), FunctionKind::Normal).unwrap();
+        for (k, h) in hashes.iter().enumerate() {
+            let start = 0x1000 + 0x10 * k as u64;
+            let mut bb =
+                BasicBlock::new(format!("bb{k}"), at(start), at(start + 0x10), 1, None, None);
+            bb.structural_hash = Some(h.to_string());
+            f.basic_blocks.push(bb);
+        }
+        f
+    }
+
+    #[test]
+    fn pairs_functions_by_shared_blocks() {
+        let old = [
+            function("parse", &["p1", "p2", "p3", "ret"]),
+            function("check", &["c1", "c2", "ret"]),
+            function("gone", &["g1", "g2", "ret"]),
+            function("empty", &[]),
+        ];
+        // `check` gained a block in the patch; `parse` moved unchanged.
+        let new = [
+            function("sub_2000", &["c1", "c2", "c_fix", "ret"]),
+            function("sub_1000", &["ret", "p3", "p2", "p1"]),
+            function("added", &["a1", "ret"]),
+            function("empty", &[]),
+        ];
+        let matches = match_functions(&old, &new);
+        assert_eq!(matches.len(), 2);
+        assert_eq!(
+            (matches[0].0.as_str(), matches[0].1.as_str(), matches[0].2),
+            ("parse", "sub_1000", 1.0)
+        );
+        assert_eq!(
+            (matches[1].0.as_str(), matches[1].1.as_str()),
+            ("check", "sub_2000")
+        );
+        assert!((matches[1].2 - 6.0 / 7.0).abs() < 1e-9);
+    }
+}
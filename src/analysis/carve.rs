@@ -0,0 +1,311 @@
+//! Carving of executables embedded at arbitrary offsets.
+//!
+//! Droppers stack payloads in overlays, resources or plain concatenation.
+//! [`embedded_executables`] finds every PE, ELF and Mach-O header in a
+//! buffer, validates enough of it to reject stray magic bytes, and
+//! estimates the image's on-disk size from its section, segment or load
+//! command tables. Estimates come from headers alone, so an image cut
+//! short by the end of the buffer still reports its full declared size.
+
+use crate::core::binary::{Endianness, Format};
+use crate::formats::read::{u16_at, u32_at, u64_at};
+use crate::triage::search;
+
+/// Upper bound on images reported for one buffer.
+pub const MAX_CARVED: usize = 256;
+
+/// Upper bound on table entries walked when sizing one image.
+const MAX_TABLE_ENTRIES: usize = 4096;
+
+/// An executable image found inside a larger buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Carved {
+    /// Offset of the image's first header byte.
+    pub offset: usize,
+    pub format: Format,
+    /// Bytes spanned by headers, sections and signature data.
+    pub size_estimate: usize,
+}
+
+/// Every PE, ELF and Mach-O image in `data`, ordered by offset, including
+/// one at offset 0. At most [`MAX_CARVED`] images are returned.
+pub fn embedded_executables(data: &[u8]) -> Vec<Carved> {
+    let candidates: [(&[u8], Format); 6] = [
+        (b"MZ", Format::PE),
+        (b"\x7FELF", Format::ELF),
+        (b"\xCE\xFA\xED\xFE", Format::MachO),
+        (b"\xCF\xFA\xED\xFE", Format::MachO),
+        (b"\xFE\xED\xFA\xCE", Format::MachO),
+        (b"\xFE\xED\xFA\xCF", Format::MachO),
+    ];
+    let mut out: Vec<Carved> = candidates
+        .iter()
+        .flat_map(|&(magic, format)| {
+            search::find_all(data, magic)
+                .into_iter()
+                .map(move |off| (off, format))
+        })
+        .filter_map(|(offset, format)| {
+            let image = &data[offset..];
+            let size_estimate = match format {
+                Format::PE => pe_size(image),
+                Format::ELF => elf_size(image),
+                _ => macho_size(image),
+            }?;
+            Some(Carved {
+                offset,
+                format,
+                size_estimate,
+            })
+        })
+        .collect();
+    out.sort_unstable_by_key(|c| c.offset);
+    out.truncate(MAX_CARVED);
+    out
+}
+
+/// Size of a PE image: the furthest raw section end, the headers, or the
+/// Authenticode blob, whichever reaches furthest.
+fn pe_size(d: &[u8]) -> Option<usize> {
+    let le = Endianness::Little;
+    let pe = u32_at(d, 0x3C, le).ok()? as usize;
+    if d.get(pe..pe.checked_add(4)?)? != b"PE\0\0" {
+        return None;
+    }
+    let sections = u16_at(d, pe + 6, le).ok()? as usize;
+    let opt_size = u16_at(d, pe + 20, le).ok()? as usize;
+    let opt = pe + 24;
+    let mut end = u32_at(d, opt + 60, le).map_or(0, |h| h as usize);
+
+    let table = opt + opt_size;
+    for i in 0..sections.min(MAX_TABLE_ENTRIES) {
+        let sh = table + i * 40;
+        let (Ok(raw_size), Ok(raw_ptr)) = (u32_at(d, sh + 16, le), u32_at(d, sh + 20, le)) else {
+            break;
+        };
+        end = end.max((raw_ptr as usize).saturating_add(raw_size as usize));
+    }
+
+    // The security directory holds a file offset rather than an RVA.
+    let dirs = match u16_at(d, opt, le).ok()? {
+        0x10B => opt + 96,
+        0x20B => opt + 112,
+        _ => return Some(end.max(table + sections * 40)),
+    };
+    let count = u32_at(d, dirs - 4, le).unwrap_or(0);
+    if count > 4 {
+        if let (Ok(cert_off), Ok(cert_size)) = (u32_at(d, dirs + 32, le), u32_at(d, dirs + 36, le))
+        {
+            if cert_off != 0 {
+                end = end.max((cert_off as usize).saturating_add(cert_size as usize));
+            }
+        }
+    }
+    Some(end.max(table + sections * 40))
+}
+
+/// Size of an ELF image: the furthest end of the header, program
+/// headers, section headers and file-backed segments and sections.
+fn elf_size(d: &[u8]) -> Option<usize> {
+    let ident = d.get(..16)?;
+    let is64 = match ident[4] {
+        1 => false,
+        2 => true,
+        _ => return None,
+    };
+    let e = match ident[5] {
+        1 => Endianness::Little,
+        2 => Endianness::Big,
+        _ => return None,
+    };
+    if ident[6] != 1 {
+        return None;
+    }
+    let word = |off: usize| -> Option<usize> {
+        if is64 {
+            u64_at(d, off, e).ok().and_then(|v| usize::try_from(v).ok())
+        } else {
+            u32_at(d, off, e).ok().map(|v| v as usize)
+        }
+    };
+    let half = |off: usize| u16_at(d, off, e).ok().map(usize::from);
+    // e_ehsize is followed by the program and section header geometry.
+    let (phoff, shoff, geometry) = if is64 {
+        (word(0x20)?, word(0x28)?, 0x34)
+    } else {
+        (word(0x1C)?, word(0x20)?, 0x28)
+    };
+    let (phentsize, phnum) = (half(geometry + 2)?, half(geometry + 4)?);
+    let (shentsize, shnum) = (half(geometry + 6)?, half(geometry + 8)?);
+
+    let mut end = half(geometry)?.max(if is64 { 0x40 } else { 0x34 });
+    if phnum > 0 {
+        end = end.max(phoff.saturating_add(phnum * phentsize));
+    }
+    if shnum > 0 {
+        end = end.max(shoff.saturating_add(shnum * shentsize));
+    }
+    // p_offset/p_filesz and sh_type/sh_offset/sh_size positions per class.
+    let (p_off, p_size, sh_off, sh_size) = if is64 {
+        (0x08, 0x20, 0x18, 0x20)
+    } else {
+        (0x04, 0x10, 0x10, 0x14)
+    };
+    // Header-supplied table offsets can sit anywhere up to u64::MAX, so
+    // every field position is computed with checked arithmetic.
+    let field = |base: usize, i: usize, entsize: usize, rel: usize| {
+        base.checked_add(i.checked_mul(entsize)?)?.checked_add(rel)
+    };
+    for i in 0..phnum.min(MAX_TABLE_ENTRIES) {
+        let fields = (
+            field(phoff, i, phentsize, p_off).and_then(word),
+            field(phoff, i, phentsize, p_size).and_then(word),
+        );
+        let (Some(off), Some(size)) = fields else {
+            break;
+        };
+        end = end.max(off.saturating_add(size));
+    }
+    for i in 0..shnum.min(MAX_TABLE_ENTRIES) {
+        let fields = (
+            field(shoff, i, shentsize, 4).and_then(|o| u32_at(d, o, e).ok()),
+            field(shoff, i, shentsize, sh_off).and_then(word),
+            field(shoff, i, shentsize, sh_size).and_then(word),
+        );
+        let (Some(sh_type), Some(off), Some(size)) = fields else {
+            break;
+        };
+        // SHT_NOBITS occupies no file space.
+        if sh_type == 8 {
+            continue;
+        }
+        end = end.max(off.saturating_add(size));
+    }
+    Some(end)
+}
+
+/// Size of a thin Mach-O image: the load commands or the furthest
+/// segment file range.
+fn macho_size(d: &[u8]) -> Option<usize> {
+    let (e, is64) = match d.get(..4)? {
+        b"\xCE\xFA\xED\xFE" => (Endianness::Little, false),
+        b"\xCF\xFA\xED\xFE" => (Endianness::Little, true),
+        b"\xFE\xED\xFA\xCE" => (Endianness::Big, false),
+        b"\xFE\xED\xFA\xCF" => (Endianness::Big, true),
+        _ => return None,
+    };
+    let filetype = u32_at(d, 12, e).ok()?;
+    let ncmds = u32_at(d, 16, e).ok()? as usize;
+    let sizeofcmds = u32_at(d, 20, e).ok()? as usize;
+    if !(1..=12).contains(&filetype) || ncmds == 0 || ncmds > MAX_TABLE_ENTRIES {
+        return None;
+    }
+    let header = if is64 { 32 } else { 28 };
+    let mut end = header + sizeofcmds;
+    let mut lc = header;
+    for _ in 0..ncmds {
+        let (Ok(cmd), Ok(cmdsize)) = (u32_at(d, lc, e), u32_at(d, lc + 4, e)) else {
+            break;
+        };
+        let range = match cmd {
+            // LC_SEGMENT: fileoff and filesize as u32.
+            0x1 => u32_at(d, lc + 32, e)
+                .ok()
+                .zip(u32_at(d, lc + 36, e).ok())
+                .map(|(o, s)| (o as u64, s as u64)),
+            // LC_SEGMENT_64: fileoff and filesize as u64.
+            0x19 => u64_at(d, lc + 40, e).ok().zip(u64_at(d, lc + 48, e).ok()),
+            _ => None,
+        };
+        if let Some((off, size)) = range {
+            let seg_end = usize::try_from(off.saturating_add(size)).unwrap_or(usize::MAX);
+            end = end.max(seg_end);
+        }
+        if cmdsize < 8 {
+            break;
+        }
+        lc += cmdsize as usize;
+    }
+    Some(end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// PE32 with one section at 0x200 of 0x100 bytes.
+    fn tiny_pe() -> Vec<u8> {
+        let mut pe = vec![0u8; 0x300];
+        pe[..2].copy_from_slice(b"MZ");
+        pe[0x3C..0x40].copy_from_slice(&0x80u32.to_le_bytes());
+        pe[0x80..0x84].copy_from_slice(b"PE\0\0");
+        pe[0x86..0x88].copy_from_slice(&1u16.to_le_bytes());
+        pe[0x94..0x96].copy_from_slice(&0xE0u16.to_le_bytes());
+        let opt = 0x98;
+        pe[opt..opt + 2].copy_from_slice(&0x10Bu16.to_le_bytes());
+        pe[opt + 60..opt + 64].copy_from_slice(&0x200u32.to_le_bytes());
+        pe[opt + 92..opt + 96].copy_from_slice(&16u32.to_le_bytes());
+        let sh = opt + 0xE0;
+        pe[sh..sh + 5].copy_from_slice(b".text");
+        pe[sh + 16..sh + 20].copy_from_slice(&0x100u32.to_le_bytes());
+        pe[sh + 20..sh + 24].copy_from_slice(&0x200u32.to_le_bytes());
+        pe
+    }
+
+    /// ELF64 LE whose section header table ends the image at 0x180.
+    fn tiny_elf() -> Vec<u8> {
+        let mut elf = vec![0u8; 0x180];
+        elf[..7].copy_from_slice(b"\x7FELF\x02\x01\x01");
+        elf[0x28..0x30].copy_from_slice(&0x100u64.to_le_bytes());
+        elf[0x34..0x36].copy_from_slice(&0x40u16.to_le_bytes());
+        elf[0x3A..0x3C].copy_from_slice(&0x40u16.to_le_bytes());
+        elf[0x3C..0x3E].copy_from_slice(&2u16.to_le_bytes());
+        elf
+    }
+
+    #[test]
+    fn carves_stacked_payloads_with_header_sizes() {
+        let mut dropper = tiny_pe();
+        dropper.extend(b"padding!");
+        let elf_at = dropper.len();
+        dropper.extend(tiny_elf());
+        let macho_at = dropper.len();
+        // MH_MAGIC_64, MH_EXECUTE, one LC_SEGMENT_64 covering 0x400 bytes.
+        let mut macho = vec![0u8; 0x80];
+        macho[..4].copy_from_slice(b"\xCF\xFA\xED\xFE");
+        macho[12..16].copy_from_slice(&2u32.to_le_bytes());
+        macho[16..20].copy_from_slice(&1u32.to_le_bytes());
+        macho[20..24].copy_from_slice(&72u32.to_le_bytes());
+        macho[32..36].copy_from_slice(&0x19u32.to_le_bytes());
+        macho[36..40].copy_from_slice(&72u32.to_le_bytes());
+        macho[32 + 48..32 + 56].copy_from_slice(&0x400u64.to_le_bytes());
+        dropper.extend(macho);
+        // Stray magics that must not be carved.
+        dropper.extend(b"MZ\0\0\x7FELF\x09\x09\x09");
+
+        let carved = embedded_executables(&dropper);
+        let found: Vec<_> = carved
+            .iter()
+            .map(|c| (c.offset, c.format, c.size_estimate))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (0, Format::PE, 0x300),
+                (elf_at, Format::ELF, 0x180),
+                (macho_at, Format::MachO, 0x400),
+            ]
+        );
+    }
+
+    #[test]
+    fn elf_table_offsets_near_u64_max_do_not_overflow() {
+        for field in [0x20, 0x28] {
+            let mut elf = tiny_elf();
+            elf[0x36..0x38].copy_from_slice(&0x38u16.to_le_bytes());
+            elf[0x38..0x3A].copy_from_slice(&1u16.to_le_bytes());
+            elf[field..field + 8].copy_from_slice(&u64::MAX.to_le_bytes());
+            assert_eq!(elf_size(&elf), Some(usize::MAX));
+        }
+    }
+}
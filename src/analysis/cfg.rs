@@ -398,7 +398,8 @@ fn arm_pop_writes_pc(ins: &Instruction) -> bool {
     false
 }
 
-fn classify_ctrl_flow(mnemonic: &str, arch: BArch) -> (bool, bool, bool) {
+/// Classify a mnemonic as `(is_branch, is_call, is_ret)` for `arch`.
+pub(crate) fn classify_ctrl_flow(mnemonic: &str, arch: BArch) -> (bool, bool, bool) {
     let lower = mnemonic.to_ascii_lowercase();
     // Strip the Thumb-2 `.w`/`.n` width qualifier so `bne.w`, `bl.w`, `b.w`
     // classify the same as their base mnemonics.
@@ -488,6 +489,9 @@ fn classify_ctrl_flow(mnemonic: &str, arch: BArch) -> (bool, bool, bool) {
             (false, false, false)
         }
         BArch::RISCV | BArch::RISCV64 => {
+            // RVC forms (`c.jal`, `c.jalr`, `c.beqz`, ...) classify like their
+            // 4-byte counterparts.
+            let m = m.strip_prefix("c.").unwrap_or(&m);
             if m == "jal" {
                 return (false, true, false);
             }
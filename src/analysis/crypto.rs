@@ -0,0 +1,551 @@
+//! Embedded key material and cryptographic constants.
+//!
+//! RATs and loaders pin their C2's certificate or public key, or ship a
+//! private key for their own TLS listener, as raw DER. [`find_der_blobs`]
+//! scans for ASN.1 SEQUENCEs and keeps the ones whose shape matches an
+//! X.509 certificate, a SubjectPublicKeyInfo / PKCS#1 public key, or a
+//! PKCS#1 / PKCS#8 private key. The SHA-256 of each blob is a stable pivot
+//! across samples that embed the same material.
+//!
+//! [`detect_crypto_constants`] names the primitives a sample carries from
+//! their tables and magic numbers (AES S-boxes, hash round constants,
+//! base64 alphabets), the way triage YARA rules do.
+
+use std::ops::Range;
+
+use sha2::{Digest, Sha256};
+
+use crate::triage::signatures::{MultiScanner, SignatureHits};
+
+/// Upper bound on blobs reported for one buffer.
+pub const MAX_DER_BLOBS: usize = 256;
+
+/// Smallest blob considered; an Ed25519 SubjectPublicKeyInfo is 44 bytes.
+const MIN_DER_LEN: usize = 32;
+
+/// Upper bound on elements walked in one SEQUENCE.
+const MAX_CHILDREN: usize = 32;
+
+const TAG_INTEGER: u8 = 0x02;
+const TAG_BIT_STRING: u8 = 0x03;
+const TAG_OCTET_STRING: u8 = 0x04;
+const TAG_OID: u8 = 0x06;
+const TAG_SEQUENCE: u8 = 0x30;
+/// `[0] EXPLICIT`, the X.509 version wrapper.
+const TAG_CONTEXT_0: u8 = 0xA0;
+
+/// Kind of key material a DER blob holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DerKind {
+    /// X.509 certificate.
+    Certificate,
+    /// SubjectPublicKeyInfo or PKCS#1 `RSAPublicKey`.
+    PublicKey,
+    /// PKCS#1 `RSAPrivateKey` or PKCS#8 `PrivateKeyInfo`.
+    PrivateKey,
+}
+
+/// A DER-encoded certificate or key found in a buffer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DerBlob {
+    pub offset: usize,
+    /// Encoded length including the outer tag and length bytes.
+    pub length: usize,
+    pub kind: DerKind,
+    /// Key algorithm (`rsa`, `ec`, `dsa`, `ed25519`, `x25519`) when known.
+    pub key_algorithm: Option<&'static str>,
+    /// Lowercase hex SHA-256 of the blob.
+    pub sha256: String,
+}
+
+/// Every certificate and key blob in `data`, ordered by offset. Blobs nested
+/// in a reported blob (a certificate's public key) are not reported again.
+/// At most [`MAX_DER_BLOBS`] blobs are returned.
+pub fn find_der_blobs(data: &[u8]) -> Vec<DerBlob> {
+    let mut out = Vec::new();
+    let mut next = 0;
+    for offset in memchr::memchr_iter(TAG_SEQUENCE, data) {
+        if offset < next {
+            continue;
+        }
+        let Some((length, kind, key_algorithm)) = classify(data, offset) else {
+            continue;
+        };
+        let blob = &data[offset..offset + length];
+        out.push(DerBlob {
+            offset,
+            length,
+            kind,
+            key_algorithm,
+            sha256: hex::encode(Sha256::digest(blob)),
+        });
+        if out.len() >= MAX_DER_BLOBS {
+            break;
+        }
+        next = offset + length;
+    }
+    out
+}
+
+/// Length, kind and key algorithm of the blob at `offset`.
+fn classify(d: &[u8], offset: usize) -> Option<(usize, DerKind, Option<&'static str>)> {
+    let (tag, body, end) = tlv(d, offset)?;
+    let length = end - offset;
+    if tag != TAG_SEQUENCE || length < MIN_DER_LEN {
+        return None;
+    }
+    let kids = children(d, body)?;
+    let tags: Vec<u8> = kids.iter().map(|(t, _)| *t).collect();
+    let found = match tags.as_slice() {
+        [TAG_SEQUENCE, TAG_SEQUENCE, TAG_BIT_STRING] => (
+            DerKind::Certificate,
+            certificate_key_algorithm(d, &kids[0].1)?,
+        ),
+        [TAG_SEQUENCE, TAG_BIT_STRING] => (DerKind::PublicKey, Some(algorithm_id(d, &kids[0].1)?)),
+        // RSAPublicKey: modulus and exponent.
+        [TAG_INTEGER, TAG_INTEGER] if kids[0].1.len() >= 64 => (DerKind::PublicKey, Some("rsa")),
+        // PrivateKeyInfo: version, algorithm, key.
+        [TAG_INTEGER, TAG_SEQUENCE, TAG_OCTET_STRING, ..] if is_zero(d, &kids[0].1) => {
+            (DerKind::PrivateKey, Some(algorithm_id(d, &kids[1].1)?))
+        }
+        // RSAPrivateKey: version, then n, e, d, p, q, dP, dQ, qInv.
+        [TAG_INTEGER, rest @ ..]
+            if rest.len() >= 8
+                && rest.iter().all(|&t| t == TAG_INTEGER)
+                && is_zero(d, &kids[0].1) =>
+        {
+            (DerKind::PrivateKey, Some("rsa"))
+        }
+        _ => return None,
+    };
+    Some((length, found.0, found.1))
+}
+
+/// Key algorithm of the SubjectPublicKeyInfo inside a TBSCertificate, or
+/// `None` when `tbs` does not have a certificate's shape.
+fn certificate_key_algorithm(d: &[u8], tbs: &Range<usize>) -> Option<Option<&'static str>> {
+    let kids = children(d, tbs.clone())?;
+    let fields = match kids.first() {
+        Some((TAG_CONTEXT_0, _)) => &kids[1..],
+        _ => &kids[..],
+    };
+    // serialNumber, signature, issuer, validity, subject, subjectPublicKeyInfo
+    if fields.len() < 6
+        || fields[0].0 != TAG_INTEGER
+        || fields[1..6].iter().any(|(t, _)| *t != TAG_SEQUENCE)
+    {
+        return None;
+    }
+    let spki = children(d, fields[5].1.clone())?;
+    Some(spki.first().and_then(|(_, alg)| algorithm_id(d, alg)))
+}
+
+/// Name of the key algorithm in an AlgorithmIdentifier, `None` when it does
+/// not start with an OID. Unrecognized OIDs map to `unknown`.
+fn algorithm_id(d: &[u8], alg: &Range<usize>) -> Option<&'static str> {
+    let kids = children(d, alg.clone())?;
+    let (TAG_OID, oid) = kids.first()? else {
+        return None;
+    };
+    Some(match &d[oid.clone()] {
+        [0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x01] => "rsa",
+        [0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x02, 0x01] => "ec",
+        [0x2A, 0x86, 0x48, 0xCE, 0x38, 0x04, 0x01] => "dsa",
+        [0x2B, 0x65, 0x70] => "ed25519",
+        [0x2B, 0x65, 0x6E] => "x25519",
+        _ => "unknown",
+    })
+}
+
+fn is_zero(d: &[u8], int: &Range<usize>) -> bool {
+    d[int.clone()] == [0]
+}
+
+/// Tag, content range and end offset of the element at `pos`. Lengths
+/// beyond three bytes and indefinite lengths are rejected.
+fn tlv(d: &[u8], pos: usize) -> Option<(u8, Range<usize>, usize)> {
+    let tag = *d.get(pos)?;
+    let first = *d.get(pos + 1)? as usize;
+    let (len, header) = match first {
+        0..=0x7F => (first, 2),
+        0x81..=0x83 => {
+            let n = first & 0x7F;
+            let bytes = d.get(pos + 2..pos + 2 + n)?;
+            (
+                bytes.iter().fold(0usize, |acc, &b| (acc << 8) | b as usize),
+                2 + n,
+            )
+        }
+        _ => return None,
+    };
+    let start = pos + header;
+    let end = start.checked_add(len).filter(|&e| e <= d.len())?;
+    Some((tag, start..end, end))
+}
+
+/// Elements of a constructed value, which must tile `body` exactly.
+fn children(d: &[u8], body: Range<usize>) -> Option<Vec<(u8, Range<usize>)>> {
+    let mut out = Vec::new();
+    let mut pos = body.start;
+    while pos < body.end {
+        if out.len() >= MAX_CHILDREN {
+            return None;
+        }
+        let (tag, content, end) = tlv(d, pos)?;
+        if end > body.end {
+            return None;
+        }
+        out.push((tag, content));
+        pos = end;
+    }
+    Some(out)
+}
+
+/// A cryptographic primitive identified by a constant it embeds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CryptoAlgo {
+    /// Primitive name, e.g. `AES`, `SHA-256`, `Base64`.
+    pub algorithm: &'static str,
+    /// Which constant matched, e.g. `sbox`, `round_constants`.
+    pub constant: &'static str,
+    /// Offset of the first matched byte.
+    pub offset: usize,
+}
+
+/// How a signature appears in the data.
+enum Needle {
+    /// A contiguous byte sequence.
+    Bytes(&'static [u8]),
+    /// 32-bit words that must all be present in one byte order, anywhere:
+    /// as a table or as instruction immediates.
+    Words(&'static [u32]),
+}
+
+/// `(algorithm, constant, needle)` for every fixed signature.
+const SIGNATURES: &[(&str, &str, Needle)] = &[
+    (
+        "AES",
+        "sbox",
+        Needle::Bytes(&[
+            0x63, 0x7C, 0x77, 0x7B, 0xF2, 0x6B, 0x6F, 0xC5, 0x30, 0x01, 0x67, 0x2B, 0xFE, 0xD7,
+            0xAB, 0x76,
+        ]),
+    ),
+    (
+        "AES",
+        "inverse_sbox",
+        Needle::Bytes(&[
+            0x52, 0x09, 0x6A, 0xD5, 0x30, 0x36, 0xA5, 0x38, 0xBF, 0x40, 0xA3, 0x9E, 0x81, 0xF3,
+            0xD7, 0xFB,
+        ]),
+    ),
+    (
+        "AES",
+        "te_table",
+        Needle::Words(&[0xC663_63A5, 0xF87C_7C84, 0xEE77_7799, 0xF67B_7B8D]),
+    ),
+    (
+        "AES",
+        "td_table",
+        Needle::Words(&[0x51F4_A750, 0x7E41_6553, 0x1A17_A4C3, 0x3A27_5E96]),
+    ),
+    (
+        "SHA-256",
+        "round_constants",
+        Needle::Words(&[
+            0x428A_2F98,
+            0x7137_4491,
+            0xB5C0_FBCF,
+            0xE9B5_DBA5,
+            0x3956_C25B,
+            0x59F1_11F1,
+        ]),
+    ),
+    (
+        "SHA-256",
+        "initial_state",
+        Needle::Words(&[0x6A09_E667, 0xBB67_AE85, 0x3C6E_F372, 0xA54F_F53A]),
+    ),
+    (
+        "SHA-1",
+        "round_constants",
+        Needle::Words(&[0x5A82_7999, 0x6ED9_EBA1, 0x8F1B_BCDC, 0xCA62_C1D6]),
+    ),
+    (
+        "MD5",
+        "sine_table",
+        Needle::Words(&[
+            0xD76A_A478,
+            0xE8C7_B756,
+            0x2420_70DB,
+            0xC1BD_CEEE,
+            0xF57C_0FAF,
+            0x4787_C62A,
+        ]),
+    ),
+    (
+        "ChaCha20/Salsa20",
+        "sigma",
+        Needle::Bytes(b"expand 32-byte k"),
+    ),
+    (
+        "Base64",
+        "standard_alphabet",
+        Needle::Bytes(b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/"),
+    ),
+    (
+        "Base64",
+        "url_safe_alphabet",
+        Needle::Bytes(b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_"),
+    ),
+];
+
+/// Every primitive whose constants appear in `data`, ordered by offset.
+///
+/// Besides the fixed tables this reports `RC4` when the 256-byte identity
+/// permutation its key schedule starts from is stored verbatim, and
+/// `Base64` / `custom_alphabet` for any other run of 64 distinct
+/// base64-class characters, the usual sign of a shuffled alphabet.
+pub fn detect_crypto_constants(data: &[u8]) -> Vec<CryptoAlgo> {
+    detect_crypto_constants_with_hits(data, &MultiScanner::builtin().scan(data))
+}
+
+/// [`detect_crypto_constants`] reusing the caller's
+/// [`MultiScanner::builtin`] scan of `data`, shared with the packer and
+/// installer detectors.
+pub fn detect_crypto_constants_with_hits(data: &[u8], hits: &SignatureHits<'_>) -> Vec<CryptoAlgo> {
+    let mut out: Vec<CryptoAlgo> = SIGNATURES
+        .iter()
+        .filter_map(|&(algorithm, constant, ref needle)| {
+            let offset = match needle {
+                Needle::Bytes(bytes) => hits.find(bytes),
+                Needle::Words(words) => find_words(hits, words, u32::to_le_bytes)
+                    .or_else(|| find_words(hits, words, u32::to_be_bytes)),
+            }?;
+            Some(CryptoAlgo {
+                algorithm,
+                constant,
+                offset,
+            })
+        })
+        .collect();
+
+    if let Some(offset) = hits.find(&identity_permutation()) {
+        out.push(CryptoAlgo {
+            algorithm: "RC4",
+            constant: "identity_permutation",
+            offset,
+        });
+    }
+    let known: Vec<usize> = out
+        .iter()
+        .filter(|c| c.algorithm == "Base64")
+        .map(|c| c.offset)
+        .collect();
+    if let Some(offset) = find_custom_alphabet(data, &known) {
+        out.push(CryptoAlgo {
+            algorithm: "Base64",
+            constant: "custom_alphabet",
+            offset,
+        });
+    }
+    out.sort_by_key(|c| c.offset);
+    out
+}
+
+/// Every literal [`detect_crypto_constants`] looks for, with table words
+/// in both byte orders; fed to [`MultiScanner::builtin`].
+pub(crate) fn literal_patterns() -> Vec<Vec<u8>> {
+    let mut out = vec![identity_permutation()];
+    for (_, _, needle) in SIGNATURES {
+        match needle {
+            Needle::Bytes(bytes) => out.push(bytes.to_vec()),
+            Needle::Words(words) => {
+                for &w in words.iter() {
+                    out.push(w.to_le_bytes().to_vec());
+                    out.push(w.to_be_bytes().to_vec());
+                }
+            }
+        }
+    }
+    out
+}
+
+/// The 256-byte identity permutation RC4's key schedule starts from.
+fn identity_permutation() -> Vec<u8> {
+    (0..=255).collect()
+}
+
+/// Offset of the earliest of `words` when all of them occur in the scanned
+/// data encoded with `encode`.
+fn find_words(
+    hits: &SignatureHits<'_>,
+    words: &[u32],
+    encode: fn(u32) -> [u8; 4],
+) -> Option<usize> {
+    words
+        .iter()
+        .map(|&w| hits.find(&encode(w)))
+        .collect::<Option<Vec<usize>>>()?
+        .into_iter()
+        .min()
+}
+
+/// First window of 64 distinct base64-class bytes that does not start at
+/// one of the `known` standard alphabets.
+fn find_custom_alphabet(data: &[u8], known: &[usize]) -> Option<usize> {
+    const WINDOW: usize = 64;
+    let is_b64 = |b: u8| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'/' | b'-' | b'_');
+    let mut counts = [0u8; 256];
+    let mut duplicates = 0usize;
+    let mut start = 0usize;
+    for (i, &b) in data.iter().enumerate() {
+        if !is_b64(b) {
+            counts = [0; 256];
+            duplicates = 0;
+            start = i + 1;
+            continue;
+        }
+        counts[b as usize] += 1;
+        if counts[b as usize] == 2 {
+            duplicates += 1;
+        }
+        if i + 1 - start > WINDOW {
+            let old = data[start] as usize;
+            if counts[old] == 2 {
+                duplicates -= 1;
+            }
+            counts[old] -= 1;
+            start += 1;
+        }
+        if i + 1 - start == WINDOW && duplicates == 0 && !known.contains(&start) {
+            return Some(start);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn der(tag: u8, content: &[u8]) -> Vec<u8> {
+        let mut out = vec![tag];
+        match content.len() {
+            n if n < 0x80 => out.push(n as u8),
+            n if n < 0x100 => out.extend([0x81, n as u8]),
+            n => out.extend([0x82, (n >> 8) as u8, n as u8]),
+        }
+        out.extend(content);
+        out
+    }
+
+    fn seq(parts: &[Vec<u8>]) -> Vec<u8> {
+        der(TAG_SEQUENCE, &parts.concat())
+    }
+
+    #[test]
+    fn finds_certificates_and_keys_between_junk() {
+        let ed25519 = seq(&[der(TAG_OID, &[0x2B, 0x65, 0x70])]);
+        let spki = seq(&[ed25519.clone(), der(TAG_BIT_STRING, &[0; 33])]);
+        let name = seq(&[der(0x31, &seq(&[der(TAG_OID, &[0x55, 0x04, 0x03])]))]);
+        let tbs = seq(&[
+            der(TAG_CONTEXT_0, &der(TAG_INTEGER, &[2])),
+            der(TAG_INTEGER, &[0x11; 16]),
+            ed25519.clone(),
+            name.clone(),
+            seq(&[der(0x17, b"250101000000Z"), der(0x17, b"350101000000Z")]),
+            name,
+            spki.clone(),
+        ]);
+        let cert = seq(&[tbs, ed25519.clone(), der(TAG_BIT_STRING, &[0; 65])]);
+        let pkcs8 = seq(&[
+            der(TAG_INTEGER, &[0]),
+            ed25519,
+            der(TAG_OCTET_STRING, &der(TAG_OCTET_STRING, &[0x42; 32])),
+        ]);
+
+        let mut data = b"0000\x30\x82\xff\xffjunk".to_vec();
+        let cert_at = data.len();
+        data.extend(&cert);
+        data.extend(b"\x30\x03\x02\x01\x00");
+        let spki_at = data.len();
+        data.extend(&spki);
+        let key_at = data.len();
+        data.extend(&pkcs8);
+
+        let found: Vec<_> = find_der_blobs(&data)
+            .into_iter()
+            .map(|b| (b.offset, b.length, b.kind, b.key_algorithm))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (cert_at, cert.len(), DerKind::Certificate, Some("ed25519")),
+                (spki_at, spki.len(), DerKind::PublicKey, Some("ed25519")),
+                (key_at, pkcs8.len(), DerKind::PrivateKey, Some("ed25519")),
+            ]
+        );
+        let hashes: Vec<_> = find_der_blobs(&spki)
+            .into_iter()
+            .map(|b| b.sha256)
+            .collect();
+        assert_eq!(hashes, vec![hex::encode(Sha256::digest(&spki))]);
+    }
+
+    #[test]
+    fn names_embedded_crypto_constants() {
+        let mut data = vec![0u8; 16];
+        // MD5 round constants as little-endian instruction immediates.
+        for k in [
+            0xD76A_A478u32,
+            0xE8C7_B756,
+            0x2420_70DB,
+            0xC1BD_CEEE,
+            0xF57C_0FAF,
+            0x4787_C62A,
+        ] {
+            data.push(0x05); // add eax, imm32
+            data.extend(k.to_le_bytes());
+        }
+        let sbox_at = data.len();
+        data.extend([
+            0x63, 0x7C, 0x77, 0x7B, 0xF2, 0x6B, 0x6F, 0xC5, 0x30, 0x01, 0x67, 0x2B, 0xFE, 0xD7,
+            0xAB, 0x76,
+        ]);
+        let sha_at = data.len();
+        for k in [
+            0x428A_2F98u32,
+            0x7137_4491,
+            0xB5C0_FBCF,
+            0xE9B5_DBA5,
+            0x3956_C25B,
+            0x59F1_11F1,
+        ] {
+            data.extend(k.to_be_bytes());
+        }
+        let std_at = data.len() + 1;
+        data.extend(b"\0ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/=\0");
+        // The standard alphabet rotated by 13, as droppers often ship.
+        let custom_at = data.len() + 1;
+        data.extend(b"\0NOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/ABCDEFGHIJKLM\0");
+        // Long base64 payload text is not an alphabet.
+        data.extend(b"TWFsd2FyZSBjb25maWcgYmxvYiBUV0ZzZDJGeVpTQmpiMjVtYVdjZ1lteHZZZz09\0");
+
+        let found: Vec<_> = detect_crypto_constants(&data)
+            .into_iter()
+            .map(|c| (c.algorithm, c.constant, c.offset))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("MD5", "sine_table", 17),
+                ("AES", "sbox", sbox_at),
+                ("SHA-256", "round_constants", sha_at),
+                ("Base64", "standard_alphabet", std_at),
+                ("Base64", "custom_alphabet", custom_at),
+            ]
+        );
+        assert!(detect_crypto_constants(b"plain text, no tables here").is_empty());
+    }
+}
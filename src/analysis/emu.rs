@@ -0,0 +1,302 @@
+//! Tiny bounded emulator for evaluating string-decryption loops.
+//!
+//! Wraps the concrete execution engine ([`crate::exec`]) with an
+//! instruction-stepped driver over a raw code window: the window is lifted to
+//! LLIR once, then executed one machine instruction at a time until a stop VA
+//! is reached, control leaves the window, or the step budget runs out. This is
+//! deliberately not a loader or an OS model — calls, returns and syscalls stop
+//! execution — but it is enough to run a self-contained XOR/ADD decrypt loop
+//! found by [`ControlFlowGraph::natural_loops`] and read back the plaintext.
+//!
+//! [`ControlFlowGraph::natural_loops`]: crate::core::control_flow_graph::ControlFlowGraph::natural_loops
+
+use std::collections::HashMap;
+
+use crate::core::binary::Arch;
+use crate::exec::{Concrete, Domain, Flow, Halt, Machine, RegArch};
+use crate::ir::types::{Endian, LlirInstr, Op, VReg, Width};
+
+/// Emulation limits and initial machine state.
+#[derive(Debug, Clone)]
+pub struct EmuConfig {
+    /// Architecture of the code window.
+    pub arch: Arch,
+    /// Maximum number of machine instructions to execute.
+    pub max_steps: u64,
+    /// Initial stack pointer value.
+    pub stack_pointer: u64,
+}
+
+impl EmuConfig {
+    /// Default limits for `arch`.
+    pub fn new(arch: Arch) -> Self {
+        Self {
+            arch,
+            max_steps: 100_000,
+            stack_pointer: 0x7fff_ffff_0000,
+        }
+    }
+
+    /// Override the instruction budget.
+    pub fn with_max_steps(mut self, max_steps: u64) -> Self {
+        self.max_steps = max_steps;
+        self
+    }
+}
+
+/// Why [`Emu::run_until`] stopped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EmuStop {
+    /// Reached the requested stop VA.
+    Reached,
+    /// Control transferred outside the lifted window.
+    LeftRegion(u64),
+    /// The instruction budget was exhausted.
+    BudgetExhausted,
+    /// Reached a call (target VA if resolved).
+    CalledOut(Option<u64>),
+    /// Reached a return.
+    Returned,
+    /// The engine halted (unsupported intrinsic, unresolved address, ...).
+    Halted(Halt),
+}
+
+/// Instruction-stepped concrete emulator over a lifted code window.
+pub struct Emu {
+    machine: Machine<Concrete>,
+    /// Machine instructions in address order: (va, next va, LLIR ops).
+    instrs: Vec<(u64, u64, Vec<Op>)>,
+    index: HashMap<u64, usize>,
+    endian: Endian,
+    max_steps: u64,
+    steps: u64,
+}
+
+impl Emu {
+    /// Lift `code` (mapped at `code_va`) and prepare a machine for it.
+    ///
+    /// The code bytes are also mapped into emulator memory so loops that read
+    /// inline data work. Returns `None` for architectures without a modelled
+    /// register file (only x86-64 and AArch64 today) or when nothing in the
+    /// window decodes.
+    pub fn new(code: &[u8], code_va: u64, config: &EmuConfig) -> Option<Self> {
+        let (lifted, reg_arch, sp_name): (Vec<LlirInstr>, RegArch, &str) = match config.arch {
+            Arch::X86_64 => (
+                crate::ir::lift_x86::lift_bytes(code, code_va, 64),
+                RegArch::X86_64,
+                "rsp",
+            ),
+            Arch::AArch64 => (
+                crate::ir::lift_arm64::lift_bytes(code, code_va),
+                RegArch::AArch64,
+                "sp",
+            ),
+            _ => return None,
+        };
+        if lifted.is_empty() {
+            return None;
+        }
+
+        let mut instrs: Vec<(u64, u64, Vec<Op>)> = Vec::new();
+        for ins in lifted {
+            match instrs.last_mut() {
+                Some((va, _, ops)) if *va == ins.va => ops.push(ins.op),
+                _ => instrs.push((ins.va, 0, vec![ins.op])),
+            }
+        }
+        let window_end = code_va.saturating_add(code.len() as u64);
+        let next_vas: Vec<u64> = instrs
+            .iter()
+            .skip(1)
+            .map(|(va, _, _)| *va)
+            .chain(std::iter::once(window_end))
+            .collect();
+        for (ins, next_va) in instrs.iter_mut().zip(next_vas) {
+            ins.1 = next_va;
+        }
+        let index = instrs
+            .iter()
+            .enumerate()
+            .map(|(i, (va, _, _))| (*va, i))
+            .collect();
+
+        let mut emu = Self {
+            machine: Machine::new_with_arch(Concrete, reg_arch),
+            instrs,
+            index,
+            endian: Endian::Little,
+            max_steps: config.max_steps,
+            steps: 0,
+        };
+        emu.write_mem(code_va, code);
+        emu.set_reg(sp_name, config.stack_pointer);
+        Some(emu)
+    }
+
+    /// Copy `bytes` into emulator memory at `va`.
+    pub fn write_mem(&mut self, va: u64, bytes: &[u8]) {
+        let m = &mut self.machine;
+        for (i, b) in bytes.iter().enumerate() {
+            let v = m.dom.constant(Width::W8, *b as u128);
+            m.mem
+                .store(&mut m.dom, va.wrapping_add(i as u64), &v, 1, self.endian);
+        }
+    }
+
+    /// Read `len` bytes of emulator memory at `va` (unwritten bytes read 0).
+    pub fn read_mem(&mut self, va: u64, len: usize) -> Vec<u8> {
+        let m = &mut self.machine;
+        (0..len)
+            .map(|i| {
+                m.mem
+                    .load(&mut m.dom, va.wrapping_add(i as u64), 1, self.endian)
+                    as u8
+            })
+            .collect()
+    }
+
+    /// Set a physical register by name.
+    pub fn set_reg(&mut self, name: &str, value: u64) {
+        let m = &mut self.machine;
+        let reg = VReg::phys(name);
+        let width = m.regs.width(&reg).unwrap_or(Width::W64);
+        let v = m.dom.constant(width, value as u128);
+        m.regs.write(&mut m.dom, &reg, v);
+    }
+
+    /// Read a physical register by name.
+    pub fn reg(&mut self, name: &str) -> u64 {
+        let m = &mut self.machine;
+        let v = m.regs.read(&mut m.dom, &VReg::phys(name));
+        v as u64
+    }
+
+    /// Number of machine instructions executed so far.
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    /// Current program counter.
+    pub fn pc(&self) -> u64 {
+        self.machine.pc
+    }
+
+    /// Execute the machine instruction at the current PC.
+    ///
+    /// Returns `None` to continue or the reason execution must stop.
+    pub fn step(&mut self) -> Option<EmuStop> {
+        if self.steps >= self.max_steps {
+            return Some(EmuStop::BudgetExhausted);
+        }
+        let pc = self.machine.pc;
+        let Some(&idx) = self.index.get(&pc) else {
+            return Some(EmuStop::LeftRegion(pc));
+        };
+        self.steps += 1;
+        let (_, next_va, ops) = &self.instrs[idx];
+        let mut flow = Flow::Next;
+        for op in ops {
+            flow = self.machine.step(op);
+            if !matches!(flow, Flow::Next) {
+                break;
+            }
+        }
+        let next = match flow {
+            Flow::Next => *next_va,
+            Flow::Jump(t) => t,
+            Flow::Branch { target, taken } => {
+                if taken {
+                    target
+                } else {
+                    *next_va
+                }
+            }
+            Flow::Call(t) => return Some(EmuStop::CalledOut(t)),
+            Flow::Return => return Some(EmuStop::Returned),
+            Flow::Halt(h) => return Some(EmuStop::Halted(h)),
+        };
+        self.machine.pc = next;
+        None
+    }
+
+    /// Run from `start_va` until `stop_va` is reached or execution stops.
+    pub fn run_until(&mut self, start_va: u64, stop_va: u64) -> EmuStop {
+        self.machine.pc = start_va;
+        loop {
+            if self.machine.pc == stop_va {
+                return EmuStop::Reached;
+            }
+            if let Some(stop) = self.step() {
+                return stop;
+            }
+        }
+    }
+}
+
+/// Run a decrypt loop over `buf` and return the transformed bytes.
+///
+/// `code` is mapped at `code_va`; `buf` is written at `buf_va` before running
+/// from `start_va` to `stop_va` (typically the loop's preheader and its exit
+/// block). Returns `None` unless execution reaches `stop_va` within budget.
+pub fn decrypt_loop(
+    code: &[u8],
+    code_va: u64,
+    start_va: u64,
+    stop_va: u64,
+    buf_va: u64,
+    buf: &[u8],
+    config: &EmuConfig,
+) -> Option<Vec<u8>> {
+    let mut emu = Emu::new(code, code_va, config)?;
+    emu.write_mem(buf_va, buf);
+    match emu.run_until(start_va, stop_va) {
+        EmuStop::Reached => Some(emu.read_mem(buf_va, buf.len())),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // mov rsi, 0x2000; mov ecx, 5
+    // loop: xor byte [rsi], 0x41; inc rsi; dec ecx; jnz loop
+    const XOR_LOOP: &[u8] = &[
+        0x48, 0xc7, 0xc6, 0x00, 0x20, 0x00, 0x00, // 0x1000 mov rsi, 0x2000
+        0xb9, 0x05, 0x00, 0x00, 0x00, // 0x1007 mov ecx, 5
+        0x80, 0x36, 0x41, // 0x100c xor byte [rsi], 0x41
+        0x48, 0xff, 0xc6, // 0x100f inc rsi
+        0xff, 0xc9, // 0x1012 dec ecx
+        0x75, 0xf6, // 0x1014 jnz 0x100c
+    ];
+
+    #[test]
+    fn decrypts_single_byte_xor_loop() {
+        let cipher: Vec<u8> = b"hello".iter().map(|b| b ^ 0x41).collect();
+        let cfg = EmuConfig::new(Arch::X86_64);
+        let plain = decrypt_loop(XOR_LOOP, 0x1000, 0x1000, 0x1016, 0x2000, &cipher, &cfg);
+        assert_eq!(plain.as_deref(), Some(&b"hello"[..]));
+    }
+
+    #[test]
+    fn budget_bounds_execution() {
+        let cfg = EmuConfig::new(Arch::X86_64).with_max_steps(4);
+        let mut emu = Emu::new(XOR_LOOP, 0x1000, &cfg).expect("lift");
+        assert_eq!(emu.run_until(0x1000, 0x1016), EmuStop::BudgetExhausted);
+        assert_eq!(emu.steps(), 4);
+        // mov, mov, xor, inc: rsi has advanced once.
+        assert_eq!(emu.reg("rsi"), 0x2001);
+    }
+
+    #[test]
+    fn leaving_the_window_stops() {
+        let cfg = EmuConfig::new(Arch::X86_64);
+        let mut emu = Emu::new(XOR_LOOP, 0x1000, &cfg).expect("lift");
+        // No stop VA inside the window: the loop runs out the bottom.
+        assert_eq!(emu.run_until(0x1000, 0xdead), EmuStop::LeftRegion(0x1016));
+        assert_eq!(emu.run_until(0x1000, 0x1000), EmuStop::Reached);
+        assert!(Emu::new(XOR_LOOP, 0, &EmuConfig::new(Arch::MIPS)).is_none());
+        assert!(Emu::new(XOR_LOOP, 0, &EmuConfig::new(Arch::X86)).is_none());
+        assert!(Emu::new(XOR_LOOP, 0, &EmuConfig::new(Arch::ARM)).is_none());
+    }
+}
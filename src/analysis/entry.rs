@@ -2,9 +2,14 @@
 //!
 //! This module centralizes high-level routines for locating the program entrypoint
 //! and mapping virtual addresses to file offsets across common formats (ELF/PE/Mach-O).
+//! [`all_entry_points`] additionally enumerates code the loader runs before or
+//! alongside the header entry (PE TLS callbacks, ELF initializers).
 //! Implementations are bounded and deterministic and avoid allocating large buffers.
 
+use crate::core::address::{Address, AddressKind};
 use crate::core::binary::{Arch, Endianness, Format};
+use crate::formats::elf::ElfParser;
+use crate::formats::pe::PeParser;
 use object::{ObjectSection, ObjectSegment};
 
 /// Entry info returned by `detect_entry`.
@@ -120,3 +125,124 @@ pub fn va_to_file_offset(data: &[u8], va: u64) -> Option<usize> {
     }
     None
 }
+
+/// Parsers that can enumerate every loader-invoked entry point.
+pub trait EntrySource {
+    /// Entry VAs, primary entry first; see [`all_entry_points`].
+    fn entry_vas(&self) -> Vec<u64>;
+    /// Address width used when wrapping VAs in [`Address`].
+    fn address_bits(&self) -> u8;
+}
+
+/// Every executable entry of `parser` as VA [`Address`]es.
+///
+/// The header entry point comes first (when the format declares one),
+/// followed by the other code the loader runs on its own:
+/// - PE: TLS callbacks, in directory order.
+/// - ELF: [`ElfParser::init_functions`], in the order the dynamic loader
+///   calls them.
+///
+/// Duplicates are dropped, keeping the first occurrence.
+pub fn all_entry_points<P: EntrySource + ?Sized>(parser: &P) -> Vec<Address> {
+    let bits = parser.address_bits();
+    let mut seen = std::collections::HashSet::new();
+    parser
+        .entry_vas()
+        .into_iter()
+        .filter(|&va| seen.insert(va))
+        .filter_map(|va| Address::new(AddressKind::VA, va, bits, None, None).ok())
+        .collect()
+}
+
+impl EntrySource for PeParser<'_> {
+    fn entry_vas(&self) -> Vec<u64> {
+        let mut vas = Vec::new();
+        if self.entry_point() != 0 {
+            vas.push(self.image_base().wrapping_add(self.entry_point() as u64));
+        }
+        if let Ok(tls) = self.tls() {
+            vas.extend(tls.callbacks.iter().copied().filter(|&va| va != 0));
+        }
+        vas
+    }
+
+    fn address_bits(&self) -> u8 {
+        if self.is_64bit() {
+            64
+        } else {
+            32
+        }
+    }
+}
+
+impl EntrySource for ElfParser<'_> {
+    fn entry_vas(&self) -> Vec<u64> {
+        let mut vas = Vec::new();
+        if self.header().entry_point() != 0 {
+            vas.push(self.header().entry_point());
+        }
+        vas.extend(self.init_functions());
+        vas
+    }
+
+    fn address_bits(&self) -> u8 {
+        self.header().ident.class.bits()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 64-bit LE ELF with an entry point and a four-slot `.init_array`.
+    fn elf_with_init_array(slots: [u64; 4]) -> Vec<u8> {
+        let mut data = vec![0u8; 0x400];
+        data[0..4].copy_from_slice(b"\x7fELF");
+        data[4] = 2; // 64-bit
+        data[5] = 1; // little endian
+        data[6] = 1;
+        data[16] = 3; // ET_DYN
+        data[18] = 62; // EM_X86_64
+        data[20] = 1;
+        data[24..32].copy_from_slice(&0x1040u64.to_le_bytes()); // e_entry
+        data[40..48].copy_from_slice(&0x100u64.to_le_bytes()); // e_shoff
+        data[52] = 64; // e_ehsize
+        data[54] = 56; // e_phentsize
+        data[58] = 64; // e_shentsize
+        data[60] = 3; // e_shnum
+        data[62] = 2; // e_shstrndx
+
+        // Section 1: .init_array at VA 0x3000, file offset 0x200.
+        let sh = 0x140;
+        data[sh] = 1; // sh_name
+        data[sh + 4] = 14; // SHT_INIT_ARRAY
+        data[sh + 8] = 3; // SHF_WRITE | SHF_ALLOC
+        data[sh + 16..sh + 24].copy_from_slice(&0x3000u64.to_le_bytes());
+        data[sh + 24..sh + 32].copy_from_slice(&0x200u64.to_le_bytes());
+        data[sh + 32..sh + 40].copy_from_slice(&32u64.to_le_bytes());
+        for (i, slot) in slots.iter().enumerate() {
+            data[0x200 + i * 8..0x208 + i * 8].copy_from_slice(&slot.to_le_bytes());
+        }
+
+        // Section 2: .shstrtab at file offset 0x300.
+        let sh = 0x180;
+        data[sh] = 13; // sh_name
+        data[sh + 4] = 3; // SHT_STRTAB
+        data[sh + 24..sh + 32].copy_from_slice(&0x300u64.to_le_bytes());
+        data[sh + 32..sh + 40].copy_from_slice(&0x20u64.to_le_bytes());
+        data[0x301..0x301 + 23].copy_from_slice(b".init_array\0.shstrtab\0\0");
+
+        data
+    }
+
+    #[test]
+    fn elf_entry_points_include_init_array_in_order() {
+        // 0 and -1 are sentinels; the last slot duplicates e_entry.
+        let data = elf_with_init_array([0x1100, 0, u64::MAX, 0x1040]);
+        let elf = ElfParser::parse(&data).unwrap();
+
+        let vas: Vec<u64> = all_entry_points(&elf).iter().map(|a| a.value).collect();
+        assert_eq!(vas, vec![0x1040, 0x1100]);
+        assert!(all_entry_points(&elf).iter().all(|a| a.bits == 64));
+    }
+}
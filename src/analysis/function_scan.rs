@@ -0,0 +1,244 @@
+//! Function-boundary heuristics for stripped binaries.
+//!
+//! When symbols, eh_frame and pclntab are all unavailable, scanning
+//! executable sections for common prologue byte patterns still yields
+//! candidate function starts that can seed CFG discovery. Results are
+//! heuristic: callers should treat them as hints, not ground truth.
+
+use crate::analysis::view::BinaryView;
+use crate::core::address::AddressKind;
+use crate::core::binary::{Arch, Endianness};
+
+/// Scan the executable sections of `view` for prologue signatures.
+///
+/// Section bytes are read from the view's attached data (see
+/// [`BinaryView::with_data`]) via each section's file offset; a view
+/// without data yields nothing. Results are returned as sorted,
+/// de-duplicated VAs. The scan uses `arch` rather than `view.arch()` so
+/// callers can override a misdetected architecture.
+pub fn find_prologues(view: &BinaryView, arch: Arch) -> Vec<u64> {
+    let Some(data) = view.data() else {
+        return Vec::new();
+    };
+    let mut out = Vec::new();
+    for sec in view.sections.iter().filter(|s| s.is_executable()) {
+        let base_va = match sec.range.start.kind {
+            AddressKind::VA => sec.range.start.value,
+            AddressKind::RVA => match view.rva_to_va(&sec.range.start) {
+                Some(va) => va.value,
+                None => continue,
+            },
+            _ => continue,
+        };
+        let start = sec.file_offset.value as usize;
+        if start >= data.len() {
+            continue;
+        }
+        let end = start.saturating_add(sec.size() as usize).min(data.len());
+        out.extend(scan_prologues(
+            &data[start..end],
+            base_va,
+            arch,
+            view.endianness(),
+        ));
+    }
+    out.sort_unstable();
+    out.dedup();
+    out
+}
+
+/// Scan a raw code buffer mapped at `base_va` for prologue signatures.
+///
+/// Recognized patterns:
+/// - x86/x86-64: `push ebp/rbp; mov ebp/rbp, esp/rsp` (both encodings),
+///   optionally preceded by `endbr32`/`endbr64`.
+/// - AArch64: `stp x29, x30, [sp, #-N]!`, optionally preceded by
+///   `paciasp` or `bti c`.
+/// - ARM (A32): `push {..., lr}` (`stmdb sp!, {..., lr}`).
+pub fn scan_prologues(code: &[u8], base_va: u64, arch: Arch, endian: Endianness) -> Vec<u64> {
+    match arch {
+        Arch::X86_64 => scan_x86(code, base_va, true),
+        Arch::X86 => scan_x86(code, base_va, false),
+        Arch::AArch64 => scan_aarch64(code, base_va, endian),
+        Arch::ARM => scan_arm(code, base_va, endian),
+        _ => Vec::new(),
+    }
+}
+
+const ENDBR64: [u8; 4] = [0xf3, 0x0f, 0x1e, 0xfa];
+const ENDBR32: [u8; 4] = [0xf3, 0x0f, 0x1e, 0xfb];
+
+fn scan_x86(code: &[u8], base_va: u64, is_64: bool) -> Vec<u64> {
+    let frame: &[&[u8]] = if is_64 {
+        &[&[0x55, 0x48, 0x89, 0xe5], &[0x55, 0x48, 0x8b, 0xec]]
+    } else {
+        &[&[0x55, 0x89, 0xe5], &[0x55, 0x8b, 0xec]]
+    };
+    let endbr = if is_64 { ENDBR64 } else { ENDBR32 };
+    let mut out = Vec::new();
+    let mut i = 0usize;
+    while i < code.len() {
+        let rest = &code[i..];
+        let (start, len) = if let Some(p) = frame.iter().find(|p| rest.starts_with(p)) {
+            (i, p.len())
+        } else if let Some(p) = rest
+            .strip_prefix(&endbr[..])
+            .and_then(|after| frame.iter().find(|p| after.starts_with(p)))
+        {
+            (i, endbr.len() + p.len())
+        } else {
+            i += 1;
+            continue;
+        };
+        // Reduce mid-function hits: the start must be aligned or follow
+        // padding / a return.
+        let boundary =
+            start == 0 || start % 16 == 0 || matches!(code[start - 1], 0xc3 | 0xcc | 0x90 | 0x00);
+        if boundary {
+            out.push(base_va + start as u64);
+        }
+        i = start + len;
+    }
+    out
+}
+
+fn words(code: &[u8], endian: Endianness) -> impl Iterator<Item = (usize, u32)> + '_ {
+    code.chunks_exact(4).enumerate().map(move |(i, c)| {
+        let b = [c[0], c[1], c[2], c[3]];
+        let w = match endian {
+            Endianness::Little => u32::from_le_bytes(b),
+            Endianness::Big => u32::from_be_bytes(b),
+        };
+        (i * 4, w)
+    })
+}
+
+fn scan_aarch64(code: &[u8], base_va: u64, endian: Endianness) -> Vec<u64> {
+    const PACIASP: u32 = 0xd503_233f;
+    const BTI_C: u32 = 0xd503_245f;
+    let mut out = Vec::new();
+    let mut prev: Option<u32> = None;
+    for (off, w) in words(code, endian) {
+        // stp x29, x30, [sp, #imm]! (pre-index, any imm7)
+        if w & 0xffc0_7fff == 0xa980_7bfd {
+            let start = match prev {
+                Some(PACIASP) | Some(BTI_C) => off - 4,
+                _ => off,
+            };
+            out.push(base_va + start as u64);
+        }
+        prev = Some(w);
+    }
+    out
+}
+
+fn scan_arm(code: &[u8], base_va: u64, endian: Endianness) -> Vec<u64> {
+    words(code, endian)
+        // stmdb sp!, {..., lr} with AL condition
+        .filter(|&(_, w)| w & 0xffff_4000 == 0xe92d_4000)
+        .map(|(off, _)| base_va + off as u64)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn x86_64_frame_prologues() {
+        let mut code = vec![0xccu8; 0x40];
+        // Plain prologue at 0x0, endbr64-prefixed at 0x10, mov rbp,rsp alt at 0x20.
+        code[0x00..0x04].copy_from_slice(&[0x55, 0x48, 0x89, 0xe5]);
+        code[0x10..0x18].copy_from_slice(&[0xf3, 0x0f, 0x1e, 0xfa, 0x55, 0x48, 0x89, 0xe5]);
+        code[0x21..0x25].copy_from_slice(&[0x55, 0x48, 0x8b, 0xec]);
+        let hits = scan_prologues(&code, 0x1000, Arch::X86_64, Endianness::Little);
+        assert_eq!(hits, vec![0x1000, 0x1010, 0x1021]);
+    }
+
+    #[test]
+    fn x86_endbr32_prologue_skips_whole_match() {
+        // endbr32; push ebp; mov ebp, esp at 9 is 7 bytes long, so the
+        // aligned prologue straight after it at 0x10 is still seen.
+        let mut code = vec![0xccu8; 9];
+        code.extend_from_slice(&[0xf3, 0x0f, 0x1e, 0xfb, 0x55, 0x89, 0xe5]);
+        code.extend_from_slice(&[0x55, 0x8b, 0xec]);
+        let hits = scan_prologues(&code, 0x100, Arch::X86, Endianness::Little);
+        assert_eq!(hits, vec![0x109, 0x110]);
+    }
+
+    #[test]
+    fn find_prologues_scans_executable_sections_of_the_view() {
+        use crate::core::address::Address;
+        use crate::core::address_range::AddressRange;
+        use crate::core::binary::{Binary, Format};
+        use crate::core::section::{Section, SectionPerms};
+
+        let binary = Binary::new(
+            "id".to_string(),
+            "path".to_string(),
+            Format::ELF,
+            Arch::X86_64,
+            64,
+            Endianness::Little,
+            vec![],
+            0x40,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let section = Section::new(
+            "text".to_string(),
+            ".text".to_string(),
+            AddressRange::new(
+                Address::new(AddressKind::VA, 0x401000, 64, None, None).unwrap(),
+                0x20,
+                None,
+            )
+            .unwrap(),
+            Address::new(AddressKind::FileOffset, 0x20, 64, None, None).unwrap(),
+            Some(SectionPerms::new(true, false, true)),
+            0,
+            None,
+        )
+        .unwrap();
+        let mut data = vec![0xccu8; 0x40];
+        data[0x30..0x34].copy_from_slice(&[0x55, 0x48, 0x89, 0xe5]);
+        let view = BinaryView::new(binary, None, vec![section], vec![], None);
+        assert!(find_prologues(&view, Arch::X86_64).is_empty());
+        let view = view.with_data(data);
+        assert_eq!(find_prologues(&view, Arch::X86_64), vec![0x401010]);
+    }
+
+    #[test]
+    fn x86_prologue_needs_boundary() {
+        // Not aligned and preceded by a non-padding byte.
+        let code = [0x01, 0x02, 0x03, 0x55, 0x89, 0xe5];
+        assert!(scan_prologues(&code, 0, Arch::X86, Endianness::Little).is_empty());
+    }
+
+    #[test]
+    fn aarch64_stp_prologues() {
+        let mut code = Vec::new();
+        for w in [
+            0xa9bf_7bfdu32, // stp x29, x30, [sp, #-16]!
+            0x9100_03fd,    // mov x29, sp
+            0xd65f_03c0,    // ret
+            0xd503_233f,    // paciasp
+            0xa9be_7bfd,    // stp x29, x30, [sp, #-32]!
+        ] {
+            code.extend_from_slice(&w.to_le_bytes());
+        }
+        let hits = scan_prologues(&code, 0x4000, Arch::AArch64, Endianness::Little);
+        assert_eq!(hits, vec![0x4000, 0x400c]);
+    }
+
+    #[test]
+    fn arm_push_lr() {
+        let mut code = Vec::new();
+        code.extend_from_slice(&0xe92d_4800u32.to_le_bytes()); // push {fp, lr}
+        code.extend_from_slice(&0xe12f_ff1eu32.to_le_bytes()); // bx lr
+        let hits = scan_prologues(&code, 0x8000, Arch::ARM, Endianness::Little);
+        assert_eq!(hits, vec![0x8000]);
+    }
+}
@@ -4,25 +4,42 @@
 //! analysis: a `BinaryView` container that aggregates sections/segments
 //! and image base metadata, and a `MemoryView` trait for bounded reads
 //! by `core::address::Address` with simple VA↔RVA↔FileOffset translation.
+//!
+//! Passes that decode instructions (CFG recovery, xrefs, ioctl analysis) need
+//! the `disasm` feature; format-table walkers (GOT/PLT/IAT, pclntab, ...) do
+//! not.
 
 pub mod aarch64_literals;
+pub mod bindiff;
+pub mod carve;
+#[cfg(feature = "disasm")]
 pub mod cfg;
 pub mod cil_metadata;
+pub mod crypto;
 pub mod elf_got;
 pub mod elf_plt;
+#[cfg(feature = "exec")]
+pub mod emu;
 pub mod entry;
+pub mod function_scan;
 pub mod gopclntab;
+#[cfg(feature = "disasm")]
 pub mod ioctl_surface;
+#[cfg(feature = "disasm")]
 pub mod ioctl_taint;
 pub mod java_class;
 pub mod java_jar;
 pub mod jump_table;
+#[cfg(feature = "disasm")]
 pub mod linux_ioctl;
+#[cfg(feature = "disasm")]
 pub mod linux_symbolic_frontend;
 pub mod lua_bytecode;
 pub mod macho_stubs;
 pub mod memory;
 pub mod pe_iat;
+pub mod upx;
 pub mod view;
 pub mod vtable;
+#[cfg(feature = "disasm")]
 pub mod xrefs;
@@ -2,12 +2,16 @@
 //!
 //! Aggregates `core::binary::Binary` with sections/segments and basic
 //! translation helpers between VA/RVA/FileOffset. This is intentionally
-//! lightweight and does not perform any I/O itself.
+//! lightweight and does not perform any I/O itself; the image bytes can be
+//! attached with [`BinaryView::with_data`].
 
 use crate::core::address::{Address, AddressKind};
 use crate::core::address_range::AddressRange;
 use crate::core::binary::{Arch, Binary, Endianness, Format};
 use crate::core::{Section, Segment};
+use crate::formats::section_class::{classify_name, SectionClass};
+use crate::formats::{elf, pe};
+use std::sync::Arc;
 
 /// Analysis-time container over the immutable Binary with layout data.
 #[derive(Debug, Clone)]
@@ -22,6 +26,8 @@ pub struct BinaryView {
     pub segments: Vec<Segment>,
     /// Optional overlay/trailer region (bytes beyond last mapped range)
     pub overlay: Option<AddressRange>,
+    /// Raw bytes of the image the view was built from, when attached
+    pub data: Option<Arc<[u8]>>,
 }
 
 impl BinaryView {
@@ -39,9 +45,21 @@ impl BinaryView {
             sections,
             segments,
             overlay,
+            data: None,
         }
     }
 
+    /// Attach the raw image bytes, for analyses that scan section contents.
+    pub fn with_data(mut self, data: impl Into<Arc<[u8]>>) -> Self {
+        self.data = Some(data.into());
+        self
+    }
+
+    /// Raw image bytes, if attached with [`Self::with_data`].
+    pub fn data(&self) -> Option<&[u8]> {
+        self.data.as_deref()
+    }
+
     /// Architecture of the underlying binary
     pub fn arch(&self) -> Arch {
         self.binary.arch
@@ -55,6 +73,27 @@ impl BinaryView {
         self.binary.format
     }
 
+    /// Raw file bytes of the section called `name`, sliced from `data`
+    /// (the buffer the view was built from).
+    ///
+    /// Uses the view's section table and clamps to the end of `data`;
+    /// when the view carries no sections, `data` is parsed directly via
+    /// [`section_bytes`]. BSS-style sections have no file contents and
+    /// yield `None`.
+    pub fn section_bytes<'d>(&self, data: &'d [u8], name: &str) -> Option<&'d [u8]> {
+        if self.sections.is_empty() {
+            return section_bytes(data, name);
+        }
+        let section = self.sections.iter().find(|s| s.name == name)?;
+        if classify_name(name) == Some(SectionClass::Bss) {
+            return None;
+        }
+        let start = usize::try_from(section.file_offset.value).ok()?;
+        let len = usize::try_from(section.range.size).ok()?;
+        let end = start.saturating_add(len).min(data.len());
+        data.get(start..end).filter(|b| !b.is_empty())
+    }
+
     /// Convert a VA to a FileOffset using known segments.
     pub fn va_to_file_offset(&self, va: &Address) -> Option<Address> {
         if va.kind != AddressKind::VA {
@@ -130,6 +169,28 @@ impl BinaryView {
     }
 }
 
+/// Raw file bytes of the section called `name` in a PE or ELF image.
+///
+/// Returns `None` for other formats, unknown names, sections that occupy
+/// no file space (`SHT_NOBITS`, uninitialized PE data) and sections whose
+/// file range runs past the end of `data`.
+pub fn section_bytes<'d>(data: &'d [u8], name: &str) -> Option<&'d [u8]> {
+    let bytes = if data.starts_with(b"\x7FELF") {
+        let sections = elf::ElfParser::parse(data).ok()?.sections().ok()?;
+        let section = sections.by_name(name)?;
+        if section.header.sh_type == elf::SHT_NOBITS {
+            return None;
+        }
+        section.data_checked().ok()?
+    } else if data.starts_with(b"MZ") {
+        let parser = pe::PeParser::new(data).ok()?;
+        parser.section_by_name(name)?.data_checked(data).ok()?
+    } else {
+        return None;
+    };
+    Some(bytes).filter(|b| !b.is_empty())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -216,4 +277,43 @@ mod tests {
         let back_rva = bv.va_to_rva(&va).unwrap();
         assert_eq!(back_rva.value, 0x20);
     }
+
+    #[test]
+    fn section_bytes_slices_the_backing_buffer() {
+        let mk = |name: &str, fo: u64, size: u64| {
+            let range = AddressRange::new(
+                Address::new(AddressKind::RVA, 0x1000, 64, None, None).unwrap(),
+                size,
+                None,
+            )
+            .unwrap();
+            Section::new(
+                name.to_string(),
+                name.to_string(),
+                range,
+                Address::new(AddressKind::FileOffset, fo, 64, None, None).unwrap(),
+                None,
+                0,
+                None,
+            )
+            .unwrap()
+        };
+        let sections = vec![mk(".rodata", 4, 4), mk(".data", 12, 16), mk(".bss", 0, 64)];
+        let bv = BinaryView::new(dummy_binary(), None, sections, vec![], None);
+        let data = b"....RODA....DATA";
+        assert_eq!(bv.section_bytes(data, ".rodata"), Some(&b"RODA"[..]));
+        // Clamped to the end of the buffer.
+        assert_eq!(bv.section_bytes(data, ".data"), Some(&b"DATA"[..]));
+        assert_eq!(bv.section_bytes(data, ".bss"), None);
+        assert_eq!(bv.section_bytes(data, ".text"), None);
+
+        let elf_path = "samples/binaries/platforms/linux/amd64/export/rust/hello-rust-release";
+        if let Ok(d) = std::fs::read(elf_path) {
+            let empty = BinaryView::new(dummy_binary(), None, vec![], vec![], None);
+            let rodata = empty.section_bytes(&d, ".rodata").unwrap();
+            assert!(!rodata.is_empty());
+            assert_eq!(section_bytes(&d, ".rodata"), Some(rodata));
+            assert_eq!(section_bytes(&d, ".bss"), None);
+        }
+    }
 }
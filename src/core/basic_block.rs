@@ -31,6 +31,11 @@ pub struct BasicBlock {
     pub predecessor_ids: Vec<String>,
     /// Whether relationships were explicitly provided (for entry/exit classification)
     pub relationships_known: bool,
+    /// Hash of the block's normalized instruction sequence, filled in by
+    /// `analysis::bindiff::hash_blocks`; equal across binaries for blocks
+    /// that differ only in constants and addresses.
+    #[serde(default)]
+    pub structural_hash: Option<String>,
 }
 
 impl BasicBlock {
@@ -51,6 +56,7 @@ impl BasicBlock {
             successor_ids: successor_ids.clone().unwrap_or_default(),
             predecessor_ids: predecessor_ids.clone().unwrap_or_default(),
             relationships_known: successor_ids.is_some() || predecessor_ids.is_some(),
+            structural_hash: None,
         }
     }
 
@@ -235,6 +241,10 @@ impl BasicBlock {
     pub fn predecessor_ids(&self) -> Vec<String> {
         self.predecessor_ids.clone()
     }
+    #[getter]
+    pub fn structural_hash(&self) -> Option<String> {
+        self.structural_hash.clone()
+    }
 
     // Wrappers for helper methods
     #[pyo3(name = "size_bytes")]
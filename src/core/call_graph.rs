@@ -10,6 +10,7 @@ use std::collections::{HashMap, HashSet};
 use std::fmt;
 
 use crate::core::address::Address;
+use crate::core::control_flow_graph::dot_escape;
 
 /// Call type for edges in the call graph
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -344,6 +345,105 @@ impl CallGraph {
         false
     }
 
+    /// Compute strongly connected components using Tarjan's algorithm.
+    ///
+    /// Every function appears in exactly one component; components with
+    /// more than one member (or a single self-recursive member) are the
+    /// mutually recursive groups. Components are returned in reverse
+    /// topological order (callees before callers). The traversal is
+    /// iterative, so deep call chains cannot overflow the stack.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<String>> {
+        let mut index_of: HashMap<&str, usize> = HashMap::new();
+        let mut names: Vec<&str> = Vec::new();
+        let endpoints = self
+            .edges
+            .iter()
+            .flat_map(|e| [e.caller.as_str(), e.callee.as_str()]);
+        for name in self.nodes.iter().map(String::as_str).chain(endpoints) {
+            index_of.entry(name).or_insert_with(|| {
+                names.push(name);
+                names.len() - 1
+            });
+        }
+        let mut adj: Vec<Vec<usize>> = vec![Vec::new(); names.len()];
+        for edge in &self.edges {
+            adj[index_of[edge.caller.as_str()]].push(index_of[edge.callee.as_str()]);
+        }
+
+        let n = names.len();
+        let mut index: Vec<Option<usize>> = vec![None; n];
+        let mut lowlink = vec![0usize; n];
+        let mut on_stack = vec![false; n];
+        let mut stack: Vec<usize> = Vec::new();
+        let mut next_index = 0usize;
+        let mut components = Vec::new();
+
+        for root in 0..n {
+            if index[root].is_some() {
+                continue;
+            }
+            // (node, position of the next successor to visit)
+            let mut work: Vec<(usize, usize)> = vec![(root, 0)];
+            while let Some(&mut (v, ref mut pos)) = work.last_mut() {
+                if *pos == 0 && index[v].is_none() {
+                    index[v] = Some(next_index);
+                    lowlink[v] = next_index;
+                    next_index += 1;
+                    stack.push(v);
+                    on_stack[v] = true;
+                }
+                if let Some(&w) = adj[v].get(*pos) {
+                    *pos += 1;
+                    match index[w] {
+                        None => work.push((w, 0)),
+                        Some(wi) if on_stack[w] => lowlink[v] = lowlink[v].min(wi),
+                        Some(_) => {}
+                    }
+                    continue;
+                }
+                work.pop();
+                if let Some(&(parent, _)) = work.last() {
+                    lowlink[parent] = lowlink[parent].min(lowlink[v]);
+                }
+                if Some(lowlink[v]) == index[v] {
+                    let mut component = Vec::new();
+                    while let Some(w) = stack.pop() {
+                        on_stack[w] = false;
+                        component.push(names[w].to_string());
+                        if w == v {
+                            break;
+                        }
+                    }
+                    components.push(component);
+                }
+            }
+        }
+        components
+    }
+
+    /// Order functions bottom-up (callees before callers).
+    ///
+    /// Returns the order when the graph is acyclic; otherwise returns the
+    /// cyclic components (mutually recursive groups and self-recursive
+    /// functions) that prevent a topological order.
+    pub fn topo_order(&self) -> Result<Vec<String>, Vec<Vec<String>>> {
+        let self_recursive: HashSet<&str> = self
+            .edges
+            .iter()
+            .filter(|e| e.caller == e.callee)
+            .map(|e| e.caller.as_str())
+            .collect();
+        let (cyclic, acyclic): (Vec<_>, Vec<_>) = self
+            .strongly_connected_components()
+            .into_iter()
+            .partition(|c| c.len() > 1 || self_recursive.contains(c[0].as_str()));
+        if cyclic.is_empty() {
+            Ok(acyclic.into_iter().flatten().collect())
+        } else {
+            Err(cyclic)
+        }
+    }
+
     /// Validate the call graph
     pub fn validate(&self) -> Result<(), String> {
         // Check that all edge endpoints are in nodes
@@ -431,6 +531,116 @@ impl CallGraph {
             edges: filtered_edges,
         }
     }
+
+    /// Node ids for export: `nodes` in order, then edge endpoints that were
+    /// never added as nodes, so every edge refers to a declared node.
+    fn export_nodes(&self) -> Vec<&str> {
+        let mut seen: HashSet<&str> = self.nodes.iter().map(String::as_str).collect();
+        let mut out: Vec<&str> = self.nodes.iter().map(String::as_str).collect();
+        for edge in &self.edges {
+            for id in [edge.caller.as_str(), edge.callee.as_str()] {
+                if seen.insert(id) {
+                    out.push(id);
+                }
+            }
+        }
+        out
+    }
+
+    /// Render the call graph as Graphviz DOT.
+    ///
+    /// Edges are labeled with the call type and, when more than one, the
+    /// number of call sites; non-direct calls are drawn dashed.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph \"callgraph\" {\n");
+        out.push_str("  node [shape=box, fontname=\"monospace\"];\n");
+        for node in self.export_nodes() {
+            let id = dot_escape(node);
+            out.push_str(&format!("  \"{}\" [label=\"{}\"];\n", id, id));
+        }
+        for edge in &self.edges {
+            let mut label = edge.call_type.value().to_string();
+            if edge.call_sites.len() > 1 {
+                label.push_str(&format!(" x{}", edge.call_sites.len()));
+            }
+            let style = if edge.call_type == CallType::Direct {
+                ""
+            } else {
+                ", style=dashed"
+            };
+            out.push_str(&format!(
+                "  \"{}\" -> \"{}\" [label=\"{}\"{}];\n",
+                dot_escape(&edge.caller),
+                dot_escape(&edge.callee),
+                label,
+                style
+            ));
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Render the call graph as GraphML (importable by Gephi, yEd, networkx).
+    ///
+    /// Edges carry `call_type`, `call_sites` (count) and, when known,
+    /// `confidence` attributes.
+    pub fn to_graphml(&self) -> String {
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+        out.push_str(
+            "  <key id=\"call_type\" for=\"edge\" attr.name=\"call_type\" attr.type=\"string\"/>\n",
+        );
+        out.push_str(
+            "  <key id=\"call_sites\" for=\"edge\" attr.name=\"call_sites\" attr.type=\"int\"/>\n",
+        );
+        out.push_str(
+            "  <key id=\"confidence\" for=\"edge\" attr.name=\"confidence\" attr.type=\"double\"/>\n",
+        );
+        out.push_str("  <graph id=\"callgraph\" edgedefault=\"directed\">\n");
+        for node in self.export_nodes() {
+            out.push_str(&format!("    <node id=\"{}\"/>\n", xml_escape(node)));
+        }
+        for (i, edge) in self.edges.iter().enumerate() {
+            out.push_str(&format!(
+                "    <edge id=\"e{}\" source=\"{}\" target=\"{}\">\n",
+                i,
+                xml_escape(&edge.caller),
+                xml_escape(&edge.callee)
+            ));
+            out.push_str(&format!(
+                "      <data key=\"call_type\">{}</data>\n",
+                edge.call_type.value()
+            ));
+            out.push_str(&format!(
+                "      <data key=\"call_sites\">{}</data>\n",
+                edge.call_sites.len()
+            ));
+            if let Some(confidence) = edge.confidence {
+                out.push_str(&format!(
+                    "      <data key=\"confidence\">{}</data>\n",
+                    confidence
+                ));
+            }
+            out.push_str("    </edge>\n");
+        }
+        out.push_str("  </graph>\n</graphml>\n");
+        out
+    }
+}
+
+fn xml_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            _ => out.push(c),
+        }
+    }
+    out
 }
 
 /// Statistics about a call graph
@@ -607,6 +817,29 @@ impl CallGraph {
     fn subgraph_py(&self, function_ids: Vec<String>) -> Self {
         self.subgraph(&function_ids)
     }
+
+    #[pyo3(name = "strongly_connected_components")]
+    fn strongly_connected_components_py(&self) -> Vec<Vec<String>> {
+        self.strongly_connected_components()
+    }
+
+    /// Bottom-up order; raises ValueError listing the cycles when cyclic.
+    #[pyo3(name = "topo_order")]
+    fn topo_order_py(&self) -> PyResult<Vec<String>> {
+        self.topo_order().map_err(|cycles| {
+            pyo3::exceptions::PyValueError::new_err(format!("call graph has cycles: {:?}", cycles))
+        })
+    }
+
+    #[pyo3(name = "to_dot")]
+    fn to_dot_py(&self) -> String {
+        self.to_dot()
+    }
+
+    #[pyo3(name = "to_graphml")]
+    fn to_graphml_py(&self) -> String {
+        self.to_graphml()
+    }
 }
 
 // PyO3 bindings for CallGraphEdge
@@ -956,4 +1189,104 @@ mod tests {
         assert_eq!(subgraph.function_count(), 3);
         assert_eq!(subgraph.edge_count(), 2); // main->func1, func1->helper
     }
+
+    #[test]
+    fn test_strongly_connected_components() {
+        let cg = create_test_call_graph();
+        let mut sccs: Vec<Vec<String>> = cg
+            .strongly_connected_components()
+            .into_iter()
+            .map(|mut c| {
+                c.sort();
+                c
+            })
+            .collect();
+        sccs.sort();
+        let expected: Vec<Vec<String>> = [
+            vec!["func1", "func3", "helper"],
+            vec!["func2"],
+            vec!["main"],
+        ]
+        .iter()
+        .map(|c| c.iter().map(|s| s.to_string()).collect())
+        .collect();
+        assert_eq!(sccs, expected);
+    }
+
+    #[test]
+    fn test_scc_order_and_deep_chain() {
+        // Callees come before callers.
+        let mut cg = CallGraph::new();
+        cg.add_simple_edge("a".to_string(), "b".to_string(), CallType::Direct);
+        assert_eq!(
+            cg.strongly_connected_components(),
+            vec![vec!["b".to_string()], vec!["a".to_string()]]
+        );
+
+        // A long chain closed into one cycle must not overflow the stack.
+        let mut cg = CallGraph::new();
+        for i in 0..50_000 {
+            cg.add_simple_edge(
+                format!("f{}", i),
+                format!("f{}", (i + 1) % 50_000),
+                CallType::Direct,
+            );
+        }
+        let sccs = cg.strongly_connected_components();
+        assert_eq!(sccs.len(), 1);
+        assert_eq!(sccs[0].len(), 50_000);
+    }
+
+    #[test]
+    fn test_topo_order() {
+        let mut cg = CallGraph::new();
+        cg.add_nodes(vec!["main".to_string(), "a".to_string(), "b".to_string()]);
+        cg.add_simple_edge("main".to_string(), "a".to_string(), CallType::Direct);
+        cg.add_simple_edge("a".to_string(), "b".to_string(), CallType::Direct);
+        cg.add_simple_edge("main".to_string(), "b".to_string(), CallType::Direct);
+        assert_eq!(
+            cg.topo_order(),
+            Ok(vec!["b".to_string(), "a".to_string(), "main".to_string()])
+        );
+
+        cg.add_simple_edge("b".to_string(), "b".to_string(), CallType::Direct);
+        assert_eq!(cg.topo_order(), Err(vec![vec!["b".to_string()]]));
+
+        let err = create_test_call_graph().topo_order().unwrap_err();
+        assert_eq!(err.len(), 1);
+        assert_eq!(err[0].len(), 3);
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let mut cg = create_test_call_graph();
+        cg.add_simple_edge("main".to_string(), "vfunc".to_string(), CallType::Virtual);
+        let dot = cg.to_dot();
+        assert!(dot.starts_with("digraph \"callgraph\" {\n"));
+        assert!(dot.contains("  \"main\" [label=\"main\"];\n"));
+        assert!(dot.contains("  \"vfunc\" [label=\"vfunc\"];\n"));
+        assert!(dot.contains("  \"main\" -> \"func1\" [label=\"direct\"];\n"));
+        assert!(dot.contains("  \"main\" -> \"vfunc\" [label=\"virtual\", style=dashed];\n"));
+        assert!(dot.ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_to_graphml() {
+        let mut cg = CallGraph::new();
+        cg.add_edge(CallGraphEdge::with_confidence(
+            "a<int>".to_string(),
+            "b&c".to_string(),
+            CallType::Indirect,
+            0.5,
+        ));
+        let xml = cg.to_graphml();
+        assert!(xml.contains("<graph id=\"callgraph\" edgedefault=\"directed\">"));
+        assert!(xml.contains("<node id=\"a&lt;int&gt;\"/>"));
+        assert!(xml.contains("<node id=\"b&amp;c\"/>"));
+        assert!(xml.contains("<edge id=\"e0\" source=\"a&lt;int&gt;\" target=\"b&amp;c\">"));
+        assert!(xml.contains("<data key=\"call_type\">indirect</data>"));
+        assert!(xml.contains("<data key=\"call_sites\">0</data>"));
+        assert!(xml.contains("<data key=\"confidence\">0.5</data>"));
+        assert!(xml.ends_with("</graphml>\n"));
+    }
 }
@@ -277,6 +277,177 @@ impl ControlFlowGraph {
         false
     }
 
+    /// Compute immediate dominators from `entry` (Cooper-Harvey-Kennedy).
+    ///
+    /// Maps every block reachable from `entry` to its immediate dominator.
+    /// The entry itself has no immediate dominator and is omitted, as are
+    /// unreachable blocks. Returns an empty map if `entry` is not a block.
+    pub fn dominators(&self, entry: &str) -> HashMap<String, String> {
+        let Some(entry_idx) = self.block_ids.iter().position(|b| b == entry) else {
+            return HashMap::new();
+        };
+        let index_of: HashMap<&str, usize> = self
+            .block_ids
+            .iter()
+            .enumerate()
+            .map(|(i, b)| (b.as_str(), i))
+            .collect();
+        let n = self.block_ids.len();
+        let mut succs: Vec<Vec<usize>> = vec![Vec::new(); n];
+        let mut preds: Vec<Vec<usize>> = vec![Vec::new(); n];
+        for edge in &self.edges {
+            if let (Some(&f), Some(&t)) = (
+                index_of.get(edge.from_block_id.as_str()),
+                index_of.get(edge.to_block_id.as_str()),
+            ) {
+                succs[f].push(t);
+                preds[t].push(f);
+            }
+        }
+
+        // Iterative DFS postorder from the entry.
+        let mut postorder = Vec::with_capacity(n);
+        let mut visited = vec![false; n];
+        let mut work = vec![(entry_idx, 0usize)];
+        visited[entry_idx] = true;
+        while let Some((v, pos)) = work.last_mut() {
+            if let Some(&w) = succs[*v].get(*pos) {
+                *pos += 1;
+                if !visited[w] {
+                    visited[w] = true;
+                    work.push((w, 0));
+                }
+            } else {
+                postorder.push(*v);
+                work.pop();
+            }
+        }
+        let mut po_num = vec![usize::MAX; n];
+        for (i, &b) in postorder.iter().enumerate() {
+            po_num[b] = i;
+        }
+
+        let mut idom: Vec<Option<usize>> = vec![None; n];
+        idom[entry_idx] = Some(entry_idx);
+        let intersect = |idom: &[Option<usize>], mut a: usize, mut b: usize| {
+            while a != b {
+                while po_num[a] < po_num[b] {
+                    a = idom[a].expect("processed block has idom");
+                }
+                while po_num[b] < po_num[a] {
+                    b = idom[b].expect("processed block has idom");
+                }
+            }
+            a
+        };
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &b in postorder.iter().rev().filter(|&&b| b != entry_idx) {
+                let mut new_idom: Option<usize> = None;
+                for &p in &preds[b] {
+                    if idom[p].is_none() {
+                        continue;
+                    }
+                    new_idom = Some(match new_idom {
+                        None => p,
+                        Some(cur) => intersect(&idom, p, cur),
+                    });
+                }
+                if new_idom.is_some() && idom[b] != new_idom {
+                    idom[b] = new_idom;
+                    changed = true;
+                }
+            }
+        }
+
+        idom.iter()
+            .enumerate()
+            .filter(|&(b, _)| b != entry_idx)
+            .filter_map(|(b, d)| d.map(|d| (self.block_ids[b].clone(), self.block_ids[d].clone())))
+            .collect()
+    }
+
+    /// Immediate dominator of `block_id`, taking the first block in
+    /// `block_ids` as the function entry.
+    ///
+    /// Recomputes the dominator tree on every call; use [`Self::dominators`]
+    /// when querying many blocks.
+    pub fn immediate_dominator(&self, block_id: &str) -> Option<String> {
+        let entry = self.block_ids.first()?;
+        self.dominators(entry).remove(block_id)
+    }
+
+    /// Find natural loops, taking the first block in `block_ids` as entry.
+    ///
+    /// A back edge is an edge `latch -> header` where `header` dominates
+    /// `latch`; the loop body is every block dominated by the header that
+    /// reaches a latch without passing through the header. Loops sharing a header are merged.
+    /// Loops are ordered by header position in `block_ids`.
+    pub fn natural_loops(&self) -> Vec<Loop> {
+        let Some(entry) = self.block_ids.first() else {
+            return Vec::new();
+        };
+        let idoms = self.dominators(entry);
+        let dominates = |a: &str, b: &str| {
+            let mut cur = b;
+            loop {
+                if cur == a {
+                    return true;
+                }
+                match idoms.get(cur) {
+                    Some(d) => cur = d,
+                    None => return false,
+                }
+            }
+        };
+
+        let mut latches_by_header: HashMap<&str, Vec<String>> = HashMap::new();
+        for edge in &self.edges {
+            let (latch, header) = (edge.from_block_id.as_str(), edge.to_block_id.as_str());
+            // Unreachable latches have no dominator chain; skip them.
+            if (latch == entry || idoms.contains_key(latch)) && dominates(header, latch) {
+                let latches = latches_by_header.entry(header).or_default();
+                if !latches.iter().any(|l| l == latch) {
+                    latches.push(latch.to_string());
+                }
+            }
+        }
+
+        let mut loops = Vec::new();
+        for header in &self.block_ids {
+            let Some(latches) = latches_by_header.remove(header.as_str()) else {
+                continue;
+            };
+            let mut body: HashSet<String> = HashSet::new();
+            body.insert(header.clone());
+            let mut work: Vec<String> = latches.clone();
+            while let Some(block) = work.pop() {
+                if body.insert(block.clone()) {
+                    // Only blocks the header dominates belong to the loop;
+                    // this also drops predecessors unreachable from entry.
+                    work.extend(
+                        self.predecessors(&block)
+                            .into_iter()
+                            .filter(|p| dominates(header, p)),
+                    );
+                }
+            }
+            let blocks = self
+                .block_ids
+                .iter()
+                .filter(|b| body.contains(*b))
+                .cloned()
+                .collect();
+            loops.push(Loop {
+                header: header.clone(),
+                latches,
+                blocks,
+            });
+        }
+        loops
+    }
+
     /// Validate the control flow graph
     pub fn validate(&self) -> Result<(), String> {
         // Check that all edge endpoints are in block_ids
@@ -355,6 +526,53 @@ impl ControlFlowGraph {
             edge_kind_counts: edge_counts,
         }
     }
+
+    /// Render the graph as Graphviz DOT.
+    ///
+    /// Nodes are labeled with their block IDs (typically block start
+    /// addresses) and edges with their kind; entry blocks are drawn bold.
+    pub fn to_dot(&self) -> String {
+        let name = self.function_id.as_deref().unwrap_or("cfg");
+        let entries: HashSet<String> = self.entry_blocks().into_iter().collect();
+        let mut out = format!("digraph \"{}\" {{\n", dot_escape(name));
+        out.push_str("  node [shape=box, fontname=\"monospace\"];\n");
+        for block_id in &self.block_ids {
+            let id = dot_escape(block_id);
+            if entries.contains(block_id) {
+                out.push_str(&format!("  \"{}\" [label=\"{}\", style=bold];\n", id, id));
+            } else {
+                out.push_str(&format!("  \"{}\" [label=\"{}\"];\n", id, id));
+            }
+        }
+        for edge in &self.edges {
+            let label = match edge.confidence {
+                Some(c) => format!("{} ({:.2})", edge.kind.value(), c),
+                None => edge.kind.value().to_string(),
+            };
+            out.push_str(&format!(
+                "  \"{}\" -> \"{}\" [label=\"{}\"];\n",
+                dot_escape(&edge.from_block_id),
+                dot_escape(&edge.to_block_id),
+                label
+            ));
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+/// Escape a string for use inside a double-quoted DOT identifier or label.
+pub(crate) fn dot_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            _ => out.push(c),
+        }
+    }
+    out
 }
 
 /// Statistics about a control flow graph
@@ -370,6 +588,25 @@ pub struct ControlFlowGraphStats {
     pub edge_kind_counts: HashMap<ControlFlowEdgeKind, usize>,
 }
 
+/// A natural loop identified from one or more back edges
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "python-ext", pyclass)]
+pub struct Loop {
+    /// Loop header block ID (target of the back edges)
+    pub header: String,
+    /// Blocks with a back edge to the header
+    pub latches: Vec<String>,
+    /// All blocks in the loop, including header and latches
+    pub blocks: Vec<String>,
+}
+
+impl Loop {
+    /// Check whether a block belongs to this loop
+    pub fn contains(&self, block_id: &str) -> bool {
+        self.blocks.iter().any(|b| b == block_id)
+    }
+}
+
 impl fmt::Display for ControlFlowGraph {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -557,6 +794,26 @@ impl ControlFlowGraph {
     fn subgraph_py(&self, block_ids: Vec<String>) -> Self {
         self.subgraph(&block_ids)
     }
+
+    #[pyo3(name = "dominators")]
+    fn dominators_py(&self, entry: String) -> HashMap<String, String> {
+        self.dominators(&entry)
+    }
+
+    #[pyo3(name = "immediate_dominator")]
+    fn immediate_dominator_py(&self, block_id: String) -> Option<String> {
+        self.immediate_dominator(&block_id)
+    }
+
+    #[pyo3(name = "natural_loops")]
+    fn natural_loops_py(&self) -> Vec<Loop> {
+        self.natural_loops()
+    }
+
+    #[pyo3(name = "to_dot")]
+    fn to_dot_py(&self) -> String {
+        self.to_dot()
+    }
 }
 
 // PyO3 bindings for ControlFlowEdge
@@ -737,6 +994,39 @@ impl ControlFlowGraphStats {
     }
 }
 
+#[cfg(feature = "python-ext")]
+#[pymethods]
+impl Loop {
+    fn __repr__(&self) -> String {
+        format!(
+            "Loop(header={:?}, latches={:?}, blocks={})",
+            self.header,
+            self.latches,
+            self.blocks.len()
+        )
+    }
+
+    #[getter]
+    fn header(&self) -> String {
+        self.header.clone()
+    }
+
+    #[getter]
+    fn latches(&self) -> Vec<String> {
+        self.latches.clone()
+    }
+
+    #[getter]
+    fn blocks(&self) -> Vec<String> {
+        self.blocks.clone()
+    }
+
+    #[pyo3(name = "contains")]
+    fn contains_py(&self, block_id: String) -> bool {
+        self.contains(&block_id)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -879,4 +1169,126 @@ mod tests {
         assert!(stats.has_cycles);
         assert_eq!(stats.edge_kind_counts[&ControlFlowEdgeKind::Branch], 4);
     }
+
+    #[test]
+    fn test_dominators() {
+        // entry -> a -> c, entry -> b -> c, c -> exit, plus loop c -> a.
+        let mut cfg = ControlFlowGraph::for_function("f".to_string());
+        for (from, to) in [
+            ("entry", "a"),
+            ("entry", "b"),
+            ("a", "c"),
+            ("b", "c"),
+            ("c", "a"),
+            ("c", "exit"),
+        ] {
+            cfg.add_simple_edge(
+                from.to_string(),
+                to.to_string(),
+                ControlFlowEdgeKind::Branch,
+            );
+        }
+        cfg.add_block("unreachable".to_string());
+
+        let doms = cfg.dominators("entry");
+        assert_eq!(doms.len(), 4);
+        assert_eq!(doms["a"], "entry");
+        assert_eq!(doms["b"], "entry");
+        assert_eq!(doms["c"], "entry");
+        assert_eq!(doms["exit"], "c");
+        assert!(!doms.contains_key("entry"));
+        assert!(!doms.contains_key("unreachable"));
+
+        assert_eq!(cfg.immediate_dominator("exit"), Some("c".to_string()));
+        assert_eq!(cfg.immediate_dominator("entry"), None);
+        assert!(cfg.dominators("missing").is_empty());
+
+        let loop_cfg = create_test_cfg();
+        let doms = loop_cfg.dominators("entry");
+        assert_eq!(doms["loop_header"], "entry");
+        assert_eq!(doms["loop_body"], "loop_header");
+        assert_eq!(doms["exit"], "loop_header");
+    }
+
+    #[test]
+    fn test_natural_loops() {
+        let cfg = create_test_cfg();
+        let loops = cfg.natural_loops();
+        assert_eq!(loops.len(), 1);
+        assert_eq!(loops[0].header, "loop_header");
+        assert_eq!(loops[0].latches, vec!["loop_body".to_string()]);
+        assert_eq!(
+            loops[0].blocks,
+            vec!["loop_header".to_string(), "loop_body".to_string()]
+        );
+        assert!(!loops[0].contains("exit"));
+
+        // Nested loops plus a self loop; an irreducible edge is not a loop.
+        let mut cfg = ControlFlowGraph::new();
+        for (from, to) in [
+            ("entry", "outer"),
+            ("outer", "inner"),
+            ("inner", "inner_body"),
+            ("inner_body", "inner"),
+            ("inner", "outer_latch"),
+            ("outer_latch", "outer"),
+            ("outer", "spin"),
+            ("spin", "spin"),
+            ("entry", "x"),
+            ("x", "y"),
+            ("entry", "y"),
+            ("y", "x"),
+        ] {
+            cfg.add_simple_edge(
+                from.to_string(),
+                to.to_string(),
+                ControlFlowEdgeKind::Branch,
+            );
+        }
+        let loops = cfg.natural_loops();
+        let headers: Vec<&str> = loops.iter().map(|l| l.header.as_str()).collect();
+        assert_eq!(headers, vec!["outer", "inner", "spin"]);
+        assert_eq!(
+            loops[0].blocks,
+            vec!["outer", "inner", "inner_body", "outer_latch"]
+        );
+        assert_eq!(loops[1].blocks, vec!["inner", "inner_body"]);
+        assert_eq!(loops[2].blocks, vec!["spin"]);
+        assert_eq!(loops[2].latches, vec!["spin"]);
+    }
+
+    #[test]
+    fn test_natural_loops_skip_unreachable_predecessors() {
+        let mut cfg = create_test_cfg();
+        // Dead code jumping into the loop body is not part of the loop.
+        cfg.add_simple_edge(
+            "dead".to_string(),
+            "loop_body".to_string(),
+            ControlFlowEdgeKind::Branch,
+        );
+        let loops = cfg.natural_loops();
+        assert_eq!(loops.len(), 1);
+        assert_eq!(
+            loops[0].blocks,
+            vec!["loop_header".to_string(), "loop_body".to_string()]
+        );
+        assert!(!loops[0].contains("dead"));
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let mut cfg = create_test_cfg();
+        cfg.add_edge(ControlFlowEdge::with_confidence(
+            "exit".to_string(),
+            "say \"hi\"".to_string(),
+            ControlFlowEdgeKind::Call,
+            0.5,
+        ));
+        let dot = cfg.to_dot();
+        assert!(dot.starts_with("digraph \"test_func\" {\n"));
+        assert!(dot.contains("  \"entry\" [label=\"entry\", style=bold];\n"));
+        assert!(dot.contains("  \"loop_body\" -> \"loop_header\" [label=\"branch\"];\n"));
+        assert!(dot.contains("  \"exit\" -> \"say \\\"hi\\\"\" [label=\"call (0.50)\"];\n"));
+        assert!(dot.ends_with("}\n"));
+    }
 }
@@ -9,25 +9,45 @@ use serde::{Deserialize, Serialize};
 #[cfg_attr(feature = "python-ext", pyclass)]
 pub struct ContainerChild {
     pub type_name: String,
+    /// Offset of the child within the analyzed buffer, or within the
+    /// decompressed stream when `in_decompressed_stream` is set
     pub offset: u64,
     pub size: u64,
+    /// True when the child's bytes only exist after decompressing an ancestor
+    /// (e.g. `.tar.gz` members, files in compressed CAB folders)
+    #[serde(default)]
+    pub in_decompressed_stream: bool,
+    /// Member path/name when the child is a named archive entry (e.g., tar member)
+    #[serde(default)]
+    pub name: Option<String>,
     /// Optional container metadata (e.g., counts, sizes)
     pub metadata: Option<ContainerMetadata>,
     /// Optional nested children (recursion tree)
     pub children: Option<Vec<ContainerChild>>,
+    /// Shannon entropy of the child bytes (when child fingerprinting is enabled)
+    #[serde(default)]
+    pub entropy: Option<f64>,
+    /// CTPH digest of the child bytes (when child fingerprinting is enabled)
+    #[serde(default)]
+    pub ctph: Option<String>,
 }
 
 #[cfg(feature = "python-ext")]
 #[pymethods]
 impl ContainerChild {
     #[new]
+    #[pyo3(signature = (type_name, offset, size))]
     pub fn new_py(type_name: String, offset: u64, size: u64) -> Self {
         Self {
             type_name,
             offset,
             size,
+            in_decompressed_stream: false,
+            name: None,
             metadata: None,
             children: None,
+            entropy: None,
+            ctph: None,
         }
     }
     #[getter]
@@ -43,6 +63,14 @@ impl ContainerChild {
         self.size
     }
     #[getter]
+    fn in_decompressed_stream(&self) -> bool {
+        self.in_decompressed_stream
+    }
+    #[getter]
+    fn name(&self) -> Option<String> {
+        self.name.clone()
+    }
+    #[getter]
     fn metadata(&self) -> Option<ContainerMetadata> {
         self.metadata.clone()
     }
@@ -51,6 +79,14 @@ impl ContainerChild {
     fn children(&self) -> Option<Vec<ContainerChild>> {
         self.children.clone()
     }
+    #[getter]
+    fn entropy(&self) -> Option<f64> {
+        self.entropy
+    }
+    #[getter]
+    fn ctph(&self) -> Option<String> {
+        self.ctph.clone()
+    }
 }
 
 /// Optional metadata extracted from container formats without full extraction.
@@ -90,8 +126,20 @@ impl ContainerChild {
             type_name,
             offset,
             size,
+            in_decompressed_stream: false,
+            name: None,
             metadata: None,
             children: None,
+            entropy: None,
+            ctph: None,
+        }
+    }
+
+    /// Construct a named archive member child.
+    pub fn member(type_name: String, name: String, offset: u64, size: u64) -> Self {
+        Self {
+            name: Some(name),
+            ..Self::new(type_name, offset, size)
         }
     }
 }
@@ -4,6 +4,8 @@
 use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use crate::entropy::RunningStats;
+
 /// Entropy summary for an input.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(feature = "python-ext", pyclass)]
@@ -31,15 +33,7 @@ impl EntropySummary {
         window_size: Option<u32>,
         windows: Option<Vec<f64>>,
     ) -> Self {
-        Self {
-            overall,
-            window_size,
-            windows,
-            mean: None,
-            std_dev: None,
-            min: None,
-            max: None,
-        }
+        Self::new(overall, window_size, windows)
     }
 
     #[getter]
@@ -147,6 +141,44 @@ impl EntropyAnomaly {
     }
 }
 
+/// Window whose entropy stands out from the rest of the file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "python-ext", pyclass)]
+pub struct EntropyOutlier {
+    /// Index into `EntropySummary.windows`.
+    pub index: usize,
+    pub entropy: f64,
+    /// Signed distance from the window mean in standard deviations;
+    /// negative for windows less random than their surroundings.
+    pub z_score: f64,
+}
+
+#[cfg(feature = "python-ext")]
+#[pymethods]
+impl EntropyOutlier {
+    #[getter]
+    fn index(&self) -> usize {
+        self.index
+    }
+
+    #[getter]
+    fn entropy(&self) -> f64 {
+        self.entropy
+    }
+
+    #[getter]
+    fn z_score(&self) -> f64 {
+        self.z_score
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "EntropyOutlier(index={}, entropy={:.2}, z_score={:.2})",
+            self.index, self.entropy, self.z_score
+        )
+    }
+}
+
 /// Heuristics to detect packing/compression patterns.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(feature = "python-ext", pyclass)]
@@ -186,6 +218,9 @@ pub struct EntropyAnalysis {
     pub classification: EntropyClass,
     pub packed_indicators: PackedIndicators,
     pub anomalies: Vec<EntropyAnomaly>,
+    /// Windows far from the mean window entropy (z-score based).
+    #[serde(default)]
+    pub outliers: Vec<EntropyOutlier>,
 }
 
 #[cfg(feature = "python-ext")]
@@ -214,19 +249,26 @@ impl EntropyAnalysis {
     fn anomalies(&self) -> Vec<EntropyAnomaly> {
         self.anomalies.clone()
     }
+    #[getter]
+    fn outliers(&self) -> Vec<EntropyOutlier> {
+        self.outliers.clone()
+    }
 }
 
 // Pure Rust constructors and helpers
 impl EntropySummary {
+    /// Summary with `mean`, `std_dev`, `min` and `max` computed from
+    /// `windows`; they stay `None` when there are no windows.
     pub fn new(overall: Option<f64>, window_size: Option<u32>, windows: Option<Vec<f64>>) -> Self {
+        let stats: RunningStats = windows.iter().flatten().copied().collect();
         Self {
             overall,
             window_size,
             windows,
-            mean: None,
-            std_dev: None,
-            min: None,
-            max: None,
+            mean: stats.mean(),
+            std_dev: stats.std_dev(),
+            min: stats.min(),
+            max: stats.max(),
         }
     }
 }
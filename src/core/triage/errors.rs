@@ -43,30 +43,77 @@ impl TriageErrorKind {
     }
 }
 
+/// How much a triage error matters to consumers, ordered least to most severe.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Hash, Default,
+)]
+#[cfg_attr(feature = "python-ext", pyclass(eq, eq_int))]
+pub enum Severity {
+    /// Informational notice; the artifact is fine.
+    Info,
+    /// Something looks off but analysis results are still trustworthy.
+    Warning,
+    /// A genuine failure (bad magic, truncation, incoherent structure).
+    #[default]
+    Error,
+}
+
+#[cfg(feature = "python-ext")]
+#[pymethods]
+impl Severity {
+    fn __str__(&self) -> String {
+        self.to_string()
+    }
+    fn __repr__(&self) -> String {
+        format!("Severity.{}", self)
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Info => write!(f, "Info"),
+            Severity::Warning => write!(f, "Warning"),
+            Severity::Error => write!(f, "Error"),
+        }
+    }
+}
+
 /// Concrete error with optional message.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(feature = "python-ext", pyclass)]
 pub struct TriageError {
     pub kind: TriageErrorKind,
     pub message: Option<String>,
+    /// Severity of the error (defaults to `Error` for older serialized data).
+    #[serde(default)]
+    pub severity: Severity,
 }
 
 #[cfg(feature = "python-ext")]
 #[pymethods]
 impl TriageError {
     #[new]
-    #[pyo3(signature = (kind, message=None))]
-    pub fn new_py(kind: TriageErrorKind, message: Option<String>) -> Self {
-        Self { kind, message }
+    #[pyo3(signature = (kind, message=None, severity=None))]
+    pub fn new_py(
+        kind: TriageErrorKind,
+        message: Option<String>,
+        severity: Option<Severity>,
+    ) -> Self {
+        Self {
+            kind,
+            message,
+            severity: severity.unwrap_or_else(|| kind.default_severity()),
+        }
     }
     #[staticmethod]
     pub fn create(kind: TriageErrorKind, message: Option<String>) -> Self {
-        Self { kind, message }
+        Self::new(kind, message)
     }
     fn __repr__(&self) -> String {
         format!(
-            "TriageError(kind={:?}, message={:?})",
-            self.kind, self.message
+            "TriageError(kind={:?}, message={:?}, severity={:?})",
+            self.kind, self.message, self.severity
         )
     }
     #[getter]
@@ -77,6 +124,25 @@ impl TriageError {
     fn message(&self) -> Option<String> {
         self.message.clone()
     }
+    #[getter]
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+}
+
+impl TriageErrorKind {
+    /// Severity assigned to errors of this kind unless overridden.
+    ///
+    /// Budget notices are informational, cross-checks and unrecognized
+    /// variants are warnings, and structural parse failures are errors.
+    pub fn default_severity(self) -> Severity {
+        use TriageErrorKind::*;
+        match self {
+            BudgetExceeded => Severity::Info,
+            UnsupportedVariant | SnifferMismatch | Other => Severity::Warning,
+            ShortRead | BadMagic | IncoherentFields | Truncated | ParserMismatch => Severity::Error,
+        }
+    }
 }
 
 impl fmt::Display for TriageErrorKind {
@@ -98,7 +164,51 @@ impl fmt::Display for TriageErrorKind {
 
 // Pure Rust constructors and helpers
 impl TriageError {
+    /// Create an error with the kind's default severity.
     pub fn new(kind: TriageErrorKind, message: Option<String>) -> Self {
-        Self { kind, message }
+        Self {
+            kind,
+            message,
+            severity: kind.default_severity(),
+        }
+    }
+
+    /// Override the severity of this error.
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// True if this error is at least as severe as `min`.
+    pub fn is_at_least(&self, min: Severity) -> bool {
+        self.severity >= min
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_severity_per_kind() {
+        let budget = TriageError::new(TriageErrorKind::BudgetExceeded, None);
+        assert_eq!(budget.severity, Severity::Info);
+        let mismatch = TriageError::new(TriageErrorKind::SnifferMismatch, None);
+        assert_eq!(mismatch.severity, Severity::Warning);
+        let magic = TriageError::new(TriageErrorKind::BadMagic, None);
+        assert_eq!(magic.severity, Severity::Error);
+        assert!(magic.is_at_least(Severity::Warning));
+        assert!(!budget.is_at_least(Severity::Warning));
+    }
+
+    #[test]
+    fn missing_severity_deserializes_as_error() {
+        let e: TriageError =
+            serde_json::from_str(r#"{"kind":"Truncated","message":null}"#).unwrap();
+        assert_eq!(e.severity, Severity::Error);
+        let w = TriageError::new(TriageErrorKind::Other, Some("x".into()))
+            .with_severity(Severity::Info);
+        let back: TriageError = serde_json::from_str(&serde_json::to_string(&w).unwrap()).unwrap();
+        assert_eq!(back, w);
     }
 }
@@ -1,16 +1,48 @@
 //! Format-specific triage information.
 
 use crate::triage::rich_header::RichHeader;
+use chrono::{DateTime, Utc};
 #[cfg(feature = "python-ext")]
 use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
 
 /// PE-specific triage information.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[cfg_attr(feature = "python-ext", pyclass(get_all))]
+#[cfg_attr(feature = "python-ext", pyclass)]
 pub struct PeTriageInfo {
     /// Rich Header information, if present.
     pub rich_header: Option<RichHeader>,
+    /// Raw COFF `TimeDateStamp`, when the headers parsed.
+    #[serde(default)]
+    pub time_date_stamp: Option<u32>,
+    /// `time_date_stamp` decoded as UTC; `None` when the stamp is zero.
+    #[serde(default)]
+    pub compile_time: Option<DateTime<Utc>>,
+    /// The debug directory carries a REPRO entry, so the stamp is a hash.
+    #[serde(default)]
+    pub reproducible: bool,
+}
+
+#[cfg(feature = "python-ext")]
+#[pymethods]
+impl PeTriageInfo {
+    #[getter]
+    fn rich_header(&self) -> Option<RichHeader> {
+        self.rich_header.clone()
+    }
+    #[getter]
+    fn time_date_stamp(&self) -> Option<u32> {
+        self.time_date_stamp
+    }
+    #[getter]
+    fn reproducible(&self) -> bool {
+        self.reproducible
+    }
+    /// Compile time as an RFC 3339 string.
+    #[getter]
+    fn compile_time(&self) -> Option<String> {
+        self.compile_time.map(|t| t.to_rfc3339())
+    }
 }
 
 /// ELF-specific triage information.
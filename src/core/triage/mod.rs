@@ -13,13 +13,14 @@ pub mod verdict;
 // Re-exports for convenient access under crate::core::triage::*
 pub use containers::{ContainerChild, ContainerMetadata};
 pub use entropy::{
-    EntropyAnalysis, EntropyAnomaly, EntropyClass, EntropySummary, PackedIndicators,
+    EntropyAnalysis, EntropyAnomaly, EntropyClass, EntropyOutlier, EntropySummary, PackedIndicators,
 };
-pub use errors::{TriageError, TriageErrorKind};
+pub use errors::{Severity, TriageError, TriageErrorKind};
 pub use hints::{ConfidenceSignal, SnifferSource, TriageHint};
 pub use packers::PackerMatch;
 pub use parsers::{ParserKind, ParserResult};
-pub use strings::{DetectedString, IocSample, StringsSummary};
+pub use strings::{DetectedString, IocSample, StringsSummary, UrlComponents};
 pub use verdict::{
-    Budgets, SimilaritySummary, TriageVerdict, TriagedArtifact, TriagedArtifactBuilder,
+    Budgets, PhaseTimings, SimilaritySummary, TriageVerdict, TriagedArtifact,
+    TriagedArtifactBuilder,
 };
@@ -12,6 +12,24 @@ pub struct IocSample {
     pub kind: String,
     pub text: String,
     pub offset: Option<u64>,
+    /// Whether `text` was cut at `StringsConfig::ioc_sample_max_len`
+    #[serde(default)]
+    pub truncated: bool,
+    /// Parsed components of a `url` match, with
+    /// `StringsConfig::parse_url_components`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<UrlComponents>,
+}
+
+/// Components of a URL IOC, taken from the full (untruncated) match.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "python-ext", pyclass)]
+pub struct UrlComponents {
+    pub scheme: String,
+    pub host: Option<String>,
+    /// Explicit port, or the scheme's default port
+    pub port: Option<u16>,
+    pub path: String,
 }
 
 // Python accessors for IocSample are defined later in this file
@@ -32,6 +50,16 @@ pub struct DetectedString {
     pub confidence: Option<f64>,
     /// Offset in the binary where string was found
     pub offset: Option<u64>,
+    /// Whether `text` was cut at `StringsConfig::max_string_length`
+    #[serde(default)]
+    pub truncated: bool,
+    /// Occurrences collapsed into this entry by `StringsConfig::dedup_strings`
+    #[serde(default = "default_count")]
+    pub count: u32,
+}
+
+fn default_count() -> u32 {
+    1
 }
 
 #[cfg(feature = "python-ext")]
@@ -54,6 +82,8 @@ impl DetectedString {
             script,
             confidence,
             offset,
+            truncated: false,
+            count: 1,
         }
     }
 
@@ -87,6 +117,16 @@ impl DetectedString {
         self.offset
     }
 
+    #[getter]
+    fn truncated(&self) -> bool {
+        self.truncated
+    }
+
+    #[getter]
+    fn count(&self) -> u32 {
+        self.count
+    }
+
     fn __str__(&self) -> String {
         match (&self.language, &self.script, self.confidence) {
             (Some(lang), Some(script), Some(conf)) => {
@@ -223,7 +263,38 @@ impl StringsSummary {
 // Pure Rust constructors and helpers
 impl IocSample {
     pub fn new(kind: String, text: String, offset: Option<u64>) -> Self {
-        Self { kind, text, offset }
+        Self {
+            kind,
+            text,
+            offset,
+            truncated: false,
+            url: None,
+        }
+    }
+
+    /// Mark whether the text was truncated.
+    pub fn with_truncated(mut self, truncated: bool) -> Self {
+        self.truncated = truncated;
+        self
+    }
+
+    /// Attach parsed URL components.
+    pub fn with_url(mut self, url: Option<UrlComponents>) -> Self {
+        self.url = url;
+        self
+    }
+}
+
+impl UrlComponents {
+    /// Components of `text`, or `None` when it is not a valid URL.
+    pub fn parse(text: &str) -> Option<Self> {
+        let url = url::Url::parse(text).ok()?;
+        Some(Self {
+            scheme: url.scheme().to_string(),
+            host: url.host_str().map(str::to_string),
+            port: url.port_or_known_default(),
+            path: url.path().to_string(),
+        })
     }
 }
 
@@ -242,6 +313,35 @@ impl IocSample {
     fn offset(&self) -> Option<u64> {
         self.offset
     }
+    #[getter]
+    fn truncated(&self) -> bool {
+        self.truncated
+    }
+    #[getter]
+    fn url(&self) -> Option<UrlComponents> {
+        self.url.clone()
+    }
+}
+
+#[cfg(feature = "python-ext")]
+#[pymethods]
+impl UrlComponents {
+    #[getter]
+    fn scheme(&self) -> String {
+        self.scheme.clone()
+    }
+    #[getter]
+    fn host(&self) -> Option<String> {
+        self.host.clone()
+    }
+    #[getter]
+    fn port(&self) -> Option<u16> {
+        self.port
+    }
+    #[getter]
+    fn path(&self) -> String {
+        self.path.clone()
+    }
 }
 
 impl DetectedString {
@@ -260,8 +360,22 @@ impl DetectedString {
             script,
             confidence,
             offset,
+            truncated: false,
+            count: 1,
         }
     }
+
+    /// Mark whether the text was truncated.
+    pub fn with_truncated(mut self, truncated: bool) -> Self {
+        self.truncated = truncated;
+        self
+    }
+
+    /// Set the number of occurrences this entry stands for.
+    pub fn with_count(mut self, count: u32) -> Self {
+        self.count = count;
+        self
+    }
 }
 
 impl StringsSummary {
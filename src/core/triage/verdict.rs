@@ -2,7 +2,7 @@
 
 use super::containers::ContainerChild;
 use super::entropy::{EntropyAnalysis, EntropySummary};
-use super::errors::TriageError;
+use super::errors::{Severity, TriageError};
 use super::hints::{ConfidenceSignal, TriageHint};
 use super::packers::PackerMatch;
 use super::parsers::ParserResult;
@@ -63,6 +63,22 @@ pub struct Budgets {
     pub hit_byte_limit: bool,
 }
 
+/// Wall-clock time spent in each triage phase, in milliseconds.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "python-ext", pyclass(get_all))]
+pub struct PhaseTimings {
+    pub sniff_ms: u64,
+    pub headers_ms: u64,
+    pub entropy_ms: u64,
+    pub strings_ms: u64,
+    pub parsers_ms: u64,
+    pub disasm_ms: u64,
+    /// Format-specific analysis, including `symbols_ms`.
+    pub format_ms: u64,
+    pub symbols_ms: u64,
+    pub scoring_ms: u64,
+}
+
 #[cfg(feature = "python-ext")]
 #[pymethods]
 impl Budgets {
@@ -126,13 +142,20 @@ pub struct TriageVerdict {
     pub confidence: f32,
     /// Optional per-signal breakdown.
     pub signals: Option<Vec<ConfidenceSignal>>,
+    /// MIME type of the detected content, when known.
+    #[serde(default)]
+    pub mime: Option<String>,
+    /// Short lowercase type label (e.g. `elf`, `png`, `zip`).
+    #[serde(default)]
+    pub label: Option<String>,
 }
 
 #[cfg(feature = "python-ext")]
 #[pymethods]
 impl TriageVerdict {
     #[new]
-    #[pyo3(signature = (format, arch, bits, endianness, confidence, signals=None))]
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (format, arch, bits, endianness, confidence, signals=None, mime=None, label=None))]
     pub fn new(
         format: Format,
         arch: Arch,
@@ -140,9 +163,14 @@ impl TriageVerdict {
         endianness: Endianness,
         confidence: f32,
         signals: Option<Vec<ConfidenceSignal>>,
+        mime: Option<String>,
+        label: Option<String>,
     ) -> PyResult<Self> {
-        Self::try_new(format, arch, bits, endianness, confidence, signals)
-            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+        let mut v = Self::try_new(format, arch, bits, endianness, confidence, signals)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        v.mime = mime;
+        v.label = label;
+        Ok(v)
     }
 
     // Property getters
@@ -166,6 +194,14 @@ impl TriageVerdict {
     fn confidence(&self) -> f32 {
         self.confidence
     }
+    #[getter]
+    fn mime(&self) -> Option<String> {
+        self.mime.clone()
+    }
+    #[getter]
+    fn label(&self) -> Option<String> {
+        self.label.clone()
+    }
 }
 
 /// Overall triage report for an input artifact.
@@ -190,6 +226,8 @@ pub struct TriagedArtifact {
     pub strings: Option<StringsSummary>,
     /// Optional symbols summary for the artifact
     pub symbols: Option<SymbolSummary>,
+    /// Source language and compiler toolchain detection
+    pub language: Option<crate::triage::compiler_detection::LanguageDetectionResult>,
     /// Similarity summary (imphash/ctph)
     pub similarity: Option<SimilaritySummary>,
     /// Signing summary (presence bits)
@@ -213,6 +251,17 @@ pub struct TriagedArtifact {
     pub heuristic_arch: Option<Vec<(Arch, f32)>>,
     /// Optional bounded disassembly preview (rendered lines)
     pub disasm_preview: Option<Vec<String>>,
+    /// Per-phase wall-clock timings
+    #[serde(default)]
+    pub phase_timings: Option<PhaseTimings>,
+    /// The SHA-256 matched `TriageConfig::known_good_sha256`; analysis was
+    /// skipped and only identity fields are populated.
+    #[serde(default)]
+    pub known_good: bool,
+    /// `(offset, length)` of the analyzed bytes within `path` when only a
+    /// range of the file was triaged.
+    #[serde(default)]
+    pub source_range: Option<(u64, u64)>,
 }
 
 #[cfg(feature = "python-ext")]
@@ -244,7 +293,9 @@ impl TriagedArtifact {
         errors=None,
         heuristic_endianness=None,
         heuristic_arch=None,
-        disasm_preview=None
+        disasm_preview=None,
+        language=None,
+        phase_timings=None
     ))]
     pub fn new_py(
         schema_version: String,
@@ -271,6 +322,8 @@ impl TriagedArtifact {
         heuristic_endianness: Option<(Endianness, f32)>,
         heuristic_arch: Option<Vec<(Arch, f32)>>,
         disasm_preview: Option<Vec<String>>,
+        language: Option<crate::triage::compiler_detection::LanguageDetectionResult>,
+        phase_timings: Option<PhaseTimings>,
     ) -> Self {
         Self {
             schema_version,
@@ -284,6 +337,7 @@ impl TriagedArtifact {
             entropy_analysis,
             strings,
             symbols,
+            language,
             similarity,
             signing,
             packers,
@@ -297,6 +351,9 @@ impl TriagedArtifact {
             heuristic_endianness,
             heuristic_arch,
             disasm_preview,
+            phase_timings,
+            known_good: false,
+            source_range: None,
         }
     }
 
@@ -308,6 +365,30 @@ impl TriagedArtifact {
         Some(crate::similarity::ctph_similarity(a, b))
     }
 
+    /// Errors at or above the given severity (e.g. `Severity.Error` for a
+    /// health check that ignores budget notices and cross-check warnings).
+    #[pyo3(name = "errors_at_least")]
+    fn errors_at_least_py(&self, min: Severity) -> Vec<TriageError> {
+        self.errors_at_least(min).into_iter().cloned().collect()
+    }
+
+    /// Stripped status from the symbol summary; None if symbols were not analyzed.
+    #[pyo3(name = "is_stripped")]
+    fn is_stripped_py(&self) -> Option<bool> {
+        self.is_stripped()
+    }
+
+    /// True if any recorded error has `Error` severity.
+    #[pyo3(name = "has_fatal_errors")]
+    fn has_fatal_errors_py(&self) -> bool {
+        self.has_fatal_errors()
+    }
+
+    /// Flattened packed/signed/stripped/overlay/entropy/import/W^X summary.
+    fn indicators(&self) -> crate::triage::indicators::Indicators {
+        crate::triage::indicators::summarize(self)
+    }
+
     /// Serialize to JSON string.
     pub fn to_json(&self) -> PyResult<String> {
         serde_json::to_string(self).map_err(|e| {
@@ -315,6 +396,13 @@ impl TriagedArtifact {
         })
     }
 
+    /// Serialize to JSON with object keys sorted at every level.
+    #[pyo3(name = "canonical_json")]
+    fn canonical_json_py(&self) -> PyResult<String> {
+        self.canonical_json()
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
     /// Deserialize from JSON string.
     #[staticmethod]
     pub fn from_json(json_str: &str) -> PyResult<Self> {
@@ -377,6 +465,10 @@ impl TriagedArtifact {
         self.signing.clone()
     }
     #[getter]
+    fn language(&self) -> Option<crate::triage::compiler_detection::LanguageDetectionResult> {
+        self.language.clone()
+    }
+    #[getter]
     fn packers(&self) -> Option<Vec<PackerMatch>> {
         self.packers.clone()
     }
@@ -416,6 +508,18 @@ impl TriagedArtifact {
     fn heuristic_arch(&self) -> Option<Vec<(Arch, f32)>> {
         self.heuristic_arch.clone()
     }
+    #[getter]
+    fn phase_timings(&self) -> Option<PhaseTimings> {
+        self.phase_timings.clone()
+    }
+    #[getter]
+    fn known_good(&self) -> bool {
+        self.known_good
+    }
+    #[getter]
+    fn source_range(&self) -> Option<(u64, u64)> {
+        self.source_range
+    }
 }
 
 // Pure Rust constructors and helpers
@@ -456,9 +560,18 @@ impl TriageVerdict {
             endianness,
             confidence,
             signals,
+            mime: None,
+            label: None,
         })
     }
 
+    /// Attach a MIME type and type label.
+    pub fn with_type(mut self, mime: Option<String>, label: Option<String>) -> Self {
+        self.mime = mime;
+        self.label = label;
+        self
+    }
+
     /// Serialize to JSON string (pure Rust version).
     pub fn to_json_string(&self) -> Result<String, GlaurungError> {
         serde_json::to_string(self)
@@ -475,6 +588,25 @@ impl TriageVerdict {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::triage::TriageErrorKind;
+
+    #[test]
+    fn test_errors_filtered_by_severity() {
+        let artifact = TriagedArtifact::builder()
+            .with_id("test-id")
+            .with_path("/path/to/file")
+            .with_size_bytes(1024)
+            .with_errors(Some(vec![
+                TriageError::new(TriageErrorKind::BudgetExceeded, None),
+                TriageError::new(TriageErrorKind::SnifferMismatch, None),
+            ]))
+            .build()
+            .expect("Build should succeed");
+
+        assert_eq!(artifact.errors_at_least(Severity::Info).len(), 2);
+        assert_eq!(artifact.errors_at_least(Severity::Warning).len(), 1);
+        assert!(!artifact.has_fatal_errors());
+    }
 
     #[test]
     fn test_builder_pattern_basic() {
@@ -536,6 +668,29 @@ mod tests {
 
         assert_eq!(artifact.sha256, Some("def456".to_string()));
     }
+
+    #[test]
+    fn canonical_json_sorts_keys_and_round_trips() {
+        let artifact = TriagedArtifact::builder()
+            .with_id("test-id")
+            .with_path("/path/to/file")
+            .with_size_bytes(1024)
+            .with_sha256_str("abc123")
+            .with_budgets(Some(Budgets::new(1024, 3, 0)))
+            .build()
+            .expect("Build should succeed");
+
+        let json = artifact.canonical_json().unwrap();
+        assert_eq!(json, artifact.clone().canonical_json().unwrap());
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let keys: Vec<&String> = value.as_object().unwrap().keys().collect();
+        let mut sorted = keys.clone();
+        sorted.sort();
+        assert_eq!(keys, sorted);
+        // Nested objects are sorted too, unlike field order in `to_json`.
+        assert!(json.find("\"limit_bytes\"").unwrap() < json.find("\"recursion_depth\"").unwrap());
+        assert_eq!(TriagedArtifact::from_json_str(&json).unwrap(), artifact);
+    }
 }
 
 /// Builder for constructing TriagedArtifact instances with a more ergonomic API.
@@ -552,6 +707,7 @@ pub struct TriagedArtifactBuilder {
     entropy_analysis: Option<EntropyAnalysis>,
     strings: Option<StringsSummary>,
     symbols: Option<SymbolSummary>,
+    language: Option<crate::triage::compiler_detection::LanguageDetectionResult>,
     similarity: Option<SimilaritySummary>,
     signing: Option<crate::triage::signing::SigningSummary>,
     packers: Option<Vec<PackerMatch>>,
@@ -565,6 +721,9 @@ pub struct TriagedArtifactBuilder {
     heuristic_endianness: Option<(Endianness, f32)>,
     heuristic_arch: Option<Vec<(Arch, f32)>>,
     disasm_preview: Option<Vec<String>>,
+    phase_timings: Option<PhaseTimings>,
+    known_good: bool,
+    source_range: Option<(u64, u64)>,
 }
 
 impl TriagedArtifactBuilder {
@@ -645,6 +804,15 @@ impl TriagedArtifactBuilder {
         self
     }
 
+    /// Sets the source language / compiler detection result.
+    pub fn with_language(
+        mut self,
+        language: Option<crate::triage::compiler_detection::LanguageDetectionResult>,
+    ) -> Self {
+        self.language = language;
+        self
+    }
+
     /// Sets the similarity summary.
     pub fn with_similarity(mut self, similarity: Option<SimilaritySummary>) -> Self {
         self.similarity = similarity;
@@ -732,6 +900,24 @@ impl TriagedArtifactBuilder {
         self
     }
 
+    /// Sets the per-phase timings.
+    pub fn with_phase_timings(mut self, phase_timings: Option<PhaseTimings>) -> Self {
+        self.phase_timings = phase_timings;
+        self
+    }
+
+    /// Marks the artifact as allowlisted known-good.
+    pub fn with_known_good(mut self, known_good: bool) -> Self {
+        self.known_good = known_good;
+        self
+    }
+
+    /// Sets the `(offset, length)` of the analyzed range within the file.
+    pub fn with_source_range(mut self, source_range: Option<(u64, u64)>) -> Self {
+        self.source_range = source_range;
+        self
+    }
+
     /// Builds the TriagedArtifact. Returns an error if required fields are missing.
     pub fn build(self) -> Result<TriagedArtifact, String> {
         let id = self.id.ok_or("id is required")?;
@@ -751,6 +937,7 @@ impl TriagedArtifactBuilder {
             entropy_analysis: self.entropy_analysis,
             strings: self.strings,
             symbols: self.symbols,
+            language: self.language,
             similarity: self.similarity,
             signing: self.signing,
             packers: self.packers,
@@ -764,10 +951,29 @@ impl TriagedArtifactBuilder {
             heuristic_endianness: self.heuristic_endianness,
             heuristic_arch: self.heuristic_arch,
             disasm_preview: self.disasm_preview,
+            phase_timings: self.phase_timings,
+            known_good: self.known_good,
+            source_range: self.source_range,
         })
     }
 }
 
+/// `value` with every object's entries in key order, independent of the
+/// map type or serde_json's `preserve_order` feature.
+fn sort_keys(value: serde_json::Value) -> serde_json::Value {
+    use serde_json::Value;
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(String, Value)> =
+                map.into_iter().map(|(k, v)| (k, sort_keys(v))).collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            Value::Object(entries.into_iter().collect())
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(sort_keys).collect()),
+        other => other,
+    }
+}
+
 impl TriagedArtifact {
     /// Creates a new builder for constructing TriagedArtifact instances.
     pub fn builder() -> TriagedArtifactBuilder {
@@ -825,6 +1031,36 @@ impl TriagedArtifact {
             .expect("All required fields should be provided in new()")
     }
 
+    /// Serialize to JSON with object keys sorted at every level, so equal
+    /// artifacts produce byte-identical output for golden files and
+    /// content-addressed caches.
+    pub fn canonical_json(&self) -> Result<String, GlaurungError> {
+        let value = serde_json::to_value(self).map_err(|e| {
+            GlaurungError::Serialization(format!("JSON serialization error: {}", e))
+        })?;
+        serde_json::to_string(&sort_keys(value))
+            .map_err(|e| GlaurungError::Serialization(format!("JSON serialization error: {}", e)))
+    }
+
+    /// Errors whose severity is at least `min`.
+    pub fn errors_at_least(&self, min: Severity) -> Vec<&TriageError> {
+        self.errors
+            .iter()
+            .flatten()
+            .filter(|e| e.is_at_least(min))
+            .collect()
+    }
+
+    /// Stripped status from the symbol summary; None if symbols were not analyzed.
+    pub fn is_stripped(&self) -> Option<bool> {
+        self.symbols.as_ref().map(SymbolSummary::is_stripped)
+    }
+
+    /// True if any recorded error has `Error` severity.
+    pub fn has_fatal_errors(&self) -> bool {
+        !self.errors_at_least(Severity::Error).is_empty()
+    }
+
     pub fn to_json_string(&self) -> Result<String, GlaurungError> {
         serde_json::to_string(self)
             .map_err(|e| GlaurungError::Serialization(format!("JSON serialization error: {}", e)))
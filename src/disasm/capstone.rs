@@ -7,7 +7,7 @@ use crate::core::instruction::{Access, Instruction, Operand};
 use capstone::arch::arm::ArmOperandType;
 use capstone::arch::arm64::Arm64OperandType;
 use capstone::prelude::*;
-use capstone::{Arch, Capstone, Endian, Mode, NO_EXTRA_MODE};
+use capstone::{Arch, Capstone, Endian, ExtraMode, Mode};
 
 pub struct CapstoneDisassembler {
     cs: capstone::Capstone,
@@ -16,71 +16,39 @@ pub struct CapstoneDisassembler {
 }
 
 fn cs_arch_mode(arch: Architecture, end: Endianness) -> Option<(Arch, Mode, Option<Endian>)> {
+    // ARM, MIPS and PPC are bi-endian; capstone decodes them little-endian
+    // unless the mode carries an explicit byte order.
+    let endian = Some(match end {
+        Endianness::Big => Endian::Big,
+        Endianness::Little => Endian::Little,
+    });
     match arch {
-        Architecture::ARM => Some((
-            Arch::ARM,
-            Mode::Arm,
-            Some(if matches!(end, Endianness::Big) {
-                Endian::Big
-            } else {
-                Endian::Little
-            }),
-        )),
-        Architecture::ARM64 => Some((
-            Arch::ARM64,
-            Mode::Arm,
-            Some(if matches!(end, Endianness::Big) {
-                Endian::Big
-            } else {
-                Endian::Little
-            }),
-        )),
-        Architecture::MIPS => Some((
-            Arch::MIPS,
-            Mode::Mips32,
-            Some(if matches!(end, Endianness::Big) {
-                Endian::Big
-            } else {
-                Endian::Little
-            }),
-        )),
-        Architecture::MIPS64 => Some((
-            Arch::MIPS,
-            Mode::Mips64,
-            Some(if matches!(end, Endianness::Big) {
-                Endian::Big
-            } else {
-                Endian::Little
-            }),
-        )),
-        Architecture::PPC => Some((
-            Arch::PPC,
-            Mode::Mode32,
-            Some(if matches!(end, Endianness::Big) {
-                Endian::Big
-            } else {
-                Endian::Little
-            }),
-        )),
-        Architecture::PPC64 => Some((
-            Arch::PPC,
-            Mode::Mode64,
-            Some(if matches!(end, Endianness::Big) {
-                Endian::Big
-            } else {
-                Endian::Little
-            }),
-        )),
+        Architecture::ARM => Some((Arch::ARM, Mode::Arm, endian)),
+        Architecture::ARM64 => Some((Arch::ARM64, Mode::Arm, endian)),
+        Architecture::MIPS => Some((Arch::MIPS, Mode::Mips32, endian)),
+        Architecture::MIPS64 => Some((Arch::MIPS, Mode::Mips64, endian)),
+        Architecture::PPC => Some((Arch::PPC, Mode::Mode32, endian)),
+        Architecture::PPC64 => Some((Arch::PPC, Mode::Mode64, endian)),
         Architecture::RISCV => Some((Arch::RISCV, Mode::RiscV32, None)),
         Architecture::RISCV64 => Some((Arch::RISCV, Mode::RiscV64, None)),
         Architecture::X86 | Architecture::X86_64 | Architecture::Unknown => None,
     }
 }
 
+/// Extra modes enabled for `arch`: RISC-V binaries routinely mix 2-byte
+/// RVC encodings into the 4-byte stream, so the C extension is always on.
+fn cs_extra_modes(arch: Architecture) -> &'static [ExtraMode] {
+    match arch {
+        Architecture::RISCV | Architecture::RISCV64 => &[ExtraMode::RiscVC],
+        _ => &[],
+    }
+}
+
 impl CapstoneDisassembler {
     pub fn new(arch: Architecture, endianness: Endianness) -> Option<Self> {
         let (a, m, endian) = cs_arch_mode(arch, endianness)?;
-        let mut cs = Capstone::new_raw(a, m, NO_EXTRA_MODE, endian).ok()?;
+        let extra = cs_extra_modes(arch).iter().copied();
+        let mut cs = Capstone::new_raw(a, m, extra, endian).ok()?;
         // Enable details to recover structured operands (needed for PC-relative addressing)
         let _ = cs.set_detail(true);
         Some(Self {
@@ -423,4 +391,53 @@ mod tests {
         cs.set_thumb_mode(true).expect("no-op on arm64");
         cs.set_thumb_mode(false).expect("no-op on arm64");
     }
+
+    #[test]
+    fn big_endian_mips_and_arm_decode() {
+        // `addiu $sp, $sp, -32` stored big-endian: 27 BD FF E0
+        let be = CapstoneDisassembler::new(Architecture::MIPS, Endianness::Big)
+            .expect("capstone mips backend");
+        let ins = be
+            .disassemble_instruction(&va(0x400000), &[0x27, 0xbd, 0xff, 0xe0])
+            .expect("decode");
+        assert_eq!(ins.mnemonic, "addiu");
+        assert_eq!(be.endianness(), Endianness::Big);
+
+        // The same bytes read little-endian are a different instruction.
+        let le = CapstoneDisassembler::new(Architecture::MIPS, Endianness::Little)
+            .expect("capstone mips backend");
+        let other = le.disassemble_instruction(&va(0x400000), &[0x27, 0xbd, 0xff, 0xe0]);
+        assert!(!matches!(other, Ok(i) if i.mnemonic == "addiu"));
+
+        // `mov r0, r0` stored big-endian: E1 A0 00 00
+        let arm = CapstoneDisassembler::new(Architecture::ARM, Endianness::Big)
+            .expect("capstone arm backend");
+        let ins = arm
+            .disassemble_instruction(&va(0x1000), &[0xe1, 0xa0, 0x00, 0x00])
+            .expect("decode");
+        assert_eq!(ins.length, 4);
+        assert!(
+            ins.mnemonic == "mov" || ins.mnemonic == "nop",
+            "got {:?}",
+            ins.mnemonic
+        );
+    }
+
+    #[test]
+    fn riscv_decodes_mixed_compressed_stream() {
+        // c.addi sp, sp, -16; sd ra, 8(sp); c.ret
+        let code = [0x41, 0x11, 0x23, 0x34, 0x11, 0x00, 0x82, 0x80];
+        let cs = CapstoneDisassembler::new(Architecture::RISCV64, Endianness::Little)
+            .expect("capstone riscv backend");
+        let mut lengths = Vec::new();
+        let mut off = 0;
+        while off < code.len() {
+            let ins = cs
+                .disassemble_instruction(&va(off as u64), &code[off..])
+                .expect("decode");
+            lengths.push(ins.length);
+            off += ins.length as usize;
+        }
+        assert_eq!(lengths, vec![2, 4, 2]);
+    }
 }
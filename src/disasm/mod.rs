@@ -1,12 +1,15 @@
 //! Disassembly engines and registry, exposed to Rust and Python.
 //!
-//! Always-on adapters:
+//! Compiled with the `disasm` feature (on by default):
 //! - iced-x86 for x86/x64
 //! - capstone for ARM/AArch64, MIPS, PPC, RISC-V (and fallback)
 
 pub mod capstone;
 pub mod iced;
 pub mod registry;
+pub mod stats;
+
+pub use stats::code_likelihood;
 
 #[cfg(feature = "python-ext")]
 pub mod py_api;
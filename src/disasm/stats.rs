@@ -0,0 +1,221 @@
+//! Mnemonic statistics over decoded instructions.
+//!
+//! Real code has a characteristic mnemonic distribution (lots of moves,
+//! arithmetic, calls and branches) while random bytes tend to decode to a
+//! long tail of rare instructions. These counts let callers quantify a
+//! disassembly preview instead of eyeballing it.
+
+use std::collections::HashMap;
+
+use crate::analysis::cfg::classify_ctrl_flow;
+use crate::core::address::{Address, AddressKind};
+use crate::core::binary::{Arch, Endianness};
+use crate::core::disassembler::{Architecture, Disassembler};
+use crate::core::instruction::Instruction;
+
+/// Upper bound on bytes decoded by [`code_likelihood`].
+const CODE_LIKELIHOOD_WINDOW: usize = 64 * 1024;
+
+/// Per-window instruction counts.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InstructionStats {
+    /// Number of instructions counted.
+    pub total: u32,
+    /// Calls (`call`, `bl`, `blr`, `jal`, ...).
+    pub calls: u32,
+    /// Conditional and unconditional jumps/branches.
+    pub jumps: u32,
+    /// Returns.
+    pub returns: u32,
+    /// Integer arithmetic, logic and shift instructions.
+    pub arithmetic: u32,
+    /// Lowercased mnemonic -> count.
+    pub histogram: HashMap<String, u32>,
+}
+
+impl InstructionStats {
+    /// Fraction of instructions that are control flow (calls, jumps, returns).
+    pub fn control_flow_ratio(&self) -> f32 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        (self.calls + self.jumps + self.returns) as f32 / self.total as f32
+    }
+
+    /// Distinct mnemonics per instruction; high values suggest decoded noise.
+    pub fn distinct_ratio(&self) -> f32 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        self.histogram.len() as f32 / self.total as f32
+    }
+}
+
+/// Count mnemonics (lowercased).
+pub fn instruction_histogram(instructions: &[Instruction]) -> HashMap<String, u32> {
+    let mut hist = HashMap::new();
+    for ins in instructions {
+        *hist.entry(ins.mnemonic.to_ascii_lowercase()).or_insert(0) += 1;
+    }
+    hist
+}
+
+/// Histogram plus call/jump/return/arithmetic counts for `arch`.
+pub fn instruction_stats(instructions: &[Instruction], arch: Arch) -> InstructionStats {
+    let histogram = instruction_histogram(instructions);
+    let mut stats = InstructionStats {
+        total: instructions.len() as u32,
+        ..Default::default()
+    };
+    for (mnemonic, &count) in &histogram {
+        let (is_branch, is_call, is_ret) = classify_ctrl_flow(mnemonic, arch);
+        if is_call {
+            stats.calls += count;
+        } else if is_ret {
+            stats.returns += count;
+        } else if is_branch {
+            stats.jumps += count;
+        } else if is_arithmetic(mnemonic) {
+            stats.arithmetic += count;
+        }
+    }
+    stats.histogram = histogram;
+    stats
+}
+
+/// Integer ALU mnemonics across the supported ISAs.
+fn is_arithmetic(mnemonic: &str) -> bool {
+    let m = mnemonic
+        .strip_suffix(".w")
+        .or_else(|| mnemonic.strip_suffix(".n"))
+        .unwrap_or(mnemonic);
+    matches!(
+        m,
+        // x86
+        "add" | "adc" | "sub" | "sbb" | "inc" | "dec" | "neg" | "mul" | "imul" | "div" | "idiv"
+            | "and" | "or" | "xor" | "not" | "shl" | "shr" | "sal" | "sar" | "rol" | "ror"
+            // ARM / AArch64
+            | "adds" | "subs" | "rsb" | "madd" | "msub" | "udiv" | "sdiv" | "eor" | "orr"
+            | "bic" | "lsl" | "lsr" | "asr"
+            // MIPS / RISC-V
+            | "addi" | "addiu" | "addu" | "subu" | "addw" | "addiw" | "subw" | "andi" | "ori"
+            | "xori" | "sll" | "srl" | "sra" | "slli" | "srli" | "srai"
+    )
+}
+
+/// Fraction of `data` that linearly decodes as valid, unprivileged code.
+///
+/// Decodes from offset 0 and stops at the first invalid or privileged
+/// instruction (e.g. `hlt`, `in`/`out`, `wrmsr`, `eret`): the result is the
+/// number of bytes consumed before that point divided by the region size
+/// (capped at 64 KiB). Data regions tend to hit a bad opcode within a few
+/// bytes, so this separates code from data better than entropy does.
+/// Returns 0.0 for empty input or an architecture without a decoder.
+pub fn code_likelihood(data: &[u8], arch: Arch, endian: Endianness) -> f64 {
+    let region = &data[..data.len().min(CODE_LIKELIHOOD_WINDOW)];
+    if region.is_empty() {
+        return 0.0;
+    }
+    let darch: Architecture = arch.into();
+    let Some(backend) = crate::disasm::registry::for_arch(darch, endian) else {
+        return 0.0;
+    };
+    let bits = darch.address_bits();
+    let mut off = 0usize;
+    while off < region.len() {
+        let Ok(addr) = Address::new(AddressKind::VA, off as u64, bits, None, None) else {
+            break;
+        };
+        match backend.disassemble_instruction(&addr, &region[off..]) {
+            Ok(ins) if ins.length > 0 && !is_privileged(&ins.mnemonic) => {
+                off += ins.length as usize;
+            }
+            _ => break,
+        }
+    }
+    off.min(region.len()) as f64 / region.len() as f64
+}
+
+/// Instructions that fault outside kernel/hypervisor mode.
+fn is_privileged(mnemonic: &str) -> bool {
+    let m = mnemonic.to_ascii_lowercase();
+    matches!(
+        m.as_str(),
+        // x86
+        "hlt" | "cli" | "sti" | "in" | "out" | "insb" | "insw" | "insd" | "outsb" | "outsw"
+            | "outsd" | "lgdt" | "lidt" | "lldt" | "ltr" | "lmsw" | "clts" | "invd" | "wbinvd"
+            | "invlpg" | "rdmsr" | "wrmsr" | "swapgs" | "sysret" | "sysexit"
+            // ARM / AArch64
+            | "eret" | "hvc" | "smc" | "wfi" | "cpsid" | "cpsie" | "mcr" | "mrc"
+            // MIPS / RISC-V
+            | "mtc0" | "mfc0" | "mret" | "sret"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::address::{Address, AddressKind};
+
+    fn ins(mnemonic: &str) -> Instruction {
+        Instruction::new(
+            Address::new(AddressKind::VA, 0x1000, 64, None, None).unwrap(),
+            vec![0x90],
+            mnemonic.to_string(),
+            vec![],
+            1,
+            "x86_64".to_string(),
+            None,
+            None,
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn counts_mnemonic_classes() {
+        let code: Vec<Instruction> = ["push", "mov", "add", "XOR", "call", "jne", "jmp", "ret"]
+            .into_iter()
+            .map(ins)
+            .collect();
+        let stats = instruction_stats(&code, Arch::X86_64);
+        assert_eq!(stats.total, 8);
+        assert_eq!(stats.calls, 1);
+        assert_eq!(stats.jumps, 2);
+        assert_eq!(stats.returns, 1);
+        assert_eq!(stats.arithmetic, 2);
+        assert_eq!(stats.histogram.get("xor"), Some(&1));
+        assert_eq!(stats.control_flow_ratio(), 0.5);
+        assert_eq!(stats.distinct_ratio(), 1.0);
+
+        let arm: Vec<Instruction> = ["bl", "b.ne", "eor", "ret"].into_iter().map(ins).collect();
+        let stats = instruction_stats(&arm, Arch::AArch64);
+        assert_eq!((stats.calls, stats.jumps, stats.returns), (1, 1, 1));
+        assert_eq!(stats.arithmetic, 1);
+
+        assert_eq!(instruction_stats(&[], Arch::X86).distinct_ratio(), 0.0);
+    }
+
+    #[test]
+    fn code_likelihood_separates_code_from_data() {
+        // push rbp; mov rbp, rsp; xor eax, eax; pop rbp; ret
+        let code = [0x55, 0x48, 0x89, 0xe5, 0x31, 0xc0, 0x5d, 0xc3];
+        assert_eq!(
+            code_likelihood(&code, Arch::X86_64, Endianness::Little),
+            1.0
+        );
+
+        // Two valid instructions, then `hlt` (privileged) ends the run.
+        let with_hlt = [0x55, 0x5d, 0xf4, 0x90];
+        assert_eq!(
+            code_likelihood(&with_hlt, Arch::X86_64, Endianness::Little),
+            0.5
+        );
+
+        assert_eq!(code_likelihood(&[], Arch::X86_64, Endianness::Little), 0.0);
+        assert_eq!(
+            code_likelihood(&code, Arch::Unknown, Endianness::Little),
+            0.0
+        );
+    }
+}
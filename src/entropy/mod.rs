@@ -6,6 +6,7 @@
 //! - Core Shannon entropy calculations
 //! - Optimized sliding window analysis
 //! - Statistical utilities for entropy data
+//! - Comparison of window-entropy profiles across files
 //!
 //! # Performance Considerations
 //!
@@ -34,13 +35,19 @@
 //! ```
 
 pub mod core;
+pub mod profile;
 pub mod stats;
 pub mod window;
 
 // Re-export main functionality
 pub use self::core::{shannon_entropy, Histogram};
-pub use self::stats::{calculate_median, detect_anomalies_zscore, find_outliers, Stats};
-pub use self::window::{analyze_chunks, analyze_windows, WindowAnalysis, WindowConfig};
+pub use self::profile::compare_profiles;
+pub use self::stats::{
+    calculate_median, detect_anomalies_zscore, find_outliers, RunningStats, Stats,
+};
+pub use self::window::{
+    analyze_chunks, analyze_windows, analyze_windows_while, WindowAnalysis, WindowConfig,
+};
 
 // Backwards compatibility aliases
 pub use self::core::shannon_entropy as calculate;
@@ -0,0 +1,97 @@
+//! Comparison of window-entropy profiles.
+//!
+//! Files produced by the same packer or compressor tend to share an entropy
+//! *shape* — a low-entropy stub, a long high-entropy payload, a trailing
+//! overlay — even when their bytes and sizes differ. Profiles are aligned
+//! with dynamic time warping so that shape, not length, drives the distance.
+
+/// Profiles longer than this are averaged down before alignment, bounding
+/// the quadratic DTW cost.
+pub const MAX_PROFILE_LEN: usize = 1024;
+
+/// Upper bound of Shannon entropy over bytes, used to normalize distances.
+const MAX_ENTROPY: f64 = 8.0;
+
+/// Normalized distance between two window-entropy sequences, in `[0.0, 1.0]`.
+///
+/// The sequences are aligned with dynamic time warping and the result is the
+/// mean per-step entropy difference along the optimal path, divided by 8
+/// bits; `0.0` means identical shapes. Two empty profiles compare as `0.0`,
+/// an empty and a non-empty one as `1.0`.
+pub fn compare_profiles(a: &[f64], b: &[f64]) -> f64 {
+    match (a.is_empty(), b.is_empty()) {
+        (true, true) => return 0.0,
+        (true, false) | (false, true) => return 1.0,
+        _ => {}
+    }
+    let a = downsample(a, MAX_PROFILE_LEN);
+    let b = downsample(b, MAX_PROFILE_LEN);
+
+    // Each cell holds (accumulated cost, path length) of the cheapest
+    // alignment of a[..=i] with b[..=j]; only the previous row is kept.
+    let m = b.len();
+    let mut prev: Vec<(f64, usize)> = Vec::with_capacity(m);
+    let mut cur: Vec<(f64, usize)> = Vec::with_capacity(m);
+    for (i, &x) in a.iter().enumerate() {
+        cur.clear();
+        for (j, &y) in b.iter().enumerate() {
+            let step = ((x - y).abs() / MAX_ENTROPY).min(1.0);
+            let best = match (i, j) {
+                (0, 0) => (0.0, 0),
+                (0, _) => cur[j - 1],
+                (_, 0) => prev[0],
+                _ => [prev[j - 1], prev[j], cur[j - 1]]
+                    .into_iter()
+                    .min_by(|p, q| p.0.total_cmp(&q.0).then(p.1.cmp(&q.1)))
+                    .unwrap_or((0.0, 0)),
+            };
+            cur.push((best.0 + step, best.1 + 1));
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    let (cost, len) = prev[m - 1];
+    (cost / len as f64).clamp(0.0, 1.0)
+}
+
+/// Averages `values` into at most `max_len` equal-width buckets.
+fn downsample(values: &[f64], max_len: usize) -> Vec<f64> {
+    if values.len() <= max_len {
+        return values.to_vec();
+    }
+    (0..max_len)
+        .map(|k| {
+            let lo = k * values.len() / max_len;
+            let hi = ((k + 1) * values.len() / max_len).max(lo + 1);
+            values[lo..hi].iter().sum::<f64>() / (hi - lo) as f64
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_shape_at_different_lengths_is_close() {
+        // Stub, packed payload, overlay — stretched to twice the windows.
+        let small = [2.0, 2.1, 7.9, 7.9, 7.9, 7.9, 3.0];
+        let large = [
+            2.0, 2.0, 2.1, 2.1, 7.9, 7.9, 7.9, 7.9, 7.9, 7.9, 7.9, 7.9, 3.0, 3.0,
+        ];
+        let text = [4.5, 4.6, 4.4, 4.5, 4.5, 4.6, 4.5];
+
+        assert_eq!(compare_profiles(&small, &small), 0.0);
+        assert!(compare_profiles(&small, &large) < 0.01);
+        assert!(compare_profiles(&small, &text) > 0.2);
+        assert!(
+            (compare_profiles(&small, &large) - compare_profiles(&large, &small)).abs() < 1e-12
+        );
+
+        assert_eq!(compare_profiles(&[], &[]), 0.0);
+        assert_eq!(compare_profiles(&small, &[]), 1.0);
+
+        let long: Vec<f64> = (0..5000).map(|i| if i < 500 { 1.0 } else { 7.5 }).collect();
+        let short: Vec<f64> = (0..50).map(|i| if i < 5 { 1.0 } else { 7.5 }).collect();
+        assert!(compare_profiles(&long, &short) < 0.01);
+    }
+}
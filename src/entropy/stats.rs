@@ -19,41 +19,81 @@ impl Stats {
     ///
     /// Returns None if the input is empty.
     pub fn from_values(values: &[f64]) -> Option<Self> {
-        if values.is_empty() {
-            return None;
+        let running: RunningStats = values.iter().copied().collect();
+        Some(Stats {
+            mean: running.mean()?,
+            std_dev: running.std_dev()?,
+            min: running.min()?,
+            max: running.max()?,
+            median: calculate_median(values),
+        })
+    }
+}
+
+/// Single-pass mean, population standard deviation, min and max.
+///
+/// Uses Welford's update, which stays accurate when the variance is small
+/// relative to the mean (e.g. window entropies of a uniformly packed file
+/// all near 7.99), where summing squares loses precision.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RunningStats {
+    count: u64,
+    mean: f64,
+    m2: f64,
+    min: f64,
+    max: f64,
+}
+
+impl RunningStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds one value.
+    pub fn push(&mut self, x: f64) {
+        self.count += 1;
+        if self.count == 1 {
+            self.min = x;
+            self.max = x;
+        } else {
+            self.min = self.min.min(x);
+            self.max = self.max.max(x);
         }
+        let delta = x - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (x - self.mean);
+    }
 
-        let len = values.len() as f64;
+    /// Number of values added.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
 
-        // Calculate mean
-        let sum: f64 = values.iter().sum();
-        let mean = sum / len;
+    pub fn mean(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.mean)
+    }
 
-        // Calculate variance and std_dev
-        let variance: f64 = values
-            .iter()
-            .map(|&x| {
-                let diff = x - mean;
-                diff * diff
-            })
-            .sum::<f64>()
-            / len;
-        let std_dev = variance.sqrt();
+    /// Population standard deviation.
+    pub fn std_dev(&self) -> Option<f64> {
+        (self.count > 0).then(|| (self.m2 / self.count as f64).sqrt())
+    }
 
-        // Find min and max
-        let min = values.iter().copied().reduce(f64::min).unwrap_or(0.0);
-        let max = values.iter().copied().reduce(f64::max).unwrap_or(0.0);
+    pub fn min(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.min)
+    }
 
-        // Calculate median
-        let median = calculate_median(values);
+    pub fn max(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.max)
+    }
+}
 
-        Some(Stats {
-            mean,
-            std_dev,
-            min,
-            max,
-            median,
-        })
+impl FromIterator<f64> for RunningStats {
+    fn from_iter<I: IntoIterator<Item = f64>>(iter: I) -> Self {
+        let mut stats = Self::new();
+        for x in iter {
+            stats.push(x);
+        }
+        stats
     }
 }
 
@@ -161,6 +201,21 @@ mod tests {
         assert!(stats.std_dev > 1.4 && stats.std_dev < 1.5);
     }
 
+    #[test]
+    fn test_running_stats_is_stable_with_large_offset() {
+        let values: Vec<f64> = (0..1000).map(|i| 1e9 + (i % 2) as f64).collect();
+        let running: RunningStats = values.iter().copied().collect();
+        assert_eq!(running.count(), 1000);
+        assert!((running.mean().unwrap() - (1e9 + 0.5)).abs() < 1e-6);
+        assert!((running.std_dev().unwrap() - 0.5).abs() < 1e-9);
+        assert_eq!(running.min(), Some(1e9));
+        assert_eq!(running.max(), Some(1e9 + 1.0));
+
+        let flat: RunningStats = std::iter::repeat(7.99).take(64).collect();
+        assert_eq!(flat.std_dev(), Some(0.0));
+        assert_eq!(RunningStats::new().std_dev(), None);
+    }
+
     #[test]
     fn test_stats_empty() {
         let values: Vec<f64> = vec![];
@@ -4,6 +4,7 @@
 //! entropy changes across data regions.
 
 use crate::entropy::core::{shannon_entropy, Histogram};
+use crate::entropy::stats::RunningStats;
 
 /// Configuration for sliding window entropy analysis.
 #[derive(Debug, Clone)]
@@ -60,26 +61,17 @@ impl WindowAnalysis {
 
     /// Calculates the mean entropy.
     pub fn mean(&self) -> Option<f64> {
-        if self.entropies.is_empty() {
-            return None;
-        }
-        let sum: f64 = self.entropies.iter().sum();
-        Some(sum / self.entropies.len() as f64)
+        self.stats().mean()
     }
 
     /// Calculates the standard deviation of entropy values.
     pub fn std_dev(&self) -> Option<f64> {
-        let mean = self.mean()?;
-        let variance: f64 = self
-            .entropies
-            .iter()
-            .map(|&x| {
-                let diff = x - mean;
-                diff * diff
-            })
-            .sum::<f64>()
-            / self.entropies.len() as f64;
-        Some(variance.sqrt())
+        self.stats().std_dev()
+    }
+
+    /// Mean, standard deviation, min and max in one pass.
+    pub fn stats(&self) -> RunningStats {
+        self.entropies.iter().copied().collect()
     }
 
     /// Detects entropy cliffs (sudden changes between consecutive windows).
@@ -102,6 +94,16 @@ impl WindowAnalysis {
 /// This function uses an optimized histogram-based approach that updates
 /// incrementally as the window slides, avoiding redundant recalculation.
 pub fn analyze_windows(data: &[u8], config: &WindowConfig) -> WindowAnalysis {
+    analyze_windows_while(data, config, || true)
+}
+
+/// Like [`analyze_windows`], polling `keep_going` every 64 slides and
+/// returning the windows computed so far once it yields `false`.
+pub fn analyze_windows_while(
+    data: &[u8],
+    config: &WindowConfig,
+    mut keep_going: impl FnMut() -> bool,
+) -> WindowAnalysis {
     if data.is_empty() || config.window_size == 0 || config.step_size == 0 {
         return WindowAnalysis {
             entropies: Vec::new(),
@@ -148,6 +150,9 @@ pub fn analyze_windows(data: &[u8], config: &WindowConfig) -> WindowAnalysis {
         if position + window_size + step_size > data.len() {
             break;
         }
+        if computed % 64 == 63 && !keep_going() {
+            break;
+        }
 
         // Slide the histogram efficiently
         let old_start = position;
@@ -237,6 +242,18 @@ mod tests {
         assert!(std_dev < 0.5); // Low variation
     }
 
+    #[test]
+    fn test_analyze_windows_while_stops_early() {
+        let data = vec![0u8; 100_000];
+        let config = WindowConfig {
+            window_size: 10,
+            step_size: 10,
+            max_windows: 10_000,
+        };
+        assert_eq!(analyze_windows(&data, &config).len(), 10_000);
+        assert_eq!(analyze_windows_while(&data, &config, || false).len(), 64);
+    }
+
     #[test]
     fn test_chunks_analysis() {
         let mut data = vec![0u8; 256];
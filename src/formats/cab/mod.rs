@@ -0,0 +1,268 @@
+//! Microsoft Cabinet (`.cab`) reader.
+//!
+//! Droppers and MSCF-based installers frequently ship their payloads inside a
+//! cabinet. This parses the `CFHEADER`, the `CFFOLDER` table and the `CFFILE`
+//! table to enumerate members (name, uncompressed size, location) without
+//! decompressing anything. MSZIP/Quantum/LZX folders are reported but not
+//! inflated.
+//!
+//! For members of an uncompressed (`stored`) folder that sit inside a single
+//! `CFDATA` block, the absolute offset of the member bytes within the cabinet
+//! is resolved so recursion can slice the payload directly.
+//!
+//! Layout reference: Microsoft "Cabinet File Format" (MS-CAB).
+
+use crate::core::binary::Endianness;
+use crate::formats::read::{bytes_at, u16_at, u32_at, u8_at, OutOfBounds};
+use std::fmt;
+
+/// Cabinet parsing errors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CabError {
+    /// The buffer does not start with the `MSCF` signature.
+    BadMagic,
+    /// A structure ran past the end of the buffer.
+    Truncated { offset: usize, needed: usize },
+}
+
+impl fmt::Display for CabError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BadMagic => write!(f, "not a cabinet (missing MSCF signature)"),
+            Self::Truncated { offset, needed } => {
+                write!(f, "truncated at {:#x}, needed {} bytes", offset, needed)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CabError {}
+
+impl From<OutOfBounds> for CabError {
+    fn from(e: OutOfBounds) -> Self {
+        Self::Truncated {
+            offset: e.offset,
+            needed: e.needed,
+        }
+    }
+}
+
+pub type Result<T> = std::result::Result<T, CabError>;
+
+/// `MSCF` signature that opens every cabinet.
+pub const CAB_MAGIC: &[u8; 4] = b"MSCF";
+/// Fixed portion of `CFHEADER`.
+const CFHEADER_SIZE: usize = 36;
+/// Cabinets are little-endian throughout.
+const LE: Endianness = Endianness::Little;
+/// `CFHEADER.flags`: a previous cabinet is chained.
+const FLAG_PREV_CABINET: u16 = 0x0001;
+/// `CFHEADER.flags`: a next cabinet is chained.
+const FLAG_NEXT_CABINET: u16 = 0x0002;
+/// `CFHEADER.flags`: per-structure reserved areas are present.
+const FLAG_RESERVE_PRESENT: u16 = 0x0004;
+/// Bound on folders/files walked from a single header.
+const MAX_ENTRIES: usize = 65_535;
+
+/// Folder compression method (low nibble of `CFFOLDER.typeCompress`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CabCompression {
+    None,
+    MsZip,
+    Quantum,
+    Lzx,
+    Unknown(u16),
+}
+
+impl CabCompression {
+    fn from_type(t: u16) -> Self {
+        match t & 0x000F {
+            0 => Self::None,
+            1 => Self::MsZip,
+            2 => Self::Quantum,
+            3 => Self::Lzx,
+            other => Self::Unknown(other),
+        }
+    }
+}
+
+/// One `CFFOLDER` entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CabFolder {
+    /// Absolute offset of the folder's first `CFDATA` block.
+    pub data_offset: u32,
+    /// Number of `CFDATA` blocks in the folder.
+    pub data_blocks: u16,
+    pub compression: CabCompression,
+}
+
+/// One `CFFILE` entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CabFile {
+    pub name: String,
+    /// Uncompressed size in bytes.
+    pub size: u32,
+    /// Offset of the member within its folder's uncompressed stream.
+    pub folder_offset: u32,
+    /// Folder index (values >= 0xFFFD denote continuation across cabinets).
+    pub folder: u16,
+    pub attributes: u16,
+    /// Absolute offset of the member bytes in the cabinet, when the folder is
+    /// stored uncompressed and the member fits inside one `CFDATA` block.
+    pub data_offset: Option<u64>,
+}
+
+/// Parsed cabinet header plus folder and file tables.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cabinet {
+    /// `cbCabinet`: declared total size of the cabinet.
+    pub declared_size: u32,
+    /// `(major, minor)` format version.
+    pub version: (u8, u8),
+    pub flags: u16,
+    pub set_id: u16,
+    pub index: u16,
+    pub folders: Vec<CabFolder>,
+    pub files: Vec<CabFile>,
+}
+
+/// Read a NUL-terminated string starting at `off`; returns the string and the
+/// offset just past the terminator.
+fn cstr(d: &[u8], off: usize) -> Result<(String, usize)> {
+    let rest = d.get(off..).ok_or(CabError::Truncated {
+        offset: off,
+        needed: 1,
+    })?;
+    let len = memchr::memchr(0, rest).ok_or(CabError::Truncated {
+        offset: off,
+        needed: rest.len() + 1,
+    })?;
+    Ok((
+        String::from_utf8_lossy(&rest[..len]).into_owned(),
+        off + len + 1,
+    ))
+}
+
+/// True if `data` starts with a plausible cabinet header.
+pub fn is_cab(data: &[u8]) -> bool {
+    data.len() >= CFHEADER_SIZE
+        && data.starts_with(CAB_MAGIC)
+        // reserved1 is always zero; cheap guard against stray "MSCF" text.
+        && data[4..8] == [0, 0, 0, 0]
+}
+
+impl Cabinet {
+    /// Parse the header, folder and file tables of a cabinet.
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        if !data.starts_with(CAB_MAGIC) {
+            return Err(CabError::BadMagic);
+        }
+        bytes_at(data, 0, CFHEADER_SIZE)?;
+        let declared_size = u32_at(data, 8, LE)?;
+        let files_off = u32_at(data, 16, LE)? as usize;
+        let version = (u8_at(data, 25)?, u8_at(data, 24)?);
+        let n_folders = (u16_at(data, 26, LE)? as usize).min(MAX_ENTRIES);
+        let n_files = (u16_at(data, 28, LE)? as usize).min(MAX_ENTRIES);
+        let flags = u16_at(data, 30, LE)?;
+        let set_id = u16_at(data, 32, LE)?;
+        let index = u16_at(data, 34, LE)?;
+
+        let mut off = CFHEADER_SIZE;
+        let (mut folder_reserve, mut data_reserve) = (0usize, 0usize);
+        if flags & FLAG_RESERVE_PRESENT != 0 {
+            let header_reserve = u16_at(data, off, LE)? as usize;
+            folder_reserve = u8_at(data, off + 2)? as usize;
+            data_reserve = u8_at(data, off + 3)? as usize;
+            off += 4 + header_reserve;
+        }
+        if flags & FLAG_PREV_CABINET != 0 {
+            off = cstr(data, off)?.1;
+            off = cstr(data, off)?.1;
+        }
+        if flags & FLAG_NEXT_CABINET != 0 {
+            off = cstr(data, off)?.1;
+            off = cstr(data, off)?.1;
+        }
+
+        let mut folders = Vec::with_capacity(n_folders);
+        for _ in 0..n_folders {
+            folders.push(CabFolder {
+                data_offset: u32_at(data, off, LE)?,
+                data_blocks: u16_at(data, off + 4, LE)?,
+                compression: CabCompression::from_type(u16_at(data, off + 6, LE)?),
+            });
+            off += 8 + folder_reserve;
+        }
+
+        let mut files = Vec::with_capacity(n_files);
+        let mut off = files_off;
+        for _ in 0..n_files {
+            let size = u32_at(data, off, LE)?;
+            let folder_offset = u32_at(data, off + 4, LE)?;
+            let folder = u16_at(data, off + 8, LE)?;
+            let attributes = u16_at(data, off + 14, LE)?;
+            let (name, next) = cstr(data, off + 16)?;
+            let data_offset = folders
+                .get(folder as usize)
+                .filter(|f| f.compression == CabCompression::None)
+                .and_then(|f| stored_member_offset(data, f, data_reserve, folder_offset, size));
+            files.push(CabFile {
+                name,
+                size,
+                folder_offset,
+                folder,
+                attributes,
+                data_offset,
+            });
+            off = next;
+        }
+
+        Ok(Self {
+            declared_size,
+            version,
+            flags,
+            set_id,
+            index,
+            folders,
+            files,
+        })
+    }
+
+    /// Sum of member uncompressed sizes.
+    pub fn total_uncompressed_size(&self) -> u64 {
+        self.files.iter().map(|f| f.size as u64).sum()
+    }
+}
+
+/// Walk a stored folder's `CFDATA` blocks and map a member's folder-relative
+/// range to an absolute cabinet offset, if it lies inside a single block.
+fn stored_member_offset(
+    data: &[u8],
+    folder: &CabFolder,
+    data_reserve: usize,
+    member_off: u32,
+    member_size: u32,
+) -> Option<u64> {
+    let mut block = folder.data_offset as usize;
+    let mut stream_pos: u64 = 0;
+    let member_off = member_off as u64;
+    let member_end = member_off + member_size as u64;
+    for _ in 0..folder.data_blocks {
+        let cb_data = u16_at(data, block + 4, LE).ok()? as u64;
+        let payload = block + 8 + data_reserve;
+        if member_off >= stream_pos && member_end <= stream_pos + cb_data {
+            let abs = payload as u64 + (member_off - stream_pos);
+            return (abs + member_size as u64 <= data.len() as u64).then_some(abs);
+        }
+        stream_pos += cb_data;
+        if stream_pos > member_off {
+            // Member straddles blocks; not contiguous in the file.
+            return None;
+        }
+        block = payload + cb_data as usize;
+    }
+    None
+}
+
+#[cfg(test)]
+pub(crate) mod tests;
@@ -0,0 +1,101 @@
+//! Cabinet reader tests over a hand-assembled MS-CAB image (one stored folder,
+//! two members in a single `CFDATA` block).
+
+use super::*;
+
+/// Build a minimal uncompressed cabinet holding `files`; shared with the
+/// triage container tests.
+pub(crate) fn build_cab(files: &[(&str, &[u8])]) -> Vec<u8> {
+    let files_off = 36 + 8; // header + one CFFOLDER
+    let file_table_len: usize = files.iter().map(|(n, _)| 16 + n.len() + 1).sum();
+    let data_off = files_off + file_table_len;
+    let payload: Vec<u8> = files.iter().flat_map(|(_, b)| b.iter().copied()).collect();
+    let total = data_off + 8 + payload.len();
+
+    let mut d = Vec::with_capacity(total);
+    d.extend_from_slice(b"MSCF");
+    d.extend_from_slice(&0u32.to_le_bytes());
+    d.extend_from_slice(&(total as u32).to_le_bytes());
+    d.extend_from_slice(&0u32.to_le_bytes());
+    d.extend_from_slice(&(files_off as u32).to_le_bytes());
+    d.extend_from_slice(&0u32.to_le_bytes());
+    d.extend_from_slice(&[3, 1]); // version 1.3
+    d.extend_from_slice(&1u16.to_le_bytes()); // cFolders
+    d.extend_from_slice(&(files.len() as u16).to_le_bytes());
+    d.extend_from_slice(&0u16.to_le_bytes()); // flags
+    d.extend_from_slice(&0x1234u16.to_le_bytes()); // setID
+    d.extend_from_slice(&0u16.to_le_bytes()); // iCabinet
+                                              // CFFOLDER: stored
+    d.extend_from_slice(&(data_off as u32).to_le_bytes());
+    d.extend_from_slice(&1u16.to_le_bytes());
+    d.extend_from_slice(&0u16.to_le_bytes());
+    // CFFILE entries
+    let mut uoff = 0u32;
+    for (name, body) in files {
+        d.extend_from_slice(&(body.len() as u32).to_le_bytes());
+        d.extend_from_slice(&uoff.to_le_bytes());
+        d.extend_from_slice(&0u16.to_le_bytes()); // iFolder
+        d.extend_from_slice(&[0u8; 4]); // date/time
+        d.extend_from_slice(&0x20u16.to_le_bytes()); // _A_ARCH
+        d.extend_from_slice(name.as_bytes());
+        d.push(0);
+        uoff += body.len() as u32;
+    }
+    // CFDATA
+    d.extend_from_slice(&0u32.to_le_bytes());
+    d.extend_from_slice(&(payload.len() as u16).to_le_bytes());
+    d.extend_from_slice(&(payload.len() as u16).to_le_bytes());
+    d.extend_from_slice(&payload);
+    d
+}
+
+#[test]
+fn parses_header_folders_and_files() {
+    let cab = build_cab(&[
+        ("setup.exe", &b"MZ\x90\x00"[..]),
+        ("readme.txt", &b"hi"[..]),
+    ]);
+    assert!(is_cab(&cab));
+    let c = Cabinet::parse(&cab).expect("parse cab");
+    assert_eq!(c.declared_size as usize, cab.len());
+    assert_eq!(c.version, (1, 3));
+    assert_eq!(c.set_id, 0x1234);
+    assert_eq!(c.folders.len(), 1);
+    assert_eq!(c.folders[0].compression, CabCompression::None);
+    let names: Vec<&str> = c.files.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(names, vec!["setup.exe", "readme.txt"]);
+    assert_eq!(c.total_uncompressed_size(), 6);
+}
+
+#[test]
+fn resolves_stored_member_offsets() {
+    let cab = build_cab(&[
+        ("setup.exe", &b"MZ\x90\x00"[..]),
+        ("readme.txt", &b"hi"[..]),
+    ]);
+    let c = Cabinet::parse(&cab).unwrap();
+    for (f, want) in c.files.iter().zip([&b"MZ\x90\x00"[..], &b"hi"[..]]) {
+        let off = f.data_offset.expect("stored member offset") as usize;
+        assert_eq!(&cab[off..off + f.size as usize], want);
+    }
+}
+
+#[test]
+fn compressed_folders_have_no_direct_offset() {
+    let mut cab = build_cab(&[("a.bin", &b"abcd"[..])]);
+    // typeCompress = MSZIP
+    cab[36 + 6..36 + 8].copy_from_slice(&1u16.to_le_bytes());
+    let c = Cabinet::parse(&cab).unwrap();
+    assert_eq!(c.folders[0].compression, CabCompression::MsZip);
+    assert_eq!(c.files[0].data_offset, None);
+}
+
+#[test]
+fn rejects_bad_magic_and_truncation() {
+    assert_eq!(Cabinet::parse(b"PK\x03\x04"), Err(CabError::BadMagic));
+    let cab = build_cab(&[("a.bin", &b"abcd"[..])]);
+    assert!(matches!(
+        Cabinet::parse(&cab[..50]),
+        Err(CabError::Truncated { .. })
+    ));
+}
@@ -13,6 +13,8 @@ pub mod symbols;
 pub mod types;
 pub mod utils;
 
+use crate::formats::pe::{utils::calculate_entropy, PackerDetection};
+use crate::formats::section_class::{classify_name, SectionClass};
 use dynamic::DynamicSection;
 use headers::parse_header;
 use notes::NoteSection;
@@ -22,6 +24,9 @@ use segments::SegmentTable;
 use symbols::SymbolTable;
 pub use types::*;
 
+/// Upper bound on slots read from one `.init_array`-style section.
+const MAX_POINTER_ARRAY_ENTRIES: usize = 4096;
+
 /// Main ELF parser
 pub struct ElfParser<'data> {
     data: &'data [u8],
@@ -343,6 +348,59 @@ impl<'data> ElfParser<'data> {
             .unwrap_or(false)
     }
 
+    /// Constructor addresses in the order the dynamic loader runs them:
+    /// `.preinit_array`, `DT_INIT`, then `.init_array`.
+    ///
+    /// Zero and `-1` sentinel slots are skipped. Slots left zero on disk in
+    /// PIE/shared objects are resolved through their `R_*_RELATIVE` addend
+    /// in `.rela.dyn` when one exists.
+    pub fn init_functions(&self) -> Vec<u64> {
+        let dynamic = self.dynamic().ok().flatten();
+        let mut funcs = self.pointer_array(".preinit_array");
+        funcs.extend(dynamic.and_then(|d| d.init_func()).filter(|&f| f != 0));
+        funcs.extend(self.pointer_array(".init_array"));
+        funcs
+    }
+
+    /// Destructor addresses in the order they run at exit: `.fini_array`
+    /// (walked back to front), then `DT_FINI`. Slots are filtered as in
+    /// [`init_functions`](Self::init_functions).
+    pub fn fini_functions(&self) -> Vec<u64> {
+        let mut funcs = self.pointer_array(".fini_array");
+        funcs.reverse();
+        let dynamic = self.dynamic().ok().flatten();
+        funcs.extend(dynamic.and_then(|d| d.fini_func()).filter(|&f| f != 0));
+        funcs
+    }
+
+    /// Decode the function-pointer array in section `name`.
+    fn pointer_array(&self, name: &str) -> Vec<u64> {
+        let Some(section) = self.sections().ok().and_then(|s| s.by_name(name)) else {
+            return Vec::new();
+        };
+        let relocs = self.got_relocations().ok().flatten();
+        let ElfIdent { class, data, .. } = self.header.ident;
+        let width = (class.bits() / 8) as usize;
+        let sentinel = match class {
+            ElfClass::Elf32 => u32::MAX as u64,
+            ElfClass::Elf64 => u64::MAX,
+        };
+        (0..section.data.len() / width)
+            .take(MAX_POINTER_ARRAY_ENTRIES)
+            .filter_map(|i| {
+                let slot = i * width;
+                let ptr = match utils::read_addr(section.data, slot, class, data).ok()? {
+                    0 => {
+                        let vaddr = section.addr().wrapping_add(slot as u64);
+                        relocs.as_ref()?.by_offset(vaddr)?.r_addend as u64
+                    }
+                    ptr => ptr,
+                };
+                (ptr != 0 && ptr != sentinel).then_some(ptr)
+            })
+            .collect()
+    }
+
     /// Get build ID from note sections
     pub fn build_id(&self) -> Option<Vec<u8>> {
         self.sections().ok().and_then(|sections| {
@@ -357,6 +415,76 @@ impl<'data> ElfParser<'data> {
         })
     }
 
+    /// Packing indicators: UPX markers, high-entropy `PT_LOAD` segments,
+    /// missing section headers and an entry point outside code sections.
+    pub fn packer_indicators(&self) -> PackerDetection {
+        let mut indicators = Vec::new();
+        let mut confidence: f32 = 0.0;
+        let mut packer_name = None;
+
+        let sections: Vec<Section<'data>> = self
+            .sections()
+            .map(|t| t.sections().collect())
+            .unwrap_or_default();
+
+        // UPX section names, or the `UPX!` marker UPX writes after the program
+        // headers of section-less packed executables
+        let head = &self.data[..self.data.len().min(4096)];
+        if sections
+            .iter()
+            .any(|s| s.name().to_ascii_uppercase().contains("UPX"))
+        {
+            packer_name = Some("UPX".to_string());
+            confidence = 0.9;
+            indicators.push("UPX section name".to_string());
+        } else if head.windows(4).any(|w| w == b"UPX!") {
+            packer_name = Some("UPX".to_string());
+            confidence = 0.9;
+            indicators.push("UPX! marker in headers".to_string());
+        }
+
+        // High-entropy loadable segments (compressed/encrypted payload)
+        if let Ok(segments) = self.segments() {
+            for seg in segments.load_segments() {
+                if seg.data.len() < 512 {
+                    continue;
+                }
+                let entropy = calculate_entropy(seg.data);
+                if entropy > 7.0 {
+                    indicators.push(format!(
+                        "High entropy in PT_LOAD at 0x{:x}: {:.2}",
+                        seg.header.p_vaddr, entropy
+                    ));
+                    confidence = confidence.max(0.6);
+                }
+            }
+        }
+
+        if sections.is_empty() && self.header.e_type == 2 {
+            indicators.push("No section headers".to_string());
+            confidence = confidence.max(0.4);
+        } else if !sections.is_empty() && self.header.entry_point() != 0 {
+            match self.entry_section() {
+                Some(s) if classify_name(s.name()) == Some(SectionClass::Code) => {}
+                Some(s) => {
+                    indicators.push(format!("Entry point in non-code section {}", s.name()));
+                    confidence = confidence.max(0.5);
+                }
+                None => {
+                    indicators.push("Entry point outside any section".to_string());
+                    confidence = confidence.max(0.5);
+                }
+            }
+        }
+
+        PackerDetection {
+            is_packed: confidence > 0.5,
+            packer_name,
+            confidence,
+            indicators,
+        }
+    }
+
     /// Validate ELF structure
     pub fn validate(&self) -> Vec<String> {
         let mut errors = Vec::new();
@@ -424,6 +552,52 @@ mod tests {
         assert!(elf.header().is_pie());
     }
 
+    /// `minimal_elf` plus `.init_array`/`.fini_array` sections (two slots
+    /// each, VAs 0x3000/0x3010) and a `.shstrtab`.
+    fn elf_with_ctor_arrays(init: [u64; 2], fini: [u64; 2]) -> Vec<u8> {
+        let mut data = minimal_elf();
+        data.resize(0x400, 0);
+        data[40..48].copy_from_slice(&0x100u64.to_le_bytes()); // e_shoff
+        data[58] = 64; // e_shentsize
+        data[60] = 4; // e_shnum
+        data[62] = 3; // e_shstrndx
+
+        let names = b"\0.init_array\0.fini_array\0.shstrtab\0";
+        data[0x300..0x300 + names.len()].copy_from_slice(names);
+        // (sh_name, sh_type, sh_addr, sh_offset, sh_size)
+        let headers = [
+            (1u32, SHT_INIT_ARRAY, 0x3000u64, 0x200u64, 16u64),
+            (13, SHT_FINI_ARRAY, 0x3010, 0x210, 16),
+            (25, SHT_STRTAB, 0, 0x300, names.len() as u64),
+        ];
+        for (i, (name, ty, addr, off, size)) in headers.into_iter().enumerate() {
+            let sh = 0x100 + (i + 1) * 64;
+            data[sh..sh + 4].copy_from_slice(&name.to_le_bytes());
+            data[sh + 4..sh + 8].copy_from_slice(&ty.to_le_bytes());
+            data[sh + 16..sh + 24].copy_from_slice(&addr.to_le_bytes());
+            data[sh + 24..sh + 32].copy_from_slice(&off.to_le_bytes());
+            data[sh + 32..sh + 40].copy_from_slice(&size.to_le_bytes());
+        }
+        for (i, ptr) in init.iter().chain(fini.iter()).enumerate() {
+            data[0x200 + i * 8..0x208 + i * 8].copy_from_slice(&ptr.to_le_bytes());
+        }
+        data
+    }
+
+    #[test]
+    fn test_init_and_fini_functions() {
+        let data = elf_with_ctor_arrays([0x1100, u64::MAX], [0x1200, 0x1300]);
+        let elf = ElfParser::parse(&data).unwrap();
+
+        assert_eq!(elf.init_functions(), vec![0x1100]);
+        // .fini_array runs back to front.
+        assert_eq!(elf.fini_functions(), vec![0x1300, 0x1200]);
+        assert!(ElfParser::parse(&minimal_elf())
+            .unwrap()
+            .init_functions()
+            .is_empty());
+    }
+
     #[test]
     fn test_security_features() {
         let data = minimal_elf();
@@ -435,6 +609,44 @@ mod tests {
         assert!(!security.stack_canary);
     }
 
+    #[test]
+    fn test_packer_indicators_upx_like() {
+        // ET_EXEC, one PT_LOAD over 4 KiB of pseudo-random bytes, no sections
+        let mut data = minimal_elf();
+        data[16] = 2;
+        data[32..40].copy_from_slice(&64u64.to_le_bytes()); // e_phoff
+        data[54] = 56; // e_phentsize
+        data[56] = 1; // e_phnum
+        let mut ph = [0u8; 56];
+        ph[0] = 1; // PT_LOAD
+        ph[4] = 5; // R+X
+        ph[8..16].copy_from_slice(&0x200u64.to_le_bytes()); // p_offset
+        ph[16..24].copy_from_slice(&0x400000u64.to_le_bytes()); // p_vaddr
+        ph[32..40].copy_from_slice(&4096u64.to_le_bytes()); // p_filesz
+        ph[40..48].copy_from_slice(&4096u64.to_le_bytes()); // p_memsz
+        data.extend_from_slice(&ph);
+        data.resize(0x200, 0);
+        data[0x100..0x104].copy_from_slice(b"UPX!");
+        let mut x: u32 = 0x1234_5678;
+        for _ in 0..4096 {
+            x = x.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            data.push((x >> 16) as u8);
+        }
+
+        let elf = ElfParser::parse(&data).unwrap();
+        let det = elf.packer_indicators();
+        assert!(det.is_packed);
+        assert_eq!(det.packer_name.as_deref(), Some("UPX"));
+        assert!(det.indicators.iter().any(|i| i.starts_with("High entropy")));
+        assert!(det.indicators.iter().any(|i| i == "No section headers"));
+
+        let plain = ElfParser::parse(&minimal_elf())
+            .unwrap()
+            .packer_indicators();
+        assert!(!plain.is_packed);
+        assert!(plain.indicators.is_empty());
+    }
+
     #[test]
     fn test_invalid_elf() {
         // Test with wrong magic but correct size
@@ -37,7 +37,7 @@ impl<'a> SectionTable<'a> {
 
         // Check bounds
         let total_size = sh_num * sh_entsize;
-        if sh_offset + total_size > data.len() {
+        if sh_offset.saturating_add(total_size) > data.len() {
             return Err(ElfError::Truncated {
                 offset: sh_offset,
                 needed: total_size,
@@ -59,11 +59,10 @@ impl<'a> SectionTable<'a> {
             let str_header = &headers[shstrndx];
             let str_offset = str_header.sh_offset as usize;
             let str_size = str_header.sh_size as usize;
-            if str_offset + str_size <= data.len() {
-                &data[str_offset..str_offset + str_size]
-            } else {
-                &[]
-            }
+            str_offset
+                .checked_add(str_size)
+                .and_then(|end| data.get(str_offset..end))
+                .unwrap_or(&[])
         } else {
             &[]
         };
@@ -97,11 +96,10 @@ impl<'a> SectionTable<'a> {
             let name = read_cstring(self.strings, header.sh_name as usize).unwrap_or("");
             let offset = header.sh_offset as usize;
             let size = header.sh_size as usize;
-            let data = if offset + size <= self.data.len() {
-                &self.data[offset..offset + size]
-            } else {
-                &[]
-            };
+            let data = offset
+                .checked_add(size)
+                .and_then(|end| self.data.get(offset..end))
+                .unwrap_or(&[]);
             Section {
                 header: *header,
                 name,
@@ -113,7 +111,7 @@ impl<'a> SectionTable<'a> {
     /// Find section containing virtual address
     pub fn by_addr(&self, addr: u64) -> Option<Section<'a>> {
         for (i, header) in self.headers.iter().enumerate() {
-            if header.sh_addr <= addr && addr < header.sh_addr + header.sh_size {
+            if header.sh_addr <= addr && addr - header.sh_addr < header.sh_size {
                 return self.by_index(i);
             }
         }
@@ -285,6 +283,25 @@ mod tests {
         assert_eq!(shstrtab.header.sh_type, SHT_STRTAB);
     }
 
+    #[test]
+    fn test_data_checked_rejects_overflowing_range() {
+        let mut data = create_test_elf_with_sections();
+        // .text sh_offset close to u64::MAX: offset + size overflows
+        let sect1_offset = 0x100 + 64;
+        data[sect1_offset + 24..sect1_offset + 32].copy_from_slice(&(u64::MAX - 4).to_le_bytes());
+        let header = parse_header(&data).unwrap();
+        let sections = SectionTable::parse(&data, &header).unwrap();
+
+        let text = sections.by_name(".text").unwrap();
+        assert!(text.data.is_empty());
+        assert!(matches!(
+            text.data_checked(),
+            Err(ElfError::Truncated { .. })
+        ));
+        let shstrtab = sections.by_name(".shstrtab").unwrap();
+        assert_eq!(shstrtab.data_checked().unwrap().len(), 0x20);
+    }
+
     #[test]
     fn test_section_by_addr() {
         let data = create_test_elf_with_sections();
@@ -26,7 +26,7 @@ impl<'a> SegmentTable<'a> {
 
         // Check bounds
         let total_size = ph_num * ph_entsize;
-        if ph_offset + total_size > data.len() {
+        if ph_offset.saturating_add(total_size) > data.len() {
             return Err(ElfError::Truncated {
                 offset: ph_offset,
                 needed: total_size,
@@ -61,7 +61,7 @@ impl<'a> SegmentTable<'a> {
             .binary_search_by(|ph| {
                 if vaddr < ph.p_vaddr {
                     std::cmp::Ordering::Greater
-                } else if vaddr >= ph.p_vaddr + ph.p_memsz {
+                } else if vaddr - ph.p_vaddr >= ph.p_memsz {
                     std::cmp::Ordering::Less
                 } else {
                     std::cmp::Ordering::Equal
@@ -72,7 +72,7 @@ impl<'a> SegmentTable<'a> {
         let ph = load_segments[idx];
         let offset = vaddr - ph.p_vaddr;
         if offset < ph.p_filesz {
-            Some((ph.p_offset + offset) as usize)
+            ph.p_offset.checked_add(offset).map(|o| o as usize)
         } else {
             None // In memory but not in file
         }
@@ -82,7 +82,7 @@ impl<'a> SegmentTable<'a> {
     pub fn segment_at_vaddr(&self, vaddr: u64) -> Option<Segment<'a>> {
         self.headers
             .iter()
-            .find(|ph| vaddr >= ph.p_vaddr && vaddr < ph.p_vaddr + ph.p_memsz)
+            .find(|ph| vaddr >= ph.p_vaddr && vaddr - ph.p_vaddr < ph.p_memsz)
             .map(|header| self.create_segment(header))
     }
 
@@ -123,14 +123,10 @@ impl<'a> SegmentTable<'a> {
             .and_then(|ph| {
                 let offset = ph.p_offset as usize;
                 let size = ph.p_filesz as usize;
-                if offset + size <= self.data.len() {
-                    let bytes = &self.data[offset..offset + size];
-                    // Remove trailing null
-                    let len = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
-                    std::str::from_utf8(&bytes[..len]).ok()
-                } else {
-                    None
-                }
+                let bytes = self.data.get(offset..offset.checked_add(size)?)?;
+                // Remove trailing null
+                let len = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+                std::str::from_utf8(&bytes[..len]).ok()
             })
     }
 
@@ -142,11 +138,10 @@ impl<'a> SegmentTable<'a> {
     fn create_segment(&self, header: &ProgramHeader) -> Segment<'a> {
         let offset = header.p_offset as usize;
         let size = header.p_filesz as usize;
-        let data = if offset + size <= self.data.len() {
-            &self.data[offset..offset + size]
-        } else {
-            &[]
-        };
+        let data = offset
+            .checked_add(size)
+            .and_then(|end| self.data.get(offset..end))
+            .unwrap_or(&[]);
         Segment {
             header: *header,
             data,
@@ -40,6 +40,15 @@ impl fmt::Display for ElfError {
 
 impl std::error::Error for ElfError {}
 
+impl From<crate::formats::read::OutOfBounds> for ElfError {
+    fn from(e: crate::formats::read::OutOfBounds) -> Self {
+        Self::Truncated {
+            offset: e.offset,
+            needed: e.needed,
+        }
+    }
+}
+
 pub type Result<T> = std::result::Result<T, ElfError>;
 
 /// ELF magic number
@@ -90,6 +99,15 @@ impl ElfData {
     }
 }
 
+impl From<ElfData> for crate::core::binary::Endianness {
+    fn from(data: ElfData) -> Self {
+        match data {
+            ElfData::Little => Self::Little,
+            ElfData::Big => Self::Big,
+        }
+    }
+}
+
 /// ELF file type
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ElfType {
@@ -223,6 +241,9 @@ pub const SHT_NOBITS: u32 = 8;
 pub const SHT_REL: u32 = 9;
 pub const SHT_SHLIB: u32 = 10;
 pub const SHT_DYNSYM: u32 = 11;
+pub const SHT_INIT_ARRAY: u32 = 14;
+pub const SHT_FINI_ARRAY: u32 = 15;
+pub const SHT_PREINIT_ARRAY: u32 = 16;
 pub const SHT_GNU_HASH: u32 = 0x6ffffff6;
 pub const SHT_GNU_VERSYM: u32 = 0x6fffffff;
 pub const SHT_GNU_VERNEED: u32 = 0x6ffffffe;
@@ -462,6 +483,21 @@ impl<'a> Section<'a> {
     pub fn is_allocated(&self) -> bool {
         (self.header.sh_flags & SHF_ALLOC) != 0
     }
+
+    /// Section file contents, or an error when `sh_offset + sh_size` does
+    /// not fit in the file (`data` is left empty in that case).
+    ///
+    /// `SHT_NOBITS` sections occupy no file space and always yield an empty
+    /// slice.
+    pub fn data_checked(&self) -> Result<&'a [u8]> {
+        if self.header.sh_type == SHT_NOBITS || self.data.len() as u64 == self.header.sh_size {
+            return Ok(self.data);
+        }
+        Err(ElfError::Truncated {
+            offset: usize::try_from(self.header.sh_offset).unwrap_or(usize::MAX),
+            needed: usize::try_from(self.header.sh_size).unwrap_or(usize::MAX),
+        })
+    }
 }
 
 /// Program segment
@@ -1,6 +1,7 @@
 //! Utility functions for ELF parsing
 
 use crate::formats::elf::types::{ElfClass, ElfData, ElfError, Result};
+use crate::formats::read;
 
 /// Trait for reading values with endianness support
 pub trait EndianRead {
@@ -13,58 +14,23 @@ pub trait EndianRead {
 
 impl EndianRead for [u8] {
     fn read_u16(&self, offset: usize, data: ElfData) -> Result<u16> {
-        if offset + 2 > self.len() {
-            return Err(ElfError::Truncated { offset, needed: 2 });
-        }
-        let bytes: [u8; 2] = self[offset..offset + 2].try_into().unwrap();
-        Ok(match data {
-            ElfData::Little => u16::from_le_bytes(bytes),
-            ElfData::Big => u16::from_be_bytes(bytes),
-        })
+        Ok(read::u16_at(self, offset, data.into())?)
     }
 
     fn read_u32(&self, offset: usize, data: ElfData) -> Result<u32> {
-        if offset + 4 > self.len() {
-            return Err(ElfError::Truncated { offset, needed: 4 });
-        }
-        let bytes: [u8; 4] = self[offset..offset + 4].try_into().unwrap();
-        Ok(match data {
-            ElfData::Little => u32::from_le_bytes(bytes),
-            ElfData::Big => u32::from_be_bytes(bytes),
-        })
+        Ok(read::u32_at(self, offset, data.into())?)
     }
 
     fn read_u64(&self, offset: usize, data: ElfData) -> Result<u64> {
-        if offset + 8 > self.len() {
-            return Err(ElfError::Truncated { offset, needed: 8 });
-        }
-        let bytes: [u8; 8] = self[offset..offset + 8].try_into().unwrap();
-        Ok(match data {
-            ElfData::Little => u64::from_le_bytes(bytes),
-            ElfData::Big => u64::from_be_bytes(bytes),
-        })
+        Ok(read::u64_at(self, offset, data.into())?)
     }
 
     fn read_i32(&self, offset: usize, data: ElfData) -> Result<i32> {
-        if offset + 4 > self.len() {
-            return Err(ElfError::Truncated { offset, needed: 4 });
-        }
-        let bytes: [u8; 4] = self[offset..offset + 4].try_into().unwrap();
-        Ok(match data {
-            ElfData::Little => i32::from_le_bytes(bytes),
-            ElfData::Big => i32::from_be_bytes(bytes),
-        })
+        Ok(read::i32_at(self, offset, data.into())?)
     }
 
     fn read_i64(&self, offset: usize, data: ElfData) -> Result<i64> {
-        if offset + 8 > self.len() {
-            return Err(ElfError::Truncated { offset, needed: 8 });
-        }
-        let bytes: [u8; 8] = self[offset..offset + 8].try_into().unwrap();
-        Ok(match data {
-            ElfData::Little => i64::from_le_bytes(bytes),
-            ElfData::Big => i64::from_be_bytes(bytes),
-        })
+        Ok(read::i64_at(self, offset, data.into())?)
     }
 }
 
@@ -78,14 +44,10 @@ pub fn read_addr(data: &[u8], offset: usize, class: ElfClass, endian: ElfData) -
 
 /// Read a null-terminated string from data
 pub fn read_cstring(data: &[u8], offset: usize) -> Result<&str> {
-    if offset >= data.len() {
-        return Err(ElfError::InvalidOffset { offset });
-    }
-
-    let slice = &data[offset..];
-    let end = slice.iter().position(|&b| b == 0).unwrap_or(slice.len());
-
-    std::str::from_utf8(&slice[..end]).map_err(|_| ElfError::InvalidString)
+    read::read_cstr(data, offset, usize::MAX).map_err(|e| match e {
+        read::StrError::OutOfBounds { offset } => ElfError::InvalidOffset { offset },
+        read::StrError::InvalidEncoding { .. } => ElfError::InvalidString,
+    })
 }
 
 /// Align a value up to the specified alignment
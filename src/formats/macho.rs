@@ -0,0 +1,165 @@
+//! Mach-O universal ("fat") binary headers.
+//!
+//! A universal binary is a big-endian `fat_header` followed by one
+//! `fat_arch` (or `fat_arch_64`) record per architecture, each pointing at
+//! a complete thin Mach-O image. [`parse_fat`] reads that table so the
+//! slices can be triaged independently; thin images are parsed through
+//! `object` in [`crate::formats::parse_any`].
+
+use crate::core::binary::Endianness;
+use crate::formats::read::{u32_at, u64_at};
+
+/// `FAT_MAGIC`, 32-bit offsets and sizes.
+pub const FAT_MAGIC: u32 = 0xCAFE_BABE;
+/// `FAT_MAGIC_64`, 64-bit offsets and sizes.
+pub const FAT_MAGIC_64: u32 = 0xCAFE_BABF;
+
+/// Upper bound on architecture records read.
+pub const MAX_FAT_ARCHS: usize = 64;
+
+const CPU_ARCH_ABI64: u32 = 0x0100_0000;
+const CPU_ARCH_ABI64_32: u32 = 0x0200_0000;
+const CPU_TYPE_X86: u32 = 7;
+const CPU_TYPE_ARM: u32 = 12;
+const CPU_TYPE_POWERPC: u32 = 18;
+/// High byte of `cpusubtype` carries capability bits (e.g. pointer auth).
+const CPU_SUBTYPE_MASK: u32 = 0x00FF_FFFF;
+
+/// One architecture slice of a universal binary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FatSlice {
+    pub cputype: u32,
+    pub cpusubtype: u32,
+    /// File offset of the slice's thin Mach-O header.
+    pub offset: u64,
+    pub size: u64,
+    /// Slice alignment as a power of two.
+    pub align: u32,
+}
+
+impl FatSlice {
+    /// Conventional architecture name (`x86_64`, `arm64e`, ...), as used by
+    /// `lipo`; `unknown` for CPU types not listed here.
+    pub fn arch_name(&self) -> &'static str {
+        cpu_name(self.cputype, self.cpusubtype)
+    }
+}
+
+/// Slices of a universal binary, in table order. Records whose range falls
+/// outside `data` or is empty are skipped. `None` when `data` does not start
+/// with a fat header or declares no, or more than [`MAX_FAT_ARCHS`],
+/// architectures.
+///
+/// `CAFEBABE` is shared with Java class files; callers that may see both
+/// should disambiguate first (see `triage::format_detection::classify_cafebabe`).
+/// Byte-swapped headers written by some tools are accepted.
+pub fn parse_fat(data: &[u8]) -> Option<Vec<FatSlice>> {
+    let (e, magic) = match u32_at(data, 0, Endianness::Big).ok()? {
+        m @ (FAT_MAGIC | FAT_MAGIC_64) => (Endianness::Big, m),
+        m => match m.swap_bytes() {
+            s @ (FAT_MAGIC | FAT_MAGIC_64) => (Endianness::Little, s),
+            _ => return None,
+        },
+    };
+    let nfat = u32_at(data, 4, e).ok()? as usize;
+    if nfat == 0 || nfat > MAX_FAT_ARCHS {
+        return None;
+    }
+    let is64 = magic == FAT_MAGIC_64;
+    // fat_arch: cputype, cpusubtype, offset, size, align (20 bytes);
+    // fat_arch_64 widens offset and size and adds a reserved word (32 bytes).
+    let entry_size = if is64 { 32 } else { 20 };
+    let mut slices = Vec::with_capacity(nfat);
+    for i in 0..nfat {
+        let base = 8 + i * entry_size;
+        let cputype = u32_at(data, base, e).ok()?;
+        let cpusubtype = u32_at(data, base + 4, e).ok()?;
+        let (offset, size, align) = if is64 {
+            (
+                u64_at(data, base + 8, e).ok()?,
+                u64_at(data, base + 16, e).ok()?,
+                u32_at(data, base + 24, e).ok()?,
+            )
+        } else {
+            (
+                u64::from(u32_at(data, base + 8, e).ok()?),
+                u64::from(u32_at(data, base + 12, e).ok()?),
+                u32_at(data, base + 16, e).ok()?,
+            )
+        };
+        let in_bounds = offset
+            .checked_add(size)
+            .is_some_and(|end| end <= data.len() as u64);
+        if offset == 0 || size == 0 || !in_bounds {
+            continue;
+        }
+        slices.push(FatSlice {
+            cputype,
+            cpusubtype,
+            offset,
+            size,
+            align,
+        });
+    }
+    Some(slices)
+}
+
+/// Architecture name for a Mach-O `cputype` / `cpusubtype` pair.
+pub fn cpu_name(cputype: u32, cpusubtype: u32) -> &'static str {
+    let sub = cpusubtype & CPU_SUBTYPE_MASK;
+    match (cputype, sub) {
+        (CPU_TYPE_X86, _) => "i386",
+        (t, 8) if t == CPU_TYPE_X86 | CPU_ARCH_ABI64 => "x86_64h",
+        (t, _) if t == CPU_TYPE_X86 | CPU_ARCH_ABI64 => "x86_64",
+        (CPU_TYPE_ARM, 9) => "armv7",
+        (CPU_TYPE_ARM, 11) => "armv7s",
+        (CPU_TYPE_ARM, 12) => "armv7k",
+        (CPU_TYPE_ARM, _) => "arm",
+        (t, 2) if t == CPU_TYPE_ARM | CPU_ARCH_ABI64 => "arm64e",
+        (t, _) if t == CPU_TYPE_ARM | CPU_ARCH_ABI64 => "arm64",
+        (t, _) if t == CPU_TYPE_ARM | CPU_ARCH_ABI64_32 => "arm64_32",
+        (CPU_TYPE_POWERPC, _) => "ppc",
+        (t, _) if t == CPU_TYPE_POWERPC | CPU_ARCH_ABI64 => "ppc64",
+        _ => "unknown",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_fat_and_fat64_tables() {
+        // x86_64 + arm64e (pointer-auth capability bits set), 32-bit records.
+        let mut fat = vec![0u8; 0x300];
+        fat[..8].copy_from_slice(&[0xCA, 0xFE, 0xBA, 0xBE, 0, 0, 0, 2]);
+        for (i, (cpu, sub, off)) in [
+            (0x0100_0007u32, 3u32, 0x100u32),
+            (0x0100_000C, 0x8000_0002, 0x200),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let b = 8 + i * 20;
+            fat[b..b + 4].copy_from_slice(&cpu.to_be_bytes());
+            fat[b + 4..b + 8].copy_from_slice(&sub.to_be_bytes());
+            fat[b + 8..b + 12].copy_from_slice(&off.to_be_bytes());
+            fat[b + 12..b + 16].copy_from_slice(&0x100u32.to_be_bytes());
+            fat[b + 16..b + 20].copy_from_slice(&12u32.to_be_bytes());
+        }
+        let slices = parse_fat(&fat).unwrap();
+        let names: Vec<_> = slices.iter().map(|s| (s.arch_name(), s.offset)).collect();
+        assert_eq!(names, vec![("x86_64", 0x100), ("arm64e", 0x200)]);
+
+        // One fat_arch_64 record whose slice runs past the end is dropped.
+        let mut fat64 = vec![0u8; 0x80];
+        fat64[..8].copy_from_slice(&[0xCA, 0xFE, 0xBA, 0xBF, 0, 0, 0, 1]);
+        fat64[8..12].copy_from_slice(&0x0100_000Cu32.to_be_bytes());
+        fat64[16..24].copy_from_slice(&0x40u64.to_be_bytes());
+        fat64[24..32].copy_from_slice(&0x1000u64.to_be_bytes());
+        assert_eq!(parse_fat(&fat64), Some(Vec::new()));
+
+        assert!(parse_fat(b"\xCA\xFE\xBA\xBE\0\0\0\0").is_none());
+        assert!(parse_fat(b"\x7FELF\x02\x01\x01\0").is_none());
+    }
+}
@@ -2,7 +2,15 @@
 
 pub mod apk;
 pub mod axml;
+pub mod cab;
 pub mod dex;
 pub mod elf;
+pub mod macho;
+pub mod ole;
+pub mod parse_any;
 pub mod pe;
+pub mod read;
+pub mod section_class;
 pub mod sepolicy;
+
+pub use parse_any::{try_parse_any, ParseOutcome, ParsedBinary, ParsedSection};
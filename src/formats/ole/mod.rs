@@ -0,0 +1,431 @@
+//! OLE Compound File Binary (`D0 CF 11 E0`) reader.
+//!
+//! Windows Installer packages, legacy Office documents (`.doc`, `.xls`,
+//! `.ppt`) and many other formats are compound files: a FAT-style
+//! filesystem of sectors holding a tree of storages and streams. This parses
+//! the header, the FAT (through the DIFAT), the mini FAT and the directory
+//! to enumerate every storage and stream with its path and size, without
+//! reading stream contents. [`OleFile::read_stream`] follows a stream's
+//! sector chain on demand.
+//!
+//! Windows Installer stores its table and stream names in a compressed
+//! encoding; [`decode_msi_name`] expands them.
+//!
+//! Layout reference: Microsoft "Compound File Binary File Format" (MS-CFB).
+
+use crate::core::binary::Endianness;
+use crate::formats::read::{bytes_at, u16_at, u32_at, u64_at, OutOfBounds};
+use std::fmt;
+
+/// Compound file parsing errors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OleError {
+    /// The buffer does not start with the compound file signature.
+    BadMagic,
+    /// A structure ran past the end of the buffer.
+    Truncated { offset: usize, needed: usize },
+    /// A header or directory field holds an impossible value.
+    Malformed(&'static str),
+}
+
+impl fmt::Display for OleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BadMagic => write!(f, "not a compound file (missing D0CF11E0 signature)"),
+            Self::Truncated { offset, needed } => {
+                write!(f, "truncated at {:#x}, needed {} bytes", offset, needed)
+            }
+            Self::Malformed(what) => write!(f, "malformed compound file: {}", what),
+        }
+    }
+}
+
+impl std::error::Error for OleError {}
+
+impl From<OutOfBounds> for OleError {
+    fn from(e: OutOfBounds) -> Self {
+        Self::Truncated {
+            offset: e.offset,
+            needed: e.needed,
+        }
+    }
+}
+
+pub type Result<T> = std::result::Result<T, OleError>;
+
+/// Signature that opens every compound file.
+pub const OLE_MAGIC: [u8; 8] = [0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
+/// Header size; the header occupies all of sector -1.
+const HEADER_SIZE: usize = 512;
+/// Compound files are little-endian throughout.
+const LE: Endianness = Endianness::Little;
+/// FAT sector locations held in the header itself.
+const HEADER_DIFAT_ENTRIES: usize = 109;
+/// Largest regular sector number; higher values are markers.
+const MAXREGSECT: u32 = 0xFFFF_FFFA;
+/// Empty sibling/child link in a directory entry.
+const NOSTREAM: u32 = 0xFFFF_FFFF;
+const DIR_ENTRY_SIZE: usize = 128;
+/// Bound on directory entries read.
+const MAX_DIR_ENTRIES: usize = 65_536;
+
+/// Directory object type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OleEntryKind {
+    Storage,
+    Stream,
+}
+
+/// One storage or stream reachable from the root storage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OleEntry {
+    /// Directory entry index (stream ID).
+    pub id: u32,
+    /// Raw entry name; see [`decode_msi_name`] for Windows Installer names.
+    pub name: String,
+    /// `/`-separated path from the root storage, e.g. `Macros/VBA/dir`.
+    pub path: String,
+    pub kind: OleEntryKind,
+    pub clsid: [u8; 16],
+    pub start_sector: u32,
+    /// Stream size in bytes (zero for storages).
+    pub size: u64,
+    /// Absolute offset of the stream's bytes when they are stored
+    /// contiguously in the file.
+    pub data_offset: Option<u64>,
+}
+
+/// Parsed compound file header, allocation tables and directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OleFile {
+    /// 3 (512-byte sectors) or 4 (4096-byte sectors).
+    pub major_version: u16,
+    pub sector_size: usize,
+    pub mini_sector_size: usize,
+    /// Streams smaller than this live in the mini stream.
+    pub mini_stream_cutoff: u32,
+    /// CLSID of the root storage (identifies MSI packages, Office documents).
+    pub root_clsid: [u8; 16],
+    /// Storages and streams below the root, ordered by path.
+    pub entries: Vec<OleEntry>,
+    fat: Vec<u32>,
+    minifat: Vec<u32>,
+    /// Main-FAT sectors holding the mini stream.
+    ministream: Vec<u32>,
+}
+
+/// True if `data` starts with the compound file signature.
+pub fn is_ole(data: &[u8]) -> bool {
+    data.len() >= HEADER_SIZE && data.starts_with(&OLE_MAGIC)
+}
+
+/// Sector numbers of the chain starting at `start`. Stops at any marker
+/// value, an out-of-range link, or after visiting every table slot (cycles).
+fn chain(table: &[u32], start: u32) -> Vec<u32> {
+    let mut out = Vec::new();
+    let mut s = start;
+    while (s as usize) < table.len() && out.len() < table.len() {
+        out.push(s);
+        s = table[s as usize];
+    }
+    out
+}
+
+/// Read every sector of `sectors` as little-endian `u32` entries; sectors
+/// past the end of `data` are skipped.
+fn read_table(data: &[u8], sectors: &[u32], sector_size: usize) -> Vec<u32> {
+    sectors
+        .iter()
+        .filter_map(|&s| bytes_at(data, sector_offset(s, sector_size), sector_size).ok())
+        .flat_map(|b| {
+            b.chunks_exact(4)
+                .map(|w| u32::from_le_bytes([w[0], w[1], w[2], w[3]]))
+        })
+        .collect()
+}
+
+fn sector_offset(sector: u32, sector_size: usize) -> usize {
+    (sector as usize)
+        .saturating_add(1)
+        .saturating_mul(sector_size)
+}
+
+/// Directory entry as stored, before the tree is walked.
+struct RawEntry {
+    name: String,
+    object_type: u8,
+    left: u32,
+    right: u32,
+    child: u32,
+    clsid: [u8; 16],
+    start_sector: u32,
+    size: u64,
+}
+
+impl RawEntry {
+    fn parse(b: &[u8], major_version: u16) -> Result<Self> {
+        let name_len = (u16_at(b, 0x40, LE)? as usize).min(64);
+        let units: Vec<u16> = bytes_at(b, 0, name_len)?
+            .chunks_exact(2)
+            .map(|w| u16::from_le_bytes([w[0], w[1]]))
+            .take_while(|&u| u != 0)
+            .collect();
+        let mut clsid = [0u8; 16];
+        clsid.copy_from_slice(bytes_at(b, 0x50, 16)?);
+        let mut size = u64_at(b, 0x78, LE)?;
+        // Version 3 files may leave garbage in the high half.
+        if major_version == 3 {
+            size &= 0xFFFF_FFFF;
+        }
+        Ok(Self {
+            name: String::from_utf16_lossy(&units),
+            object_type: bytes_at(b, 0x42, 1)?[0],
+            left: u32_at(b, 0x44, LE)?,
+            right: u32_at(b, 0x48, LE)?,
+            child: u32_at(b, 0x4C, LE)?,
+            clsid,
+            start_sector: u32_at(b, 0x74, LE)?,
+            size,
+        })
+    }
+}
+
+impl OleFile {
+    /// Parse the header, allocation tables and directory of a compound file.
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        if !data.starts_with(&OLE_MAGIC) {
+            return Err(OleError::BadMagic);
+        }
+        bytes_at(data, 0, HEADER_SIZE)?;
+        let major_version = u16_at(data, 0x1A, LE)?;
+        let sector_shift = u16_at(data, 0x1E, LE)?;
+        let mini_shift = u16_at(data, 0x20, LE)?;
+        if !matches!(sector_shift, 9 | 12) || mini_shift != 6 {
+            return Err(OleError::Malformed("sector size"));
+        }
+        let sector_size = 1usize << sector_shift;
+        let n_fat = u32_at(data, 0x2C, LE)? as usize;
+        let first_dir = u32_at(data, 0x30, LE)?;
+        let mini_stream_cutoff = u32_at(data, 0x38, LE)?;
+        let first_minifat = u32_at(data, 0x3C, LE)?;
+        let first_difat = u32_at(data, 0x44, LE)?;
+        let n_difat = u32_at(data, 0x48, LE)? as usize;
+
+        // FAT sector locations: 109 in the header, the rest in the DIFAT
+        // chain, each DIFAT sector ending with the link to the next one.
+        let max_sectors = data.len() / sector_size;
+        let mut fat_sectors = Vec::new();
+        for i in 0..HEADER_DIFAT_ENTRIES {
+            let s = u32_at(data, 0x4C + 4 * i, LE)?;
+            if s <= MAXREGSECT {
+                fat_sectors.push(s);
+            }
+        }
+        let per_difat = sector_size / 4 - 1;
+        let mut difat = first_difat;
+        for _ in 0..n_difat.min(max_sectors) {
+            if difat > MAXREGSECT {
+                break;
+            }
+            let base = sector_offset(difat, sector_size);
+            for i in 0..per_difat {
+                let s = u32_at(data, base + 4 * i, LE)?;
+                if s <= MAXREGSECT {
+                    fat_sectors.push(s);
+                }
+            }
+            difat = u32_at(data, base + 4 * per_difat, LE)?;
+        }
+        fat_sectors.truncate(n_fat);
+        let fat = read_table(data, &fat_sectors, sector_size);
+        let minifat = read_table(data, &chain(&fat, first_minifat), sector_size);
+
+        let mut raw = Vec::new();
+        'dir: for s in chain(&fat, first_dir) {
+            let Ok(sector) = bytes_at(data, sector_offset(s, sector_size), sector_size) else {
+                break;
+            };
+            for b in sector.chunks_exact(DIR_ENTRY_SIZE) {
+                if raw.len() >= MAX_DIR_ENTRIES {
+                    break 'dir;
+                }
+                raw.push(RawEntry::parse(b, major_version)?);
+            }
+        }
+        let root = match raw.first() {
+            Some(r) if r.object_type == 5 => r,
+            _ => return Err(OleError::Malformed("missing root entry")),
+        };
+
+        let mut file = Self {
+            major_version,
+            sector_size,
+            mini_sector_size: 1 << mini_shift,
+            mini_stream_cutoff,
+            root_clsid: root.clsid,
+            entries: Vec::new(),
+            ministream: chain(&fat, root.start_sector),
+            fat,
+            minifat,
+        };
+
+        // Siblings form a red-black tree and each storage's `child` roots
+        // the tree of its members; walk iteratively so a hostile tree can
+        // neither recurse deeply nor loop.
+        let mut visited = vec![false; raw.len()];
+        let mut stack = vec![(root.child, String::new())];
+        while let Some((id, parent)) = stack.pop() {
+            let Some(e) = raw.get(id as usize).filter(|_| id != NOSTREAM) else {
+                continue;
+            };
+            if std::mem::replace(&mut visited[id as usize], true) {
+                continue;
+            }
+            stack.push((e.left, parent.clone()));
+            stack.push((e.right, parent.clone()));
+            let kind = match e.object_type {
+                1 => OleEntryKind::Storage,
+                2 => OleEntryKind::Stream,
+                _ => continue,
+            };
+            let path = if parent.is_empty() {
+                e.name.clone()
+            } else {
+                format!("{}/{}", parent, e.name)
+            };
+            if kind == OleEntryKind::Storage {
+                stack.push((e.child, path.clone()));
+            }
+            let mut entry = OleEntry {
+                id,
+                name: e.name.clone(),
+                path,
+                kind,
+                clsid: e.clsid,
+                start_sector: e.start_sector,
+                size: if kind == OleEntryKind::Stream {
+                    e.size
+                } else {
+                    0
+                },
+                data_offset: None,
+            };
+            if kind == OleEntryKind::Stream {
+                entry.data_offset = match file.segments(data, &entry).as_slice() {
+                    [(off, len)] if *len as u64 == entry.size => Some(*off as u64),
+                    _ => None,
+                };
+            }
+            file.entries.push(entry);
+        }
+        file.entries.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(file)
+    }
+
+    /// Entry at `path` (as in [`OleEntry::path`]).
+    pub fn entry(&self, path: &str) -> Option<&OleEntry> {
+        self.entries.iter().find(|e| e.path == path)
+    }
+
+    /// Iterate over stream entries only.
+    pub fn streams(&self) -> impl Iterator<Item = &OleEntry> {
+        self.entries
+            .iter()
+            .filter(|e| e.kind == OleEntryKind::Stream)
+    }
+
+    /// File offset of a stream's first byte, whether or not the rest of
+    /// the stream follows contiguously.
+    pub fn stream_start(&self, data: &[u8], entry: &OleEntry) -> Option<u64> {
+        if entry.kind != OleEntryKind::Stream {
+            return None;
+        }
+        self.segments(data, entry)
+            .first()
+            .map(|&(off, _)| off as u64)
+    }
+
+    /// Contents of a stream, following its sector chain in the FAT or the
+    /// mini FAT. `None` for storages or when the chain is shorter than the
+    /// declared size.
+    pub fn read_stream(&self, data: &[u8], entry: &OleEntry) -> Option<Vec<u8>> {
+        if entry.kind != OleEntryKind::Stream {
+            return None;
+        }
+        let mut out = Vec::with_capacity(entry.size.min(data.len() as u64) as usize);
+        for (off, len) in self.segments(data, entry) {
+            out.extend_from_slice(&data[off..off + len]);
+        }
+        (out.len() as u64 == entry.size).then_some(out)
+    }
+
+    /// File ranges holding `entry`'s bytes, in order, adjacent ranges merged
+    /// and the last one trimmed to the declared size. Stops early at the
+    /// end of the chain or of `data`.
+    fn segments(&self, data: &[u8], entry: &OleEntry) -> Vec<(usize, usize)> {
+        let (units, unit_size): (Vec<usize>, usize) = if entry.size < self.mini_stream_cutoff as u64
+        {
+            let mini = self.mini_sector_size;
+            let offsets = chain(&self.minifat, entry.start_sector)
+                .into_iter()
+                .map_while(|m| {
+                    let pos = m as usize * mini;
+                    let host = *self.ministream.get(pos / self.sector_size)?;
+                    Some(sector_offset(host, self.sector_size) + pos % self.sector_size)
+                })
+                .collect();
+            (offsets, mini)
+        } else {
+            let offsets = chain(&self.fat, entry.start_sector)
+                .into_iter()
+                .map(|s| sector_offset(s, self.sector_size))
+                .collect();
+            (offsets, self.sector_size)
+        };
+
+        let mut out: Vec<(usize, usize)> = Vec::new();
+        let mut remaining = usize::try_from(entry.size).unwrap_or(usize::MAX);
+        for off in units {
+            if remaining == 0 {
+                break;
+            }
+            let len = unit_size.min(remaining);
+            if off.checked_add(len).is_none_or(|end| end > data.len()) {
+                break;
+            }
+            match out.last_mut() {
+                Some((prev, prev_len)) if *prev + *prev_len == off => *prev_len += len,
+                _ => out.push((off, len)),
+            }
+            remaining -= len;
+        }
+        out
+    }
+}
+
+/// Expand a Windows Installer stream name.
+///
+/// MSI packs two characters of `[0-9A-Za-z._]` into each code unit in
+/// `U+3800..U+47FF`, one into `U+4800..U+483F`, and prefixes table streams
+/// with `U+4840`, rendered here as `!`. Other characters pass through.
+pub fn decode_msi_name(name: &str) -> String {
+    const CHARSET: &[u8; 64] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz._";
+    let mut out = String::with_capacity(name.len());
+    for c in name.chars() {
+        let u = c as u32;
+        match u {
+            0x3800..=0x47FF => {
+                let v = (u - 0x3800) as usize;
+                out.push(CHARSET[v & 0x3F] as char);
+                out.push(CHARSET[(v >> 6) & 0x3F] as char);
+            }
+            0x4800..=0x483F => out.push(CHARSET[(u - 0x4800) as usize] as char),
+            0x4840 => out.push('!'),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,158 @@
+//! Compound file reader tests over a hand-assembled version 3 image: a
+//! `Macros` storage holding a regular-FAT stream, and a sibling stream
+//! stored in the mini stream.
+
+use super::*;
+
+const ENDOFCHAIN: u32 = 0xFFFF_FFFE;
+const FATSECT: u32 = 0xFFFF_FFFD;
+const FREESECT: u32 = 0xFFFF_FFFF;
+
+fn put32(d: &mut [u8], off: usize, v: u32) {
+    d[off..off + 4].copy_from_slice(&v.to_le_bytes());
+}
+
+fn dir_entry(
+    d: &mut [u8],
+    off: usize,
+    name: &str,
+    object_type: u8,
+    (left, right, child): (u32, u32, u32),
+    start: u32,
+    size: u32,
+) {
+    let units: Vec<u16> = name.encode_utf16().collect();
+    for (i, u) in units.iter().enumerate() {
+        d[off + 2 * i..off + 2 * i + 2].copy_from_slice(&u.to_le_bytes());
+    }
+    d[off + 0x40..off + 0x42].copy_from_slice(&((units.len() as u16 + 1) * 2).to_le_bytes());
+    d[off + 0x42] = object_type;
+    put32(d, off + 0x44, left);
+    put32(d, off + 0x48, right);
+    put32(d, off + 0x4C, child);
+    put32(d, off + 0x74, start);
+    put32(d, off + 0x78, size);
+}
+
+/// Sector 0: FAT; 1: directory; 2..=10: `Macros/big` (4608 bytes);
+/// 11: mini stream; 12: mini FAT. `small` (100 bytes) uses mini sectors 0-1.
+fn build_ole() -> Vec<u8> {
+    let sector = |n: usize| (n + 1) * 512;
+    let mut d = vec![0u8; sector(13)];
+    d[..8].copy_from_slice(&OLE_MAGIC);
+    d[0x18..0x1A].copy_from_slice(&0x3Eu16.to_le_bytes());
+    d[0x1A..0x1C].copy_from_slice(&3u16.to_le_bytes());
+    d[0x1C..0x1E].copy_from_slice(&0xFFFEu16.to_le_bytes());
+    d[0x1E..0x20].copy_from_slice(&9u16.to_le_bytes());
+    d[0x20..0x22].copy_from_slice(&6u16.to_le_bytes());
+    put32(&mut d, 0x2C, 1); // FAT sectors
+    put32(&mut d, 0x30, 1); // first directory sector
+    put32(&mut d, 0x38, 4096); // mini stream cutoff
+    put32(&mut d, 0x3C, 12); // first mini FAT sector
+    put32(&mut d, 0x40, 1);
+    put32(&mut d, 0x44, ENDOFCHAIN);
+    for i in 0..HEADER_DIFAT_ENTRIES {
+        put32(&mut d, 0x4C + 4 * i, if i == 0 { 0 } else { FREESECT });
+    }
+
+    let fat = sector(0);
+    for i in 0..128 {
+        let v = match i {
+            0 => FATSECT,
+            2..=9 => i as u32 + 1,
+            1 | 10 | 11 | 12 => ENDOFCHAIN,
+            _ => FREESECT,
+        };
+        put32(&mut d, fat + 4 * i, v);
+    }
+    let minifat = sector(12);
+    for i in 0..128 {
+        let v = match i {
+            0 => 1,
+            1 => ENDOFCHAIN,
+            _ => FREESECT,
+        };
+        put32(&mut d, minifat + 4 * i, v);
+    }
+
+    let dir = sector(1);
+    let none = NOSTREAM;
+    dir_entry(&mut d, dir, "Root Entry", 5, (none, none, 1), 11, 128);
+    dir_entry(&mut d, dir + 128, "Macros", 1, (none, 3, 2), 0, 0);
+    dir_entry(&mut d, dir + 256, "big", 2, (none, none, none), 2, 4608);
+    dir_entry(&mut d, dir + 384, "small", 2, (none, none, none), 0, 100);
+
+    for (i, b) in d[sector(2)..sector(11)].iter_mut().enumerate() {
+        *b = (i % 251) as u8;
+    }
+    d[sector(11)..sector(11) + 100].fill(0xAB);
+    d
+}
+
+#[test]
+fn enumerates_storages_and_streams() {
+    let data = build_ole();
+    assert!(is_ole(&data));
+    let ole = OleFile::parse(&data).unwrap();
+    assert_eq!((ole.major_version, ole.sector_size), (3, 512));
+
+    let listing: Vec<_> = ole
+        .entries
+        .iter()
+        .map(|e| (e.path.as_str(), e.kind, e.size, e.data_offset))
+        .collect();
+    assert_eq!(
+        listing,
+        vec![
+            ("Macros", OleEntryKind::Storage, 0, None),
+            ("Macros/big", OleEntryKind::Stream, 4608, Some(3 * 512)),
+            ("small", OleEntryKind::Stream, 100, Some(12 * 512)),
+        ]
+    );
+    assert_eq!(ole.streams().count(), 2);
+}
+
+#[test]
+fn reads_regular_and_mini_streams() {
+    let data = build_ole();
+    let ole = OleFile::parse(&data).unwrap();
+
+    let big = ole
+        .read_stream(&data, ole.entry("Macros/big").unwrap())
+        .unwrap();
+    assert_eq!(big.len(), 4608);
+    assert_eq!(&big[..4], &[0, 1, 2, 3]);
+
+    let small = ole.read_stream(&data, ole.entry("small").unwrap()).unwrap();
+    assert_eq!(small, vec![0xAB; 100]);
+
+    assert!(ole
+        .read_stream(&data, ole.entry("Macros").unwrap())
+        .is_none());
+}
+
+#[test]
+fn rejects_bad_input_and_survives_cycles() {
+    assert_eq!(OleFile::parse(b"PK\x03\x04"), Err(OleError::BadMagic));
+    assert!(matches!(
+        OleFile::parse(&OLE_MAGIC),
+        Err(OleError::Truncated { .. })
+    ));
+
+    // Sibling links pointing back at themselves are walked once.
+    let mut data = build_ole();
+    put32(&mut data, 2 * 512 + 128 + 0x48, 1);
+    let ole = OleFile::parse(&data).unwrap();
+    assert_eq!(ole.entries.len(), 2);
+}
+
+#[test]
+fn decodes_msi_stream_names() {
+    // "_T" packed into one unit, behind the table marker.
+    assert_eq!(decode_msi_name("\u{4840}\u{3F7F}"), "!_T");
+    assert_eq!(decode_msi_name("\u{4811}"), "H");
+    assert_eq!(
+        decode_msi_name("\u{5}SummaryInformation"),
+        "\u{5}SummaryInformation"
+    );
+}
@@ -0,0 +1,251 @@
+//! Panic-free "parse whatever this is" entry point for untrusted input.
+//!
+//! [`try_parse_any`] is the supported way to feed arbitrary uploads to the
+//! format parsers. It dispatches on magic bytes to the ELF and PE parsers
+//! (PE in [`ParseOptions::best_effort`] mode) or to `object` for Mach-O, and
+//! flattens the result into an owned [`ParsedBinary`]. Every path it takes
+//! uses checked arithmetic and bounds-checked slicing, so any input yields a
+//! [`ParseOutcome`] rather than a panic; callers do not need `catch_unwind`.
+//! Mach-O handling inherits `object`'s own no-panic guarantee.
+
+use crate::core::binary::Format;
+use crate::formats::elf::{ElfClass, ElfParser};
+use crate::formats::pe::{ParseOptions, PeParser};
+
+/// A section as reported by [`try_parse_any`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedSection {
+    pub name: String,
+    /// Virtual address (PE: image base + RVA).
+    pub addr: u64,
+    /// Size in memory.
+    pub size: u64,
+    pub file_offset: u64,
+    /// Bytes actually backed by the file (0 for BSS-like sections).
+    pub file_size: u64,
+}
+
+/// Owned summary of a successfully parsed binary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedBinary {
+    pub format: Format,
+    pub is_64bit: bool,
+    /// Entry point VA, when the format declares one.
+    pub entry_point: Option<u64>,
+    pub sections: Vec<ParsedSection>,
+    /// Non-fatal problems encountered (truncated tables, clamped ranges).
+    pub issues: Vec<String>,
+}
+
+/// Outcome of [`try_parse_any`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseOutcome {
+    Parsed(ParsedBinary),
+    /// The magic matched `format` but its headers could not be parsed.
+    Failed {
+        format: Format,
+        error: String,
+    },
+    /// No supported magic.
+    Unrecognized,
+}
+
+/// Parse `data` as ELF, PE or (thin) Mach-O without ever panicking.
+pub fn try_parse_any(data: &[u8]) -> ParseOutcome {
+    if data.starts_with(b"\x7fELF") {
+        parse_elf(data)
+    } else if data.starts_with(b"MZ") {
+        parse_pe(data)
+    } else if is_thin_macho(data) {
+        parse_macho(data)
+    } else {
+        ParseOutcome::Unrecognized
+    }
+}
+
+fn parse_elf(data: &[u8]) -> ParseOutcome {
+    let elf = match ElfParser::parse(data) {
+        Ok(elf) => elf,
+        Err(e) => return failed(Format::ELF, e),
+    };
+    let header = elf.header();
+    let mut issues = Vec::new();
+    let sections = match elf.sections() {
+        Ok(table) => table
+            .sections()
+            .map(|s| ParsedSection {
+                name: s.name().to_string(),
+                addr: s.header.sh_addr,
+                size: s.header.sh_size,
+                file_offset: s.header.sh_offset,
+                file_size: s.data.len() as u64,
+            })
+            .collect(),
+        Err(e) => {
+            issues.push(format!("section headers: {}", e));
+            Vec::new()
+        }
+    };
+    if let Err(e) = elf.segments() {
+        issues.push(format!("program headers: {}", e));
+    }
+    let entry = header.entry_point();
+    ParseOutcome::Parsed(ParsedBinary {
+        format: Format::ELF,
+        is_64bit: header.ident.class == ElfClass::Elf64,
+        entry_point: (entry != 0).then_some(entry),
+        sections,
+        issues,
+    })
+}
+
+fn parse_pe(data: &[u8]) -> ParseOutcome {
+    let options = ParseOptions {
+        best_effort: true,
+        ..Default::default()
+    };
+    let pe = match PeParser::with_options(data, options) {
+        Ok(pe) => pe,
+        Err(e) => return failed(Format::PE, e),
+    };
+    let base = pe.image_base();
+    let sections = pe
+        .sections()
+        .iter()
+        .map(|s| ParsedSection {
+            name: s.header.name(),
+            addr: base.wrapping_add(s.header.virtual_address as u64),
+            size: s.header.virtual_size as u64,
+            file_offset: s.data.start as u64,
+            file_size: s.data.len() as u64,
+        })
+        .collect();
+    let entry = pe.entry_point();
+    ParseOutcome::Parsed(ParsedBinary {
+        format: Format::PE,
+        is_64bit: pe.is_64bit(),
+        entry_point: (entry != 0).then(|| base.wrapping_add(entry as u64)),
+        sections,
+        issues: pe.issues().iter().map(|i| i.message.clone()).collect(),
+    })
+}
+
+fn is_thin_macho(data: &[u8]) -> bool {
+    matches!(
+        data.get(..4),
+        Some([0xfe, 0xed, 0xfa, 0xce | 0xcf] | [0xce | 0xcf, 0xfa, 0xed, 0xfe])
+    )
+}
+
+fn parse_macho(data: &[u8]) -> ParseOutcome {
+    use object::{Object, ObjectSection};
+
+    let file = match object::read::File::parse(data) {
+        Ok(file) => file,
+        Err(e) => return failed(Format::MachO, e),
+    };
+    let sections = file
+        .sections()
+        .map(|s| {
+            let (file_offset, file_size) = s.file_range().unwrap_or((0, 0));
+            ParsedSection {
+                name: s.name().unwrap_or("").to_string(),
+                addr: s.address(),
+                size: s.size(),
+                file_offset,
+                file_size,
+            }
+        })
+        .collect();
+    let entry = file.entry();
+    ParseOutcome::Parsed(ParsedBinary {
+        format: Format::MachO,
+        is_64bit: file.is_64(),
+        entry_point: (entry != 0).then_some(entry),
+        sections,
+        issues: Vec::new(),
+    })
+}
+
+fn failed(format: Format, error: impl std::fmt::Display) -> ParseOutcome {
+    ParseOutcome::Failed {
+        format,
+        error: error.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn minimal_elf() -> Vec<u8> {
+        let mut data = vec![0u8; 64];
+        data[0..4].copy_from_slice(b"\x7fELF");
+        data[4] = 2;
+        data[5] = 1;
+        data[6] = 1;
+        data[16] = 2;
+        data[18] = 62;
+        data[20] = 1;
+        data[24..32].copy_from_slice(&0x401000u64.to_le_bytes());
+        data[52] = 64;
+        data
+    }
+
+    #[test]
+    fn dispatches_on_magic() {
+        match try_parse_any(&minimal_elf()) {
+            ParseOutcome::Parsed(b) => {
+                assert_eq!(b.format, Format::ELF);
+                assert!(b.is_64bit);
+                assert_eq!(b.entry_point, Some(0x401000));
+                assert!(b.sections.is_empty());
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(matches!(
+            try_parse_any(b"MZ truncated"),
+            ParseOutcome::Failed {
+                format: Format::PE,
+                ..
+            }
+        ));
+        assert_eq!(try_parse_any(b"hello"), ParseOutcome::Unrecognized);
+        assert_eq!(try_parse_any(&[]), ParseOutcome::Unrecognized);
+    }
+
+    #[test]
+    fn never_panics_on_mutated_headers() {
+        // Deterministic header fuzzing: random bytes over the ELF header and
+        // the first program/section header slots, at several lengths.
+        let mut rng: u64 = 0x243f_6a88_85a3_08d3;
+        let mut next = || {
+            rng ^= rng << 13;
+            rng ^= rng >> 7;
+            rng ^= rng << 17;
+            rng
+        };
+        for round in 0..2000 {
+            let mut data = minimal_elf();
+            data.resize(256, 0);
+            for _ in 0..8 {
+                let pos = 16 + (next() % 240) as usize;
+                data[pos] = next() as u8;
+            }
+            data[4] = 1 + (round % 2) as u8;
+            data.truncate(64 + (next() % 192) as usize);
+            let _ = try_parse_any(&data);
+
+            let mut pe = b"MZ".to_vec();
+            pe.resize(512, 0);
+            pe[0x3c] = 0x40;
+            pe[0x40..0x44].copy_from_slice(b"PE\0\0");
+            for _ in 0..12 {
+                let pos = 0x44 + (next() % 0x1bc) as usize;
+                pe[pos] = next() as u8;
+            }
+            pe.truncate(0x58 + (next() % 0x1a8) as usize);
+            let _ = try_parse_any(&pe);
+        }
+    }
+}
@@ -7,6 +7,7 @@ use crate::formats::pe::types::{DataDirectory, DebugEntry, PeError, Result};
 use crate::formats::pe::utils::ReadExt;
 
 const IMAGE_DEBUG_TYPE_CODEVIEW: u32 = 2;
+const IMAGE_DEBUG_TYPE_REPRO: u32 = 16;
 const IMAGE_DEBUG_DIRECTORY_ENTRY_SIZE: usize = 28;
 const CODEVIEW_RSDS_SIGNATURE: &[u8; 4] = b"RSDS";
 
@@ -63,6 +64,16 @@ pub struct DebugDirectory {
     pub warnings: Vec<&'static str>,
 }
 
+impl DebugDirectory {
+    /// True when the linker emitted an `IMAGE_DEBUG_TYPE_REPRO` entry
+    /// (`/Brepro`), meaning `TimeDateStamp` is a content hash, not a time.
+    pub fn is_reproducible(&self) -> bool {
+        self.entries
+            .iter()
+            .any(|e| e.debug_type == IMAGE_DEBUG_TYPE_REPRO)
+    }
+}
+
 /// Parse the PE debug directory and extract the first CodeView RSDS record.
 pub fn parse_debug_directory(
     data: &[u8],
@@ -13,6 +13,7 @@ pub struct ImportTable<'a> {
     pub by_name: HashMap<&'a str, Vec<ImportEntry<'a>>>,
     pub by_dll: HashMap<&'a str, Vec<ImportEntry<'a>>>,
     pub iat_map: BTreeMap<u64, &'a str>,
+    pub bound_imports: Vec<BoundImport<'a>>,
 }
 
 impl<'a> ImportTable<'a> {
@@ -41,6 +42,18 @@ impl<'a> ImportTable<'a> {
         self.by_dll.get(dll).map(|v| v.as_slice())
     }
 
+    /// Descriptors read from the delay-load directory
+    pub fn delay_descriptors(&self) -> impl Iterator<Item = &ImportDescriptor<'a>> + '_ {
+        self.descriptors.iter().filter(|d| d.is_delay)
+    }
+
+    /// Check if `name` is imported only through the delay-load directory
+    pub fn is_delay_loaded(&self, name: &str) -> bool {
+        self.by_name
+            .get(name)
+            .is_some_and(|entries| entries.iter().all(|e| e.is_delay))
+    }
+
     /// Calculate import hash (for imphash)
     pub fn import_hash(&self) -> String {
         let mut entries = Vec::new();
@@ -66,6 +79,7 @@ pub fn parse_imports<'a>(
     sections: &SectionTable,
     import_dir: &DataDirectory,
     delay_dir: &DataDirectory,
+    bound_dir: &DataDirectory,
     image_base: u64,
     is_64bit: bool,
     options: &ParseOptions,
@@ -100,16 +114,74 @@ pub fn parse_imports<'a>(
         )?;
     }
 
+    if bound_dir.virtual_address != 0 && bound_dir.size > 0 && options.parse_imports {
+        table.bound_imports = parse_bound_imports(data, sections, bound_dir);
+    }
+
     Ok(table)
 }
 
+/// Walk `IMAGE_BOUND_IMPORT_DESCRIPTOR`s. Name offsets are relative to
+/// the start of the directory. The directory normally lives in the
+/// header area, where RVAs equal file offsets. Stops quietly at the
+/// first malformed record.
+fn parse_bound_imports<'a>(
+    data: &'a [u8],
+    sections: &SectionTable,
+    bound_dir: &DataDirectory,
+) -> Vec<BoundImport<'a>> {
+    let rva = bound_dir.virtual_address;
+    let base = sections.rva_to_offset(rva).unwrap_or(rva as usize);
+    let end = base.saturating_add(bound_dir.size as usize).min(data.len());
+    let name_at = |off: u16| read_cstring(data, base.saturating_add(off as usize), 256).ok();
+
+    let mut imports = Vec::new();
+    let mut offset = base;
+    while offset.saturating_add(8) <= end {
+        let (Some(stamp), Some(name_off), Some(refs)) = (
+            data.read_u32_le_at(offset),
+            data.read_u16_le_at(offset + 4),
+            data.read_u16_le_at(offset + 6),
+        ) else {
+            break;
+        };
+        if stamp == 0 && name_off == 0 {
+            break;
+        }
+        offset += 8;
+
+        let mut forwarders = Vec::new();
+        for _ in 0..refs {
+            if offset + 8 > end {
+                break;
+            }
+            let fwd_stamp = data.read_u32_le_at(offset).unwrap_or(0);
+            let fwd_name = data.read_u16_le_at(offset + 4).and_then(name_at);
+            if let Some(name) = fwd_name {
+                forwarders.push((name, fwd_stamp));
+            }
+            offset += 8;
+        }
+
+        let Some(dll_name) = name_at(name_off) else {
+            break;
+        };
+        imports.push(BoundImport {
+            dll_name,
+            time_date_stamp: stamp,
+            forwarders,
+        });
+    }
+    imports
+}
+
 fn parse_import_directory<'a>(
     data: &'a [u8],
     sections: &SectionTable,
     dir_rva: u32,
     image_base: u64,
     is_64bit: bool,
-    _is_delay: bool,
+    is_delay: bool,
     table: &mut ImportTable<'a>,
     max_imports: usize,
 ) -> Result<()> {
@@ -119,41 +191,52 @@ fn parse_import_directory<'a>(
 
     let mut total_imports = 0;
 
+    // IMAGE_IMPORT_DESCRIPTOR is 20 bytes; ImgDelayDescr is 32.
+    let desc_size = if is_delay { 32 } else { 20 };
+
     // Parse import descriptors
     loop {
-        if offset + 20 > data.len() {
+        if offset + desc_size > data.len() {
             break;
         }
 
         // Check for terminator (all zeros)
-        let desc_data = &data[offset..offset + 20];
+        let desc_data = &data[offset..offset + desc_size];
         if desc_data.iter().all(|&b| b == 0) {
             break;
         }
 
-        let original_first_thunk = data
-            .read_u32_le_at(offset)
-            .ok_or(PeError::InvalidOffset { offset })?;
-        let time_date_stamp = data
-            .read_u32_le_at(offset + 4)
-            .ok_or(PeError::InvalidOffset { offset: offset + 4 })?;
-        let forwarder_chain = data
-            .read_u32_le_at(offset + 8)
-            .ok_or(PeError::InvalidOffset { offset: offset + 8 })?;
-        let name_rva = data
-            .read_u32_le_at(offset + 12)
-            .ok_or(PeError::InvalidOffset {
-                offset: offset + 12,
-            })?;
-        let first_thunk = data
-            .read_u32_le_at(offset + 16)
-            .ok_or(PeError::InvalidOffset {
-                offset: offset + 16,
-            })?;
+        let field = |at: usize| {
+            data.read_u32_le_at(offset + at)
+                .ok_or(PeError::InvalidOffset {
+                    offset: offset + at,
+                })
+        };
+        let (original_first_thunk, time_date_stamp, forwarder_chain, name_rva, first_thunk) =
+            if is_delay {
+                // grAttrs, szName, phmod, pIAT, pINT, pBoundIAT, pUnloadIAT,
+                // dwTimeStamp. Without dlattrRva (pre-VC7) the pointers are
+                // VAs rather than RVAs.
+                let rva_based = field(0)? & 1 != 0;
+                let to_rva = |v: u32| match v {
+                    0 => 0,
+                    v if rva_based => v,
+                    v => (v as u64).wrapping_sub(image_base) as u32,
+                };
+                (
+                    to_rva(field(16)?),
+                    field(28)?,
+                    0,
+                    to_rva(field(4)?),
+                    to_rva(field(12)?),
+                )
+            } else {
+                (field(0)?, field(4)?, field(8)?, field(12)?, field(16)?)
+            };
 
         // Skip invalid entries
         if name_rva == 0 {
-            offset += 20;
+            offset += desc_size;
             continue;
         }
 
@@ -171,6 +254,7 @@ fn parse_import_directory<'a>(
             first_thunk,
             image_base,
             is_64bit,
+            is_delay,
             max_imports - total_imports,
         )?;
 
@@ -202,11 +286,12 @@ fn parse_import_directory<'a>(
             name_rva,
             first_thunk,
             entries,
+            is_delay,
         };
 
         table.descriptors.push(descriptor);
 
-        offset += 20;
+        offset += desc_size;
 
         if total_imports >= max_imports {
             break;
@@ -223,6 +308,7 @@ fn parse_thunks<'a>(
     first_thunk: u32,
     image_base: u64,
     is_64bit: bool,
+    is_delay: bool,
     max_count: usize,
 ) -> Result<Vec<ImportEntry<'a>>> {
     let mut entries = Vec::new();
@@ -311,6 +397,7 @@ fn parse_thunks<'a>(
             ordinal,
             hint,
             iat_va,
+            is_delay,
         });
 
         thunk_offset += entry_size;
@@ -334,24 +421,28 @@ mod tests {
                 forwarder_chain: 0,
                 name_rva: 0,
                 first_thunk: 0,
+                is_delay: false,
                 entries: vec![
                     ImportEntry {
                         name: Some("CreateFileA"),
                         ordinal: None,
                         hint: None,
                         iat_va: 0,
+                        is_delay: false,
                     },
                     ImportEntry {
                         name: Some("ReadFile"),
                         ordinal: None,
                         hint: None,
                         iat_va: 0,
+                        is_delay: false,
                     },
                 ],
             }],
             by_name: HashMap::new(),
             by_dll: HashMap::new(),
             iat_map: BTreeMap::new(),
+            bound_imports: Vec::new(),
         };
 
         let hash = table.import_hash();
@@ -368,6 +459,7 @@ mod tests {
             ordinal: None,
             hint: Some(100),
             iat_va: 0x1000,
+            is_delay: false,
         };
 
         let entry2 = ImportEntry {
@@ -375,6 +467,7 @@ mod tests {
             ordinal: None,
             hint: Some(200),
             iat_va: 0x1008,
+            is_delay: true,
         };
 
         table.by_name.insert("CreateFileA", vec![entry1.clone()]);
@@ -387,5 +480,40 @@ mod tests {
 
         let dll_imports = table.imports_from_dll("kernel32.dll").unwrap();
         assert_eq!(dll_imports.len(), 2);
+
+        assert!(table.is_delay_loaded("ReadFile"));
+        assert!(!table.is_delay_loaded("CreateFileA"));
+        assert!(!table.is_delay_loaded("WriteFile"));
+    }
+
+    #[test]
+    fn test_parse_bound_imports() {
+        // Directory at file offset 0x10: two descriptors, the first with one
+        // forwarder ref, then the null terminator; names follow at +0x28.
+        let mut data = vec![0u8; 0x80];
+        let dir = 0x10;
+        let put = |data: &mut Vec<u8>, at: usize, stamp: u32, name: u16, refs: u16| {
+            data[dir + at..dir + at + 4].copy_from_slice(&stamp.to_le_bytes());
+            data[dir + at + 4..dir + at + 6].copy_from_slice(&name.to_le_bytes());
+            data[dir + at + 6..dir + at + 8].copy_from_slice(&refs.to_le_bytes());
+        };
+        put(&mut data, 0, 0x3B7D_FE0E, 0x28, 1);
+        put(&mut data, 8, 0x3B7D_FE0F, 0x35, 0);
+        put(&mut data, 16, 0x3B7D_FE10, 0x3F, 0);
+        let names = b"KERNEL32.dll\0NTDLL.DLL\0USER32.dll\0";
+        data[dir + 0x28..dir + 0x28 + names.len()].copy_from_slice(names);
+
+        let bound_dir = DataDirectory {
+            virtual_address: dir as u32,
+            size: 0x50,
+        };
+        let bound = parse_bound_imports(&data, &SectionTable::new(Vec::new()), &bound_dir);
+
+        assert_eq!(bound.len(), 2);
+        assert_eq!(bound[0].dll_name, "KERNEL32.dll");
+        assert_eq!(bound[0].time_date_stamp, 0x3B7D_FE0E);
+        assert_eq!(bound[0].forwarders, vec![("NTDLL.DLL", 0x3B7D_FE0F)]);
+        assert_eq!(bound[1].dll_name, "USER32.dll");
+        assert!(bound[1].forwarders.is_empty());
     }
 }
@@ -0,0 +1,281 @@
+//! .NET (CLR) metadata streams.
+//!
+//! Locates the metadata root (ECMA-335 II.24.2.1) through the COM
+//! descriptor directory and exposes its streams. The `#US` heap holds
+//! every literal loaded by `ldstr` as a length-prefixed UTF-16 blob,
+//! which the generic string scanner splits or drops; [`parse_user_strings`]
+//! decodes it directly. `#Strings` holds the NUL-terminated UTF-8 type,
+//! member and namespace names.
+
+use crate::formats::pe::types::*;
+use crate::formats::pe::utils::{format_guid, ReadExt};
+
+/// `"BSJB"`, the metadata root signature.
+const METADATA_SIGNATURE: u32 = 0x424A_5342;
+
+/// Upper bound on stream headers read; real assemblies carry at most six.
+const MAX_STREAMS: usize = 64;
+
+/// Longest stream name accepted (including the terminator).
+const MAX_STREAM_NAME: usize = 32;
+
+/// One stream named in the metadata root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetadataStream<'data> {
+    /// Stream name, e.g. `#~`, `#Strings`, `#US`, `#GUID`, `#Blob`.
+    pub name: &'data str,
+    /// Offset of the stream from the start of the metadata root.
+    pub offset: u32,
+    pub data: &'data [u8],
+}
+
+/// Parsed metadata root of a .NET assembly.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DotNetMetadata<'data> {
+    /// Runtime version string, e.g. `v4.0.30319`.
+    pub version: &'data str,
+    pub streams: Vec<MetadataStream<'data>>,
+}
+
+/// A string literal from the `#US` heap.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserString {
+    /// Heap offset; the low 24 bits of the `ldstr` token (`0x70xxxxxx`).
+    pub index: u32,
+    pub value: String,
+}
+
+impl<'data> DotNetMetadata<'data> {
+    /// Contents of the stream called `name`.
+    pub fn stream(&self, name: &str) -> Option<&'data [u8]> {
+        self.streams.iter().find(|s| s.name == name).map(|s| s.data)
+    }
+
+    /// Decoded `#US` heap; empty when the stream is absent.
+    pub fn user_strings(&self) -> Vec<UserString> {
+        self.stream("#US")
+            .map(parse_user_strings)
+            .unwrap_or_default()
+    }
+
+    /// Entries of the `#GUID` heap (module MVID, type library and
+    /// interface GUIDs) in registry spelling, skipping nil GUIDs.
+    pub fn guids(&self) -> Vec<String> {
+        self.stream("#GUID")
+            .map(|heap| {
+                heap.chunks_exact(16)
+                    .filter_map(|c| <&[u8; 16]>::try_from(c).ok())
+                    .filter(|g| g.iter().any(|&b| b != 0))
+                    .map(format_guid)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Identifier names from the `#Strings` heap, in heap order.
+    pub fn identifiers(&self) -> Vec<&'data str> {
+        self.stream("#Strings")
+            .map(parse_string_heap)
+            .unwrap_or_default()
+    }
+}
+
+/// Locate and parse the metadata root of a .NET PE.
+///
+/// `com_descriptor` is the COM descriptor data directory; `rva_to_offset`
+/// maps RVAs to file offsets.
+pub fn parse_dotnet_metadata<'data>(
+    data: &'data [u8],
+    com_descriptor: &DataDirectory,
+    rva_to_offset: impl Fn(u32) -> Option<usize>,
+) -> Result<DotNetMetadata<'data>> {
+    let rva = com_descriptor.virtual_address;
+    if rva == 0 || com_descriptor.size == 0 {
+        return Err(PeError::DataDirectoryNotFound {
+            index: IMAGE_DIRECTORY_ENTRY_COM_DESCRIPTOR,
+        });
+    }
+    // IMAGE_COR20_HEADER: cb, runtime version, then the MetaData directory.
+    let clr = rva_to_offset(rva).ok_or(PeError::InvalidRva { rva })?;
+    let meta_rva = data
+        .read_u32_le_at(clr + 8)
+        .ok_or(PeError::TruncatedHeader {
+            expected: clr + 16,
+            actual: data.len(),
+        })?;
+    let meta_size = data.read_u32_le_at(clr + 12).unwrap_or(0) as usize;
+    let meta_offset = rva_to_offset(meta_rva).ok_or(PeError::InvalidRva { rva: meta_rva })?;
+    let meta = data
+        .read_slice_at(meta_offset, meta_size)
+        .ok_or(PeError::TruncatedHeader {
+            expected: meta_offset.saturating_add(meta_size),
+            actual: data.len(),
+        })?;
+    parse_metadata_root(meta)
+}
+
+/// Parse a metadata root starting at its `BSJB` signature.
+pub fn parse_metadata_root(meta: &[u8]) -> Result<DotNetMetadata<'_>> {
+    let truncated = |expected: usize| PeError::TruncatedHeader {
+        expected,
+        actual: meta.len(),
+    };
+    if meta.read_u32_le_at(0) != Some(METADATA_SIGNATURE) {
+        return Err(PeError::InvalidClrMetadata("bad metadata signature"));
+    }
+    let version_len = meta.read_u32_le_at(12).ok_or_else(|| truncated(16))? as usize;
+    let version_bytes = meta
+        .read_slice_at(16, version_len)
+        .ok_or_else(|| truncated(16usize.saturating_add(version_len)))?;
+    let nul = version_bytes
+        .iter()
+        .position(|&b| b == 0)
+        .unwrap_or(version_bytes.len());
+    let version = std::str::from_utf8(&version_bytes[..nul])
+        .map_err(|_| PeError::InvalidClrMetadata("version string"))?;
+
+    // Flags (u16) and stream count (u16) follow the 4-byte aligned version.
+    let mut pos = 16 + ((version_len + 3) & !3);
+    let count = meta
+        .read_u16_le_at(pos + 2)
+        .ok_or_else(|| truncated(pos + 4))? as usize;
+    if count > MAX_STREAMS {
+        return Err(PeError::LimitExceeded("metadata streams"));
+    }
+    pos += 4;
+
+    let mut streams = Vec::with_capacity(count);
+    for _ in 0..count {
+        let offset = meta.read_u32_le_at(pos).ok_or_else(|| truncated(pos + 8))?;
+        let size = meta
+            .read_u32_le_at(pos + 4)
+            .ok_or_else(|| truncated(pos + 8))?;
+        let name = meta
+            .read_cstring_at(pos + 8, MAX_STREAM_NAME)
+            .ok_or(PeError::InvalidClrMetadata("stream name"))?;
+        // Name plus terminator, padded to four bytes.
+        pos += 8 + ((name.len() + 4) & !3);
+        let data = meta
+            .read_slice_at(offset as usize, size as usize)
+            .ok_or(PeError::InvalidClrMetadata("stream out of bounds"))?;
+        streams.push(MetadataStream { name, offset, data });
+    }
+    Ok(DotNetMetadata { version, streams })
+}
+
+/// Decode every non-empty entry of a `#US` heap.
+///
+/// Each entry is a compressed length (II.23.2) followed by UTF-16LE code
+/// units and a one-byte "needs special handling" flag counted in the
+/// length. Decoding stops at the first entry that runs past the heap.
+pub fn parse_user_strings(heap: &[u8]) -> Vec<UserString> {
+    let mut out = Vec::new();
+    let mut pos = 0;
+    while pos < heap.len() {
+        let Some((len, header)) = read_compressed_len(heap, pos) else {
+            break;
+        };
+        let start = pos + header;
+        let Some(blob) = heap.get(start..start + len) else {
+            break;
+        };
+        let units: Vec<u16> = blob
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .collect();
+        if !units.is_empty() {
+            out.push(UserString {
+                index: pos as u32,
+                value: String::from_utf16_lossy(&units),
+            });
+        }
+        pos = start + len;
+    }
+    out
+}
+
+/// Split a `#Strings` heap into its non-empty, valid UTF-8 entries.
+pub fn parse_string_heap(heap: &[u8]) -> Vec<&str> {
+    heap.split(|&b| b == 0)
+        .filter(|s| !s.is_empty())
+        .filter_map(|s| std::str::from_utf8(s).ok())
+        .collect()
+}
+
+/// ECMA-335 compressed unsigned length at `pos`: `(length, header_bytes)`.
+fn read_compressed_len(heap: &[u8], pos: usize) -> Option<(usize, usize)> {
+    let b0 = heap.read_u8_at(pos)? as usize;
+    if b0 & 0x80 == 0 {
+        Some((b0, 1))
+    } else if b0 & 0xC0 == 0x80 {
+        let b1 = heap.read_u8_at(pos + 1)? as usize;
+        Some((((b0 & 0x3F) << 8) | b1, 2))
+    } else if b0 & 0xE0 == 0xC0 {
+        let rest = heap.read_slice_at(pos + 1, 3)?;
+        let len = ((b0 & 0x1F) << 24)
+            | ((rest[0] as usize) << 16)
+            | ((rest[1] as usize) << 8)
+            | rest[2] as usize;
+        Some((len, 4))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utf16_entry(s: &str) -> Vec<u8> {
+        let mut body: Vec<u8> = s.encode_utf16().flat_map(|u| u.to_le_bytes()).collect();
+        body.push(0); // trailing flag byte
+        let mut entry = if body.len() < 0x80 {
+            vec![body.len() as u8]
+        } else {
+            vec![0x80 | (body.len() >> 8) as u8, body.len() as u8]
+        };
+        entry.extend(body);
+        entry
+    }
+
+    #[test]
+    fn decodes_user_strings_and_metadata_root() {
+        let long = "x".repeat(100);
+        let mut us = vec![0u8];
+        us.extend(utf16_entry("http://c2.example/gate.php"));
+        us.extend(utf16_entry(&long));
+        us.extend([0, 0]);
+        let strings = b"\0<Module>\0Program\0Main\0\0";
+
+        // Root header, a 12-byte version, then two stream headers.
+        let mut meta = Vec::new();
+        meta.extend(METADATA_SIGNATURE.to_le_bytes());
+        meta.extend([1, 0, 1, 0, 0, 0, 0, 0]);
+        meta.extend(12u32.to_le_bytes());
+        meta.extend(b"v4.0.30319\0\0");
+        meta.extend([0, 0, 2, 0]);
+        let streams_at = meta.len() + 2 * 8 + 4 + 12;
+        meta.extend((streams_at as u32).to_le_bytes());
+        meta.extend((us.len() as u32).to_le_bytes());
+        meta.extend(b"#US\0");
+        meta.extend(((streams_at + us.len()) as u32).to_le_bytes());
+        meta.extend((strings.len() as u32).to_le_bytes());
+        meta.extend(b"#Strings\0\0\0\0");
+        assert_eq!(meta.len(), streams_at);
+        meta.extend(&us);
+        meta.extend(strings);
+
+        let md = parse_metadata_root(&meta).unwrap();
+        assert_eq!(md.version, "v4.0.30319");
+        assert_eq!(md.streams.len(), 2);
+        let user = md.user_strings();
+        assert_eq!(user.len(), 2);
+        assert_eq!(user[0].index, 1);
+        assert_eq!(user[0].value, "http://c2.example/gate.php");
+        assert_eq!(user[1].value, long);
+        assert_eq!(md.identifiers(), vec!["<Module>", "Program", "Main"]);
+
+        meta[0] = b'X';
+        assert!(parse_metadata_root(&meta).is_err());
+    }
+}
@@ -3,6 +3,18 @@
 use crate::formats::pe::types::*;
 use crate::formats::pe::utils::ReadExt;
 
+/// End of the `len`-byte range at `offset`, or `TruncatedHeader` when the
+/// range overflows or runs past the end of `data`.
+pub(crate) fn checked_end(data: &[u8], offset: usize, len: usize) -> Result<usize> {
+    match offset.checked_add(len) {
+        Some(end) if end <= data.len() => Ok(end),
+        _ => Err(PeError::TruncatedHeader {
+            expected: offset.saturating_add(len),
+            actual: data.len(),
+        }),
+    }
+}
+
 /// Parse DOS header from data
 pub fn parse_dos_header(data: &[u8]) -> Result<DosHeader> {
     if data.len() < 64 {
@@ -38,12 +50,7 @@ pub fn parse_dos_header(data: &[u8]) -> Result<DosHeader> {
 
 /// Parse COFF header from data at offset
 pub fn parse_coff_header(data: &[u8], offset: usize) -> Result<CoffHeader> {
-    if offset + 20 > data.len() {
-        return Err(PeError::TruncatedHeader {
-            expected: offset + 20,
-            actual: data.len(),
-        });
-    }
+    checked_end(data, offset, 20)?;
 
     Ok(CoffHeader {
         machine: Machine::from(data.read_u16_le_at(offset).unwrap()),
@@ -65,12 +72,7 @@ pub fn parse_optional_header(data: &[u8], offset: usize, size: u16) -> Result<Op
         });
     }
 
-    if offset + size as usize > data.len() {
-        return Err(PeError::TruncatedHeader {
-            expected: offset + size as usize,
-            actual: data.len(),
-        });
-    }
+    checked_end(data, offset, size as usize)?;
 
     let magic = data.read_u16_le_at(offset).unwrap();
 
@@ -186,8 +188,8 @@ pub fn parse_data_directories(
     let count = count.min(16); // Maximum 16 directories
 
     for i in 0..count {
-        let dir_offset = offset + (i as usize * 8);
-        if dir_offset + 8 > data.len() {
+        let dir_offset = offset.saturating_add(i as usize * 8);
+        if checked_end(data, dir_offset, 8).is_err() {
             break;
         }
 
@@ -207,13 +209,26 @@ pub fn parse_data_directories(
 
 /// Parse NT headers (PE signature + COFF + Optional)
 pub fn parse_nt_headers(data: &[u8], offset: usize) -> Result<(NtHeaders, Vec<DataDirectory>)> {
-    // Check PE signature
-    if offset + 4 > data.len() {
-        return Err(PeError::TruncatedHeader {
-            expected: offset + 4,
-            actual: data.len(),
-        });
-    }
+    parse_nt_headers_impl(data, offset, None)
+}
+
+/// Like [`parse_nt_headers`], but an optional header running past the end of
+/// the file is clamped to the available bytes and recorded in `issues`.
+pub fn parse_nt_headers_lenient(
+    data: &[u8],
+    offset: usize,
+    issues: &mut Vec<ParseIssue>,
+) -> Result<(NtHeaders, Vec<DataDirectory>)> {
+    parse_nt_headers_impl(data, offset, Some(issues))
+}
+
+fn parse_nt_headers_impl(
+    data: &[u8],
+    offset: usize,
+    issues: Option<&mut Vec<ParseIssue>>,
+) -> Result<(NtHeaders, Vec<DataDirectory>)> {
+    // Check PE signature, COFF header and optional header start
+    checked_end(data, offset, 24)?;
 
     let signature = [
         data[offset],
@@ -231,12 +246,25 @@ pub fn parse_nt_headers(data: &[u8], offset: usize) -> Result<(NtHeaders, Vec<Da
 
     // Parse optional header
     let opt_offset = offset + 24; // 4 (signature) + 20 (COFF)
-    let optional_header =
-        parse_optional_header(data, opt_offset, coff_header.size_of_optional_header)?;
+    let mut opt_size = coff_header.size_of_optional_header;
+    let available = data.len().saturating_sub(opt_offset);
+    if let Some(issues) = issues {
+        if opt_size as usize > available {
+            issues.push(ParseIssue::new(
+                opt_offset,
+                format!(
+                    "optional header truncated: {} of {} bytes present",
+                    available, opt_size
+                ),
+            ));
+            opt_size = available as u16;
+        }
+    }
+    let optional_header = parse_optional_header(data, opt_offset, opt_size)?;
 
-    // Parse data directories
-    let dir_offset = opt_offset + coff_header.size_of_optional_header as usize
-        - (optional_header.number_of_rva_and_sizes() * 8) as usize;
+    // Parse data directories (located by the declared header size)
+    let dir_offset = (opt_offset + coff_header.size_of_optional_header as usize)
+        .saturating_sub((optional_header.number_of_rva_and_sizes() * 8) as usize);
     let directories =
         parse_data_directories(data, dir_offset, optional_header.number_of_rva_and_sizes())?;
 
@@ -305,6 +333,25 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_offsets_near_usize_max_do_not_panic() {
+        let data = vec![0u8; 128];
+        for offset in [usize::MAX, usize::MAX - 3, usize::MAX - 23] {
+            assert!(matches!(
+                parse_nt_headers(&data, offset),
+                Err(PeError::TruncatedHeader { .. })
+            ));
+            assert!(parse_coff_header(&data, offset).is_err());
+            assert!(parse_optional_header(&data, offset, 0xe0).is_err());
+        }
+        assert_eq!(
+            parse_data_directories(&data, usize::MAX - 4, 16)
+                .unwrap()
+                .len(),
+            16
+        );
+    }
+
     #[test]
     fn test_parse_coff_header() {
         let mut data = vec![0u8; 100];
@@ -1,15 +1,20 @@
 //! Unified PE parser implementation
 
+use crate::formats::section_class::{classify_name, SectionClass};
+use chrono::{DateTime, Utc};
 use std::cell::OnceCell;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
 
 pub mod directories;
+pub mod dotnet;
 pub mod headers;
 pub mod sections;
+pub mod typelib;
 pub mod types;
 pub mod utils;
 
 use directories::*;
+use dotnet::DotNetMetadata;
 use headers::*;
 use sections::*;
 pub use types::*;
@@ -23,6 +28,7 @@ pub struct PeParser<'data> {
     data_directories: Vec<DataDirectory>,
     section_table: SectionTable,
     options: ParseOptions,
+    issues: Vec<ParseIssue>,
 
     // Lazy-loaded data
     imports: OnceCell<ImportTable<'data>>,
@@ -43,21 +49,55 @@ impl<'data> PeParser<'data> {
         // Parse DOS header
         let dos_header = parse_dos_header(data)?;
 
+        let mut issues = Vec::new();
+
         // Parse NT headers
-        let (nt_headers, data_directories) = parse_nt_headers(data, dos_header.e_lfanew as usize)?;
+        let nt_offset = dos_header.e_lfanew as usize;
+        let (nt_headers, data_directories) = if options.best_effort {
+            parse_nt_headers_lenient(data, nt_offset, &mut issues)?
+        } else {
+            parse_nt_headers(data, nt_offset)?
+        };
 
         // Parse section headers
-        let section_offset = dos_header.e_lfanew as usize
-            + 24
-            + nt_headers.file_header.size_of_optional_header as usize;
-        let section_headers = parse_section_headers(
-            data,
-            section_offset,
-            nt_headers.file_header.number_of_sections,
-        )?;
+        let section_offset = nt_offset
+            .checked_add(24 + nt_headers.file_header.size_of_optional_header as usize)
+            .ok_or(PeError::TruncatedHeader {
+                expected: usize::MAX,
+                actual: data.len(),
+            })?;
+        let section_count = nt_headers.file_header.number_of_sections;
+        let section_headers = match parse_section_headers(data, section_offset, section_count) {
+            Ok(headers) => headers,
+            Err(e) if options.best_effort => {
+                let fit = data.len().saturating_sub(section_offset) / 40;
+                let kept = fit.min(section_count as usize) as u16;
+                issues.push(ParseIssue::new(
+                    section_offset,
+                    format!("{}; kept {} of {} section headers", e, kept, section_count),
+                ));
+                parse_section_headers(data, section_offset, kept)?
+            }
+            Err(e) => return Err(e),
+        };
 
         // Create section table
-        let sections = create_sections(section_headers);
+        let mut sections = create_sections(section_headers);
+        if options.best_effort {
+            for section in &mut sections {
+                if section.data.end > data.len() {
+                    issues.push(ParseIssue::new(
+                        section.data.start,
+                        format!(
+                            "section {} raw data truncated at end of file",
+                            section.header.name()
+                        ),
+                    ));
+                    section.data.end = data.len();
+                    section.data.start = section.data.start.min(data.len());
+                }
+            }
+        }
         let section_table = SectionTable::new(sections);
 
         Ok(Self {
@@ -67,6 +107,7 @@ impl<'data> PeParser<'data> {
             data_directories,
             section_table,
             options,
+            issues,
             imports: OnceCell::new(),
             exports: OnceCell::new(),
             debug: OnceCell::new(),
@@ -75,6 +116,12 @@ impl<'data> PeParser<'data> {
         })
     }
 
+    /// Problems tolerated by a [`ParseOptions::best_effort`] parse (always
+    /// empty for strict parses, which fail instead).
+    pub fn issues(&self) -> &[ParseIssue] {
+        &self.issues
+    }
+
     // Header access methods
 
     /// Get DOS header
@@ -117,6 +164,19 @@ impl<'data> PeParser<'data> {
         self.nt_headers.optional_header.subsystem()
     }
 
+    /// Get raw COFF timestamp (seconds since the Unix epoch)
+    pub fn time_date_stamp(&self) -> u32 {
+        self.nt_headers.file_header.time_date_stamp
+    }
+
+    /// Get link time as UTC; `None` when the timestamp is zeroed
+    pub fn compile_timestamp(&self) -> Option<DateTime<Utc>> {
+        match self.time_date_stamp() {
+            0 => None,
+            ts => DateTime::from_timestamp(ts as i64, 0),
+        }
+    }
+
     // Section access methods
 
     /// Get all sections
@@ -144,6 +204,10 @@ impl<'data> PeParser<'data> {
     // Import/Export methods
 
     /// Get imports (lazy-loaded)
+    ///
+    /// Regular and delay-load descriptors share one table; check
+    /// `is_delay` on descriptors/entries to tell them apart. Bound-import
+    /// timestamps are in `bound_imports`.
     pub fn imports(&self) -> Result<&ImportTable<'data>> {
         if let Some(imports) = self.imports.get() {
             return Ok(imports);
@@ -151,12 +215,14 @@ impl<'data> PeParser<'data> {
 
         let import_dir = self.data_directory(IMAGE_DIRECTORY_ENTRY_IMPORT)?;
         let delay_dir = self.data_directory(IMAGE_DIRECTORY_ENTRY_DELAY_IMPORT)?;
+        let bound_dir = self.data_directory(IMAGE_DIRECTORY_ENTRY_BOUND_IMPORT)?;
 
         let imports = parse_imports(
             self.data,
             &self.section_table,
             import_dir,
             delay_dir,
+            bound_dir,
             self.image_base(),
             self.is_64bit(),
             &self.options,
@@ -351,16 +417,62 @@ impl<'data> PeParser<'data> {
             .unwrap_or(false)
     }
 
+    /// Parse the .NET metadata root: its streams and the `#US` /
+    /// `#Strings` heaps. Fails for non-.NET images.
+    pub fn dotnet_metadata(&self) -> Result<DotNetMetadata<'data>> {
+        let dir = self.data_directory(IMAGE_DIRECTORY_ENTRY_COM_DESCRIPTOR)?;
+        dotnet::parse_dotnet_metadata(self.data, dir, |rva| self.rva_to_offset(rva))
+    }
+
+    /// COM-relevant GUIDs from the .NET `#GUID` heap and the GUID table
+    /// of every `TYPELIB` resource, deduplicated in discovery order.
+    pub fn guids(&self) -> Vec<String> {
+        let mut guids = self
+            .dotnet_metadata()
+            .map(|md| md.guids())
+            .unwrap_or_default();
+        if let Ok(resources) = self.resources() {
+            for resource in &resources.resources {
+                let is_typelib = resource
+                    .type_id
+                    .as_name()
+                    .is_some_and(|n| n.eq_ignore_ascii_case("TYPELIB"));
+                if is_typelib {
+                    guids.extend(typelib::typelib_guids(resource.data));
+                }
+            }
+        }
+        let mut seen = HashSet::new();
+        guids.retain(|g| seen.insert(g.clone()));
+        guids
+    }
+
     /// Validate checksum
     pub fn checksum_valid(&self) -> bool {
-        let stored = self.nt_headers.optional_header.checksum();
+        let stored = self.stored_checksum();
         if stored == 0 {
             return true; // No checksum to validate
         }
+        stored == self.compute_checksum()
+    }
 
-        let checksum_offset = self.dos_header.e_lfanew as usize + 24 + 64; // Approximate
-        let calculated = calculate_pe_checksum(self.data, checksum_offset);
-        stored == calculated
+    /// Checksum recorded in the optional header.
+    pub fn stored_checksum(&self) -> u32 {
+        self.nt_headers.optional_header.checksum()
+    }
+
+    /// Recompute the image checksum over the whole file.
+    pub fn compute_checksum(&self) -> u32 {
+        calculate_pe_checksum(self.data, self.checksum_offset())
+    }
+
+    /// File offset of the optional header's `CheckSum` field: signature (4)
+    /// and COFF header (20) precede the optional header, and the field sits
+    /// at +64 in both PE32 and PE32+ layouts.
+    fn checksum_offset(&self) -> usize {
+        (self.dos_header.e_lfanew as usize)
+            .saturating_add(4 + 20)
+            .saturating_add(64)
     }
 
     /// Detect anomalies
@@ -369,7 +481,7 @@ impl<'data> PeParser<'data> {
 
         // Check for suspicious entry point
         if let Some(entry_section) = self.entry_section() {
-            if !entry_section.starts_with(".text") && !entry_section.starts_with("CODE") {
+            if classify_name(&entry_section) != Some(SectionClass::Code) {
                 anomalies.push(PeAnomaly::SuspiciousEntryPoint {
                     section: entry_section,
                 });
@@ -535,6 +647,34 @@ mod tests {
         data
     }
 
+    #[test]
+    fn best_effort_keeps_what_fits() {
+        // Two section headers declared, file cut inside the second one; the
+        // first section's raw data (0x200..0x400) is missing too.
+        let mut data = create_minimal_pe();
+        data[0x86] = 0x02;
+        data.truncate(0xF8 + 40 + 10);
+        assert!(matches!(
+            PeParser::new(&data),
+            Err(PeError::TruncatedHeader { .. })
+        ));
+
+        let options = ParseOptions {
+            best_effort: true,
+            ..Default::default()
+        };
+        let pe = PeParser::with_options(&data, options.clone()).unwrap();
+        assert_eq!(pe.sections().len(), 1);
+        assert_eq!(pe.sections()[0].header.name(), ".text");
+        assert!(pe.sections()[0].data(&data).unwrap().is_empty());
+        assert_eq!(pe.issues().len(), 2);
+        assert_eq!(pe.issues()[0].offset, 0xF8);
+
+        let intact = create_minimal_pe();
+        let pe = PeParser::with_options(&intact, options).unwrap();
+        assert!(pe.issues().is_empty());
+    }
+
     fn create_pe_with_version_resource() -> Vec<u8> {
         let mut data = vec![0u8; 1024];
 
@@ -690,6 +830,39 @@ mod tests {
         assert_eq!(sections[0].header.name(), ".text");
     }
 
+    #[test]
+    fn test_checksum_recomputation() {
+        let mut data = create_minimal_pe();
+        data[0x200..0x210].copy_from_slice(b"\x55\x8b\xec\x90checksum me!");
+        let sum = PeParser::new(&data).unwrap().compute_checksum();
+        assert_ne!(sum, 0);
+
+        // Storing the checksum must not change what is computed
+        data[0xD8..0xDC].copy_from_slice(&sum.to_le_bytes());
+        let parser = PeParser::new(&data).unwrap();
+        assert_eq!(parser.stored_checksum(), sum);
+        assert_eq!(parser.compute_checksum(), sum);
+        assert!(parser.checksum_valid());
+
+        data[0x205] ^= 0xff;
+        assert!(!PeParser::new(&data).unwrap().checksum_valid());
+    }
+
+    #[test]
+    fn test_compile_timestamp() {
+        let mut data = create_minimal_pe();
+        assert_eq!(PeParser::new(&data).unwrap().compile_timestamp(), None);
+
+        // 2020-01-01T00:00:00Z
+        data[0x88..0x8C].copy_from_slice(&1_577_836_800u32.to_le_bytes());
+        let parser = PeParser::new(&data).unwrap();
+        assert_eq!(parser.time_date_stamp(), 1_577_836_800);
+        assert_eq!(
+            parser.compile_timestamp().unwrap().to_rfc3339(),
+            "2020-01-01T00:00:00+00:00"
+        );
+    }
+
     #[test]
     fn test_rva_to_offset() {
         let data = create_minimal_pe();
@@ -1,7 +1,9 @@
 //! Section management for PE files
 
+use crate::formats::pe::headers::checked_end;
 use crate::formats::pe::types::*;
 use crate::formats::pe::utils::{calculate_entropy, ReadExt};
+use crate::formats::section_class::classify_name;
 
 /// Section table for efficient RVA resolution
 #[derive(Debug, Clone)]
@@ -153,17 +155,11 @@ impl SectionTable {
     pub fn detect_anomalies(&self) -> Vec<PeAnomaly> {
         let mut anomalies = Vec::new();
 
-        // Check for unusual section names
-        const KNOWN_SECTIONS: &[&str] = &[
-            ".text", ".data", ".rdata", ".bss", ".idata", ".edata", ".rsrc", ".reloc", ".tls",
-            ".debug", ".pdata", ".xdata", "CODE", "DATA", "BSS", ".CRT", ".INIT", ".PAGE",
-        ];
-
         for section in &self.sections {
             let name = section.header.name();
 
             // Check for unusual names
-            if !name.is_empty() && !KNOWN_SECTIONS.iter().any(|&s| name.starts_with(s)) {
+            if !name.is_empty() && classify_name(&name).is_none() {
                 // Flag as unusual if not in known list
                 anomalies.push(PeAnomaly::UnusualSectionName { name: name.clone() });
             }
@@ -213,13 +209,8 @@ pub fn parse_section_headers(data: &[u8], offset: usize, count: u16) -> Result<V
     let mut sections = Vec::new();
 
     for i in 0..count {
-        let section_offset = offset + (i as usize * 40);
-        if section_offset + 40 > data.len() {
-            return Err(PeError::TruncatedHeader {
-                expected: section_offset + 40,
-                actual: data.len(),
-            });
-        }
+        let section_offset = offset.saturating_add(i as usize * 40);
+        checked_end(data, section_offset, 40)?;
 
         let mut name = [0u8; 8];
         name.copy_from_slice(&data[section_offset..section_offset + 8]);
@@ -249,7 +240,7 @@ pub fn create_sections(headers: Vec<SectionHeader>) -> Vec<Section> {
         .into_iter()
         .map(|header| {
             let start = header.pointer_to_raw_data as usize;
-            let end = start + header.size_of_raw_data as usize;
+            let end = start.saturating_add(header.size_of_raw_data as usize);
             Section {
                 header,
                 data: start..end,
@@ -264,6 +255,17 @@ impl Section {
         file_data.get(self.data.clone())
     }
 
+    /// Get section data, failing when the raw data range does not fit in
+    /// `file_data`.
+    pub fn data_checked<'a>(&self, file_data: &'a [u8]) -> Result<&'a [u8]> {
+        file_data
+            .get(self.data.clone())
+            .ok_or(PeError::TruncatedHeader {
+                expected: self.data.end,
+                actual: file_data.len(),
+            })
+    }
+
     /// Calculate entropy of section data
     pub fn entropy(&self, file_data: &[u8]) -> Option<f64> {
         self.data(file_data).map(calculate_entropy)
@@ -329,6 +331,33 @@ mod tests {
         assert_eq!(table.rva_to_offset(0x5000), None);
     }
 
+    #[test]
+    fn test_section_headers_reject_overflowing_offset() {
+        let data = vec![0u8; 0x200];
+        assert!(matches!(
+            parse_section_headers(&data, usize::MAX - 10, 2),
+            Err(PeError::TruncatedHeader { .. })
+        ));
+        assert!(parse_section_headers(&data, 0x1f0, 1).is_err());
+        assert_eq!(parse_section_headers(&data, 0x100, 2).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_data_checked_validates_range() {
+        let file = vec![0u8; 0x800];
+        let inside = create_test_section(".text", 0x1000, 0x200, 0x400, 0x200);
+        assert_eq!(inside.data_checked(&file).unwrap().len(), 0x200);
+
+        let past_end = create_test_section(".data", 0x2000, 0x1000, 0x600, 0x1000);
+        assert!(matches!(
+            past_end.data_checked(&file),
+            Err(PeError::TruncatedHeader {
+                expected: 0x1600,
+                ..
+            })
+        ));
+    }
+
     #[test]
     fn test_section_table_offset_to_rva() {
         let sections = vec![
@@ -0,0 +1,108 @@
+//! COM type library (`TYPELIB` resource) GUID extraction.
+//!
+//! COM servers embed their type library as an MSFT-format resource. Its
+//! GUID table lists the library ID plus every CLSID, IID and dispinterface
+//! ID the server declares, all stored as raw 16-byte GUIDs. Only the GUID
+//! table is read; type infos, names and the older SLTG format are not.
+
+use crate::formats::pe::utils::{format_guid, ReadExt};
+
+/// `"MSFT"`, the type library signature.
+const MSFT_MAGIC: u32 = 0x5446_534D;
+
+/// Size of the fixed MSFT header.
+const MSFT_HEADER_SIZE: usize = 0x54;
+
+/// `varflags` bit announcing an extra help-DLL field after the header.
+const HELPDLL_FLAG: u32 = 0x100;
+
+/// Index of the GUID table in the segment directory.
+const GUID_SEGMENT: usize = 5;
+
+/// Segment directory entry: offset, length and two reserved words.
+const SEGMENT_ENTRY_SIZE: usize = 16;
+
+/// GUID table entry: the GUID, its href type and a hash chain link.
+const GUID_ENTRY_SIZE: usize = 24;
+
+/// Upper bound on type infos accepted from the header.
+const MAX_TYPEINFOS: usize = 0x10000;
+
+/// GUIDs declared in an MSFT type library, in table order; empty when
+/// `data` is not an MSFT type library.
+pub fn typelib_guids(data: &[u8]) -> Vec<String> {
+    if data.read_u32_le_at(0) != Some(MSFT_MAGIC) {
+        return Vec::new();
+    }
+    let varflags = data.read_u32_le_at(20).unwrap_or(0);
+    let typeinfos = data.read_u32_le_at(32).unwrap_or(0) as usize;
+    if typeinfos > MAX_TYPEINFOS {
+        return Vec::new();
+    }
+
+    // Header, optional help-DLL name offset, then one offset per type info.
+    let mut segdir = MSFT_HEADER_SIZE + typeinfos * 4;
+    if varflags & HELPDLL_FLAG != 0 {
+        segdir += 4;
+    }
+    let entry = segdir + GUID_SEGMENT * SEGMENT_ENTRY_SIZE;
+    let (Some(offset), Some(length)) = (data.read_u32_le_at(entry), data.read_u32_le_at(entry + 4))
+    else {
+        return Vec::new();
+    };
+    // Absent segments are recorded with offset -1.
+    if offset == u32::MAX {
+        return Vec::new();
+    }
+    let Some(table) = data.read_slice_at(offset as usize, length as usize) else {
+        return Vec::new();
+    };
+
+    table
+        .chunks_exact(GUID_ENTRY_SIZE)
+        .filter_map(|e| <&[u8; 16]>::try_from(&e[..16]).ok())
+        .filter(|g| g.iter().any(|&b| b != 0))
+        .map(format_guid)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_guid_table_from_msft_typelib() {
+        let libid: [u8; 16] = [
+            0x40, 0xFC, 0x29, 0x6B, 0x47, 0xCA, 0x67, 0x10, 0xB3, 0x1D, 0x00, 0xDD, 0x01, 0x06,
+            0x62, 0xDA,
+        ];
+        let mut lib = vec![0u8; MSFT_HEADER_SIZE];
+        lib[..4].copy_from_slice(&MSFT_MAGIC.to_le_bytes());
+        lib[20..24].copy_from_slice(&HELPDLL_FLAG.to_le_bytes());
+        lib[32..36].copy_from_slice(&1u32.to_le_bytes());
+        lib.extend([0; 4]); // help DLL
+        lib.extend([0; 4]); // type info offset
+        let table_at = lib.len() + 15 * SEGMENT_ENTRY_SIZE;
+        for seg in 0..15 {
+            let (offset, length) = if seg == GUID_SEGMENT {
+                (table_at as u32, 2 * GUID_ENTRY_SIZE as u32)
+            } else {
+                (u32::MAX, 0)
+            };
+            lib.extend(offset.to_le_bytes());
+            lib.extend(length.to_le_bytes());
+            lib.extend([0; 8]);
+        }
+        lib.extend(libid);
+        lib.extend([0; 8]);
+        lib.extend([0; GUID_ENTRY_SIZE]); // nil entry is skipped
+
+        assert_eq!(
+            typelib_guids(&lib),
+            vec!["6B29FC40-CA47-1067-B31D-00DD010662DA"]
+        );
+        assert!(typelib_guids(b"SLTG").is_empty());
+        lib.truncate(table_at + 8);
+        assert!(typelib_guids(&lib).is_empty());
+    }
+}
@@ -61,6 +61,7 @@ pub enum PeError {
     MalformedExportTable,
     MalformedResourceDirectory,
     ResourceDepthExceeded,
+    InvalidClrMetadata(&'static str),
     SectionNotFound { name: String },
     DataDirectoryNotFound { index: usize },
     Timeout,
@@ -89,6 +90,7 @@ impl fmt::Display for PeError {
             Self::MalformedExportTable => write!(f, "Malformed export table"),
             Self::MalformedResourceDirectory => write!(f, "Malformed resource directory"),
             Self::ResourceDepthExceeded => write!(f, "Resource directory depth exceeded"),
+            Self::InvalidClrMetadata(what) => write!(f, "Invalid CLR metadata: {}", what),
             Self::SectionNotFound { name } => write!(f, "Section not found: {}", name),
             Self::DataDirectoryNotFound { index } => {
                 write!(f, "Data directory {} not found", index)
@@ -412,6 +414,10 @@ pub struct ImportDescriptor<'a> {
     pub name_rva: u32,
     pub first_thunk: u32,
     pub entries: Vec<ImportEntry<'a>>,
+    /// Read from the delay-load directory rather than the import
+    /// directory. `original_first_thunk`/`first_thunk` then hold the
+    /// delay INT/IAT RVAs and `forwarder_chain` is always 0.
+    pub is_delay: bool,
 }
 
 /// Import entry
@@ -421,6 +427,18 @@ pub struct ImportEntry<'a> {
     pub ordinal: Option<u16>,
     pub hint: Option<u16>,
     pub iat_va: u64,
+    /// Resolved lazily on first call rather than at load time.
+    pub is_delay: bool,
+}
+
+/// `IMAGE_BOUND_IMPORT_DESCRIPTOR`: the timestamp of the DLL an import
+/// was pre-bound against.
+#[derive(Debug, Clone)]
+pub struct BoundImport<'a> {
+    pub dll_name: &'a str,
+    pub time_date_stamp: u32,
+    /// `IMAGE_BOUND_FORWARDER_REF` entries: `(dll_name, time_date_stamp)`.
+    pub forwarders: Vec<(&'a str, u32)>,
 }
 
 /// Export entry
@@ -468,6 +486,10 @@ pub struct ParseOptions {
     pub max_exports: usize,
     pub timeout_ms: Option<u64>,
     pub validate_checksums: bool,
+    /// Tolerate truncated headers and section data: parse what fits and
+    /// record the problems as [`ParseIssue`]s instead of failing. DOS/PE
+    /// signatures and the COFF header remain fatal.
+    pub best_effort: bool,
 }
 
 impl Default for ParseOptions {
@@ -488,6 +510,24 @@ impl Default for ParseOptions {
             max_exports: 10000,
             timeout_ms: None,
             validate_checksums: false,
+            best_effort: false,
+        }
+    }
+}
+
+/// Non-fatal problem recorded by a best-effort parse.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseIssue {
+    /// File offset the problem was found at.
+    pub offset: usize,
+    pub message: String,
+}
+
+impl ParseIssue {
+    pub fn new(offset: usize, message: impl Into<String>) -> Self {
+        Self {
+            offset,
+            message: message.into(),
         }
     }
 }
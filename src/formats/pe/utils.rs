@@ -1,6 +1,8 @@
 //! Utility functions for PE parsing
 
+use crate::core::binary::Endianness;
 use crate::formats::pe::types::{PeError, Result};
+use crate::formats::read;
 
 /// Extension trait for reading primitive types from byte slices
 pub trait ReadExt {
@@ -15,77 +17,59 @@ pub trait ReadExt {
 impl ReadExt for [u8] {
     #[inline(always)]
     fn read_u8_at(&self, offset: usize) -> Option<u8> {
-        self.get(offset).copied()
+        read::u8_at(self, offset).ok()
     }
 
     #[inline(always)]
     fn read_u16_le_at(&self, offset: usize) -> Option<u16> {
-        self.get(offset..offset + 2)
-            .and_then(|b| b.try_into().ok())
-            .map(u16::from_le_bytes)
+        read::u16_at(self, offset, Endianness::Little).ok()
     }
 
     #[inline(always)]
     fn read_u32_le_at(&self, offset: usize) -> Option<u32> {
-        self.get(offset..offset + 4)
-            .and_then(|b| b.try_into().ok())
-            .map(u32::from_le_bytes)
+        read::u32_at(self, offset, Endianness::Little).ok()
     }
 
     #[inline(always)]
     fn read_u64_le_at(&self, offset: usize) -> Option<u64> {
-        self.get(offset..offset + 8)
-            .and_then(|b| b.try_into().ok())
-            .map(u64::from_le_bytes)
+        read::u64_at(self, offset, Endianness::Little).ok()
     }
 
     fn read_cstring_at(&self, offset: usize, max_len: usize) -> Option<&str> {
-        let start = offset;
-        let end = (offset + max_len).min(self.len());
-        let slice = self.get(start..end)?;
-
-        // Find null terminator
-        let len = slice.iter().position(|&b| b == 0).unwrap_or(slice.len());
-        std::str::from_utf8(&slice[..len]).ok()
+        read::read_cstr(self, offset, max_len).ok()
     }
 
     #[inline(always)]
     fn read_slice_at(&self, offset: usize, len: usize) -> Option<&[u8]> {
-        if offset + len > self.len() {
-            None
-        } else {
-            Some(&self[offset..offset + len])
-        }
+        read::bytes_at(self, offset, len).ok()
     }
 }
 
 /// Helper to read a null-terminated string from a buffer
 pub fn read_cstring(data: &[u8], offset: usize, max_len: usize) -> Result<&str> {
-    data.read_cstring_at(offset, max_len)
-        .ok_or(PeError::InvalidString)
+    read::read_cstr(data, offset, max_len).map_err(|_| PeError::InvalidString)
 }
 
 /// Helper to read a UTF-16LE string from a buffer
 pub fn read_utf16le_string(data: &[u8], offset: usize, max_len: usize) -> Result<String> {
-    let end = (offset + max_len * 2).min(data.len());
-    if offset >= end {
-        return Err(PeError::InvalidOffset { offset });
+    match read::read_cstring(data, offset, max_len, read::StrEncoding::Utf16Le) {
+        Ok(s) => Ok(s.into_owned()),
+        Err(read::StrError::OutOfBounds { offset }) => Err(PeError::InvalidOffset { offset }),
+        Err(read::StrError::InvalidEncoding { .. }) => Err(PeError::InvalidString),
     }
+}
 
-    let slice = &data[offset..end];
-    let mut words = Vec::new();
-    let mut i = 0;
-
-    while i + 1 < slice.len() {
-        let word = u16::from_le_bytes([slice[i], slice[i + 1]]);
-        if word == 0 {
-            break;
-        }
-        words.push(word);
-        i += 2;
+/// Registry spelling of a GUID stored in its on-disk (mixed-endian)
+/// layout: `6B29FC40-CA47-1067-B31D-00DD010662DA`.
+pub fn format_guid(guid: &[u8; 16]) -> String {
+    let d1 = u32::from_le_bytes([guid[0], guid[1], guid[2], guid[3]]);
+    let d2 = u16::from_le_bytes([guid[4], guid[5]]);
+    let d3 = u16::from_le_bytes([guid[6], guid[7]]);
+    let mut out = format!("{d1:08X}-{d2:04X}-{d3:04X}-{:02X}{:02X}-", guid[8], guid[9]);
+    for byte in &guid[10..] {
+        out.push_str(&format!("{byte:02X}"));
     }
-
-    String::from_utf16(&words).map_err(|_| PeError::InvalidString)
+    out
 }
 
 /// Calculate entropy of a byte slice
@@ -143,15 +127,17 @@ pub fn is_high_entropy(data: &[u8]) -> bool {
     calculate_entropy(data) > 7.0
 }
 
-/// Simple checksum validation helper
+/// Compute the optional-header checksum (as `MapFileAndCheckSum` does) over
+/// `data`, treating the 4-byte field at `checksum_offset` as zero.
 pub fn calculate_pe_checksum(data: &[u8], checksum_offset: usize) -> u32 {
     let mut sum = 0u64;
     let mut i = 0;
+    let field = checksum_offset..checksum_offset.saturating_add(4);
 
     // Sum all 16-bit words
     while i < data.len() {
         // Skip the checksum field itself
-        if i == checksum_offset || i == checksum_offset + 1 {
+        if field.contains(&i) {
             i += 2;
             continue;
         }
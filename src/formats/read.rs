@@ -0,0 +1,212 @@
+//! Bounds-checked integer reads shared by the format parsers.
+//!
+//! All offsets are checked with overflow-safe arithmetic, so a hostile
+//! `offset` near `usize::MAX` yields [`OutOfBounds`] instead of a panic.
+//! Format modules wrap these in their own error types.
+
+use crate::core::binary::Endianness;
+use std::borrow::Cow;
+use std::fmt;
+
+/// A read of `needed` bytes at `offset` ran past the end of the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutOfBounds {
+    pub offset: usize,
+    pub needed: usize,
+}
+
+impl fmt::Display for OutOfBounds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "read of {} bytes at {:#x} is out of bounds",
+            self.needed, self.offset
+        )
+    }
+}
+
+impl std::error::Error for OutOfBounds {}
+
+pub type Result<T> = std::result::Result<T, OutOfBounds>;
+
+/// Borrow `len` bytes at `offset`.
+#[inline]
+pub fn bytes_at(data: &[u8], offset: usize, len: usize) -> Result<&[u8]> {
+    offset
+        .checked_add(len)
+        .and_then(|end| data.get(offset..end))
+        .ok_or(OutOfBounds {
+            offset,
+            needed: len,
+        })
+}
+
+#[inline]
+fn array_at<const N: usize>(data: &[u8], offset: usize) -> Result<[u8; N]> {
+    // `bytes_at` returns exactly N bytes, so the conversion cannot fail.
+    bytes_at(data, offset, N).map(|b| b.try_into().unwrap_or([0; N]))
+}
+
+macro_rules! read_fn {
+    ($name:ident, $ty:ty) => {
+        #[doc = concat!("Read a `", stringify!($ty), "` at `offset` in `endian` byte order.")]
+        #[inline]
+        pub fn $name(data: &[u8], offset: usize, endian: Endianness) -> Result<$ty> {
+            let bytes = array_at(data, offset)?;
+            Ok(match endian {
+                Endianness::Little => <$ty>::from_le_bytes(bytes),
+                Endianness::Big => <$ty>::from_be_bytes(bytes),
+            })
+        }
+    };
+}
+
+read_fn!(u16_at, u16);
+read_fn!(u32_at, u32);
+read_fn!(u64_at, u64);
+read_fn!(i16_at, i16);
+read_fn!(i32_at, i32);
+read_fn!(i64_at, i64);
+
+/// Read a byte at `offset`.
+#[inline]
+pub fn u8_at(data: &[u8], offset: usize) -> Result<u8> {
+    data.get(offset)
+        .copied()
+        .ok_or(OutOfBounds { offset, needed: 1 })
+}
+
+/// Encoding of a NUL-terminated string for [`read_cstring`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StrEncoding {
+    /// Single-byte units validated as UTF-8 (ASCII is a subset).
+    Utf8,
+    /// 16-bit little-endian units, e.g. PE resource and version strings.
+    Utf16Le,
+    /// 16-bit big-endian units.
+    Utf16Be,
+}
+
+/// Why [`read_cstring`] could not produce a string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StrError {
+    /// `offset` is at or past the end of the input.
+    OutOfBounds { offset: usize },
+    /// The units before the terminator are not valid in the encoding.
+    InvalidEncoding { offset: usize },
+}
+
+impl fmt::Display for StrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfBounds { offset } => write!(f, "string offset {:#x} out of bounds", offset),
+            Self::InvalidEncoding { offset } => write!(f, "invalid string at {:#x}", offset),
+        }
+    }
+}
+
+impl std::error::Error for StrError {}
+
+/// Read a NUL-terminated string of at most `max` code units at `offset`.
+///
+/// `max` counts bytes for [`StrEncoding::Utf8`] and 16-bit units for the
+/// UTF-16 encodings. The terminator is optional: an unterminated string
+/// ends at `max` units or the end of `data`, whichever comes first. A
+/// trailing odd byte is ignored for UTF-16. UTF-8 strings are borrowed.
+pub fn read_cstring(
+    data: &[u8],
+    offset: usize,
+    max: usize,
+    encoding: StrEncoding,
+) -> std::result::Result<Cow<'_, str>, StrError> {
+    let endian = match encoding {
+        StrEncoding::Utf8 => return read_cstr(data, offset, max).map(Cow::Borrowed),
+        StrEncoding::Utf16Le => Endianness::Little,
+        StrEncoding::Utf16Be => Endianness::Big,
+    };
+    let window = bounded_window(data, offset, max.saturating_mul(2))?;
+    let units: Vec<u16> = window
+        .chunks_exact(2)
+        .map(|c| match endian {
+            Endianness::Little => u16::from_le_bytes([c[0], c[1]]),
+            Endianness::Big => u16::from_be_bytes([c[0], c[1]]),
+        })
+        .take_while(|&u| u != 0)
+        .collect();
+    String::from_utf16(&units)
+        .map(Cow::Owned)
+        .map_err(|_| StrError::InvalidEncoding { offset })
+}
+
+/// Single-byte form of [`read_cstring`] returning a borrowed `&str`.
+pub fn read_cstr(data: &[u8], offset: usize, max: usize) -> std::result::Result<&str, StrError> {
+    let window = bounded_window(data, offset, max)?;
+    let len = window.iter().position(|&b| b == 0).unwrap_or(window.len());
+    std::str::from_utf8(&window[..len]).map_err(|_| StrError::InvalidEncoding { offset })
+}
+
+/// `data[offset..offset + max]`, clamped to the end of `data`.
+fn bounded_window(data: &[u8], offset: usize, max: usize) -> std::result::Result<&[u8], StrError> {
+    if offset >= data.len() {
+        return Err(StrError::OutOfBounds { offset });
+    }
+    let end = offset.saturating_add(max).min(data.len());
+    Ok(&data[offset..end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_both_byte_orders_and_rejects_overruns() {
+        let data = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08];
+        assert_eq!(u16_at(&data, 0, Endianness::Little), Ok(0x0201));
+        assert_eq!(u16_at(&data, 0, Endianness::Big), Ok(0x0102));
+        assert_eq!(u32_at(&data, 4, Endianness::Big), Ok(0x0506_0708));
+        assert_eq!(
+            u64_at(&data, 0, Endianness::Little),
+            Ok(0x0807_0605_0403_0201)
+        );
+        assert_eq!(i16_at(&[0xff, 0xfe], 0, Endianness::Big), Ok(-2));
+        assert_eq!(u8_at(&data, 7), Ok(8));
+
+        assert_eq!(
+            u32_at(&data, 6, Endianness::Little),
+            Err(OutOfBounds {
+                offset: 6,
+                needed: 4
+            })
+        );
+        assert!(u64_at(&data, usize::MAX - 2, Endianness::Little).is_err());
+        assert!(u8_at(&data, 8).is_err());
+    }
+
+    #[test]
+    fn cstrings_share_termination_rules_across_encodings() {
+        let utf8 = b"hello\0world";
+        assert_eq!(read_cstr(utf8, 0, 64), Ok("hello"));
+        assert_eq!(read_cstr(utf8, 6, 64), Ok("world"));
+        assert_eq!(read_cstr(utf8, 0, 3), Ok("hel"));
+        assert_eq!(
+            read_cstr(utf8, 11, 4),
+            Err(StrError::OutOfBounds { offset: 11 })
+        );
+        assert_eq!(
+            read_cstr(b"\xff\xfe", 0, 4),
+            Err(StrError::InvalidEncoding { offset: 0 })
+        );
+
+        let le = b"H\0i\0\0\0junk";
+        assert_eq!(
+            read_cstring(le, 0, usize::MAX, StrEncoding::Utf16Le).unwrap(),
+            "Hi"
+        );
+        let be = b"\0O\0K\0";
+        assert_eq!(read_cstring(be, 0, 8, StrEncoding::Utf16Be).unwrap(), "OK");
+        assert!(matches!(
+            read_cstring(utf8, 0, 64, StrEncoding::Utf8),
+            Ok(Cow::Borrowed("hello"))
+        ));
+    }
+}
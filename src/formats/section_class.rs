@@ -0,0 +1,125 @@
+//! Format-agnostic section classification.
+//!
+//! Normalizes section names across PE (`.text`, `CODE`, `.text$mn`), ELF
+//! (`.text.startup`, `.rodata.str1.1`) and Mach-O (`__text`, `__cstring`)
+//! and maps them to a coarse [`SectionClass`]. Names the table does not know,
+//! including packer sections such as `UPX1` or `.aspack`, fall back to the
+//! section's permissions.
+
+use crate::core::section::SectionPerms;
+
+/// Coarse role of a section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SectionClass {
+    Code,
+    Data,
+    ReadOnlyData,
+    Bss,
+    Debug,
+    Resource,
+    Unknown,
+}
+
+/// Section names introduced by common packers and protectors (normalized).
+const PACKER_SECTIONS: &[&str] = &[
+    "upx0", "upx1", "upx2", "aspack", "adata", "mpress1", "mpress2", "petite", "packed", "themida",
+    "winlicen", "vmp0", "vmp1", "vmp2", "enigma1", "enigma2", "nsp0", "nsp1",
+];
+
+/// Reduce a raw section name to its lowercase base component.
+///
+/// Strips NUL padding, a leading `.` or `__`, PE grouping suffixes (`$mn`)
+/// and ELF sub-section suffixes (`.startup`, `.str1.1`).
+pub fn normalize(name: &str) -> String {
+    let trimmed = name.trim_end_matches('\0').trim();
+    let stripped = trimmed
+        .strip_prefix("__")
+        .or_else(|| trimmed.strip_prefix('.'))
+        .unwrap_or(trimmed);
+    stripped
+        .split(['.', '$'])
+        .next()
+        .unwrap_or("")
+        .to_ascii_lowercase()
+}
+
+/// True when `name` is a well-known packer/protector section.
+pub fn is_packer_section(name: &str) -> bool {
+    PACKER_SECTIONS.contains(&normalize(name).as_str())
+}
+
+/// Classify a section by name alone; `None` when the name is not recognized.
+pub fn classify_name(name: &str) -> Option<SectionClass> {
+    let base = normalize(name);
+    let class = match base.as_str() {
+        "text" | "code" | "textbss" | "init" | "fini" | "plt" | "page" | "stubs"
+        | "stub_helper" => SectionClass::Code,
+        "data" | "sdata" | "got" | "tls" | "tdata" | "idata" | "crt" | "init_array"
+        | "fini_array" | "preinit_array" | "dynamic" | "la_symbol_ptr" | "nl_symbol_ptr" => {
+            SectionClass::Data
+        }
+        "rdata" | "rodata" | "srodata" | "edata" | "const" | "cstring" | "eh_frame"
+        | "eh_frame_hdr" | "gcc_except_table" | "pdata" | "xdata" | "unwind_info" | "reloc"
+        | "rel" | "rela" | "dynsym" | "dynstr" | "hash" | "gnu" | "interp" | "note" => {
+            SectionClass::ReadOnlyData
+        }
+        "bss" | "sbss" | "tbss" | "common" => SectionClass::Bss,
+        "symtab" | "strtab" | "shstrtab" | "comment" | "stab" | "stabstr" => SectionClass::Debug,
+        b if b.starts_with("debug") || b.starts_with("zdebug") => SectionClass::Debug,
+        "rsrc" => SectionClass::Resource,
+        _ => return None,
+    };
+    Some(class)
+}
+
+/// Classify a section by name, falling back to its permissions.
+pub fn classify(name: &str, perms: SectionPerms) -> SectionClass {
+    if let Some(class) = classify_name(name) {
+        return class;
+    }
+    if perms.has_execute() {
+        SectionClass::Code
+    } else if perms.has_write() {
+        SectionClass::Data
+    } else if perms.has_read() {
+        SectionClass::ReadOnlyData
+    } else {
+        SectionClass::Unknown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_normalize_across_formats() {
+        let none = SectionPerms::new(false, false, false);
+        for name in [".text", "CODE", ".text$mn", ".text.startup", "__text"] {
+            assert_eq!(classify(name, none), SectionClass::Code, "{}", name);
+        }
+        assert_eq!(classify(".rodata.str1.1", none), SectionClass::ReadOnlyData);
+        assert_eq!(classify(".rdata\0\0", none), SectionClass::ReadOnlyData);
+        assert_eq!(classify("BSS", none), SectionClass::Bss);
+        assert_eq!(classify(".debug_info", none), SectionClass::Debug);
+        assert_eq!(classify(".debug$S", none), SectionClass::Debug);
+        assert_eq!(classify(".rsrc", none), SectionClass::Resource);
+        assert_eq!(classify(".weird", none), SectionClass::Unknown);
+    }
+
+    #[test]
+    fn packer_sections_fall_back_to_perms() {
+        assert!(is_packer_section("UPX1"));
+        assert!(is_packer_section(".aspack"));
+        assert!(!is_packer_section(".text"));
+        assert_eq!(classify_name("UPX1"), None);
+        assert_eq!(
+            classify("UPX1", SectionPerms::new(true, true, true)),
+            SectionClass::Code
+        );
+        assert_eq!(
+            classify("UPX0", SectionPerms::new(true, true, false)),
+            SectionClass::Data
+        );
+    }
+}
@@ -3,13 +3,25 @@
 //! This module provides a `SafeReader` for accessing file contents in a safe,
 //! efficient, and ergonomic way. It uses memory-mapping for performance and
 //! enforces strict resource limits to prevent DoS from malicious files.
+//!
+//! On targets without mmap (`wasm32`) the file is instead read once into a
+//! buffer bounded by `IOLimits::max_file_size`; the `SafeReader` API and the
+//! per-read `max_read_bytes` budget are unchanged.
+//!
+//! [`SafeReader::with_hashing`] hashes bytes as they are read, so callers
+//! that already stream a file's prefix get whole-file digests without a
+//! second full pass over the data they have seen.
 
 pub mod error;
 
+use crate::core::binary::Hashes;
 use crate::io::error::{IoError, Result};
 use bytes::Bytes;
+#[cfg(not(target_arch = "wasm32"))]
 use memmap2::Mmap;
+use sha2::{Digest, Sha256};
 use std::fs::File;
+use std::io::Read;
 use std::path::{Path, PathBuf};
 use tracing::{debug, trace, warn};
 
@@ -33,8 +45,133 @@ impl Default for IOLimits {
     fn default() -> Self {
         Self {
             max_file_size: 100 * 1024 * 1024, // 100MB
-            max_read_bytes: 10 * 1024 * 1024,  // 10MB
+            max_read_bytes: 10 * 1024 * 1024, // 10MB
+        }
+    }
+}
+
+/// Contents backing a `SafeReader`: a read-only file map where mmap exists,
+/// or owned bytes (in-memory data, and files on targets without mmap).
+enum Backing {
+    #[cfg(not(target_arch = "wasm32"))]
+    Mapped(Mmap),
+    Owned(Bytes),
+}
+
+impl std::ops::Deref for Backing {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            #[cfg(not(target_arch = "wasm32"))]
+            Backing::Mapped(m) => m,
+            Backing::Owned(b) => b,
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn map_file(file: &File, _file_size: u64) -> Result<Backing> {
+    // Safety: The file is backed by a real file on disk and we only request a read-only map.
+    Ok(Backing::Mapped(unsafe { Mmap::map(file)? }))
+}
+
+#[cfg(target_arch = "wasm32")]
+fn map_file(file: &File, file_size: u64) -> Result<Backing> {
+    Ok(Backing::Owned(read_file_bounded(file, file_size)?.into()))
+}
+
+/// Read at most `file_size` bytes of `file` into memory. The caller has
+/// already checked `file_size` against `IOLimits::max_file_size`, and the
+/// `take` keeps a file that grows after the check from exceeding it.
+///
+/// Fails with `IoError::FileTooLarge` when `file_size` does not fit the
+/// address space (a 32-bit `usize` on wasm32).
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+fn read_file_bounded(file: &File, file_size: u64) -> Result<Vec<u8>> {
+    let capacity = usize::try_from(file_size).map_err(|_| IoError::FileTooLarge {
+        limit: usize::MAX as u64,
+        found: file_size,
+    })?;
+    let mut buf = Vec::with_capacity(capacity);
+    file.take(file_size).read_to_end(&mut buf)?;
+    Ok(buf)
+}
+
+/// Digest algorithms [`SafeReader::with_hashing`] and
+/// [`crate::triage::io::SafeFileReader::with_hashing`] can compute inline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HashAlg {
+    Sha256,
+    Md5,
+}
+
+enum Hasher {
+    Sha256(Sha256),
+    Md5(md5::Context),
+}
+
+/// Hashers fed with the file's bytes in order; `position` is how much of
+/// the file they have consumed.
+pub(crate) struct InlineHashing {
+    hashers: Vec<Hasher>,
+    pub(crate) position: u64,
+}
+
+impl InlineHashing {
+    pub(crate) fn new(algs: &[HashAlg]) -> Self {
+        let mut hashers = Vec::new();
+        for (i, alg) in algs.iter().enumerate() {
+            if algs[..i].contains(alg) {
+                continue;
+            }
+            hashers.push(match alg {
+                HashAlg::Sha256 => Hasher::Sha256(Sha256::new()),
+                HashAlg::Md5 => Hasher::Md5(md5::Context::new()),
+            });
+        }
+        Self {
+            hashers,
+            position: 0,
+        }
+    }
+
+    /// Consume the part of `bytes` (read at `offset`) that extends the
+    /// hashed prefix. Reads that start past `position` leave a gap and are
+    /// picked up again by [`SafeReader::finalize_hashes`].
+    pub(crate) fn observe(&mut self, offset: u64, bytes: &[u8]) {
+        let end = offset + bytes.len() as u64;
+        if offset > self.position || end <= self.position {
+            return;
+        }
+        let fresh = &bytes[(self.position - offset) as usize..];
+        for hasher in &mut self.hashers {
+            match hasher {
+                Hasher::Sha256(h) => h.update(fresh),
+                Hasher::Md5(h) => h.consume(fresh),
+            }
+        }
+        self.position = end;
+    }
+
+    pub(crate) fn finish(self) -> Hashes {
+        let mut hashes = no_hashes();
+        for hasher in self.hashers {
+            match hasher {
+                Hasher::Sha256(h) => hashes.sha256 = Some(format!("{:x}", h.finalize())),
+                Hasher::Md5(h) => hashes.md5 = Some(format!("{:x}", h.compute())),
+            }
         }
+        hashes
+    }
+}
+
+fn no_hashes() -> Hashes {
+    Hashes {
+        sha256: None,
+        md5: None,
+        sha1: None,
+        additional: None,
     }
 }
 
@@ -45,10 +182,11 @@ impl Default for IOLimits {
 pub struct SafeReader {
     path: PathBuf,
     // None when the file size is zero; memmap cannot map empty files.
-    mmap: Option<Mmap>,
+    mmap: Option<Backing>,
     limits: IOLimits,
     bytes_read: u64,
     file_size: u64,
+    hashing: Option<InlineHashing>,
 }
 
 impl SafeReader {
@@ -86,15 +224,79 @@ impl SafeReader {
         let mmap = if file_size == 0 {
             None
         } else {
-            // Safety: The file is backed by a real file on disk and we only request a read-only map.
-            Some(unsafe { Mmap::map(&file)? })
+            Some(map_file(&file, file_size)?)
         };
 
-        Ok(Self { path: path.to_path_buf(), mmap, limits, bytes_read: 0, file_size })
+        Ok(Self {
+            path: path.to_path_buf(),
+            mmap,
+            limits,
+            bytes_read: 0,
+            file_size,
+            hashing: None,
+        })
+    }
+
+    /// Wraps already in-memory data (e.g. an extracted container member) in a
+    /// `SafeReader` with the same bounded-read semantics as [`SafeReader::open`].
+    ///
+    /// Fails with `IoError::FileTooLarge` if `data` exceeds `limits.max_file_size`.
+    /// Log messages refer to the data as `<memory>`.
+    pub fn from_bytes(data: Bytes, limits: IOLimits) -> Result<Self> {
+        let file_size = data.len() as u64;
+        if file_size > limits.max_file_size {
+            warn!(
+                size = file_size,
+                limit = limits.max_file_size,
+                "In-memory data is too large"
+            );
+            return Err(IoError::FileTooLarge {
+                limit: limits.max_file_size,
+                found: file_size,
+            });
+        }
+        let mmap = (file_size > 0).then(|| Backing::Owned(data));
+        Ok(Self {
+            path: PathBuf::from("<memory>"),
+            mmap,
+            limits,
+            bytes_read: 0,
+            file_size,
+            hashing: None,
+        })
+    }
+
+    /// Hash bytes with `algs` as they are returned by [`SafeReader::read_at`].
+    ///
+    /// Reads that extend the contiguous prefix already hashed feed the
+    /// hashers directly; [`SafeReader::finalize_hashes`] covers whatever
+    /// the reads did not.
+    pub fn with_hashing(mut self, algs: &[HashAlg]) -> Self {
+        self.hashing = Some(InlineHashing::new(algs));
+        self
+    }
+
+    /// Whole-file digests for the algorithms passed to
+    /// [`SafeReader::with_hashing`]; all fields are `None` without it.
+    ///
+    /// Bytes past the hashed prefix are read straight from the backing
+    /// store and do not count against the read budget. Hashing stops after
+    /// this call.
+    pub fn finalize_hashes(&mut self) -> Hashes {
+        let Some(mut hashing) = self.hashing.take() else {
+            return no_hashes();
+        };
+        if let Some(map) = &self.mmap {
+            let position = hashing.position as usize;
+            hashing.observe(hashing.position, &map[position.min(map.len())..]);
+        }
+        hashing.finish()
     }
 
     /// Returns the total size of the underlying file in bytes.
-    pub fn size(&self) -> u64 { self.file_size }
+    pub fn size(&self) -> u64 {
+        self.file_size
+    }
 
     /// Returns the total number of bytes read so far.
     pub fn bytes_read(&self) -> u64 {
@@ -120,60 +322,132 @@ impl SafeReader {
         let offset = offset as usize;
 
         // Check if the read would exceed the total read budget.
-        if self.bytes_read.saturating_add(len) > self.limits.max_read_bytes {
-            warn!(
-                path = %self.path.display(),
-                current_read = self.bytes_read,
-                requested = len,
-                limit = self.limits.max_read_bytes,
-                "Read limit exceeded"
-            );
-            return Err(IoError::ReadLimitExceeded {
-                limit: self.limits.max_read_bytes,
-                current: self.bytes_read,
-            });
-        }
+        check_budget(&self.path, self.bytes_read, len, self.limits.max_read_bytes)?;
 
-        // If the file is empty or offset is beyond EOF, return empty without changing state.
-        if self.file_size == 0 {
-            return Ok(Bytes::new());
+        let out = self.slice_range(offset, requested_len);
+        self.bytes_read += out.len() as u64;
+        if let Some(hashing) = &mut self.hashing {
+            hashing.observe(offset as u64, &out);
         }
 
+        trace!(
+            path = %self.path.display(),
+            offset = offset,
+            len = out.len(),
+            total_read = self.bytes_read,
+            "Performed read"
+        );
+
+        Ok(out)
+    }
+
+    /// `[offset, offset + len)` clamped to EOF; empty past EOF or for empty
+    /// data. Does not touch the read budget.
+    fn slice_range(&self, offset: usize, len: usize) -> Bytes {
         let map = match &self.mmap {
             Some(m) => m,
-            None => return Ok(Bytes::new()),
+            None => return Bytes::new(),
         };
 
         // Ensure the read is within the file's bounds.
-        let end = offset.saturating_add(requested_len);
         if offset >= map.len() {
-            return Ok(Bytes::new()); // Read starts past EOF.
+            return Bytes::new(); // Read starts past EOF.
         }
-        let bounded_end = std::cmp::min(end, map.len());
-        let actual_len = bounded_end - offset;
+        let bounded_end = std::cmp::min(offset.saturating_add(len), map.len());
+
+        match map {
+            // Owned data (e.g. from `from_bytes`) is shared, not copied.
+            Backing::Owned(b) => b.slice(offset..bounded_end),
+            // Copy out a Bytes buffer referencing owned data (avoid invalid from_static).
+            #[cfg(not(target_arch = "wasm32"))]
+            Backing::Mapped(m) => Bytes::copy_from_slice(&m[offset..bounded_end]),
+        }
+    }
+
+    /// A convenience method to read a prefix of the file.
+    ///
+    /// Equivalent to `read_at(0, len)`.
+    pub fn read_prefix(&mut self, len: u64) -> Result<Bytes> {
+        self.read_at(0, len)
+    }
 
-        // Copy out a Bytes buffer referencing owned data (avoid invalid from_static).
-        let slice = &map[offset..bounded_end];
-        let out = Bytes::copy_from_slice(slice);
-        self.bytes_read += actual_len as u64;
+    /// Resets the accumulated read budget so a new, logically separate
+    /// analysis pass starts from zero.
+    pub fn reset_budget(&mut self) {
+        self.bytes_read = 0;
+    }
 
-        trace!(
-            path = %self.path.display(),
-            offset = offset,
-            len = actual_len,
-            total_read = self.bytes_read,
-            "Performed read"
+    /// Starts an independent read budget of `max_bytes` (capped at
+    /// `limits.max_read_bytes`) for one analysis pass.
+    ///
+    /// Reads through the returned scope do not count against this reader's
+    /// own `bytes_read`, so passes that re-read overlapping regions do not
+    /// starve each other.
+    pub fn sub_budget(&self, max_bytes: u64) -> ScopedBudget<'_> {
+        ScopedBudget {
+            reader: self,
+            limit: max_bytes.min(self.limits.max_read_bytes),
+            bytes_read: 0,
+        }
+    }
+}
+
+/// Fail with `IoError::ReadLimitExceeded` (and log it) when reading `len`
+/// more bytes would take `bytes_read` past `limit`.
+fn check_budget(path: &Path, bytes_read: u64, len: u64, limit: u64) -> Result<()> {
+    if bytes_read.saturating_add(len) > limit {
+        warn!(
+            path = %path.display(),
+            current_read = bytes_read,
+            requested = len,
+            limit = limit,
+            "Read limit exceeded"
         );
+        return Err(IoError::ReadLimitExceeded {
+            limit,
+            current: bytes_read,
+        });
+    }
+    Ok(())
+}
+
+/// A read budget scoped to one analysis pass over a `SafeReader`.
+///
+/// Created by [`SafeReader::sub_budget`].
+pub struct ScopedBudget<'a> {
+    reader: &'a SafeReader,
+    limit: u64,
+    bytes_read: u64,
+}
 
+impl ScopedBudget<'_> {
+    /// Reads a slice at `offset`, charging this scope's budget.
+    ///
+    /// # Errors
+    ///
+    /// Returns `IoError::ReadLimitExceeded` if the read would exceed the
+    /// scope's limit.
+    pub fn read_at(&mut self, offset: u64, len: u64) -> Result<Bytes> {
+        check_budget(&self.reader.path, self.bytes_read, len, self.limit)?;
+        let out = self.reader.slice_range(offset as usize, len as usize);
+        self.bytes_read += out.len() as u64;
         Ok(out)
     }
 
-    /// A convenience method to read a prefix of the file.
-    ///
     /// Equivalent to `read_at(0, len)`.
     pub fn read_prefix(&mut self, len: u64) -> Result<Bytes> {
         self.read_at(0, len)
     }
+
+    /// Bytes read through this scope so far.
+    pub fn bytes_read(&self) -> u64 {
+        self.bytes_read
+    }
+
+    /// Bytes still available in this scope.
+    pub fn remaining(&self) -> u64 {
+        self.limit - self.bytes_read
+    }
 }
 
 #[cfg(test)]
@@ -267,7 +541,7 @@ mod tests {
             max_read_bytes: 50,
         };
         let mut reader = SafeReader::open(file.path(), limits).unwrap();
-        
+
         // First read, should succeed
         let data1 = reader.read_prefix(30).unwrap();
         assert_eq!(data1.len(), 30);
@@ -276,11 +550,11 @@ mod tests {
         // Second read, should fail
         let result = reader.read_at(30, 30);
         assert!(matches!(result, Err(IoError::ReadLimitExceeded { .. })));
-        
+
         // State should not have changed
         assert_eq!(reader.bytes_read(), 30);
     }
-    
+
     #[test]
     fn read_up_to_exact_limit() {
         let file = create_temp_file(&[0; 100]);
@@ -289,13 +563,13 @@ mod tests {
             max_read_bytes: 50,
         };
         let mut reader = SafeReader::open(file.path(), limits).unwrap();
-        
+
         let data1 = reader.read_prefix(30).unwrap();
         assert_eq!(data1.len(), 30);
 
         let data2 = reader.read_at(30, 20).unwrap();
         assert_eq!(data2.len(), 20);
-        
+
         assert_eq!(reader.bytes_read(), 50);
 
         // Next read should return empty without error, as we request 0 bytes effectively
@@ -303,6 +577,105 @@ mod tests {
         assert!(matches!(result, Err(IoError::ReadLimitExceeded { .. })));
     }
 
+    #[test]
+    fn reset_and_scoped_budgets_are_independent() {
+        let file = create_temp_file(&[7; 100]);
+        let limits = IOLimits {
+            max_file_size: 1000,
+            max_read_bytes: 50,
+        };
+        let mut reader = SafeReader::open(file.path(), limits).unwrap();
+        reader.read_prefix(40).unwrap();
+        assert!(reader.read_prefix(40).is_err());
+        reader.reset_budget();
+        assert_eq!(reader.read_prefix(40).unwrap().len(), 40);
+
+        // Scopes are capped at max_read_bytes and leave the parent untouched.
+        let mut pass = reader.sub_budget(1000);
+        assert_eq!(pass.remaining(), 50);
+        assert_eq!(pass.read_at(60, 30).unwrap(), &[7u8; 30][..]);
+        assert!(matches!(
+            pass.read_prefix(30),
+            Err(IoError::ReadLimitExceeded {
+                limit: 50,
+                current: 30
+            })
+        ));
+        assert_eq!(pass.bytes_read(), 30);
+        let mut other = reader.sub_budget(10);
+        assert_eq!(other.read_at(95, 10).unwrap().len(), 5);
+        assert_eq!(reader.bytes_read(), 40);
+    }
+
+    #[test]
+    fn from_bytes_enforces_limits() {
+        let limits = IOLimits {
+            max_file_size: 100,
+            max_read_bytes: 8,
+        };
+        let data = Bytes::from_static(b"hello world");
+        let mut reader = SafeReader::from_bytes(data.clone(), limits.clone()).unwrap();
+        assert_eq!(reader.size(), 11);
+        let world = reader.read_at(6, 5).unwrap();
+        assert_eq!(world, &b"world"[..]);
+        // Reads share the caller's buffer.
+        assert_eq!(world.as_ptr(), data[6..].as_ptr());
+        assert!(matches!(
+            reader.read_prefix(4),
+            Err(IoError::ReadLimitExceeded { .. })
+        ));
+
+        let big = Bytes::from(vec![0u8; 101]);
+        assert!(matches!(
+            SafeReader::from_bytes(big, limits.clone()),
+            Err(IoError::FileTooLarge { .. })
+        ));
+
+        let mut empty = SafeReader::from_bytes(Bytes::new(), limits).unwrap();
+        assert!(empty.read_prefix(4).unwrap().is_empty());
+    }
+
+    #[test]
+    fn bounded_fallback_reads_at_most_file_size() {
+        let file = create_temp_file(b"hello world");
+        let f = File::open(file.path()).unwrap();
+        assert_eq!(read_file_bounded(&f, 5).unwrap(), b"hello");
+        let f = File::open(file.path()).unwrap();
+        assert_eq!(read_file_bounded(&f, 64).unwrap(), b"hello world");
+    }
+
+    #[test]
+    fn inline_hashing_covers_the_whole_file() {
+        let file = create_temp_file(b"hello world");
+        let mut reader = SafeReader::open(file.path(), IOLimits::default())
+            .unwrap()
+            .with_hashing(&[HashAlg::Sha256, HashAlg::Md5]);
+        reader.read_prefix(5).unwrap();
+        reader.read_at(3, 4).unwrap();
+        reader.read_at(9, 2).unwrap();
+        assert_eq!(reader.bytes_read(), 11);
+
+        let hashes = reader.finalize_hashes();
+        assert_eq!(
+            hashes.sha256.as_deref(),
+            Some("b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9")
+        );
+        assert_eq!(
+            hashes.md5.as_deref(),
+            Some("5eb63bbbe01eeed093cb22bb8f5acdc3")
+        );
+        assert_eq!(hashes.sha1, None);
+        assert_eq!(reader.finalize_hashes().sha256, None);
+
+        let mut empty = SafeReader::from_bytes(Bytes::new(), IOLimits::default())
+            .unwrap()
+            .with_hashing(&[HashAlg::Sha256]);
+        assert_eq!(
+            empty.finalize_hashes().sha256.as_deref(),
+            Some("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855")
+        );
+    }
+
     #[test]
     fn open_empty_file() {
         let file = create_temp_file(b"");
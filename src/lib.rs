@@ -13,6 +13,9 @@ pub mod timeout;
 /// Triage runtime implementation
 pub mod triage;
 
+/// Bounded, memory-mapped file access with read budgets
+pub mod io;
+
 /// Symbol extraction and analysis
 pub mod symbols;
 
@@ -38,9 +41,12 @@ pub mod debug;
 pub mod flirt;
 
 /// Disassembly engines and adapters
+#[cfg(feature = "disasm")]
 pub mod disasm;
 
-/// Low-Level Intermediate Representation (Phase 2)
+/// Low-Level Intermediate Representation (Phase 2). The lifters decode with
+/// the disassembly engines, so the IR is only available with `disasm`.
+#[cfg(feature = "disasm")]
 pub mod ir;
 
 /// Binary format parsers
@@ -80,6 +86,10 @@ fn _native(py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
 
     // Top-level helper: symbol address map for a file
     m.add_function(wrap_pyfunction!(symbol_address_map_py, m)?)?;
+    m.add_function(wrap_pyfunction!(symbol_address_map_by_section_py, m)?)?;
+    m.add_function(wrap_pyfunction!(symbol_address_map_demangled_py, m)?)?;
+    m.add_function(wrap_pyfunction!(name_to_address_py, m)?)?;
+    m.add_function(wrap_pyfunction!(find_symbols_py, m)?)?;
 
     Ok(())
 }
@@ -94,36 +104,74 @@ fn symbol_address_map_py(
     max_read_bytes: u64,
     max_file_size: u64,
 ) -> PyResult<Vec<(u64, String)>> {
-    use object::read::Object;
-    use object::ObjectSymbol;
     let limit = std::cmp::min(max_read_bytes, max_file_size);
     let data = crate::triage::io::IOUtils::read_file_with_limit(&path, limit)
         .map_err(|e| pyo3::exceptions::PyIOError::new_err(format!("{:?}", e)))?;
-    let mut out: Vec<(u64, String)> = Vec::new();
-    if let Ok(obj) = object::read::File::parse(&data[..]) {
-        for sym in obj.symbols() {
-            if sym.is_definition() {
-                if let (Ok(name), addr) = (sym.name(), sym.address()) {
-                    let s = name.to_string();
-                    if !s.is_empty() {
-                        out.push((addr, s));
-                    }
-                }
-            }
-        }
-        for sym in obj.dynamic_symbols() {
-            if sym.is_definition() {
-                if let (Ok(name), addr) = (sym.name(), sym.address()) {
-                    let s = name.to_string();
-                    if !s.is_empty() {
-                        out.push((addr, s));
-                    }
-                }
-            }
-        }
-    }
-    // Dedup by address, keep first name
-    out.sort_by_key(|(a, _)| *a);
-    out.dedup_by_key(|(a, _)| *a);
-    Ok(out)
+    Ok(crate::symbols::symbol_address_map(&data))
+}
+
+/// Symbol address map with demangled names: `(address, name, demangled)`.
+#[cfg(feature = "python-ext")]
+#[pyfunction]
+#[pyo3(name = "symbol_address_map_demangled")]
+#[pyo3(signature = (path, max_read_bytes=10_485_760u64, max_file_size=104_857_600u64))]
+fn symbol_address_map_demangled_py(
+    path: String,
+    max_read_bytes: u64,
+    max_file_size: u64,
+) -> PyResult<Vec<(u64, String, Option<String>)>> {
+    let limit = std::cmp::min(max_read_bytes, max_file_size);
+    let data = crate::triage::io::IOUtils::read_file_with_limit(&path, limit)
+        .map_err(|e| pyo3::exceptions::PyIOError::new_err(format!("{:?}", e)))?;
+    Ok(crate::symbols::symbol_address_map_demangled(&data))
+}
+
+/// Address of the defined symbol named exactly `name`.
+#[cfg(feature = "python-ext")]
+#[pyfunction]
+#[pyo3(name = "name_to_address")]
+#[pyo3(signature = (path, name, max_read_bytes=10_485_760u64, max_file_size=104_857_600u64))]
+fn name_to_address_py(
+    path: String,
+    name: String,
+    max_read_bytes: u64,
+    max_file_size: u64,
+) -> PyResult<Option<u64>> {
+    let limit = std::cmp::min(max_read_bytes, max_file_size);
+    let data = crate::triage::io::IOUtils::read_file_with_limit(&path, limit)
+        .map_err(|e| pyo3::exceptions::PyIOError::new_err(format!("{:?}", e)))?;
+    Ok(crate::symbols::name_to_address(&data, &name))
+}
+
+/// Defined symbols whose name contains `query` (case-insensitive).
+#[cfg(feature = "python-ext")]
+#[pyfunction]
+#[pyo3(name = "find_symbols")]
+#[pyo3(signature = (path, query, max_read_bytes=10_485_760u64, max_file_size=104_857_600u64))]
+fn find_symbols_py(
+    path: String,
+    query: String,
+    max_read_bytes: u64,
+    max_file_size: u64,
+) -> PyResult<Vec<(u64, String)>> {
+    let limit = std::cmp::min(max_read_bytes, max_file_size);
+    let data = crate::triage::io::IOUtils::read_file_with_limit(&path, limit)
+        .map_err(|e| pyo3::exceptions::PyIOError::new_err(format!("{:?}", e)))?;
+    Ok(crate::symbols::find_symbols(&data, &query))
+}
+
+/// Symbol address map grouped by containing section name.
+#[cfg(feature = "python-ext")]
+#[pyfunction]
+#[pyo3(name = "symbol_address_map_by_section")]
+#[pyo3(signature = (path, max_read_bytes=10_485_760u64, max_file_size=104_857_600u64))]
+fn symbol_address_map_by_section_py(
+    path: String,
+    max_read_bytes: u64,
+    max_file_size: u64,
+) -> PyResult<std::collections::HashMap<String, Vec<(u64, String)>>> {
+    let limit = std::cmp::min(max_read_bytes, max_file_size);
+    let data = crate::triage::io::IOUtils::read_file_with_limit(&path, limit)
+        .map_err(|e| pyo3::exceptions::PyIOError::new_err(format!("{:?}", e)))?;
+    Ok(crate::symbols::symbol_address_map_by_section(&data))
 }
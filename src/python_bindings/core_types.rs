@@ -101,6 +101,7 @@ pub fn register_core_types(_py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult
     m.add_class::<crate::core::control_flow_graph::ControlFlowEdge>()?;
     m.add_class::<crate::core::control_flow_graph::ControlFlowGraph>()?;
     m.add_class::<crate::core::control_flow_graph::ControlFlowGraphStats>()?;
+    m.add_class::<crate::core::control_flow_graph::Loop>()?;
     m.add_class::<crate::core::call_graph::CallType>()?;
     m.add_class::<crate::core::call_graph::CallGraphEdge>()?;
     m.add_class::<crate::core::call_graph::CallGraph>()?;
@@ -20,6 +20,9 @@ pub fn register_similarity_bindings(_py: Python<'_>, m: &Bound<'_, PyModule>) ->
     )?)?;
     similarity_mod.add_function(wrap_pyfunction!(ctph_pairwise_matrix_py, &similarity_mod)?)?;
     similarity_mod.add_function(wrap_pyfunction!(ctph_top_k_py, &similarity_mod)?)?;
+    similarity_mod.add_function(wrap_pyfunction!(compare_bytes_py, &similarity_mod)?)?;
+    similarity_mod.add_function(wrap_pyfunction!(compare_files_py, &similarity_mod)?)?;
+    similarity_mod.add_class::<PyCtphIndex>()?;
 
     // Add similarity submodule to main module
     m.add_submodule(&similarity_mod)?;
@@ -41,6 +44,7 @@ fn ctph_hash_bytes_py(
         window_size,
         digest_size,
         precision,
+        ..Default::default()
     };
     crate::similarity::ctph_hash(data, &cfg)
 }
@@ -64,10 +68,58 @@ fn ctph_hash_path_py(
         window_size,
         digest_size,
         precision,
+        ..Default::default()
     };
     Ok(crate::similarity::ctph_hash(&data, &cfg))
 }
 
+/// Hash two buffers and return their CTPH similarity.
+#[pyfunction]
+#[pyo3(name = "compare_bytes")]
+#[pyo3(signature = (a, b, window_size=8, digest_size=4, precision=8))]
+fn compare_bytes_py(
+    a: &[u8],
+    b: &[u8],
+    window_size: usize,
+    digest_size: usize,
+    precision: u8,
+) -> f64 {
+    let cfg = crate::similarity::CtphConfig {
+        window_size,
+        digest_size,
+        precision,
+        ..Default::default()
+    };
+    crate::similarity::compare_bytes(a, b, &cfg)
+}
+
+/// Read two files with bounded I/O, hash both and return their CTPH similarity.
+#[pyfunction]
+#[pyo3(name = "compare_files")]
+#[pyo3(signature = (a, b, max_read_bytes=10_485_760, max_file_size=104_857_600, window_size=8, digest_size=4, precision=8))]
+fn compare_files_py(
+    a: String,
+    b: String,
+    max_read_bytes: u64,
+    max_file_size: u64,
+    window_size: usize,
+    digest_size: usize,
+    precision: u8,
+) -> PyResult<f64> {
+    let cfg = crate::similarity::CtphConfig {
+        window_size,
+        digest_size,
+        precision,
+        ..Default::default()
+    };
+    let limits = crate::io::IOLimits {
+        max_file_size,
+        max_read_bytes,
+    };
+    crate::similarity::compare_files(a.as_ref(), b.as_ref(), &cfg, &limits)
+        .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
+}
+
 /// Calculate similarity between two CTPH hashes.
 #[pyfunction]
 #[pyo3(name = "ctph_similarity")]
@@ -135,3 +187,42 @@ fn ctph_top_k_py(
     scored.truncate(k);
     scored
 }
+
+/// Inverted index over CTPH digests for top-N similarity queries.
+#[pyclass(name = "CtphIndex")]
+#[derive(Default)]
+struct PyCtphIndex {
+    inner: crate::similarity::CtphIndex,
+}
+
+#[pymethods]
+impl PyCtphIndex {
+    #[new]
+    #[pyo3(signature = (digests=None))]
+    fn new(digests: Option<Vec<String>>) -> Self {
+        let mut idx = Self::default();
+        for d in digests.unwrap_or_default() {
+            idx.inner.insert(d);
+        }
+        idx
+    }
+
+    /// Add a digest; returns its id.
+    fn insert(&mut self, digest: String) -> usize {
+        self.inner.insert(digest)
+    }
+
+    /// Return up to `top_n` `(id, digest, score)` tuples, most similar first.
+    #[pyo3(signature = (digest, top_n=5))]
+    fn query(&self, digest: &str, top_n: usize) -> Vec<(usize, String, f64)> {
+        self.inner
+            .query(digest, top_n)
+            .into_iter()
+            .map(|(id, s)| (id, self.inner.get(id).unwrap_or_default().to_string(), s))
+            .collect()
+    }
+
+    fn __len__(&self) -> usize {
+        self.inner.len()
+    }
+}
@@ -13,6 +13,10 @@ pub fn register_symbols_bindings(_py: Python<'_>, m: &Bound<'_, PyModule>) -> Py
     // Register symbol functions
     sym_mod.add_function(wrap_pyfunction!(list_symbols_py, &sym_mod)?)?;
     sym_mod.add_function(wrap_pyfunction!(list_symbols_demangled_py, &sym_mod)?)?;
+    sym_mod.add_function(wrap_pyfunction!(list_symbols_detailed_py, &sym_mod)?)?;
+    sym_mod.add_class::<crate::symbols::SymbolInfo>()?;
+    sym_mod.add_class::<crate::symbols::SymbolType>()?;
+    sym_mod.add_class::<crate::symbols::SymbolBinding>()?;
     sym_mod.add_function(wrap_pyfunction!(imphash_py, &sym_mod)?)?;
     sym_mod.add_function(wrap_pyfunction!(analyze_exports_py, &sym_mod)?)?;
     sym_mod.add_function(wrap_pyfunction!(analyze_env_py, &sym_mod)?)?;
@@ -98,8 +102,9 @@ fn list_symbols_py(
         .map_err(|e| pyo3::exceptions::PyIOError::new_err(format!("{:?}", e)))?;
 
     // Call the actual function and convert tuple to SymbolSummary
-    let (_all_syms, _dyn_syms, imports, exports, libs) =
+    let (all_syms, _dyn_syms, imports, exports, libs) =
         crate::symbols::list_symbols_py(path, max_read_bytes, max_file_size)?;
+    let stripped = crate::symbols::SymbolSummary::stripped_from_names(&all_syms);
 
     Ok(crate::symbols::SymbolSummary {
         imports_count: imports.len() as u32,
@@ -109,7 +114,7 @@ fn list_symbols_py(
         export_names: Some(exports),
         demangled_import_names: None,
         demangled_export_names: None,
-        stripped: false, // TODO: detect this
+        stripped,
         tls_used: false, // TODO: detect this
         tls_callback_count: None,
         tls_callback_vas: None,
@@ -125,6 +130,7 @@ fn list_symbols_py(
         relocations_present: None,
         rpaths: None,
         runpaths: None,
+        wx_sections: None,
     })
 }
 
@@ -144,6 +150,7 @@ fn list_symbols_demangled_py(
     // Call the actual function and convert tuple to SymbolSummary
     let (all_syms, dyn_syms, imports, exports, libs) =
         crate::symbols::list_symbols_demangled_py(path, max_read_bytes, max_file_size)?;
+    let stripped = crate::symbols::SymbolSummary::stripped_from_names(&all_syms);
 
     Ok(crate::symbols::SymbolSummary {
         imports_count: imports.len() as u32,
@@ -153,8 +160,8 @@ fn list_symbols_demangled_py(
         export_names: Some(exports),
         demangled_import_names: Some(all_syms), // Use demangled versions
         demangled_export_names: Some(dyn_syms), // Use demangled versions
-        stripped: false,                        // TODO: detect this
-        tls_used: false,                        // TODO: detect this
+        stripped,
+        tls_used: false, // TODO: detect this
         tls_callback_count: None,
         tls_callback_vas: None,
         debug_info_present: false, // TODO: detect this
@@ -169,9 +176,25 @@ fn list_symbols_demangled_py(
         relocations_present: None,
         rpaths: None,
         runpaths: None,
+        wx_sections: None,
     })
 }
 
+/// List symbols with type (function/object/...), binding, size and section.
+#[pyfunction]
+#[pyo3(name = "list_symbols_detailed")]
+#[pyo3(signature = (path, max_read_bytes=10_485_760, max_file_size=104_857_600))]
+fn list_symbols_detailed_py(
+    path: String,
+    max_read_bytes: u64,
+    max_file_size: u64,
+) -> PyResult<Vec<crate::symbols::SymbolInfo>> {
+    let limit = std::cmp::min(max_read_bytes, max_file_size);
+    let data = crate::triage::io::IOUtils::read_file_with_limit(&path, limit)
+        .map_err(|e| pyo3::exceptions::PyIOError::new_err(format!("{:?}", e)))?;
+    Ok(crate::symbols::list_symbol_infos(&data))
+}
+
 /// Calculate imphash from a file.
 #[pyfunction]
 #[pyo3(name = "imphash")]
@@ -14,6 +14,7 @@ pub fn register_triage_bindings(py: Python<'_>, m: &Bound<'_, PyModule>) -> PyRe
     triage.add_class::<crate::core::triage::SnifferSource>()?;
     triage.add_class::<crate::core::triage::TriageHint>()?;
     triage.add_class::<crate::core::triage::TriageErrorKind>()?;
+    triage.add_class::<crate::core::triage::Severity>()?;
     triage.add_class::<crate::core::triage::TriageError>()?;
     triage.add_class::<crate::core::triage::ConfidenceSignal>()?;
     triage.add_class::<crate::core::triage::ParserKind>()?;
@@ -23,9 +24,11 @@ pub fn register_triage_bindings(py: Python<'_>, m: &Bound<'_, PyModule>) -> PyRe
     triage.add_class::<crate::core::triage::EntropyClass>()?;
     triage.add_class::<crate::core::triage::PackedIndicators>()?;
     triage.add_class::<crate::core::triage::EntropyAnomaly>()?;
+    triage.add_class::<crate::core::triage::EntropyOutlier>()?;
     triage.add_class::<crate::core::triage::DetectedString>()?;
     triage.add_class::<crate::core::triage::StringsSummary>()?;
     triage.add_class::<crate::core::triage::IocSample>()?;
+    triage.add_class::<crate::core::triage::UrlComponents>()?;
     triage.add_class::<crate::symbols::SymbolSummary>()?;
     triage.add_class::<crate::core::triage::SimilaritySummary>()?;
     triage.add_class::<crate::triage::signing::SigningSummary>()?;
@@ -33,16 +36,25 @@ pub fn register_triage_bindings(py: Python<'_>, m: &Bound<'_, PyModule>) -> PyRe
     triage.add_class::<crate::core::triage::ContainerChild>()?;
     triage.add_class::<crate::core::triage::ContainerMetadata>()?;
     triage.add_class::<crate::triage::recurse::RecursionSummary>()?;
+    triage.add_class::<crate::triage::indicators::Indicators>()?;
+
+    // Source language / compiler detection
+    triage.add_class::<crate::triage::compiler_detection::SourceLanguage>()?;
+    triage.add_class::<crate::triage::compiler_detection::CompilerVendor>()?;
+    triage.add_class::<crate::triage::compiler_detection::CompilerInfo>()?;
+    triage.add_class::<crate::triage::compiler_detection::LanguageDetectionResult>()?;
 
     // Overlay analysis classes
     triage.add_class::<crate::triage::overlay::OverlayAnalysis>()?;
     triage.add_class::<crate::triage::overlay::OverlayFormat>()?;
     triage.add_class::<crate::core::triage::Budgets>()?;
+    triage.add_class::<crate::core::triage::PhaseTimings>()?;
     triage.add_class::<crate::core::triage::TriageVerdict>()?;
     triage.add_class::<crate::core::triage::TriagedArtifact>()?;
 
     // Triage configuration classes
     triage.add_class::<crate::triage::config::TriageConfig>()?;
+    triage.add_class::<crate::triage::config::InputKind>()?;
     triage.add_class::<crate::triage::config::IOConfig>()?;
     triage.add_class::<crate::triage::config::EntropyConfig>()?;
     triage.add_class::<crate::triage::config::EntropyThresholds>()?;
@@ -53,6 +65,8 @@ pub fn register_triage_bindings(py: Python<'_>, m: &Bound<'_, PyModule>) -> PyRe
     triage.add_class::<crate::triage::config::SimilarityConfig>()?;
     triage.add_class::<crate::triage::config::HeaderConfig>()?;
     triage.add_class::<crate::triage::config::ParserConfig>()?;
+    triage.add_class::<crate::triage::config::SnifferConfig>()?;
+    triage.add_class::<crate::triage::config::OverlayConfig>()?;
 
     // Triage API functions
     triage.add_function(wrap_pyfunction!(
@@ -71,6 +85,12 @@ pub fn register_triage_bindings(py: Python<'_>, m: &Bound<'_, PyModule>) -> PyRe
         &triage
     )?)?;
 
+    // Standalone format detection
+    triage.add_function(wrap_pyfunction!(
+        crate::triage::format_detection::detect_format_py,
+        &triage
+    )?)?;
+
     // Entropy convenience functions
     triage.add_function(wrap_pyfunction!(
         crate::triage::entropy::entropy_of_bytes_py,
@@ -84,6 +104,10 @@ pub fn register_triage_bindings(py: Python<'_>, m: &Bound<'_, PyModule>) -> PyRe
         crate::triage::entropy::analyze_entropy_bytes_py,
         &triage
     )?)?;
+    triage.add_function(wrap_pyfunction!(
+        crate::triage::entropy::compare_entropy_profiles_py,
+        &triage
+    )?)?;
 
     // Language detection helper for debugging
     triage.add_function(wrap_pyfunction!(language_detection_py, &triage)?)?;
@@ -0,0 +1,176 @@
+//! Function-level fuzzy hashing for code-reuse detection.
+//!
+//! Whole-file CTPH drifts as soon as a shared component is linked at a
+//! different address or next to different code. Here each function's basic
+//! blocks are disassembled and normalized — immediates, displacements and
+//! branch targets masked, registers and mnemonics kept — and the resulting
+//! text is CTPH-hashed, so the same routine in two samples hashes alike
+//! wherever it was placed.
+
+use crate::analysis::memory::MemoryView;
+use crate::core::address::Address;
+use crate::core::basic_block::BasicBlock;
+use crate::core::binary::{Arch, Endianness};
+use crate::core::disassembler::{Architecture, Disassembler};
+use crate::core::function::Function;
+use crate::core::instruction::{Instruction, OperandKind};
+use crate::disasm::registry::{self, Backend};
+use crate::similarity::{ctph_hash, CtphConfig};
+
+/// Upper bound on bytes decoded per basic block.
+const MAX_BLOCK_BYTES: u64 = 64 * 1024;
+
+/// `(function name, CTPH digest)` for every function in `functions` whose
+/// blocks decode to at least one instruction. Compare digests with
+/// [`ctph_similarity`](crate::similarity::ctph_similarity).
+pub fn function_hashes(
+    view: &dyn MemoryView,
+    functions: &[Function],
+    arch: Arch,
+    endianness: Endianness,
+) -> Vec<(String, String)> {
+    let Some(backend) = backend_for(arch, endianness) else {
+        return Vec::new();
+    };
+    let cfg = CtphConfig::default();
+    functions
+        .iter()
+        .filter_map(|f| {
+            let mut blocks: Vec<&BasicBlock> = f.basic_blocks.iter().collect();
+            blocks.sort_by_key(|b| b.start_address.value);
+            let lines: Vec<String> = blocks
+                .iter()
+                .flat_map(|b| normalized_block(view, b, &backend))
+                .collect();
+            if lines.is_empty() {
+                return None;
+            }
+            Some((f.name.clone(), ctph_hash(lines.join("\n").as_bytes(), &cfg)))
+        })
+        .collect()
+}
+
+/// Decoder configured the way function discovery decodes `arch`.
+pub(crate) fn backend_for(arch: Arch, endianness: Endianness) -> Option<Backend> {
+    let darch: Architecture = arch.into();
+    let mut backend = registry::for_arch(darch, endianness)?;
+    // Match function discovery, which decodes ARM32 as Thumb-2.
+    if matches!(arch, Arch::ARM) {
+        let _ = backend.set_thumb_mode(true);
+    }
+    Some(backend)
+}
+
+/// Normalized text of each instruction in `block`, in address order.
+/// Decoding stops at the first undecodable instruction.
+pub fn normalized_block(
+    view: &dyn MemoryView,
+    block: &BasicBlock,
+    backend: &Backend,
+) -> Vec<String> {
+    let start = &block.start_address;
+    let len = block
+        .end_address
+        .value
+        .saturating_sub(start.value)
+        .min(MAX_BLOCK_BYTES) as usize;
+    let Ok(bytes) = view.read_bytes(start, len) else {
+        return Vec::new();
+    };
+    let mut out = Vec::new();
+    let mut off = 0usize;
+    while off < bytes.len() {
+        let Ok(addr) = Address::new(start.kind, start.value + off as u64, start.bits, None, None)
+        else {
+            break;
+        };
+        let Ok(ins) = backend.disassemble_instruction(&addr, &bytes[off..]) else {
+            break;
+        };
+        if ins.length == 0 {
+            break;
+        }
+        out.push(normalize_instruction(&ins));
+        off += ins.length as usize;
+    }
+    out
+}
+
+/// Mnemonic and operands of `ins` with every constant masked: immediates,
+/// relative targets and displacements become `imm`, memory operands keep
+/// only their base/index registers and scale.
+pub fn normalize_instruction(ins: &Instruction) -> String {
+    let mut out = ins.mnemonic.to_ascii_lowercase();
+    for (i, op) in ins.operands.iter().enumerate() {
+        out.push_str(if i == 0 { " " } else { "," });
+        match op.kind {
+            OperandKind::Register => {
+                out.push_str(&op.register.as_deref().unwrap_or("reg").to_ascii_lowercase())
+            }
+            OperandKind::Memory => {
+                out.push('[');
+                out.push_str(&op.base.as_deref().unwrap_or("").to_ascii_lowercase());
+                if let Some(index) = &op.index {
+                    out.push('+');
+                    out.push_str(&index.to_ascii_lowercase());
+                    out.push_str(&format!("*{}", op.scale.unwrap_or(1)));
+                }
+                out.push(']');
+            }
+            OperandKind::Immediate | OperandKind::Displacement | OperandKind::Relative => {
+                out.push_str("imm")
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::memory::SliceMemoryView;
+    use crate::core::address::AddressKind;
+    use crate::core::function::FunctionKind;
+
+    fn function_over(code: &[u8]) -> Function {
+        let at = |v: u64| Address::new(AddressKind::FileOffset, v, 64, None, None).unwrap();
+        let mut f = Function::new("f".into(), at(0), FunctionKind::Normal).unwrap();
+        f.basic_blocks.push(BasicBlock::new(
+            "bb0".into(),
+            at(0),
+            at(code.len() as u64),
+            3,
+            None,
+            None,
+        ));
+        f
+    }
+
+    fn hash(code: &[u8]) -> String {
+        let view = SliceMemoryView::new(code);
+        let hashes = function_hashes(
+            &view,
+            &[function_over(code)],
+            Arch::X86_64,
+            Endianness::Little,
+        );
+        assert_eq!(hashes.len(), 1);
+        hashes[0].1.clone()
+    }
+
+    #[test]
+    fn hashes_ignore_constants_but_not_operations() {
+        // mov eax, 0x10; add eax, ebx; ret
+        let a = hash(&[0xb8, 0x10, 0x00, 0x00, 0x00, 0x01, 0xd8, 0xc3]);
+        // mov eax, 0x99; add eax, ebx; ret
+        let b = hash(&[0xb8, 0x99, 0x00, 0x00, 0x00, 0x01, 0xd8, 0xc3]);
+        // mov eax, 0x10; sub eax, ebx; ret
+        let c = hash(&[0xb8, 0x10, 0x00, 0x00, 0x00, 0x29, 0xd8, 0xc3]);
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+
+        let view = SliceMemoryView::new(&[]);
+        let empty = function_over(&[]);
+        assert!(function_hashes(&view, &[empty], Arch::X86_64, Endianness::Little).is_empty());
+    }
+}
@@ -2,7 +2,7 @@
 
 /// Minimal, MIT/Apache-compatible Context-Triggered Piecewise Hashing (CTPH).
 /// This implementation is based on a rolling hash trigger that chunks input into
-/// pieces and emits short BLAKE3-XOF substrings per piece. The final digest is a
+/// pieces and emits short BLAKE3-XOF (or, opt-in, FNV-1a) substrings per piece. The final digest is a
 /// string "<window>:<digest>:<block1>:<block2>:..." suitable for Jaccard-based
 /// comparisons. It avoids GPL encumbrances from ssdeep/sdhash.
 /// Rolling hash functions (8/16/32/64-bit) used by CTPH.
@@ -114,11 +114,41 @@ mod rolling {
     // keep types private to this module; exposed via CTPH API
 }
 
+#[cfg(feature = "disasm")]
+pub mod code;
+pub mod ssdeep;
+
+use crate::io::{IOLimits, SafeReader};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// Hash function applied to each CTPH piece.
+///
+/// Digests produced with different piece hashes share the same
+/// `window:digest_size` prefix but are not comparable: only compare digests
+/// computed with the same variant.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PieceHash {
+    /// BLAKE3 XOF truncated to the precision's output length (the default).
+    #[default]
+    Blake3,
+    /// 64-bit FNV-1a, truncated likewise. Much cheaper per piece; intended for
+    /// bulk dedup where speed matters more than collision resistance.
+    Fnv1a,
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct CtphConfig {
     pub window_size: usize,
     pub digest_size: usize,
     pub precision: u8, // 8,16,32,64
+    pub piece_hash: PieceHash,
+    /// Size-normalized mode: when set, the trigger modulus doubles from
+    /// `digest_size` until about this many pieces are expected, so inputs of
+    /// different sizes produce a comparable number of blocks (cf. ssdeep's
+    /// blocksize). The modulus is recorded in the digest (`8:4b256:...`) and
+    /// only digests with the same modulus compare as similar.
+    pub target_pieces: Option<usize>,
 }
 impl Default for CtphConfig {
     fn default() -> Self {
@@ -126,31 +156,86 @@ impl Default for CtphConfig {
             window_size: 8,
             digest_size: 4,
             precision: 8,
+            piece_hash: PieceHash::Blake3,
+            target_pieces: None,
         }
     }
 }
 
-fn hash_piece(bytes: &[u8], out_len: usize) -> String {
-    let mut hasher = blake3::Hasher::new();
-    hasher.update(bytes);
-    let mut out = vec![0u8; out_len];
-    hasher.finalize_xof().fill(&mut out);
-    hex::encode(out)
+fn hash_piece(bytes: &[u8], out_len: usize, kind: PieceHash) -> String {
+    match kind {
+        PieceHash::Blake3 => {
+            let mut hasher = blake3::Hasher::new();
+            hasher.update(bytes);
+            let mut out = vec![0u8; out_len];
+            hasher.finalize_xof().fill(&mut out);
+            hex::encode(out)
+        }
+        PieceHash::Fnv1a => {
+            let mut h: u64 = 0xcbf2_9ce4_8422_2325;
+            for &b in bytes {
+                h ^= b as u64;
+                h = h.wrapping_mul(0x0100_0000_01b3);
+            }
+            hex::encode(&h.to_be_bytes()[..out_len.min(8)])
+        }
+    }
+}
+
+/// Trigger modulus and forced-cut length for an input of `data_len` bytes.
+fn piece_params(cfg: &CtphConfig, data_len: usize) -> (u64, usize) {
+    let base = cfg.digest_size as u64;
+    let forced = 64 * cfg.window_size;
+    let Some(target) = cfg.target_pieces.filter(|&t| t > 0) else {
+        return (base, forced);
+    };
+    // The rolling hash never exceeds its precision, so neither may the modulus
+    let cap = match cfg.precision {
+        16 => 1u64 << 16,
+        32 => 1u64 << 32,
+        64 => u64::MAX,
+        _ => 1u64 << 8,
+    };
+    let mut modulus = base.max(1);
+    while modulus.saturating_mul(target as u64) < data_len as u64 && modulus <= cap / 2 {
+        modulus *= 2;
+    }
+    (modulus, forced.max(4 * modulus as usize))
+}
+
+/// Assemble the final `window:digest_size[bMODULUS]:blocks` digest.
+fn finish_digest(cfg: &CtphConfig, modulus: u64, mut blocks: Vec<String>) -> String {
+    blocks.retain(|b| !b.is_empty());
+    if cfg.target_pieces.is_some() {
+        format!(
+            "{}:{}b{}:{}",
+            cfg.window_size,
+            cfg.digest_size,
+            modulus,
+            blocks.join(":")
+        )
+    } else {
+        format!(
+            "{}:{}:{}",
+            cfg.window_size,
+            cfg.digest_size,
+            blocks.join(":")
+        )
+    }
 }
 
 fn ctph_with8(cfg: &CtphConfig, data: &[u8]) -> String {
     use rolling::RollingHash8;
     let mut rolling = RollingHash8::new(cfg.window_size);
+    let (modulus, max_piece) = piece_params(cfg, data.len());
     let mut blocks: Vec<String> = vec![String::new()];
     let mut cur: Vec<u8> = Vec::new();
     let mut triggers = 0usize;
     for &b in data {
         rolling.update(b);
         cur.push(b);
-        if (rolling.hash() % (cfg.digest_size as u8)) == (cfg.digest_size as u8 - 1)
-            || cur.len() >= 64 * cfg.window_size
-        {
-            let piece = hash_piece(&cur, 1);
+        if (rolling.hash() as u64 % modulus) == modulus - 1 || cur.len() >= max_piece {
+            let piece = hash_piece(&cur, 1, cfg.piece_hash);
             blocks.last_mut().unwrap().push_str(&piece);
             cur.clear();
             triggers += 1;
@@ -160,31 +245,24 @@ fn ctph_with8(cfg: &CtphConfig, data: &[u8]) -> String {
         }
     }
     if !cur.is_empty() {
-        let piece = hash_piece(&cur, 1);
+        let piece = hash_piece(&cur, 1, cfg.piece_hash);
         blocks.last_mut().unwrap().push_str(&piece);
     }
-    blocks.retain(|b| !b.is_empty());
-    format!(
-        "{}:{}:{}",
-        cfg.window_size,
-        cfg.digest_size,
-        blocks.join(":")
-    )
+    finish_digest(cfg, modulus, blocks)
 }
 
 fn ctph_with16(cfg: &CtphConfig, data: &[u8]) -> String {
     use rolling::RollingHash16;
     let mut rolling = RollingHash16::new(cfg.window_size);
+    let (modulus, max_piece) = piece_params(cfg, data.len());
     let mut blocks: Vec<String> = vec![String::new()];
     let mut cur: Vec<u8> = Vec::new();
     let mut triggers = 0usize;
     for &b in data {
         rolling.update(b as u16);
         cur.push(b);
-        if (rolling.hash() % (cfg.digest_size as u16)) == (cfg.digest_size as u16 - 1)
-            || cur.len() >= 64 * cfg.window_size
-        {
-            let piece = hash_piece(&cur, 2);
+        if (rolling.hash() as u64 % modulus) == modulus - 1 || cur.len() >= max_piece {
+            let piece = hash_piece(&cur, 2, cfg.piece_hash);
             blocks.last_mut().unwrap().push_str(&piece);
             cur.clear();
             triggers += 1;
@@ -194,31 +272,24 @@ fn ctph_with16(cfg: &CtphConfig, data: &[u8]) -> String {
         }
     }
     if !cur.is_empty() {
-        let piece = hash_piece(&cur, 2);
+        let piece = hash_piece(&cur, 2, cfg.piece_hash);
         blocks.last_mut().unwrap().push_str(&piece);
     }
-    blocks.retain(|b| !b.is_empty());
-    format!(
-        "{}:{}:{}",
-        cfg.window_size,
-        cfg.digest_size,
-        blocks.join(":")
-    )
+    finish_digest(cfg, modulus, blocks)
 }
 
 fn ctph_with32(cfg: &CtphConfig, data: &[u8]) -> String {
     use rolling::RollingHash32;
     let mut rolling = RollingHash32::new(cfg.window_size);
+    let (modulus, max_piece) = piece_params(cfg, data.len());
     let mut blocks: Vec<String> = vec![String::new()];
     let mut cur: Vec<u8> = Vec::new();
     let mut triggers = 0usize;
     for &b in data {
         rolling.update(b as u32);
         cur.push(b);
-        if (rolling.hash() % (cfg.digest_size as u32)) == (cfg.digest_size as u32 - 1)
-            || cur.len() >= 64 * cfg.window_size
-        {
-            let piece = hash_piece(&cur, 4);
+        if (rolling.hash() as u64 % modulus) == modulus - 1 || cur.len() >= max_piece {
+            let piece = hash_piece(&cur, 4, cfg.piece_hash);
             blocks.last_mut().unwrap().push_str(&piece);
             cur.clear();
             triggers += 1;
@@ -228,31 +299,24 @@ fn ctph_with32(cfg: &CtphConfig, data: &[u8]) -> String {
         }
     }
     if !cur.is_empty() {
-        let piece = hash_piece(&cur, 4);
+        let piece = hash_piece(&cur, 4, cfg.piece_hash);
         blocks.last_mut().unwrap().push_str(&piece);
     }
-    blocks.retain(|b| !b.is_empty());
-    format!(
-        "{}:{}:{}",
-        cfg.window_size,
-        cfg.digest_size,
-        blocks.join(":")
-    )
+    finish_digest(cfg, modulus, blocks)
 }
 
 fn ctph_with64(cfg: &CtphConfig, data: &[u8]) -> String {
     use rolling::RollingHash64;
     let mut rolling = RollingHash64::new(cfg.window_size);
+    let (modulus, max_piece) = piece_params(cfg, data.len());
     let mut blocks: Vec<String> = vec![String::new()];
     let mut cur: Vec<u8> = Vec::new();
     let mut triggers = 0usize;
     for &b in data {
         rolling.update(b as u64);
         cur.push(b);
-        if (rolling.hash() % (cfg.digest_size as u64)) == (cfg.digest_size as u64 - 1)
-            || cur.len() >= 64 * cfg.window_size
-        {
-            let piece = hash_piece(&cur, 4);
+        if rolling.hash() % modulus == modulus - 1 || cur.len() >= max_piece {
+            let piece = hash_piece(&cur, 4, cfg.piece_hash);
             blocks.last_mut().unwrap().push_str(&piece);
             cur.clear();
             triggers += 1;
@@ -262,16 +326,10 @@ fn ctph_with64(cfg: &CtphConfig, data: &[u8]) -> String {
         }
     }
     if !cur.is_empty() {
-        let piece = hash_piece(&cur, 4);
+        let piece = hash_piece(&cur, 4, cfg.piece_hash);
         blocks.last_mut().unwrap().push_str(&piece);
     }
-    blocks.retain(|b| !b.is_empty());
-    format!(
-        "{}:{}:{}",
-        cfg.window_size,
-        cfg.digest_size,
-        blocks.join(":")
-    )
+    finish_digest(cfg, modulus, blocks)
 }
 
 /// Compute CTPH digest for data with the given configuration.
@@ -294,7 +352,6 @@ pub fn ctph_similarity(a: &str, b: &str) -> f64 {
     if at[0] != bt[0] || at[1] != bt[1] {
         return 0.0;
     }
-    use std::collections::HashSet;
     let as_: HashSet<&str> = at[2..].iter().copied().collect();
     let bs: HashSet<&str> = bt[2..].iter().copied().collect();
     let inter = as_.intersection(&bs).count() as f64;
@@ -306,9 +363,158 @@ pub fn ctph_similarity(a: &str, b: &str) -> f64 {
     }
 }
 
+/// Hash two buffers with the same configuration and return their similarity.
+pub fn compare_bytes(a: &[u8], b: &[u8], cfg: &CtphConfig) -> f64 {
+    ctph_similarity(&ctph_hash(a, cfg), &ctph_hash(b, cfg))
+}
+
+/// Read up to `limits.max_read_bytes` of each file via `SafeReader`, hash both
+/// with `cfg` and return their CTPH similarity.
+///
+/// Each file gets its own read budget; files larger than
+/// `limits.max_file_size` are rejected.
+pub fn compare_files(
+    a: &Path,
+    b: &Path,
+    cfg: &CtphConfig,
+    limits: &IOLimits,
+) -> crate::io::error::Result<f64> {
+    let da = read_bounded(a, limits)?;
+    let db = read_bounded(b, limits)?;
+    Ok(compare_bytes(&da, &db, cfg))
+}
+
+fn read_bounded(path: &Path, limits: &IOLimits) -> crate::io::error::Result<bytes::Bytes> {
+    let mut reader = SafeReader::open(path, limits.clone())?;
+    let len = reader.size().min(limits.max_read_bytes);
+    reader.read_prefix(len)
+}
+
+/// Inverted index over CTPH digests for nearest-neighbor queries.
+///
+/// Digests are bucketed by `(window, digest_size, block)`, so a query only
+/// scores entries that share at least one block with it. Entries with no
+/// common block have a Jaccard similarity of zero and are never visited.
+#[derive(Debug, Default, Clone)]
+pub struct CtphIndex {
+    digests: Vec<String>,
+    postings: HashMap<String, Vec<usize>>,
+}
+
+/// Split a digest into its `window:digest_size` prefix and block pieces.
+fn digest_parts(digest: &str) -> Option<(&str, impl Iterator<Item = &str>)> {
+    let mut colons = digest.match_indices(':').map(|(i, _)| i);
+    colons.next()?;
+    let split = colons.next()?;
+    let (prefix, rest) = (&digest[..split], &digest[split + 1..]);
+    Some((prefix, rest.split(':').filter(|b| !b.is_empty())))
+}
+
+impl CtphIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a digest and return its id (insertion order, starting at 0).
+    pub fn insert(&mut self, digest: impl Into<String>) -> usize {
+        let digest = digest.into();
+        let id = self.digests.len();
+        if let Some((prefix, blocks)) = digest_parts(&digest) {
+            let mut seen = HashSet::new();
+            for block in blocks {
+                if seen.insert(block) {
+                    self.postings
+                        .entry(format!("{}:{}", prefix, block))
+                        .or_default()
+                        .push(id);
+                }
+            }
+        }
+        self.digests.push(digest);
+        id
+    }
+
+    /// Number of indexed digests.
+    pub fn len(&self) -> usize {
+        self.digests.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.digests.is_empty()
+    }
+
+    /// Digest stored under `id`.
+    pub fn get(&self, id: usize) -> Option<&str> {
+        self.digests.get(id).map(String::as_str)
+    }
+
+    /// Return up to `top_n` `(id, similarity)` pairs, most similar first.
+    ///
+    /// Ties are broken by id so results are deterministic.
+    pub fn query(&self, digest: &str, top_n: usize) -> Vec<(usize, f64)> {
+        let Some((prefix, blocks)) = digest_parts(digest) else {
+            return Vec::new();
+        };
+        let mut candidates: HashSet<usize> = HashSet::new();
+        for block in blocks {
+            if let Some(ids) = self.postings.get(&format!("{}:{}", prefix, block)) {
+                candidates.extend(ids);
+            }
+        }
+        let mut scored: Vec<(usize, f64)> = candidates
+            .into_iter()
+            .map(|id| (id, ctph_similarity(digest, &self.digests[id])))
+            .filter(|&(_, s)| s > 0.0)
+            .collect();
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        scored.truncate(top_n);
+        scored
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::io::Write;
+
+    fn temp_with(content: &[u8]) -> tempfile::NamedTempFile {
+        let mut f = tempfile::NamedTempFile::new().unwrap();
+        f.write_all(content).unwrap();
+        f
+    }
+
+    #[test]
+    fn test_compare_bytes_identical_is_one() {
+        let data: Vec<u8> = (0..4096u32).map(|i| (i * 31 % 251) as u8).collect();
+        let cfg = CtphConfig::default();
+        assert_eq!(compare_bytes(&data, &data, &cfg), 1.0);
+    }
+
+    #[test]
+    fn test_compare_files_matches_compare_bytes() {
+        let a: Vec<u8> = (0..4096u32).map(|i| (i * 31 % 251) as u8).collect();
+        let mut b = a.clone();
+        b[2000] ^= 0xff;
+        let (fa, fb) = (temp_with(&a), temp_with(&b));
+        let cfg = CtphConfig::default();
+        let s = compare_files(fa.path(), fb.path(), &cfg, &IOLimits::default()).unwrap();
+        assert_eq!(s, compare_bytes(&a, &b, &cfg));
+        assert!(s > 0.0 && s < 1.0);
+    }
+
+    #[test]
+    fn test_compare_files_rejects_oversized() {
+        let fa = temp_with(&[0u8; 64]);
+        let limits = IOLimits {
+            max_file_size: 16,
+            max_read_bytes: 16,
+        };
+        let r = compare_files(fa.path(), fa.path(), &CtphConfig::default(), &limits);
+        assert!(matches!(
+            r,
+            Err(crate::io::error::IoError::FileTooLarge { .. })
+        ));
+    }
 
     #[test]
     fn test_ctph_basic_shape() {
@@ -318,12 +524,78 @@ mod tests {
         assert!(h.contains(':'));
     }
 
+    #[test]
+    fn test_fnv_piece_hash_keeps_shape_but_differs() {
+        let data: Vec<u8> = (0..4096u32).map(|i| (i * 31 % 251) as u8).collect();
+        for precision in [8, 16, 32, 64] {
+            let blake = CtphConfig {
+                precision,
+                ..Default::default()
+            };
+            let fnv = CtphConfig {
+                piece_hash: PieceHash::Fnv1a,
+                ..blake
+            };
+            let (a, b) = (ctph_hash(&data, &blake), ctph_hash(&data, &fnv));
+            assert_eq!(a.len(), b.len());
+            assert_ne!(a, b);
+            assert_eq!(compare_bytes(&data, &data, &fnv), 1.0);
+        }
+        let fnv = CtphConfig {
+            piece_hash: PieceHash::Fnv1a,
+            ..Default::default()
+        };
+        let mut edited = data.clone();
+        edited[2000] ^= 0xff;
+        let s = compare_bytes(&data, &edited, &fnv);
+        assert!(s > 0.0 && s < 1.0);
+    }
+
+    #[test]
+    fn test_target_pieces_normalizes_block_count() {
+        let noise = |len: usize| -> Vec<u8> {
+            let mut x: u64 = 0x9e37_79b9_7f4a_7c15;
+            (0..len)
+                .map(|_| {
+                    x = x
+                        .wrapping_mul(6364136223846793005)
+                        .wrapping_add(1442695040888963407);
+                    (x >> 33) as u8
+                })
+                .collect()
+        };
+        let (small, large) = (noise(16 * 1024), noise(256 * 1024));
+        let blocks = |d: &str| d.split(':').count() - 2;
+
+        let plain = CtphConfig {
+            precision: 32,
+            ..Default::default()
+        };
+        let ratio = blocks(&ctph_hash(&large, &plain)) / blocks(&ctph_hash(&small, &plain));
+        assert!(ratio >= 8);
+
+        let normalized = CtphConfig {
+            target_pieces: Some(64),
+            ..plain
+        };
+        let (a, b) = (
+            ctph_hash(&small, &normalized),
+            ctph_hash(&large, &normalized),
+        );
+        assert!(a.starts_with("8:4b256:"));
+        assert!(b.starts_with("8:4b4096:"));
+        let (na, nb) = (blocks(&a), blocks(&b));
+        assert!(na.max(nb) <= 3 * na.min(nb), "{} vs {}", na, nb);
+        assert_eq!(ctph_similarity(&b, &ctph_hash(&large, &normalized)), 1.0);
+    }
+
     #[test]
     fn test_ctph_similarity_is_symmetric_and_bounded() {
         let cfg = CtphConfig {
             window_size: 8,
             digest_size: 4,
             precision: 16,
+            ..Default::default()
         };
         let a = ctph_hash(b"AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA", &cfg);
         let b = ctph_hash(b"AAAAAAABAAAAAAAAAAAAAAAAAAAAAAAAAAA", &cfg);
@@ -332,4 +604,37 @@ mod tests {
         assert!((s1 - s2).abs() < 1e-6);
         assert!(s1 >= 0.0 && s1 <= 1.0);
     }
+
+    #[test]
+    fn test_ctph_index_matches_linear_scan() {
+        let cfg = CtphConfig::default();
+        let base: Vec<u8> = (0..8192u32).map(|i| (i * 7 % 253) as u8).collect();
+        let mut index = CtphIndex::new();
+        let mut digests = Vec::new();
+        for k in 0..8usize {
+            let mut v = base.clone();
+            for j in 0..k * 400 {
+                v[j] ^= 0x5a;
+            }
+            let d = ctph_hash(&v, &cfg);
+            index.insert(d.clone());
+            digests.push(d);
+        }
+        // Unrelated content and a different config never surface.
+        index.insert(ctph_hash(&[0xAAu8; 8192], &cfg));
+        index.insert("16:5:deadbeef");
+
+        let hits = index.query(&digests[0], 3);
+        assert_eq!(hits[0], (0, 1.0));
+        let mut linear: Vec<(usize, f64)> = digests
+            .iter()
+            .enumerate()
+            .map(|(i, d)| (i, ctph_similarity(&digests[0], d)))
+            .filter(|&(_, s)| s > 0.0)
+            .collect();
+        linear.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        linear.truncate(3);
+        assert_eq!(hits, linear);
+        assert!(index.query("garbage", 5).is_empty());
+    }
 }
@@ -0,0 +1,168 @@
+//! Comparison of externally computed ssdeep digests.
+//!
+//! Only the scoring side of ssdeep is implemented — no digest generation —
+//! so corpora that already store ssdeep hashes can be matched without
+//! pulling in the GPL implementation. Digests have the form
+//! `blocksize:sig1:sig2[,"filename"]`, where `sig2` was computed at twice the
+//! block size. Two digests are comparable when their block sizes are equal
+//! or differ by a factor of two.
+
+const SPAMSUM_LENGTH: u32 = 64;
+const ROLLING_WINDOW: usize = 7;
+const MIN_BLOCKSIZE: u32 = 3;
+
+/// A parsed ssdeep digest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SsdeepDigest {
+    pub block_size: u32,
+    /// Signature at `block_size`.
+    pub sig1: String,
+    /// Signature at `2 * block_size`.
+    pub sig2: String,
+}
+
+impl SsdeepDigest {
+    /// Parse `blocksize:sig1:sig2`, ignoring a trailing `,"filename"`.
+    pub fn parse(digest: &str) -> Option<Self> {
+        let digest = digest.split(',').next()?.trim();
+        let mut parts = digest.splitn(3, ':');
+        let block_size: u32 = parts.next()?.parse().ok()?;
+        let sig1 = parts.next()?;
+        let sig2 = parts.next()?;
+        if block_size == 0 {
+            return None;
+        }
+        Some(Self {
+            block_size,
+            sig1: collapse_runs(sig1),
+            sig2: collapse_runs(sig2),
+        })
+    }
+}
+
+/// Compare two ssdeep digests, returning ssdeep's 0-100 match score scaled
+/// to `0.0..=1.0` (like [`ctph_similarity`](super::ctph_similarity)).
+///
+/// Malformed digests and incompatible block sizes score 0.
+pub fn compare(a: &str, b: &str) -> f64 {
+    match (SsdeepDigest::parse(a), SsdeepDigest::parse(b)) {
+        (Some(a), Some(b)) => compare_digests(&a, &b) as f64 / 100.0,
+        _ => 0.0,
+    }
+}
+
+/// ssdeep's integer score (0-100) for two parsed digests.
+pub fn compare_digests(a: &SsdeepDigest, b: &SsdeepDigest) -> u32 {
+    let (bs1, bs2) = (a.block_size, b.block_size);
+    if bs1 == bs2 && a.sig1 == b.sig1 && a.sig2 == b.sig2 {
+        return 100;
+    }
+    if bs1 == bs2 {
+        score_strings(&a.sig1, &b.sig1, bs1).max(score_strings(
+            &a.sig2,
+            &b.sig2,
+            bs1.saturating_mul(2),
+        ))
+    } else if bs1 == bs2.saturating_mul(2) {
+        score_strings(&a.sig1, &b.sig2, bs1)
+    } else if bs2 == bs1.saturating_mul(2) {
+        score_strings(&a.sig2, &b.sig1, bs2)
+    } else {
+        0
+    }
+}
+
+/// Limit runs of identical characters to three; they carry little
+/// information and would otherwise dominate the edit distance.
+fn collapse_runs(sig: &str) -> String {
+    let mut out = String::with_capacity(sig.len());
+    let mut run = 0;
+    let mut prev = None;
+    for c in sig.chars() {
+        run = if prev == Some(c) { run + 1 } else { 1 };
+        prev = Some(c);
+        if run <= 3 {
+            out.push(c);
+        }
+    }
+    out
+}
+
+fn score_strings(s1: &str, s2: &str, block_size: u32) -> u32 {
+    let (a, b) = (s1.as_bytes(), s2.as_bytes());
+    if a.len() > SPAMSUM_LENGTH as usize
+        || b.len() > SPAMSUM_LENGTH as usize
+        || !has_common_substring(a, b)
+    {
+        return 0;
+    }
+    let (len1, len2) = (a.len() as u32, b.len() as u32);
+    let mut score = edit_distance(a, b) * SPAMSUM_LENGTH / (len1 + len2);
+    score = 100 * score / SPAMSUM_LENGTH;
+    if score >= 100 {
+        return 0;
+    }
+    score = 100 - score;
+    // Small block sizes cannot vouch for a large match
+    let small = (99 + ROLLING_WINDOW as u32) / ROLLING_WINDOW as u32 * MIN_BLOCKSIZE;
+    if block_size < small {
+        score = score.min(block_size / MIN_BLOCKSIZE * len1.min(len2));
+    }
+    score
+}
+
+/// True when the strings share a substring of `ROLLING_WINDOW` bytes, the
+/// precondition for ssdeep to score a pair at all.
+fn has_common_substring(a: &[u8], b: &[u8]) -> bool {
+    if a.len() < ROLLING_WINDOW || b.len() < ROLLING_WINDOW {
+        return false;
+    }
+    a.windows(ROLLING_WINDOW)
+        .any(|w| b.windows(ROLLING_WINDOW).any(|v| v == w))
+}
+
+/// Edit distance with ssdeep's weights: insert/delete 1, substitute 2.
+fn edit_distance(a: &[u8], b: &[u8]) -> u32 {
+    let mut prev: Vec<u32> = (0..=b.len() as u32).collect();
+    let mut cur = vec![0u32; b.len() + 1];
+    for (i, &ca) in a.iter().enumerate() {
+        cur[0] = i as u32 + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let replace = prev[j] + if ca == cb { 0 } else { 2 };
+            cur[j + 1] = replace.min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    prev[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scores_follow_ssdeep_rules() {
+        let a = "48:abcdefghij:xyz";
+        assert_eq!(compare(a, a), 1.0);
+        // One substitution in ten characters: 100 - (100 * (2 * 64 / 20) / 64)
+        assert_eq!(compare(a, "48:abcdefghik:xyz"), 0.91);
+        // Small block sizes cap the score by signature length
+        assert_eq!(compare("3:abcdefghij:xyz", "3:abcdefghik:xyz"), 0.10);
+        // sig2 of the smaller block size is compared with sig1 of the larger
+        assert_eq!(
+            compare("96:abcdefghij:q", "48:zz:abcdefghij,\"f.bin\""),
+            1.0
+        );
+        assert_eq!(compare("96:abcdefghij:q", "24:abcdefghij:abcdefghij"), 0.0);
+        // No common 7-character substring
+        assert_eq!(compare(a, "48:abcdefxyzw:xyz"), 0.0);
+        assert_eq!(compare(a, "not a digest"), 0.0);
+    }
+
+    #[test]
+    fn long_runs_are_collapsed() {
+        let d = SsdeepDigest::parse("12:aaaaaabcd:eeee").unwrap();
+        assert_eq!((d.sig1.as_str(), d.sig2.as_str()), ("aaabcd", "eee"));
+        assert_eq!(edit_distance(b"kitten", b"sitting"), 5);
+    }
+}
@@ -1,11 +1,20 @@
 //! Configuration for bounded string extraction and detection.
 
+use std::sync::Arc;
+
 #[derive(Debug, Clone)]
 pub struct StringsConfig {
     /// Minimum length for a string candidate (in characters)
     pub min_length: usize,
     /// Maximum number of sampled strings to include in the summary
     pub max_samples: usize,
+    /// Maximum length (in characters) of a sampled string; longer strings are
+    /// truncated and flagged. 0 disables truncation.
+    pub max_string_length: usize,
+    /// Collapse identical strings (per encoding) into one sample with a count
+    pub dedup_strings: bool,
+    /// Keep only sampled strings matching at least one pattern (None keeps all)
+    pub include_only: Option<Vec<regex::Regex>>,
     /// Maximum number of bytes scanned from input
     pub max_scan_bytes: usize,
     /// Time guard for scanning/detection (milliseconds)
@@ -14,6 +23,10 @@ pub struct StringsConfig {
     pub enable_language: bool,
     /// Maximum number of strings to run language detection on
     pub max_lang_detect: usize,
+    /// Hand out the `max_lang_detect` budget in sample order before
+    /// detecting in parallel, so the same input always annotates the same
+    /// strings; otherwise parallel workers race for it
+    pub ordered_lang_budget: bool,
     /// Minimum string length required to attempt language detection
     pub min_len_for_detect: usize,
     /// Maximum string length (in characters) to use lingua; longer strings use whatlang
@@ -34,6 +47,16 @@ pub struct StringsConfig {
     pub max_ioc_per_string: usize,
     /// Maximum number of IOC match samples to include in summary
     pub max_ioc_samples: usize,
+    /// Maximum length (in characters) of an IOC sample's text; longer matches
+    /// are truncated and flagged. 0 disables truncation.
+    pub ioc_sample_max_len: usize,
+    /// Parse URL IOC samples into scheme, host, port and path
+    pub parse_url_components: bool,
+    /// Emit network IOC samples (URLs, emails, hosts, domains, IPv4) and
+    /// parsed URL hosts defanged (`hxxp://`, `[.]`); counts are unaffected
+    pub defang_iocs: bool,
+    /// Pool for parallel language detection; `None` uses rayon's global pool
+    pub thread_pool: Option<Arc<rayon::ThreadPool>>,
 }
 
 impl Default for StringsConfig {
@@ -41,10 +64,14 @@ impl Default for StringsConfig {
         Self {
             min_length: 4,
             max_samples: 40,
+            max_string_length: 4096,
+            dedup_strings: false,
+            include_only: None,
             max_scan_bytes: 1_048_576, // 1 MiB
             time_guard_ms: 10,
             enable_language: true,
             max_lang_detect: 100,
+            ordered_lang_budget: false,
             min_len_for_detect: 4,
             max_len_for_lingua: 32,
             min_lang_confidence: 0.65,
@@ -55,6 +82,100 @@ impl Default for StringsConfig {
             max_classify: 200,
             max_ioc_per_string: 16,
             max_ioc_samples: 50,
+            ioc_sample_max_len: 512,
+            parse_url_components: false,
+            defang_iocs: false,
+            thread_pool: None,
+        }
+    }
+}
+
+impl StringsConfig {
+    /// String candidates the scanner keeps per encoding. Deduplication and
+    /// the `include_only` allowlist choose from every string in the scan
+    /// window, so `max_samples` is applied after them rather than here.
+    pub(crate) fn scan_sample_cap(&self) -> usize {
+        if self.dedup_strings || self.include_only.is_some() {
+            usize::MAX
+        } else {
+            self.max_samples
+        }
+    }
+
+    /// Quick triage: counts and a handful of samples, nothing expensive.
+    ///
+    /// Scans at most 256 KiB, keeps 20 samples, and turns off language
+    /// detection and IOC classification.
+    pub fn fast() -> Self {
+        Self {
+            max_samples: 20,
+            max_scan_bytes: 256 * 1024,
+            time_guard_ms: 5,
+            enable_language: false,
+            max_lang_detect: 0,
+            enable_classification: false,
+            max_classify: 0,
+            max_ioc_samples: 0,
+            ..Self::default()
         }
     }
+
+    /// Deep hunt: larger scan window and budgets, with every analysis on.
+    ///
+    /// Scans up to 16 MiB with a 250 ms guard, keeps 500 deduplicated
+    /// samples, runs both language engines (`use_fast_detection = false`)
+    /// on up to 1000 strings, and classifies up to 5000 strings for IOCs.
+    pub fn thorough() -> Self {
+        Self {
+            min_length: 5,
+            max_samples: 500,
+            dedup_strings: true,
+            max_scan_bytes: 16 * 1024 * 1024,
+            time_guard_ms: 250,
+            max_lang_detect: 1000,
+            use_fast_detection: false,
+            max_classify: 5000,
+            max_ioc_per_string: 32,
+            max_ioc_samples: 500,
+            ..Self::default()
+        }
+    }
+
+    /// IOC extraction: classification and samples only.
+    ///
+    /// Language detection is off; strings are deduplicated and the IOC
+    /// budgets are raised (2000 classified strings, 200 samples) over a
+    /// 4 MiB scan window.
+    pub fn ioc_focused() -> Self {
+        Self {
+            max_samples: 100,
+            dedup_strings: true,
+            max_scan_bytes: 4 * 1024 * 1024,
+            time_guard_ms: 50,
+            enable_language: false,
+            max_lang_detect: 0,
+            max_classify: 2000,
+            max_ioc_samples: 200,
+            ..Self::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_toggle_expected_analyses() {
+        let fast = StringsConfig::fast();
+        assert!(!fast.enable_language && !fast.enable_classification);
+
+        let thorough = StringsConfig::thorough();
+        assert!(thorough.enable_language && thorough.enable_classification);
+        assert!(thorough.max_scan_bytes > StringsConfig::default().max_scan_bytes);
+
+        let ioc = StringsConfig::ioc_focused();
+        assert!(!ioc.enable_language && ioc.enable_classification);
+        assert!(ioc.max_ioc_samples > StringsConfig::default().max_ioc_samples);
+    }
 }
@@ -4,6 +4,7 @@ use once_cell::sync::Lazy;
 use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
 use std::sync::Mutex;
+#[cfg(feature = "lang-detect")]
 use whatlang::{detect, Lang, Script};
 
 // Optional lingua detector for short texts
@@ -174,11 +175,13 @@ impl LanguageEngine for LinguaEngine {
 }
 
 /// Convert whatlang Lang to string representation (lowercase debug name)
+#[cfg(feature = "lang-detect")]
 fn lang_to_string(lang: Lang) -> String {
     format!("{:?}", lang).to_lowercase()
 }
 
 /// Convert whatlang Script to string representation
+#[cfg(feature = "lang-detect")]
 fn script_to_string(script: Script) -> String {
     format!("{:?}", script)
 }
@@ -358,6 +361,7 @@ pub fn is_texty_for_lang_with_policy(s: &str, strict: bool) -> bool {
 }
 
 /// Direct whatlang detection (no thresholds). Returns (iso639_3_lower, script, confidence).
+#[cfg(feature = "lang-detect")]
 pub fn detect_with_whatlang(text: &str) -> (Option<String>, Option<String>, Option<f64>) {
     match detect(text) {
         Some(info) => {
@@ -370,6 +374,13 @@ pub fn detect_with_whatlang(text: &str) -> (Option<String>, Option<String>, Opti
     }
 }
 
+/// Without the `lang-detect` feature no engine is compiled in; detection
+/// always returns no result.
+#[cfg(not(feature = "lang-detect"))]
+pub fn detect_with_whatlang(_text: &str) -> (Option<String>, Option<String>, Option<f64>) {
+    (None, None, None)
+}
+
 /// Cached whatlang detection
 pub fn detect_with_whatlang_cached(text: &str) -> (Option<String>, Option<String>, Option<f64>) {
     let key = text_hash(text);
@@ -418,6 +429,14 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg(not(feature = "lang-detect"))]
+    fn detection_is_empty_without_engine() {
+        let text = "This is a reasonably long English sentence for detection to work properly.";
+        assert_eq!(detect_string_language(text), (None, None, None));
+    }
+
+    #[test]
+    #[cfg(feature = "lang-detect")]
     fn detects_english_for_long_text() {
         let text = "This is a reasonably long English sentence for detection to work properly.";
         let (lang, script, conf) = detect_string_language(text);
@@ -428,6 +447,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "lang-detect")]
     fn router_detects_with_defaults() {
         let cfg = crate::strings::config::StringsConfig::default();
         let router = LanguageRouter::from_cfg(&cfg);
@@ -17,7 +17,7 @@ pub mod similarity;
 
 pub use config::StringsConfig;
 
-use crate::core::triage::{DetectedString, IocSample, StringsSummary};
+use crate::core::triage::{DetectedString, IocSample, StringsSummary, UrlComponents};
 use crate::strings::detect::LanguageRouter;
 use crate::strings::search::{MatchKind, SearchBudget};
 use rayon::prelude::*;
@@ -25,24 +25,112 @@ use std::collections::{BTreeMap, HashMap};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 
+/// A sampled string prepared for detection.
+struct SampleItem {
+    text: String,
+    offset: usize,
+    truncated: bool,
+    /// Occurrences of `text` when deduplicating (1 otherwise).
+    count: u32,
+}
+
+/// Whether `text` passes the `cfg.include_only` allowlist (if any).
+fn is_included(text: &str, cfg: &StringsConfig) -> bool {
+    match &cfg.include_only {
+        Some(patterns) => patterns.iter().any(|re| re.is_match(text)),
+        None => true,
+    }
+}
+
+/// `text` cut to at most `max` characters (0 = unlimited), and whether it
+/// was cut.
+fn truncate_chars(text: &str, max: usize) -> (String, bool) {
+    match text.char_indices().nth(max) {
+        Some((end, _)) if max > 0 => (text[..end].to_string(), true),
+        _ => (text.to_string(), false),
+    }
+}
+
+/// Select up to `cap` samples from `strings` that pass `cfg.include_only`.
+///
+/// With `cfg.dedup_strings`, repeats collapse into the first occurrence
+/// (keeping its offset) and are counted, so they do not consume the sample
+/// budget. Texts are capped at `cfg.max_string_length` characters
+/// (0 = unlimited).
+fn sample_items(strings: &[(String, usize)], cap: usize, cfg: &StringsConfig) -> Vec<SampleItem> {
+    let truncate = |text: &str| truncate_chars(text, cfg.max_string_length);
+    let mut items: Vec<SampleItem> = Vec::new();
+    let strings = strings.iter().filter(|(text, _)| is_included(text, cfg));
+    if !cfg.dedup_strings {
+        for (text, off) in strings.take(cap) {
+            let (text, truncated) = truncate(text);
+            items.push(SampleItem {
+                text,
+                offset: *off,
+                truncated,
+                count: 1,
+            });
+        }
+        return items;
+    }
+    let mut index: HashMap<&str, usize> = HashMap::new();
+    for (text, off) in strings {
+        if let Some(&i) = index.get(text.as_str()) {
+            items[i].count = items[i].count.saturating_add(1);
+        } else if items.len() < cap {
+            index.insert(text.as_str(), items.len());
+            let (t, truncated) = truncate(text);
+            items.push(SampleItem {
+                text: t,
+                offset: *off,
+                truncated,
+                count: 1,
+            });
+        }
+    }
+    items
+}
+
 /// Process language detection for a batch of strings with parallel/sequential handling
 fn process_language_detection_batch(
-    items: &[(String, usize)],
+    items: &[SampleItem],
     cfg: &StringsConfig,
     router: &LanguageRouter,
     budget: &Arc<AtomicUsize>,
 ) -> Vec<(Option<String>, Option<String>, Option<f64>)> {
     const PAR_THRESHOLD: usize = 128;
 
-    if items.len() >= PAR_THRESHOLD {
+    // An ordered budget is claimed up front, in sample order, so which
+    // strings get annotated does not depend on thread scheduling.
+    let reserved: Option<Vec<bool>> = cfg.ordered_lang_budget.then(|| {
         items
-            .par_iter()
-            .map(|(text, _off)| detect_language_for_text(text, cfg, router, budget))
+            .iter()
+            .map(|item| claim_lang_budget(&item.text, cfg, budget))
             .collect()
+    });
+    let detect = |i: usize, item: &SampleItem| match &reserved {
+        Some(r) if r[i] => router.detect(&item.text).tuple(),
+        Some(_) => (None, None, None),
+        None => detect_language_for_text(&item.text, cfg, router, budget),
+    };
+
+    if items.len() >= PAR_THRESHOLD {
+        let run = || {
+            items
+                .par_iter()
+                .enumerate()
+                .map(|(i, item)| detect(i, item))
+                .collect()
+        };
+        match &cfg.thread_pool {
+            Some(pool) => pool.install(run),
+            None => run(),
+        }
     } else {
         items
             .iter()
-            .map(|(text, _off)| detect_language_for_text(text, cfg, router, budget))
+            .enumerate()
+            .map(|(i, item)| detect(i, item))
             .collect()
     }
 }
@@ -54,39 +142,43 @@ fn detect_language_for_text(
     router: &LanguageRouter,
     budget: &Arc<AtomicUsize>,
 ) -> (Option<String>, Option<String>, Option<f64>) {
-    if cfg.enable_language
+    if claim_lang_budget(text, cfg, budget) {
+        router.detect(text).tuple()
+    } else {
+        (None, None, None)
+    }
+}
+
+/// Take one unit of the detection budget if `text` is eligible for
+/// language detection.
+fn claim_lang_budget(text: &str, cfg: &StringsConfig, budget: &AtomicUsize) -> bool {
+    // Without an engine compiled in, skip detection (and its budget) entirely.
+    if !(cfg!(feature = "lang-detect")
+        && cfg.enable_language
         && budget.load(Ordering::Relaxed) > 0
         && text.len() >= cfg.min_len_for_detect
-        && detect::is_texty_for_lang_with_policy(text, cfg.texty_strict)
+        && detect::is_texty_for_lang_with_policy(text, cfg.texty_strict))
     {
-        let mut ok = false;
-        loop {
-            let cur = budget.load(Ordering::Relaxed);
-            if cur == 0 {
-                break;
-            }
-            if budget
-                .compare_exchange_weak(cur, cur - 1, Ordering::SeqCst, Ordering::Relaxed)
-                .is_ok()
-            {
-                ok = true;
-                break;
-            }
+        return false;
+    }
+    loop {
+        let cur = budget.load(Ordering::Relaxed);
+        if cur == 0 {
+            return false;
         }
-        if ok {
-            router.detect(text).tuple()
-        } else {
-            (None, None, None)
+        if budget
+            .compare_exchange_weak(cur, cur - 1, Ordering::SeqCst, Ordering::Relaxed)
+            .is_ok()
+        {
+            return true;
         }
-    } else {
-        (None, None, None)
     }
 }
 
 /// Build detected strings from text items and language detection results
 fn build_detected_strings_batch(
     label: &str,
-    items: &[(String, usize)],
+    items: &[SampleItem],
     results: Vec<(Option<String>, Option<String>, Option<f64>)>,
 ) -> (
     Vec<DetectedString>,
@@ -97,21 +189,25 @@ fn build_detected_strings_batch(
     let mut lang_local: HashMap<String, u32> = HashMap::new();
     let mut script_local: HashMap<String, u32> = HashMap::new();
 
-    for ((text, off), (language, script, confidence)) in items.iter().zip(results.into_iter()) {
+    for (item, (language, script, confidence)) in items.iter().zip(results) {
         if let Some(ref l) = language {
             *lang_local.entry(l.clone()).or_insert(0) += 1;
         }
         if let Some(ref s) = script {
             *script_local.entry(s.clone()).or_insert(0) += 1;
         }
-        batch_ds.push(DetectedString::new(
-            text.clone(),
-            label.to_string(),
-            language,
-            script,
-            confidence,
-            Some(*off as u64),
-        ));
+        batch_ds.push(
+            DetectedString::new(
+                item.text.clone(),
+                label.to_string(),
+                language,
+                script,
+                confidence,
+                Some(item.offset as u64),
+            )
+            .with_truncated(item.truncated)
+            .with_count(item.count),
+        );
     }
     (batch_ds, lang_local, script_local)
 }
@@ -123,19 +219,19 @@ fn classify_iocs(
     cfg: &StringsConfig,
 ) -> (Option<BTreeMap<String, u32>>, Option<Vec<IocSample>>) {
     let mut texts: Vec<&str> = Vec::new();
-    for (t, _) in &scanned.ascii_strings {
+    for (t, _) in scanned.ascii_strings.iter().take(cfg.max_samples) {
         if texts.len() >= cfg.max_classify {
             break;
         }
         texts.push(t);
     }
-    for (t, _) in &scanned.utf16le_strings {
+    for (t, _) in scanned.utf16le_strings.iter().take(cfg.max_samples) {
         if texts.len() >= cfg.max_classify {
             break;
         }
         texts.push(t);
     }
-    for (t, _) in &scanned.utf16be_strings {
+    for (t, _) in scanned.utf16be_strings.iter().take(cfg.max_samples) {
         if texts.len() >= cfg.max_classify {
             break;
         }
@@ -182,12 +278,31 @@ fn classify_iocs(
         let key = (kind.to_string(), m.text.clone());
         if seen.insert(key) {
             let off = m.abs_offset.map(|x| x as u64);
-            let text = if m.text.len() > 512 {
-                m.text[..512].to_string()
+            let mut url = match m.kind {
+                MatchKind::Url if cfg.parse_url_components => UrlComponents::parse(&m.text),
+                _ => None,
+            };
+            let network = matches!(
+                m.kind,
+                MatchKind::Url
+                    | MatchKind::Email
+                    | MatchKind::Hostname
+                    | MatchKind::Domain
+                    | MatchKind::Ipv4
+            );
+            let (text, truncated) = if cfg.defang_iocs && network {
+                if let Some(host) = url.as_mut().and_then(|u| u.host.as_mut()) {
+                    *host = normalize::defang(host);
+                }
+                truncate_chars(&normalize::defang(&m.text), cfg.ioc_sample_max_len)
             } else {
-                m.text
+                truncate_chars(&m.text, cfg.ioc_sample_max_len)
             };
-            samples.push(IocSample::new(kind.to_string(), text, off));
+            samples.push(
+                IocSample::new(kind.to_string(), text, off)
+                    .with_truncated(truncated)
+                    .with_url(url),
+            );
             if samples.len() >= cfg.max_ioc_samples {
                 break;
             }
@@ -270,7 +385,7 @@ pub fn extract_summary(data: &[u8], cfg: &StringsConfig) -> StringsSummary {
 
     // Helper to process a batch for a given encoding label
     let process_batch = |label: &str,
-                         items: &[(String, usize)]|
+                         items: &[SampleItem]|
      -> (
         Vec<DetectedString>,
         HashMap<String, u32>,
@@ -286,12 +401,7 @@ pub fn extract_summary(data: &[u8], cfg: &StringsConfig) -> StringsSummary {
 
     // Prepare capped batches and process in order (ASCII, UTF-16LE, UTF-16BE)
     let cap_ascii = cfg.max_samples.saturating_sub(detected_strings.len());
-    let ascii_items: Vec<(String, usize)> = scanned
-        .ascii_strings
-        .iter()
-        .take(cap_ascii)
-        .cloned()
-        .collect();
+    let ascii_items = sample_items(&scanned.ascii_strings, cap_ascii, cfg);
     {
         let (mut v, lc, sc) = process_batch("ascii", &ascii_items);
         detected_strings.append(&mut v);
@@ -304,12 +414,7 @@ pub fn extract_summary(data: &[u8], cfg: &StringsConfig) -> StringsSummary {
     }
 
     let cap_u16le = cfg.max_samples.saturating_sub(detected_strings.len());
-    let u16le_items: Vec<(String, usize)> = scanned
-        .utf16le_strings
-        .iter()
-        .take(cap_u16le)
-        .cloned()
-        .collect();
+    let u16le_items = sample_items(&scanned.utf16le_strings, cap_u16le, cfg);
     {
         let (mut v, lc, sc) = process_batch("utf16le", &u16le_items);
         detected_strings.append(&mut v);
@@ -322,12 +427,7 @@ pub fn extract_summary(data: &[u8], cfg: &StringsConfig) -> StringsSummary {
     }
 
     let cap_u16be = cfg.max_samples.saturating_sub(detected_strings.len());
-    let u16be_items: Vec<(String, usize)> = scanned
-        .utf16be_strings
-        .iter()
-        .take(cap_u16be)
-        .cloned()
-        .collect();
+    let u16be_items = sample_items(&scanned.utf16be_strings, cap_u16be, cfg);
     {
         let (mut v, lc, sc) = process_batch("utf16be", &u16be_items);
         detected_strings.append(&mut v);
@@ -361,6 +461,7 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg(feature = "lang-detect")]
     fn extract_summary_defaults_includes_language_counts_under_budget() {
         let data = b"This is an English sentence.\x00Bonjour le monde.";
         let cfg = StringsConfig {
@@ -376,4 +477,227 @@ mod tests {
         let total: u32 = counts.values().copied().sum();
         assert_eq!(total, 1);
     }
+
+    #[test]
+    fn long_strings_are_truncated_with_flag() {
+        let mut data = vec![b'A'; 10_000];
+        data.push(0);
+        data.extend_from_slice(b"short one");
+        let cfg = StringsConfig {
+            max_string_length: 64,
+            enable_language: false,
+            enable_classification: false,
+            ..StringsConfig::default()
+        };
+        let summary = extract_summary(&data, &cfg);
+        let strings = summary.strings.expect("samples");
+        assert_eq!(strings[0].text.len(), 64);
+        assert!(strings[0].truncated);
+        assert_eq!(strings[1].text, "short one");
+        assert!(!strings[1].truncated);
+
+        let unlimited = StringsConfig {
+            max_string_length: 0,
+            ..cfg
+        };
+        let strings = extract_summary(&data, &unlimited).strings.unwrap();
+        assert_eq!(strings[0].text.len(), 10_000);
+        assert!(!strings[0].truncated);
+    }
+
+    #[test]
+    fn long_ioc_samples_respect_configured_cap() {
+        let url = format!("http://c2.example/gate?d={}", "ab".repeat(400));
+        let mut data = b"\0\0".to_vec();
+        data.extend_from_slice(url.as_bytes());
+        data.push(0);
+        let cfg = StringsConfig {
+            enable_language: false,
+            time_guard_ms: 1000,
+            ..StringsConfig::default()
+        };
+        let url_sample = |cfg: &StringsConfig| {
+            extract_summary(&data, cfg)
+                .ioc_samples
+                .unwrap()
+                .into_iter()
+                .find(|s| s.kind == "url")
+                .unwrap()
+        };
+        let capped = url_sample(&cfg);
+        assert_eq!(capped.text.len(), 512);
+        assert!(capped.truncated);
+
+        let unlimited = StringsConfig {
+            ioc_sample_max_len: 0,
+            ..cfg
+        };
+        let full = url_sample(&unlimited);
+        assert_eq!(full.text, url);
+        assert!(!full.truncated);
+    }
+
+    #[test]
+    fn url_samples_carry_components_when_enabled() {
+        let data = b"\0\0https://Update.Example.net:8443/api/v2/beacon?id=7\0\0";
+        let cfg = StringsConfig {
+            enable_language: false,
+            time_guard_ms: 1000,
+            parse_url_components: true,
+            ..StringsConfig::default()
+        };
+        let samples = extract_summary(data, &cfg).ioc_samples.unwrap();
+        let url = samples.iter().find(|s| s.kind == "url").unwrap();
+        let parts = url.url.as_ref().expect("components");
+        assert_eq!(parts.scheme, "https");
+        assert_eq!(parts.host.as_deref(), Some("update.example.net"));
+        assert_eq!(parts.port, Some(8443));
+        assert_eq!(parts.path, "/api/v2/beacon");
+        assert!(samples
+            .iter()
+            .filter(|s| s.kind != "url")
+            .all(|s| s.url.is_none()));
+
+        let off = StringsConfig {
+            parse_url_components: false,
+            ..cfg
+        };
+        let samples = extract_summary(data, &off).ioc_samples.unwrap();
+        assert!(samples.iter().all(|s| s.url.is_none()));
+    }
+
+    #[test]
+    fn ioc_samples_are_defanged_when_enabled() {
+        let data = b"\0\0http://c2.example.org/gate.php\0\0/usr/lib/libc.so.6\0\0";
+        let cfg = StringsConfig {
+            enable_language: false,
+            time_guard_ms: 1000,
+            parse_url_components: true,
+            defang_iocs: true,
+            ..StringsConfig::default()
+        };
+        let summary = extract_summary(data, &cfg);
+        let samples = summary.ioc_samples.unwrap();
+        let url = samples.iter().find(|s| s.kind == "url").unwrap();
+        assert_eq!(url.text, "hxxp://c2[.]example[.]org/gate[.]php");
+        let parts = url.url.as_ref().expect("components");
+        assert_eq!(parts.host.as_deref(), Some("c2[.]example[.]org"));
+        assert_eq!(parts.path, "/gate.php");
+        // Non-network IOCs stay verbatim.
+        assert!(samples
+            .iter()
+            .filter(|s| s.kind == "path_posix")
+            .all(|s| !s.text.contains("[.]")));
+        assert!(summary.ioc_counts.is_some_and(|c| c.contains_key("url")));
+    }
+
+    #[test]
+    fn dedicated_thread_pool_matches_global_pool() {
+        let mut data = Vec::new();
+        for i in 0..300 {
+            data.extend_from_slice(format!("sample string number {}\0", i).as_bytes());
+        }
+        let base = StringsConfig {
+            max_samples: 300,
+            max_lang_detect: 300,
+            time_guard_ms: 1_000,
+            enable_classification: false,
+            ..StringsConfig::default()
+        };
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(1)
+            .build()
+            .unwrap();
+        let pooled = StringsConfig {
+            thread_pool: Some(Arc::new(pool)),
+            ..base.clone()
+        };
+        let a = extract_summary(&data, &base).strings.unwrap();
+        let b = extract_summary(&data, &pooled).strings.unwrap();
+        assert_eq!(a.len(), 300);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn ordered_lang_budget_annotates_the_first_eligible_samples() {
+        let mut data = Vec::new();
+        for i in 0..300 {
+            data.extend_from_slice(
+                format!("the quick brown fox jumps over the lazy dog {}\0", i).as_bytes(),
+            );
+        }
+        let cfg = StringsConfig {
+            max_samples: 300,
+            max_lang_detect: 10,
+            ordered_lang_budget: true,
+            time_guard_ms: 1_000,
+            enable_classification: false,
+            ..StringsConfig::default()
+        };
+        let annotated = |cfg: &StringsConfig| -> Vec<usize> {
+            extract_summary(&data, cfg)
+                .strings
+                .unwrap()
+                .iter()
+                .enumerate()
+                .filter(|(_, s)| s.language.is_some() || s.script.is_some())
+                .map(|(i, _)| i)
+                .collect()
+        };
+        let first = annotated(&cfg);
+        // Only the first ten (all eligible) samples may hold the budget.
+        assert!(first.iter().all(|&i| i < 10));
+        assert_eq!(first.is_empty(), !cfg!(feature = "lang-detect"));
+        for _ in 0..3 {
+            assert_eq!(annotated(&cfg), first);
+        }
+    }
+
+    #[test]
+    fn dedup_collapses_repeats_with_counts() {
+        let data = b"%s: error\0%s: error\0unique\0%s: error\0";
+        let base = StringsConfig {
+            enable_language: false,
+            enable_classification: false,
+            ..StringsConfig::default()
+        };
+        let plain = extract_summary(data, &base).strings.unwrap();
+        assert_eq!(plain.len(), 4);
+
+        let cfg = StringsConfig {
+            dedup_strings: true,
+            max_samples: 2,
+            ..base
+        };
+        let deduped = extract_summary(data, &cfg).strings.unwrap();
+        assert_eq!(deduped.len(), 2);
+        assert_eq!(deduped[0].text, "%s: error");
+        assert_eq!((deduped[0].count, deduped[0].offset), (3, Some(0)));
+        assert_eq!((deduped[1].text.as_str(), deduped[1].count), ("unique", 1));
+    }
+
+    #[test]
+    fn include_only_filters_before_sampling() {
+        let data = b"noise one\0http://evil.example/a\0noise two\0C:\\Windows\\x.dll\0";
+        let cfg = StringsConfig {
+            include_only: Some(vec![
+                regex::Regex::new(r"^https?://").unwrap(),
+                regex::Regex::new(r"(?i)\.dll$").unwrap(),
+            ]),
+            max_samples: 2,
+            enable_language: false,
+            enable_classification: false,
+            ..StringsConfig::default()
+        };
+        let summary = extract_summary(data, &cfg);
+        let texts: Vec<String> = summary
+            .strings
+            .unwrap()
+            .into_iter()
+            .map(|s| s.text)
+            .collect();
+        assert_eq!(texts, vec!["http://evil.example/a", "C:\\Windows\\x.dll"]);
+        // Counts still describe everything scanned.
+        assert_eq!(summary.ascii_count, 4);
+    }
 }
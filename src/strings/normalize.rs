@@ -25,6 +25,44 @@ pub fn normalize_defanged<'a>(s: &'a str, max_len: usize) -> Cow<'a, str> {
     Cow::Owned(out)
 }
 
+/// Defang an IOC so it cannot be followed or resolved by accident.
+/// - http:// -> hxxp:// ; https:// -> hxxps:// (any case)
+/// - . -> [.]
+///   Already-defanged input is returned unchanged, so this is idempotent.
+pub fn defang(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + s.len() / 4);
+    let mut rest = s;
+    while let Some(c) = rest.chars().next() {
+        let scheme = [("https://", "hxxps://"), ("http://", "hxxp://")]
+            .into_iter()
+            .find(|(p, _)| {
+                rest.get(..p.len())
+                    .is_some_and(|h| h.eq_ignore_ascii_case(p))
+            });
+        if let Some((p, defanged)) = scheme {
+            out.push_str(defanged);
+            rest = &rest[p.len()..];
+        } else if rest.starts_with("[.]") || rest.starts_with("(.)") {
+            out.push_str(&rest[..3]);
+            rest = &rest[3..];
+        } else {
+            if c == '.' {
+                out.push_str("[.]");
+            } else {
+                out.push(c);
+            }
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    out
+}
+
+/// Undo [`defang`] and the other schemes handled by
+/// [`normalize_defanged`], regardless of length.
+pub fn refang(s: &str) -> String {
+    normalize_defanged(s, usize::MAX).into_owned()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -35,4 +73,15 @@ mod tests {
         let n = normalize_defanged(s, 4096);
         assert_eq!(n, "visit https://ex.ample.com");
     }
+
+    #[test]
+    fn defang_round_trips() {
+        let url = "https://evil.example.com/a.exe";
+        let d = defang(url);
+        assert_eq!(d, "hxxps://evil[.]example[.]com/a[.]exe");
+        assert_eq!(defang(&d), d);
+        assert_eq!(refang(&d), url);
+        assert_eq!(defang("HTTP://10.0.0.1"), "hxxp://10[.]0[.]0[.]1");
+        assert_eq!(defang("user@mail.example"), "user@mail[.]example");
+    }
 }
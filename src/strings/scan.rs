@@ -37,6 +37,7 @@ pub fn scan_strings(
 ) -> ScannedStrings {
     let mut out = ScannedStrings::new();
     let scan = &data[..data.len().min(cfg.max_scan_bytes)];
+    let cap = cfg.scan_sample_cap();
 
     // ASCII scanner with offsets
     {
@@ -56,7 +57,7 @@ pub fn scan_strings(
             } else if !cur.is_empty() {
                 if cur.len() >= cfg.min_length {
                     out.ascii_count = out.ascii_count.saturating_add(1);
-                    if out.ascii_strings.len() < cfg.max_samples {
+                    if out.ascii_strings.len() < cap {
                         if let Ok(text) = String::from_utf8(cur.clone()) {
                             out.ascii_strings.push((text, cur_offset));
                         }
@@ -67,7 +68,7 @@ pub fn scan_strings(
         }
         if cur.len() >= cfg.min_length {
             out.ascii_count = out.ascii_count.saturating_add(1);
-            if out.ascii_strings.len() < cfg.max_samples {
+            if out.ascii_strings.len() < cap {
                 if let Ok(text) = String::from_utf8(cur) {
                     out.ascii_strings.push((text, cur_offset));
                 }
@@ -215,8 +216,8 @@ pub fn scan_strings(
                 }
             }
         }
-        if out.utf8_strings.len() > cfg.max_samples {
-            out.utf8_strings.truncate(cfg.max_samples);
+        if out.utf8_strings.len() > cap {
+            out.utf8_strings.truncate(cap);
         }
     }
 
@@ -260,9 +261,9 @@ pub fn scan_strings(
                 out.utf16le_count = out.utf16le_count.saturating_add(1);
             }
         }
-        // Cap sample vectors to max_samples each to bound memory
-        if out.utf16le_strings.len() > cfg.max_samples {
-            out.utf16le_strings.truncate(cfg.max_samples);
+        // Cap sample vectors (see `StringsConfig::scan_sample_cap`)
+        if out.utf16le_strings.len() > cap {
+            out.utf16le_strings.truncate(cap);
         }
     }
 
@@ -306,8 +307,8 @@ pub fn scan_strings(
                 out.utf16be_count = out.utf16be_count.saturating_add(1);
             }
         }
-        if out.utf16be_strings.len() > cfg.max_samples {
-            out.utf16be_strings.truncate(cfg.max_samples);
+        if out.utf16be_strings.len() > cap {
+            out.utf16be_strings.truncate(cap);
         }
     }
 
@@ -322,10 +323,14 @@ mod tests {
         StringsConfig {
             min_length: 4,
             max_samples: 10,
+            max_string_length: 4096,
+            dedup_strings: false,
+            include_only: None,
             max_scan_bytes: 1_048_576,
             time_guard_ms: 1_000, // generous to avoid flakiness
             enable_language: false,
             max_lang_detect: 0,
+            ordered_lang_budget: false,
             min_len_for_detect: 4,
             max_len_for_lingua: 32,
             min_lang_confidence: 0.65,
@@ -336,6 +341,10 @@ mod tests {
             max_classify: 0,
             max_ioc_per_string: 0,
             max_ioc_samples: 0,
+            ioc_sample_max_len: 512,
+            parse_url_components: false,
+            defang_iocs: false,
+            thread_pool: None,
         }
     }
 
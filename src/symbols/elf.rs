@@ -118,6 +118,7 @@ pub fn summarize_elf(data: &[u8], caps: &BudgetCaps) -> SymbolSummary {
     let mut debug_info_present = false;
     let mut has_debuglink = false; // .gnu_debuglink indicates external debug file
     let mut has_build_id = false; // .note.gnu.build-id present
+    let mut wx_sections: Vec<String> = Vec::new(); // SHF_WRITE | SHF_EXECINSTR
     if (e_shstrndx as usize) < shdrs.len() {
         let sh = shdrs[e_shstrndx as usize];
         let base = sh.sh_offset as usize;
@@ -146,6 +147,9 @@ pub fn summarize_elf(data: &[u8], caps: &BudgetCaps) -> SymbolSummary {
                         if n == ".note.gnu.build-id" {
                             has_build_id = true;
                         }
+                        if s.sh_flags & 0x5 == 0x5 {
+                            wx_sections.push(n.to_string());
+                        }
                     }
                 }
             }
@@ -464,6 +468,7 @@ pub fn summarize_elf(data: &[u8], caps: &BudgetCaps) -> SymbolSummary {
         } else {
             Some(runpaths)
         },
+        wx_sections: Some(wx_sections),
     }
 }
 
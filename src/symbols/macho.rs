@@ -284,5 +284,6 @@ pub fn summarize_macho(data: &[u8], caps: &BudgetCaps) -> SymbolSummary {
         relocations_present: None,
         rpaths: None,
         runpaths: None,
+        wx_sections: None,
     }
 }
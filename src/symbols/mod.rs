@@ -5,6 +5,7 @@
 //! with symbols from PE, ELF, and Mach-O binaries.
 
 use crate::core::binary::Format;
+use std::collections::HashMap;
 
 #[cfg(feature = "python-ext")]
 use pyo3::prelude::*;
@@ -34,6 +35,158 @@ pub fn summarize_symbols(data: &[u8], format: Format, caps: &BudgetCaps) -> Symb
     extract_symbols(data, format, caps).unwrap_or_default()
 }
 
+/// Defined symbols (static then dynamic) as `(address, name, section)`.
+/// The section is resolved from the symbol's section index and is `None` for
+/// absolute/common symbols. Empty names are skipped.
+fn defined_symbols(data: &[u8]) -> Vec<(u64, String, Option<String>)> {
+    use object::read::{Object, ObjectSection, ObjectSymbol, SymbolSection};
+    let mut out = Vec::new();
+    let Ok(obj) = object::read::File::parse(data) else {
+        return out;
+    };
+    for sym in obj.symbols().chain(obj.dynamic_symbols()) {
+        if !sym.is_definition() {
+            continue;
+        }
+        let Ok(name) = sym.name() else { continue };
+        if name.is_empty() {
+            continue;
+        }
+        let section = match sym.section() {
+            SymbolSection::Section(idx) => obj
+                .section_by_index(idx)
+                .ok()
+                .and_then(|s| s.name().ok().map(str::to_string)),
+            _ => None,
+        };
+        out.push((sym.address(), name.to_string(), section));
+    }
+    out
+}
+
+/// Every named symbol (static then dynamic) with its type, binding, size and
+/// section. Undefined symbols are reported as imports; defined global/weak
+/// symbols as exports.
+pub fn list_symbol_infos(data: &[u8]) -> Vec<SymbolInfo> {
+    use object::read::{Object, ObjectSection, ObjectSymbol, SymbolSection};
+    use object::SymbolKind;
+    let Ok(obj) = object::read::File::parse(data) else {
+        return Vec::new();
+    };
+    let mut out = Vec::new();
+    for sym in obj.symbols().chain(obj.dynamic_symbols()) {
+        let Ok(name) = sym.name() else { continue };
+        if name.is_empty() {
+            continue;
+        }
+        let symbol_type = match sym.kind() {
+            SymbolKind::Text => SymbolType::Function,
+            SymbolKind::Data if sym.is_common() => SymbolType::Common,
+            SymbolKind::Data => SymbolType::Object,
+            SymbolKind::Section => SymbolType::Section,
+            SymbolKind::File => SymbolType::File,
+            SymbolKind::Tls => SymbolType::TLS,
+            _ => SymbolType::Unknown,
+        };
+        let binding = if sym.is_weak() {
+            SymbolBinding::Weak
+        } else if sym.is_global() {
+            SymbolBinding::Global
+        } else if sym.is_local() {
+            SymbolBinding::Local
+        } else {
+            SymbolBinding::Unknown
+        };
+        let section = match sym.section() {
+            SymbolSection::Section(idx) => obj
+                .section_by_index(idx)
+                .ok()
+                .and_then(|s| s.name().ok().map(str::to_string)),
+            _ => None,
+        };
+        out.push(SymbolInfo {
+            name: name.to_string(),
+            address: sym.address(),
+            size: sym.size(),
+            symbol_type,
+            binding,
+            section,
+            is_imported: sym.is_undefined(),
+            is_exported: sym.is_definition() && binding != SymbolBinding::Local,
+        });
+    }
+    out
+}
+
+/// Address-sorted `(address, name)` pairs for defined symbols, keeping the
+/// first name seen at each address.
+pub fn symbol_address_map(data: &[u8]) -> Vec<(u64, String)> {
+    let mut out: Vec<(u64, String)> = defined_symbols(data)
+        .into_iter()
+        .map(|(a, n, _)| (a, n))
+        .collect();
+    out.sort_by_key(|(a, _)| *a);
+    out.dedup_by_key(|(a, _)| *a);
+    out
+}
+
+/// Address of the defined symbol named exactly `name`, the inverse of
+/// [`symbol_address_map`] for a single lookup. Stops at the first match
+/// (static symbols before dynamic) without building the full map.
+pub fn name_to_address(data: &[u8], name: &str) -> Option<u64> {
+    use object::read::{Object, ObjectSymbol};
+    let obj = object::read::File::parse(data).ok()?;
+    obj.symbols()
+        .chain(obj.dynamic_symbols())
+        .find(|sym| sym.is_definition() && sym.name().is_ok_and(|n| n == name))
+        .map(|sym| sym.address())
+}
+
+/// Defined symbols whose name contains `query`, ignoring ASCII case, as
+/// `(address, name)` pairs sorted by address then name. Unlike
+/// [`symbol_address_map`], aliases sharing an address are all kept.
+pub fn find_symbols(data: &[u8], query: &str) -> Vec<(u64, String)> {
+    let query = query.to_ascii_lowercase();
+    let mut out: Vec<(u64, String)> = defined_symbols(data)
+        .into_iter()
+        .filter(|(_, n, _)| n.to_ascii_lowercase().contains(&query))
+        .map(|(a, n, _)| (a, n))
+        .collect();
+    out.sort();
+    out.dedup();
+    out
+}
+
+/// [`symbol_address_map`] with each name run through
+/// [`crate::demangle::demangle_one`]: `(address, raw_name, demangled)`, where
+/// `demangled` is `None` for names that are not recognized as mangled.
+pub fn symbol_address_map_demangled(data: &[u8]) -> Vec<(u64, String, Option<String>)> {
+    symbol_address_map(data)
+        .into_iter()
+        .map(|(addr, name)| {
+            let demangled = crate::demangle::demangle_one(&name).map(|r| r.demangled);
+            (addr, name, demangled)
+        })
+        .collect()
+}
+
+/// Like [`symbol_address_map`], grouped by the name of the section that
+/// contains each symbol (".text", ".data", ...). Symbols without a section
+/// (absolute values) are omitted.
+pub fn symbol_address_map_by_section(data: &[u8]) -> HashMap<String, Vec<(u64, String)>> {
+    let mut out: HashMap<String, Vec<(u64, String)>> = HashMap::new();
+    for (addr, name, section) in defined_symbols(data) {
+        if let Some(section) = section {
+            out.entry(section).or_default().push((addr, name));
+        }
+    }
+    for v in out.values_mut() {
+        v.sort_by_key(|(a, _)| *a);
+        v.dedup_by_key(|(a, _)| *a);
+    }
+    out
+}
+
 /// Python binding for listing symbols from a file
 #[cfg(feature = "python-ext")]
 #[pyfunction]
@@ -233,3 +386,115 @@ pub fn list_symbols_demangled_py(
         libs,
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "samples/adversarial/embedded/xor_url_in_elf.elf";
+
+    #[test]
+    fn address_map_by_section_groups_code_and_data() {
+        let Ok(data) = std::fs::read(SAMPLE) else {
+            return;
+        };
+        if !data.starts_with(b"\x7fELF") {
+            return;
+        }
+        let by_section = symbol_address_map_by_section(&data);
+        let text = by_section.get(".text").expect(".text symbols");
+        assert!(text.iter().any(|(_, n)| n == "main"));
+        assert!(text.windows(2).all(|w| w[0].0 < w[1].0));
+        let bss = by_section.get(".bss").expect(".bss symbols");
+        assert!(bss.iter().any(|(_, n)| n.starts_with("static_var")));
+        assert!(!by_section.values().flatten().any(|(_, n)| n == "hello.c"));
+
+        let flat = symbol_address_map(&data);
+        assert!(flat.iter().any(|(_, n)| n == "main"));
+    }
+
+    #[test]
+    fn name_lookup_inverts_the_address_map() {
+        let Ok(data) = std::fs::read(SAMPLE) else {
+            return;
+        };
+        if !data.starts_with(b"\x7fELF") {
+            return;
+        }
+        let flat = symbol_address_map(&data);
+        let main = flat.iter().find(|(_, n)| n == "main").unwrap().0;
+        assert_eq!(name_to_address(&data, "main"), Some(main));
+        assert_eq!(name_to_address(&data, "MAIN"), None);
+        assert_eq!(name_to_address(&data, "no_such_symbol"), None);
+
+        let hits = find_symbols(&data, "MAI");
+        assert!(hits.contains(&(main, "main".to_string())));
+        assert!(hits
+            .iter()
+            .all(|(_, n)| n.to_ascii_lowercase().contains("mai")));
+        assert!(find_symbols(b"not an object", "main").is_empty());
+    }
+
+    #[test]
+    fn demangled_address_map_keeps_raw_names() {
+        let Ok(data) = std::fs::read(SAMPLE) else {
+            return;
+        };
+        if !data.starts_with(b"\x7fELF") {
+            return;
+        }
+        let flat = symbol_address_map(&data);
+        let triples = symbol_address_map_demangled(&data);
+        assert_eq!(flat.len(), triples.len());
+        for ((a, n), (ta, tn, _)) in flat.iter().zip(&triples) {
+            assert_eq!((a, n), (ta, tn));
+        }
+        // Plain C symbols are not mangled.
+        let main = triples.iter().find(|(_, n, _)| n == "main").unwrap();
+        assert_eq!(main.2, None);
+    }
+
+    #[test]
+    fn symbol_infos_carry_type_and_binding() {
+        let Ok(data) = std::fs::read(SAMPLE) else {
+            return;
+        };
+        if !data.starts_with(b"\x7fELF") {
+            return;
+        }
+        let infos = list_symbol_infos(&data);
+        let main = infos.iter().find(|s| s.name == "main").expect("main");
+        assert_eq!(main.symbol_type, SymbolType::Function);
+        assert_eq!(main.binding, SymbolBinding::Global);
+        assert_eq!(main.section.as_deref(), Some(".text"));
+        assert!(main.is_exported && !main.is_imported);
+        let var = infos
+            .iter()
+            .find(|s| s.name.starts_with("static_var"))
+            .expect("static_var");
+        assert_eq!(var.symbol_type, SymbolType::Object);
+        assert_eq!(var.binding, SymbolBinding::Local);
+        assert!(infos
+            .iter()
+            .any(|s| s.name.starts_with("puts") && s.is_imported));
+    }
+
+    #[test]
+    fn unstripped_sample_reports_not_stripped() {
+        let Ok(data) = std::fs::read(SAMPLE) else {
+            return;
+        };
+        if !data.starts_with(b"\x7fELF") {
+            return;
+        }
+        let summary = summarize_symbols(&data, Format::ELF, &BudgetCaps::default());
+        assert!(!summary.is_stripped());
+        assert!(SymbolSummary::stripped_from_names(&[]));
+    }
+
+    #[test]
+    fn address_map_of_garbage_is_empty() {
+        assert!(symbol_address_map(b"not an object").is_empty());
+        assert!(symbol_address_map_by_section(b"not an object").is_empty());
+    }
+}
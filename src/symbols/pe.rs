@@ -33,6 +33,7 @@ struct SectionHdr {
     raw_ptr: u32,
     raw_size: u32,
     virt_size: u32,
+    characteristics: u32,
 }
 
 fn read_u16_le(data: &[u8], off: usize) -> Option<u16> {
@@ -194,12 +195,14 @@ pub fn summarize_pe(data: &[u8], caps: &BudgetCaps) -> SymbolSummary {
             let va = read_u32_le(data, off + 12).unwrap_or(0);
             let raw_size = read_u32_le(data, off + 16).unwrap_or(0);
             let raw_ptr = read_u32_le(data, off + 20).unwrap_or(0);
+            let characteristics = read_u32_le(data, off + 36).unwrap_or(0);
             sections.push(SectionHdr {
                 name,
                 va,
                 raw_ptr,
                 raw_size,
                 virt_size,
+                characteristics,
             });
             off += 40;
         }
@@ -210,6 +213,12 @@ pub fn summarize_pe(data: &[u8], caps: &BudgetCaps) -> SymbolSummary {
     let debug_info_present = debug_dd.rva != 0 && rva_to_offset(debug_dd.rva, &sections).is_some();
     let tls_used = tls_dd.rva != 0 && rva_to_offset(tls_dd.rva, &sections).is_some();
     let relocations_present = base_reloc_dd.rva != 0 && base_reloc_dd.size != 0;
+    // IMAGE_SCN_MEM_EXECUTE | IMAGE_SCN_MEM_WRITE
+    let wx_sections: Vec<String> = sections
+        .iter()
+        .filter(|s| s.characteristics & 0xA000_0000 == 0xA000_0000)
+        .map(|s| s.name.clone())
+        .collect();
 
     // Entry section name if entry point falls within one
     let address_of_entry = read_u32_le(data, opt_off + 16).unwrap_or(0);
@@ -620,6 +629,7 @@ pub fn summarize_pe(data: &[u8], caps: &BudgetCaps) -> SymbolSummary {
         relocations_present: Some(relocations_present),
         rpaths: None,
         runpaths: None,
+        wx_sections: Some(wx_sections),
     }
 }
 
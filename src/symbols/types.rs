@@ -58,6 +58,8 @@ pub struct SymbolSummary {
     pub relocations_present: Option<bool>,
     pub rpaths: Option<Vec<String>>,
     pub runpaths: Option<Vec<String>>,
+    /// Sections mapped both writable and executable (ELF/PE)
+    pub wx_sections: Option<Vec<String>>,
 }
 
 #[cfg(feature = "python-ext")]
@@ -126,6 +128,7 @@ impl SymbolSummary {
             relocations_present: None,
             rpaths,
             runpaths,
+            wx_sections: None,
         }
     }
 
@@ -234,10 +237,38 @@ impl SymbolSummary {
     fn runpaths(&self) -> Option<Vec<String>> {
         self.runpaths.clone()
     }
+    #[getter]
+    fn wx_sections(&self) -> Option<Vec<String>> {
+        self.wx_sections.clone()
+    }
+
+    /// Whether the binary appears stripped.
+    #[pyo3(name = "is_stripped")]
+    fn is_stripped_py(&self) -> bool {
+        self.is_stripped()
+    }
+}
+
+impl SymbolSummary {
+    /// Whether the binary appears stripped of its static symbol table.
+    ///
+    /// Format summarizers set this from structural evidence (missing
+    /// `.symtab`/COFF symbol table/`LC_SYMTAB`); summaries built from a bare
+    /// name list use [`SymbolSummary::stripped_from_names`].
+    pub fn is_stripped(&self) -> bool {
+        self.stripped
+    }
+
+    /// Name-count heuristic shared with compiler detection: a binary whose
+    /// symbols are almost all dynamic-linker plumbing is treated as stripped.
+    pub fn stripped_from_names(names: &[String]) -> bool {
+        crate::triage::compiler_detection::is_likely_stripped(names)
+    }
 }
 
 /// Type of symbol
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "python-ext", pyclass(eq, eq_int))]
 pub enum SymbolType {
     Function,
     Data,
@@ -250,7 +281,8 @@ pub enum SymbolType {
 }
 
 /// Symbol binding/visibility
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "python-ext", pyclass(eq, eq_int))]
 pub enum SymbolBinding {
     Local,
     Global,
@@ -259,7 +291,8 @@ pub enum SymbolBinding {
 }
 
 /// Detailed information about a single symbol
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "python-ext", pyclass)]
 pub struct SymbolInfo {
     pub name: String,
     pub address: u64,
@@ -270,3 +303,46 @@ pub struct SymbolInfo {
     pub is_imported: bool,
     pub is_exported: bool,
 }
+
+#[cfg(feature = "python-ext")]
+#[pymethods]
+impl SymbolInfo {
+    #[getter]
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+    #[getter]
+    fn address(&self) -> u64 {
+        self.address
+    }
+    #[getter]
+    fn size(&self) -> u64 {
+        self.size
+    }
+    #[getter]
+    fn symbol_type(&self) -> SymbolType {
+        self.symbol_type
+    }
+    #[getter]
+    fn binding(&self) -> SymbolBinding {
+        self.binding
+    }
+    #[getter]
+    fn section(&self) -> Option<String> {
+        self.section.clone()
+    }
+    #[getter]
+    fn is_imported(&self) -> bool {
+        self.is_imported
+    }
+    #[getter]
+    fn is_exported(&self) -> bool {
+        self.is_exported
+    }
+    fn __repr__(&self) -> String {
+        format!(
+            "SymbolInfo(name={:?}, address={:#x}, type={:?}, binding={:?})",
+            self.name, self.address, self.symbol_type, self.binding
+        )
+    }
+}
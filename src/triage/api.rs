@@ -1,30 +1,45 @@
 use crate::core::binary::{Arch, Endianness, Format};
+#[cfg(feature = "disasm")]
 use crate::core::disassembler::Disassembler;
 use crate::core::triage::formats::{FormatSpecificTriage, PeTriageInfo};
 use crate::core::triage::{
-    Budgets, ContainerChild, EntropyAnalysis, EntropySummary, PackerMatch, SimilaritySummary,
-    StringsSummary, TriageVerdict, TriagedArtifact,
+    Budgets, ContainerChild, EntropyAnalysis, EntropySummary, PackerMatch, PhaseTimings,
+    SimilaritySummary, StringsSummary, TriageVerdict, TriagedArtifact,
 };
-use crate::core::triage::{TriageError, TriageErrorKind, TriageHint};
+use crate::core::triage::{Severity, SnifferSource, TriageError, TriageErrorKind, TriageHint};
 
 use crate::symbols::SymbolSummary;
 
+use crate::io::HashAlg;
 use crate::strings::StringsConfig;
 use crate::symbols::{self, BudgetCaps};
-#[cfg(feature = "python-ext")]
-use crate::triage::config::TriageConfig;
-use crate::triage::config::{EntropyConfig, PackerConfig, SimilarityConfig};
-use crate::triage::entropy::analyze_entropy;
-use crate::triage::format_detection::{derive_format_from_hint, is_container_hint};
+use crate::triage::compiler_detection::{
+    detect_language_and_compiler_with_path, LanguageDetectionResult,
+};
+use crate::triage::config::{
+    EntropyConfig, InputKind, OverlayConfig, PackerConfig, SimilarityConfig, SnifferConfig,
+    TriageConfig,
+};
+use crate::triage::deadline::Deadline;
+use crate::triage::entropy::analyze_entropy_with_deadline;
+use crate::triage::format_detection::{
+    annotate_verdicts, derive_format_from_hint, is_container_hint,
+};
 use crate::triage::headers;
 use crate::triage::heuristics::{architecture, endianness};
 use crate::triage::io::{
-    IOLimits, SafeFileReader, MAX_ENTROPY_SIZE, MAX_HEADER_SIZE, MAX_SNIFF_SIZE,
+    IOLimits, IOUtils, SafeFileReader, MAX_ENTROPY_SIZE, MAX_HEADER_SIZE, MAX_SNIFF_SIZE,
 };
-use crate::triage::packers::detect_packers;
+use crate::triage::packers::detect_packers_with_hits;
 use crate::triage::parsers;
-use crate::triage::recurse::RecursionEngine;
+use crate::triage::progress::{self, Phase, ProgressFn};
+use crate::triage::recurse::{
+    fingerprint_children, scan_embedded_modules, RecursionEngine, MAX_DUMP_MODULES,
+};
 use crate::triage::score;
+use crate::triage::signatures::{
+    detect_installer_with_hits, InstallerFamily, MultiScanner, SignatureHits,
+};
 use crate::triage::signing::SigningSummary;
 use crate::triage::sniffers::CombinedSniffer;
 use chrono::Utc;
@@ -33,10 +48,63 @@ use pyo3::prelude::*;
 use sha2::{Digest, Sha256};
 use std::path::Path;
 use std::time::Instant;
-use tracing::{debug, info};
+use tracing::field::Empty;
+use tracing::{debug, info, Span};
+
+/// Run `f` inside the phase `span`, storing its wall time in `slot` and
+/// recording it on the span as `field`.
+fn timed<T>(span: Span, field: &'static str, slot: &mut u64, f: impl FnOnce() -> T) -> T {
+    let t = Instant::now();
+    let out = span.in_scope(f);
+    *slot = t.elapsed().as_millis() as u64;
+    span.record(field, *slot);
+    out
+}
 
+/// Resolve where code starts for the disassembly preview as
+/// `(file_offset, virtual_address)`.
+///
+/// Uses the parsed format's entry point, falling back to the first executable
+/// section. Returns `Some((0, 0))` for data no object parser recognizes (raw
+/// code, shellcode) and `None` when the format parses but its code lies
+/// outside `data`.
+#[cfg(feature = "disasm")]
+fn preview_start(data: &[u8]) -> Option<(usize, u64)> {
+    use object::read::{Object, ObjectSection};
+    use object::SectionKind;
+    let Ok(obj) = object::read::File::parse(data) else {
+        return Some((0, 0));
+    };
+    let entry = obj.entry();
+    let in_buf = |off: u64| (off < data.len() as u64).then_some(off as usize);
+    if entry != 0 {
+        for sec in obj.sections() {
+            let (addr, size) = (sec.address(), sec.size());
+            if entry < addr || entry >= addr.saturating_add(size) {
+                continue;
+            }
+            if let Some((file_off, _)) = sec.file_range() {
+                return in_buf(file_off + (entry - addr)).map(|o| (o, entry));
+            }
+        }
+    }
+    obj.sections()
+        .filter(|s| s.kind() == SectionKind::Text && s.size() > 0)
+        .find_map(|s| {
+            let (file_off, _) = s.file_range()?;
+            in_buf(file_off).map(|o| (o, s.address()))
+        })
+}
+
+/// Decode a short preview starting at the entry point (or first executable
+/// section) rather than the file header.
+///
+/// The top verdict's architecture and byte order win over the histogram
+/// guesses, which cannot tell big-endian MIPS or ARM from little-endian.
+#[cfg(feature = "disasm")]
 fn compute_disasm_preview(
     data: &[u8],
+    verdicts: &[TriageVerdict],
     arch_guesses: &[(Arch, f32)],
     e_guess: Endianness,
     max_instructions: usize,
@@ -44,18 +112,23 @@ fn compute_disasm_preview(
     max_time_ms: u64,
 ) -> Option<Vec<String>> {
     use crate::core::disassembler::Architecture as DArch;
-    let (barch, _conf) = arch_guesses.first().cloned()?;
+    let (barch, endianness) = match verdicts.first() {
+        Some(v) if v.arch != Arch::Unknown => (v.arch, v.endianness),
+        _ => (arch_guesses.first()?.0, e_guess),
+    };
     let darch: DArch = barch.into();
-    let backend = crate::disasm::registry::for_arch(darch, e_guess)?;
+    let backend = crate::disasm::registry::for_arch(darch, endianness)?;
     let bits = darch.address_bits();
+    let (start, start_va) = preview_start(data)?;
     let addr = crate::core::address::Address::new(
         crate::core::address::AddressKind::VA,
-        0,
+        start_va,
         bits,
         None,
         None,
     )
     .ok()?;
+    let data = &data[start..];
     let mut out = Vec::new();
     let mut off = 0usize;
     let limit = data.len().min(max_bytes);
@@ -93,6 +166,21 @@ fn compute_disasm_preview(
     }
 }
 
+/// Without the `disasm` feature no decoder is compiled in, so there is no
+/// preview.
+#[cfg(not(feature = "disasm"))]
+fn compute_disasm_preview(
+    _data: &[u8],
+    _verdicts: &[TriageVerdict],
+    _arch_guesses: &[(Arch, f32)],
+    _e_guess: Endianness,
+    _max_instructions: usize,
+    _max_bytes: usize,
+    _max_time_ms: u64,
+) -> Option<Vec<String>> {
+    None
+}
+
 fn generate_id(path: Option<&Path>, size: usize) -> String {
     let mut hasher = Sha256::new();
     if let Some(p) = path {
@@ -110,10 +198,26 @@ fn generate_id(path: Option<&Path>, size: usize) -> String {
     )
 }
 
+/// Minimal artifact for a file whose SHA-256 is on the known-good allowlist.
+fn known_good_artifact(path: String, size_bytes: u64, sha256: String) -> TriagedArtifact {
+    TriagedArtifact::builder()
+        .with_id(generate_id(Some(Path::new(&path)), size_bytes as usize))
+        .with_path(path)
+        .with_size_bytes(size_bytes)
+        .with_sha256(Some(sha256))
+        .with_known_good(true)
+        .build()
+        .expect("All required fields are provided")
+}
+
 /// Performs content sniffing to identify file type hints.
-fn sniff_content(sniff_buf: &[u8], path: &str) -> (Vec<TriageHint>, Vec<TriageError>) {
+fn sniff_content(
+    sniff_buf: &[u8],
+    path: &str,
+    sniffer_cfg: &SnifferConfig,
+) -> (Vec<TriageHint>, Vec<TriageError>) {
     debug!(phase = "sniffing", "content+extension sniff");
-    let sn = CombinedSniffer::sniff(sniff_buf, Some(Path::new(path)));
+    let sn = CombinedSniffer::sniff_with_config(sniff_buf, Some(Path::new(path)), sniffer_cfg);
     (sn.hints, sn.errors)
 }
 
@@ -127,6 +231,8 @@ fn validate_headers(header_buf: &[u8]) -> (Vec<TriageVerdict>, Vec<TriageError>)
 /// Performs heuristic analysis including entropy, endianness, and architecture detection.
 fn analyze_heuristics(
     heur_buf: &[u8],
+    entropy_cfg: &EntropyConfig,
+    deadline: &Deadline,
 ) -> (
     EntropyAnalysis,
     Option<f64>,
@@ -134,8 +240,7 @@ fn analyze_heuristics(
     Vec<(Arch, f32)>,
 ) {
     debug!(phase = "entropy", "compute");
-    let ecfg = EntropyConfig::default();
-    let ea = analyze_entropy(heur_buf, &ecfg);
+    let ea = analyze_entropy_with_deadline(heur_buf, entropy_cfg, deadline);
     let entropy = ea.summary.overall;
 
     debug!(phase = "heuristics", "endianness and arch");
@@ -145,23 +250,39 @@ fn analyze_heuristics(
     (ea, entropy, (e_guess, e_conf), arch_guesses)
 }
 
-/// Extracts strings from the heuristics buffer with language detection.
+/// Treat a strings time guard as a per-MiB budget, so widening
+/// `string_scan_bytes` past the default 1 MiB window does not stop the scan
+/// early under the same fixed guard.
+fn scaled_time_guard_ms(guard_ms: u64, scan_len: usize) -> u64 {
+    let mib = (scan_len as u64).div_ceil(1 << 20).max(1);
+    guard_ms.saturating_mul(mib)
+}
+
+/// Extracts strings from the strings buffer with language detection.
+///
+/// Skipped once `deadline` has expired; otherwise the scan's own time guard
+/// is scaled to the buffer size and capped to the time left.
 fn extract_strings(
-    heur_buf: &[u8],
+    strings_buf: &[u8],
     strings_cfg: &StringsConfig,
     hints: &[TriageHint],
     entropy: Option<f64>,
+    deadline: &Deadline,
 ) -> Option<StringsSummary> {
     debug!(phase = "strings", "extract with language detection");
+    if deadline.is_expired() {
+        return None;
+    }
 
     // Adjust strings settings for compressed/high-entropy inputs
     let mut adj = strings_cfg.clone();
+    adj.time_guard_ms = deadline.cap_ms(scaled_time_guard_ms(adj.time_guard_ms, strings_buf.len()));
     let is_containerish = hints.iter().any(is_container_hint);
     if is_containerish || entropy.unwrap_or(0.0) > 7.2 {
         adj.min_length = adj.min_length.max(8);
     }
 
-    let s = crate::strings::extract_summary(heur_buf, &adj);
+    let s = crate::strings::extract_summary(strings_buf, &adj);
     if s.ascii_count == 0 && s.utf16le_count == 0 && s.utf16be_count == 0 {
         None
     } else {
@@ -172,6 +293,7 @@ fn extract_strings(
 /// Discovers containers and packers within the binary.
 fn discover_containers_and_packers(
     heur_buf: &[u8],
+    signature_hits: &SignatureHits<'_>,
     hints: &[TriageHint],
     max_recursion_depth: usize,
 ) -> (Option<Vec<ContainerChild>>, u32, Option<Vec<PackerMatch>>) {
@@ -182,7 +304,7 @@ fn discover_containers_and_packers(
         // Use recursion engine to discover immediate children (containers)
         let engine = RecursionEngine::new(max_recursion_depth);
         let mut tmp_budget = Budgets::new(0, 0, 0);
-        let v = engine.discover_children(heur_buf, &mut tmp_budget, 0);
+        let v = engine.discover_children_with_hits(heur_buf, signature_hits, &mut tmp_budget, 0);
         let mut vv = v;
 
         // Deduplicate for JAR: prefer a single zip entry
@@ -225,12 +347,18 @@ fn merge_errors(
     hints: &[TriageHint],
     header_formats: &[Format],
     container_labels: &[String],
+    mismatch_severity: Severity,
     hit_byte_limit: bool,
     limit_bytes: u64,
     initial_bytes_read: u64,
 ) -> Option<Vec<TriageError>> {
     // Cross-check sniffer hints vs header verdicts
-    let mut cross = compute_sniffer_header_mismatches(hints, header_formats, container_labels);
+    let mut cross = compute_sniffer_header_mismatches(
+        hints,
+        header_formats,
+        container_labels,
+        mismatch_severity,
+    );
 
     // Merge non-fatal errors and append budget notice if applicable
     let mut merged_errors_vec: Vec<TriageError> = {
@@ -260,10 +388,12 @@ fn merge_errors(
 // Compute header vs sniffer mismatch errors.
 // - header_formats: formats derived from header validation.
 // - container_labels: names from container detection (e.g., zip, tar).
+// - severity: severity assigned to each mismatch (see SnifferConfig::mismatch_severity).
 pub(crate) fn compute_sniffer_header_mismatches(
     hints: &[TriageHint],
     header_formats: &[Format],
     container_labels: &[String],
+    severity: Severity,
 ) -> Vec<TriageError> {
     if header_formats.is_empty() || hints.is_empty() {
         return Vec::new();
@@ -285,13 +415,16 @@ pub(crate) fn compute_sniffer_header_mismatches(
         }
         if let Some(hfmt) = derive_format_from_hint(h) {
             if !header_formats.contains(&hfmt) {
-                errors.push(TriageError::new(
-                    TriageErrorKind::SnifferMismatch,
-                    Some(format!(
-                        "Sniffer suggests {:?} but headers indicate {:?}",
-                        hfmt, header_formats
-                    )),
-                ));
+                errors.push(
+                    TriageError::new(
+                        TriageErrorKind::SnifferMismatch,
+                        Some(format!(
+                            "Sniffer suggests {:?} but headers indicate {:?}",
+                            hfmt, header_formats
+                        )),
+                    )
+                    .with_severity(severity),
+                );
             }
         }
     }
@@ -299,12 +432,22 @@ pub(crate) fn compute_sniffer_header_mismatches(
 }
 
 /// Perform initial content analysis including sniffing, header validation, heuristics, and string extraction
+#[allow(clippy::too_many_arguments)]
 fn perform_content_analysis(
     sniff_buf: &[u8],
     header_buf: &[u8],
     heur_buf: &[u8],
+    strings_buf: &[u8],
+    signature_hits: &SignatureHits<'_>,
     path: &str,
+    entropy_cfg: &EntropyConfig,
     strings_cfg: &StringsConfig,
+    sniffer_cfg: &SnifferConfig,
+    input_kind: InputKind,
+    progress: Option<ProgressFn<'_>>,
+    timings: &mut PhaseTimings,
+    deadline: &Deadline,
+    budget_notes: &mut Vec<TriageError>,
 ) -> (
     Vec<TriageHint>,
     Vec<TriageError>,
@@ -319,20 +462,86 @@ fn perform_content_analysis(
     Option<EntropySummary>,
     Option<StringsSummary>,
 ) {
+    // Sniffing and header validation both assume a file format header at
+    // offset 0, which neither dumps nor shellcode have.
+    let offset_zero_header = input_kind == InputKind::File;
+
     // Phase 1: Content sniffing
-    let (hints, sniff_errors) = sniff_content(sniff_buf, path);
+    progress::report(progress, Phase::Sniffing);
+    let (mut hints, sniff_errors) = timed(
+        tracing::debug_span!("sniff", sniff_ms = Empty),
+        "sniff_ms",
+        &mut timings.sniff_ms,
+        || {
+            if offset_zero_header {
+                sniff_content(sniff_buf, path, sniffer_cfg)
+            } else {
+                (Vec::new(), Vec::new())
+            }
+        },
+    );
+    // Installer payloads sit past the sniff prefix, behind the PE stub
+    if offset_zero_header {
+        if let Some(m) = detect_installer_with_hits(heur_buf, signature_hits) {
+            let mime = (m.family == InstallerFamily::Msi).then(|| "application/x-msi".to_string());
+            hints.push(TriageHint::new(
+                SnifferSource::Other,
+                mime,
+                None,
+                Some(m.family.label().to_string()),
+            ));
+        }
+    }
 
     // Phase 2: Header validation
-    let (verdicts, header_errors) = validate_headers(header_buf);
+    progress::report(progress, Phase::Headers);
+    let (verdicts, header_errors) = timed(
+        tracing::debug_span!("headers", headers_ms = Empty),
+        "headers_ms",
+        &mut timings.headers_ms,
+        || {
+            if offset_zero_header {
+                validate_headers(header_buf)
+            } else {
+                (Vec::new(), Vec::new())
+            }
+        },
+    );
     let header_formats: Vec<Format> = verdicts.iter().map(|v| v.format).collect();
 
     // Phase 3: Heuristic analysis (entropy, endianness, architecture)
-    let (ea, entropy_overall_opt, (e_guess, e_conf), arch_guesses) = analyze_heuristics(heur_buf);
+    progress::report(progress, Phase::Entropy);
+    let (ea, entropy_overall_opt, (e_guess, e_conf), arch_guesses) = timed(
+        tracing::debug_span!("entropy", entropy_ms = Empty),
+        "entropy_ms",
+        &mut timings.entropy_ms,
+        || analyze_heuristics(heur_buf, entropy_cfg, deadline),
+    );
+    if deadline.is_expired() {
+        budget_notes.push(deadline.note("entropy"));
+    }
     let entropy_overall = entropy_overall_opt.unwrap_or(0.0);
     let entropy = Some(ea.summary.clone());
 
     // Phase 4: String extraction
-    let strings = extract_strings(heur_buf, strings_cfg, &hints, Some(entropy_overall));
+    progress::report(progress, Phase::Strings);
+    let strings = timed(
+        tracing::debug_span!("strings", strings_ms = Empty),
+        "strings_ms",
+        &mut timings.strings_ms,
+        || {
+            extract_strings(
+                strings_buf,
+                strings_cfg,
+                &hints,
+                Some(entropy_overall),
+                deadline,
+            )
+        },
+    );
+    if deadline.is_expired() {
+        budget_notes.push(deadline.note("strings"));
+    }
 
     (
         hints,
@@ -353,23 +562,42 @@ fn perform_content_analysis(
 /// Perform parser probes and container/packer discovery
 fn perform_parser_discovery(
     heur_buf: &[u8],
+    signature_hits: &SignatureHits<'_>,
     hints: &[TriageHint],
     max_recursion_depth: usize,
     packer_cfg: &PackerConfig,
+    sim_cfg: &SimilarityConfig,
+    input_kind: InputKind,
 ) -> (
     Vec<crate::core::triage::ParserResult>,
     Option<Vec<ContainerChild>>,
     usize,
     Option<Vec<PackerMatch>>,
 ) {
-    debug!(phase = "parsers", "structured parse probes");
-    let parser_results = parsers::parse(heur_buf);
-    let (mut containers, rec_depth, _packers_placeholder) =
-        discover_containers_and_packers(heur_buf, hints, max_recursion_depth);
+    let (parser_results, mut containers, rec_depth) = match input_kind {
+        InputKind::File => {
+            debug!(phase = "parsers", "structured parse probes");
+            let parser_results = parsers::parse(heur_buf);
+            let (containers, rec_depth, _packers_placeholder) = discover_containers_and_packers(
+                heur_buf,
+                signature_hits,
+                hints,
+                max_recursion_depth,
+            );
+            (parser_results, containers, rec_depth)
+        }
+        InputKind::MemoryDump => {
+            debug!(phase = "parsers", "memory dump module scan");
+            let modules = scan_embedded_modules(heur_buf, MAX_DUMP_MODULES);
+            let depth = u32::from(!modules.is_empty());
+            (Vec::new(), Some(modules).filter(|m| !m.is_empty()), depth)
+        }
+        InputKind::Shellcode => (Vec::new(), None, 0),
+    };
 
     // Compute packers here with provided config
     let packers = {
-        let v = detect_packers(heur_buf, packer_cfg);
+        let v = detect_packers_with_hits(heur_buf, signature_hits, packer_cfg);
         if v.is_empty() {
             None
         } else {
@@ -380,6 +608,9 @@ fn perform_parser_discovery(
     // Ensure deterministic ordering of children if present
     if let Some(ref mut vv) = containers {
         vv.sort_by(|a, b| a.offset.cmp(&b.offset).then(a.type_name.cmp(&b.type_name)));
+        if sim_cfg.fingerprint_children {
+            fingerprint_children(vv, heur_buf, sim_cfg);
+        }
     }
 
     (parser_results, containers, rec_depth as usize, packers)
@@ -390,6 +621,8 @@ fn perform_format_analysis(
     heur_buf: &[u8],
     header_formats: &[Format],
     sim_cfg: &SimilarityConfig,
+    overlay_cfg: &OverlayConfig,
+    timings: &mut PhaseTimings,
 ) -> (
     Option<FormatSpecificTriage>,
     Option<SymbolSummary>,
@@ -400,8 +633,21 @@ fn perform_format_analysis(
     // Format-specific analysis
     let format_specific = if header_formats.first().copied() == Some(Format::PE) {
         let rich_header = crate::triage::rich_header::parse_rich_header(heur_buf);
+        let options = crate::formats::pe::ParseOptions {
+            best_effort: true,
+            ..Default::default()
+        };
+        let pe = crate::formats::pe::PeParser::with_options(heur_buf, options).ok();
         Some(FormatSpecificTriage {
-            pe: Some(PeTriageInfo { rich_header }),
+            pe: Some(PeTriageInfo {
+                rich_header,
+                time_date_stamp: pe.as_ref().map(|p| p.time_date_stamp()),
+                compile_time: pe.as_ref().and_then(|p| p.compile_timestamp()),
+                reproducible: pe
+                    .as_ref()
+                    .and_then(|p| p.debug_directory().ok())
+                    .is_some_and(|d| d.is_reproducible()),
+            }),
             ..Default::default()
         })
     } else {
@@ -409,14 +655,21 @@ fn perform_format_analysis(
     };
 
     // Compute symbol summary, using heuristics buffer (bounded to MAX_ENTROPY_SIZE)
-    let symbols_sum = header_formats
-        .first()
-        .map(|fmt| symbols::summarize_symbols(heur_buf, *fmt, &BudgetCaps::default()));
+    let symbols_sum = timed(
+        tracing::debug_span!("symbols", symbols_ms = Empty),
+        "symbols_ms",
+        &mut timings.symbols_ms,
+        || {
+            header_formats
+                .first()
+                .map(|fmt| symbols::summarize_symbols(heur_buf, *fmt, &BudgetCaps::default()))
+        },
+    );
 
     // Detect overlay data if we have a recognized binary format
-    let overlay = header_formats
-        .first()
-        .and_then(|fmt| crate::triage::overlay::detect_overlay(heur_buf, *fmt));
+    let overlay = header_formats.first().and_then(|fmt| {
+        crate::triage::overlay::detect_overlay_with_config(heur_buf, *fmt, overlay_cfg)
+    });
 
     // Compute similarity summary (CTPH for all; imphash for PE if available)
     let similarity = {
@@ -428,22 +681,7 @@ fn perform_format_analysis(
         };
         // CTPH over bounded heuristics buffer, if enabled
         let ctph = if sim_cfg.enable_ctph {
-            let (w, d, p) = if sim_cfg.window_size == 0 || sim_cfg.digest_size == 0 {
-                if heur_buf.len() < 16 * 1024 {
-                    (8usize, 4usize, 8u8)
-                } else if heur_buf.len() < 1024 * 1024 {
-                    (16usize, 5usize, 16u8)
-                } else {
-                    (32usize, 6usize, 16u8)
-                }
-            } else {
-                (sim_cfg.window_size, sim_cfg.digest_size, sim_cfg.precision)
-            };
-            let cfg = crate::similarity::CtphConfig {
-                window_size: w,
-                digest_size: d,
-                precision: p,
-            };
+            let cfg = sim_cfg.ctph_config_for(heur_buf.len());
             Some(crate::similarity::ctph_hash(heur_buf, &cfg))
         } else {
             None
@@ -496,6 +734,72 @@ fn perform_format_analysis(
     (format_specific, symbols_sum, overlay, similarity, signing)
 }
 
+/// Run source language / compiler detection over already-extracted evidence.
+///
+/// Only runs for recognized executable formats. Symbols come from the symbol
+/// summary, strings from the string samples, the compiler hint from the PE
+/// Rich Header or the ELF `.comment` section.
+fn detect_source_language(
+    heur_buf: &[u8],
+    path: &str,
+    header_formats: &[Format],
+    symbols_sum: &Option<SymbolSummary>,
+    strings: &Option<StringsSummary>,
+    format_specific: &Option<FormatSpecificTriage>,
+) -> Option<LanguageDetectionResult> {
+    let format = header_formats.first().copied()?;
+    let symbol_names: Vec<String> = symbols_sum
+        .as_ref()
+        .map(|s| {
+            let mut v = s.import_names.clone().unwrap_or_default();
+            v.extend(s.export_names.iter().flatten().cloned());
+            v
+        })
+        .unwrap_or_default();
+    let string_texts: Vec<String> = strings
+        .as_ref()
+        .and_then(|s| s.strings.as_ref())
+        .map(|v| v.iter().map(|d| d.text.clone()).collect())
+        .unwrap_or_default();
+    let libraries: Vec<String> = string_texts
+        .iter()
+        .filter(|t| {
+            let l = t.to_ascii_lowercase();
+            l.ends_with(".dll") || l.ends_with(".dylib") || l.ends_with(".so") || l.contains(".so.")
+        })
+        .cloned()
+        .collect();
+    let rich_header = format_specific
+        .as_ref()
+        .and_then(|f| f.pe.as_ref())
+        .and_then(|pe| pe.rich_header.as_ref());
+    let elf_comment = if format == Format::ELF {
+        elf_comment_section(heur_buf)
+    } else {
+        None
+    };
+    Some(detect_language_and_compiler_with_path(
+        &symbol_names,
+        &libraries,
+        &string_texts,
+        rich_header,
+        elf_comment.as_deref(),
+        heur_buf,
+        Some(path),
+    ))
+}
+
+/// Contents of the ELF `.comment` section (NUL-separated producer strings).
+fn elf_comment_section(data: &[u8]) -> Option<String> {
+    use object::read::{Object, ObjectSection};
+    let obj = object::read::File::parse(data).ok()?;
+    let section = obj.section_by_name(".comment")?;
+    let bytes = section.data().ok()?;
+    let text = String::from_utf8_lossy(bytes).replace('\0', " ");
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
 /// Build and finalize the triaged artifact with scoring and ranking
 #[allow(clippy::too_many_arguments)]
 fn build_and_finalize_artifact(
@@ -509,6 +813,7 @@ fn build_and_finalize_artifact(
     ea: &EntropyAnalysis,
     strings: &Option<StringsSummary>,
     symbols_sum: &Option<SymbolSummary>,
+    language: &Option<LanguageDetectionResult>,
     similarity: &Option<SimilaritySummary>,
     signing: &Option<SigningSummary>,
     packers: &Option<Vec<PackerMatch>>,
@@ -519,6 +824,7 @@ fn build_and_finalize_artifact(
     parser_results: &[crate::core::triage::ParserResult],
     initial_bytes_read: u64,
     limit_bytes: u64,
+    limit_time_ms: Option<u64>,
     declared_max_recursion: usize,
     hit_byte_limit: bool,
     merged_errors: &[TriageError],
@@ -551,6 +857,7 @@ fn build_and_finalize_artifact(
         .with_entropy_analysis(Some(ea.clone()))
         .with_strings(strings.clone())
         .with_symbols(symbols_sum.clone())
+        .with_language(language.clone())
         .with_similarity(similarity.clone())
         .with_signing(signing.clone())
         .with_packers(packers.clone())
@@ -568,7 +875,7 @@ fn build_and_finalize_artifact(
             time_ms: t0.elapsed().as_millis() as u64,
             recursion_depth: rec_depth as u32,
             limit_bytes: Some(limit_bytes),
-            limit_time_ms: None,
+            limit_time_ms,
             max_recursion_depth: Some(declared_max_recursion as u32),
             hit_byte_limit,
         }))
@@ -586,8 +893,12 @@ fn build_and_finalize_artifact(
         .build()
         .expect("All required fields are provided");
 
-    // Score and rank verdicts
-    let ranked = score::score(&prelim);
+    // Score and rank verdicts; recognized data files get a typed verdict
+    let mut ranked = score::score(&prelim);
+    annotate_verdicts(&mut ranked, hints);
+    if ranked.is_empty() {
+        ranked.extend(score::data_verdict(hints));
+    }
 
     // Build final artifact with ranked verdicts
     TriagedArtifact::builder()
@@ -603,6 +914,7 @@ fn build_and_finalize_artifact(
         .with_similarity(similarity.clone())
         .with_disasm_preview(disasm_preview)
         .with_symbols(symbols_sum.clone())
+        .with_language(language.clone())
         .with_packers(packers.clone())
         .with_containers(containers.clone())
         .with_overlay(overlay.clone())
@@ -617,7 +929,7 @@ fn build_and_finalize_artifact(
             time_ms: t0.elapsed().as_millis() as u64,
             recursion_depth: rec_depth as u32,
             limit_bytes: Some(limit_bytes),
-            limit_time_ms: None,
+            limit_time_ms,
             max_recursion_depth: Some(declared_max_recursion as u32),
             hit_byte_limit,
         }))
@@ -643,53 +955,102 @@ fn build_artifact_from_buffers(
     sniff_buf: &[u8],
     header_buf: &[u8],
     heur_buf: &[u8],
+    strings_buf: &[u8],
     max_recursion_depth: usize,
     initial_bytes_read: u64,
     limit_bytes: u64,
     declared_max_recursion: usize,
     hit_byte_limit: bool,
+    entropy_cfg: &EntropyConfig,
     strings_cfg: &StringsConfig,
     packer_cfg: &PackerConfig,
     sim_cfg: &SimilarityConfig,
+    sniffer_cfg: &SnifferConfig,
+    overlay_cfg: &OverlayConfig,
+    input_kind: InputKind,
+    max_time_ms: Option<u64>,
+    progress: Option<ProgressFn<'_>>,
 ) -> TriagedArtifact {
     let t0 = Instant::now();
+    let deadline = Deadline::from_ms(max_time_ms);
     let id = generate_id(None, size_bytes);
-    let span =
-        tracing::info_span!("triage", triage_id = %id, path = %path, size_bytes = size_bytes);
+    let span = tracing::info_span!(
+        "triage",
+        triage_id = %id,
+        path = %path,
+        size_bytes = size_bytes,
+        total_ms = Empty
+    );
     let _g = span.enter();
     info!("start");
+    let mut timings = PhaseTimings::default();
+    let mut budget_notes = Vec::new();
+    // One literal-signature pass over `heur_buf` serves the installer,
+    // container and packer detectors
+    let signature_hits = MultiScanner::builtin().scan(heur_buf);
 
     // Perform initial content analysis
     let (
         hints,
         sniff_errors,
-        verdicts,
+        mut verdicts,
         header_errors,
         header_formats,
         ea,
-        _entropy_overall,
+        entropy_overall,
         e_guess,
         e_conf,
         arch_guesses,
         entropy,
         strings,
-    ) = perform_content_analysis(sniff_buf, header_buf, heur_buf, &path, strings_cfg);
+    ) = perform_content_analysis(
+        sniff_buf,
+        header_buf,
+        heur_buf,
+        strings_buf,
+        &signature_hits,
+        &path,
+        entropy_cfg,
+        strings_cfg,
+        sniffer_cfg,
+        input_kind,
+        progress,
+        &mut timings,
+        &deadline,
+        &mut budget_notes,
+    );
 
     // Perform parser probes and container/packer discovery
-    let (parser_results, containers, rec_depth, packers) =
-        perform_parser_discovery(heur_buf, &hints, max_recursion_depth, packer_cfg);
+    progress::report(progress, Phase::Parsers);
+    let (parser_results, containers, rec_depth, packers) = timed(
+        tracing::debug_span!("parsers", parsers_ms = Empty),
+        "parsers_ms",
+        &mut timings.parsers_ms,
+        || {
+            perform_parser_discovery(
+                heur_buf,
+                &signature_hits,
+                &hints,
+                max_recursion_depth,
+                packer_cfg,
+                sim_cfg,
+                input_kind,
+            )
+        },
+    );
 
     // Phase 6: Error merging
     let container_labels: Vec<String> = containers
         .as_ref()
         .map(|v| v.iter().map(|c| c.type_name.clone()).collect())
         .unwrap_or_default();
-    let merged_errors_vec = merge_errors(
+    let mut merged_errors_vec = merge_errors(
         sniff_errors,
         header_errors,
         &hints,
         &header_formats,
         &container_labels,
+        sniffer_cfg.mismatch_severity,
         hit_byte_limit,
         limit_bytes,
         initial_bytes_read,
@@ -697,22 +1058,71 @@ fn build_artifact_from_buffers(
     .unwrap_or_default();
 
     // Phase 7: Artifact construction and scoring
-    let looks_exec =
-        !header_formats.is_empty() || hints.iter().any(|h| derive_format_from_hint(h).is_some());
+    let hinted_format = hints.iter().any(|h| derive_format_from_hint(h).is_some());
 
-    // Optional disassembly preview (bounded, budgeted): only if likely executable
-    let disasm_preview = if looks_exec {
-        compute_disasm_preview(heur_buf, &arch_guesses, e_guess, 32, 512, 5)
+    // Headerless code (e.g. dumped shellcode): surface a confident arch guess
+    // as a raw verdict rather than returning no verdicts at all.
+    let shellcode = input_kind == InputKind::Shellcode;
+    let raw_verdict = if shellcode || (verdicts.is_empty() && !hinted_format) {
+        score::raw_code_verdict(&arch_guesses, Some(entropy_overall), e_guess)
     } else {
         None
     };
+    let raw_code = raw_verdict.is_some();
+    verdicts.extend(raw_verdict);
+    let looks_exec = !header_formats.is_empty() || hinted_format || raw_code || shellcode;
+
+    // Optional disassembly preview (bounded, budgeted): only if likely executable
+    progress::report(progress, Phase::Disasm);
+    let disasm_preview = timed(
+        tracing::debug_span!("disasm", disasm_ms = Empty),
+        "disasm_ms",
+        &mut timings.disasm_ms,
+        || {
+            if !looks_exec {
+                None
+            } else if deadline.is_expired() {
+                budget_notes.push(deadline.note("disasm preview"));
+                None
+            } else {
+                compute_disasm_preview(
+                    heur_buf,
+                    &verdicts,
+                    &arch_guesses,
+                    e_guess,
+                    32,
+                    512,
+                    deadline.cap_ms(5),
+                )
+            }
+        },
+    );
+    merged_errors_vec.append(&mut budget_notes);
 
     // Perform format-specific analysis
-    let (format_specific, symbols_sum, overlay, similarity, signing) =
-        perform_format_analysis(heur_buf, &header_formats, sim_cfg);
+    progress::report(progress, Phase::FormatAnalysis);
+    let format_start = Instant::now();
+    let (format_specific, symbols_sum, overlay, similarity, signing) = perform_format_analysis(
+        heur_buf,
+        &header_formats,
+        sim_cfg,
+        overlay_cfg,
+        &mut timings,
+    );
+    let language = detect_source_language(
+        heur_buf,
+        &path,
+        &header_formats,
+        &symbols_sum,
+        &strings,
+        &format_specific,
+    );
+    timings.format_ms = format_start.elapsed().as_millis() as u64;
 
     // Build and finalize the artifact
-    let art = build_and_finalize_artifact(
+    progress::report(progress, Phase::Scoring);
+    let scoring_start = Instant::now();
+    let mut art = build_and_finalize_artifact(
         id,
         path,
         size_bytes,
@@ -723,6 +1133,7 @@ fn build_artifact_from_buffers(
         &ea,
         &strings,
         &symbols_sum,
+        &language,
         &similarity,
         &signing,
         &packers,
@@ -733,6 +1144,7 @@ fn build_artifact_from_buffers(
         &parser_results,
         initial_bytes_read,
         limit_bytes,
+        deadline.limit_ms(),
         declared_max_recursion,
         hit_byte_limit,
         &merged_errors_vec,
@@ -742,7 +1154,11 @@ fn build_artifact_from_buffers(
         &arch_guesses,
         disasm_preview,
     );
+    timings.scoring_ms = scoring_start.elapsed().as_millis() as u64;
+    art.phase_timings = Some(timings);
 
+    span.record("total_ms", t0.elapsed().as_millis() as u64);
+    progress::report(progress, Phase::Done);
     info!("complete");
     art
 }
@@ -771,12 +1187,17 @@ mod tests_inner {
         let hdr = crate::triage::headers::validate(header_buf);
         let header_formats: Vec<Format> = hdr.candidates.iter().map(|v| v.format).collect();
         assert!(header_formats.contains(&Format::ELF));
-        let errs = compute_sniffer_header_mismatches(&sn.hints, &header_formats, &[]);
+        let errs =
+            compute_sniffer_header_mismatches(&sn.hints, &header_formats, &[], Severity::Warning);
         // Expect at least one mismatch due to exe extension vs ELF header
         assert!(!errs.is_empty());
         assert!(errs
             .iter()
             .any(|e| e.kind == TriageErrorKind::SnifferMismatch));
+        // A renamed-but-valid ELF is a warning by default, not an error
+        let default_sev = SnifferConfig::default().mismatch_severity;
+        assert_eq!(default_sev, Severity::Warning);
+        assert!(errs.iter().all(|e| e.severity == Severity::Warning));
     }
 
     #[test]
@@ -797,7 +1218,12 @@ mod tests_inner {
         let mut tmp_budget = Budgets::new(data.len() as u64, 0, 0);
         let containers = engine.discover_children(&data, &mut tmp_budget, 0);
         let container_labels: Vec<String> = containers.into_iter().map(|c| c.type_name).collect();
-        let errs = compute_sniffer_header_mismatches(&sn.hints, &header_formats, &container_labels);
+        let errs = compute_sniffer_header_mismatches(
+            &sn.hints,
+            &header_formats,
+            &container_labels,
+            Severity::Warning,
+        );
         // With no validated headers and a matching container hint, do not emit a mismatch
         assert!(errs.is_empty());
     }
@@ -821,7 +1247,7 @@ mod tests_inner {
         // Expect no executable header candidates
         assert!(hdr.candidates.is_empty());
         // No mismatches computed if there are no headers
-        let errs = compute_sniffer_header_mismatches(&sn.hints, &[], &[]);
+        let errs = compute_sniffer_header_mismatches(&sn.hints, &[], &[], Severity::Warning);
         assert!(errs.is_empty());
     }
 
@@ -834,16 +1260,202 @@ mod tests_inner {
         };
         let art = analyze_bytes(&data, &limits).expect("analyze_bytes");
         let b = art.budgets.expect("budgets present");
-        // sniff(4K) + header(64K capped to data) + entropy(8K)
+        // sniff(4K), header(64K capped to data) and entropy(8K) overlap: the
+        // header window covers the other two.
         let expected_header = (data.len().min(MAX_HEADER_SIZE as usize)) as u64;
-        let expected = MAX_SNIFF_SIZE + expected_header + (8 * 1024) as u64;
-        assert_eq!(b.bytes_read, expected);
+        assert_eq!(b.bytes_read, expected_header);
         assert!(b.time_ms <= 1000, "analysis took too long: {}ms", b.time_ms);
         assert!(b.recursion_depth <= 1);
         // With small max_read_bytes, we should mark hit_byte_limit
         assert!(b.hit_byte_limit, "expected hit_byte_limit to be true");
     }
 
+    #[test]
+    fn known_good_hashes_short_circuit_path_analysis() {
+        use std::io::Write;
+
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"hello world").unwrap();
+        let digest = "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9";
+
+        let mut config = TriageConfig::default();
+        config.known_good_sha256.insert(digest.to_string());
+        let art = analyze_path_with_config(file.path(), &IOLimits::default(), &config, None)
+            .expect("analyze_path_with_config");
+        assert!(art.known_good);
+        assert_eq!(art.sha256.as_deref(), Some(digest));
+        assert_eq!(art.size_bytes, 11);
+        assert!(art.verdicts.is_empty() && art.budgets.is_none());
+
+        config.known_good_sha256 = ["0".repeat(64)].into_iter().collect();
+        let art = analyze_path_with_config(file.path(), &IOLimits::default(), &config, None)
+            .expect("analyze_path_with_config");
+        assert!(!art.known_good);
+        assert!(art.budgets.is_some());
+        assert_eq!(art.sha256.as_deref(), Some(digest));
+    }
+
+    #[test]
+    fn path_analysis_hashes_the_whole_file_only_when_asked() {
+        use sha2::{Digest, Sha256};
+        use std::io::Write;
+
+        // Longer than the read limit, so the tail is only seen by hashing.
+        let data: Vec<u8> = (0..20_000u32).map(|i| (i % 251) as u8).collect();
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(&data).unwrap();
+        let limits = IOLimits {
+            max_read_bytes: 8 * 1024,
+            max_file_size: u64::MAX,
+        };
+        let art = analyze_path(file.path(), &limits).expect("analyze_path");
+        assert_eq!(art.sha256, None);
+        assert_eq!(art.budgets.unwrap().bytes_read, 8 * 1024);
+
+        let config = TriageConfig {
+            hash_full_file: true,
+            ..Default::default()
+        };
+        let art = analyze_path_with_config(file.path(), &limits, &config, None)
+            .expect("analyze_path_with_config");
+        let expected = format!("{:x}", Sha256::digest(&data));
+        assert_eq!(art.sha256, Some(expected));
+        assert_eq!(art.budgets.unwrap().bytes_read, 8 * 1024);
+
+        // A file the prefix reads cover is hashed without opting in.
+        let small = &data[..4096];
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(small).unwrap();
+        let art = analyze_path(file.path(), &limits).expect("analyze_path");
+        assert_eq!(art.sha256, Some(format!("{:x}", Sha256::digest(small))));
+    }
+
+    #[test]
+    fn path_analysis_applies_io_entropy_and_string_settings() {
+        use std::io::Write;
+
+        let mut data = Vec::new();
+        for i in 0..2_000 {
+            data.extend(format!("string number {}\0", i).bytes());
+        }
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(&data).unwrap();
+
+        let mut config = TriageConfig::default();
+        config.io.max_read_bytes = 16 * 1024;
+        config.entropy.window_size = 1024;
+        config.entropy.step = 1024;
+        config.entropy.max_windows = 3;
+        config.heuristics.string_sample_limit = 2;
+        let art = analyze_path_with_config(file.path(), &IOLimits::default(), &config, None)
+            .expect("analyze_path_with_config");
+        let budgets = art.budgets.expect("budgets");
+        assert_eq!(budgets.limit_bytes, Some(16 * 1024));
+        assert_eq!(budgets.bytes_read, 16 * 1024);
+        let entropy = art.entropy.expect("entropy");
+        assert_eq!(entropy.window_size, Some(1024));
+        assert_eq!(entropy.windows.map(|w| w.len()), Some(3));
+        let strings = art.strings.and_then(|s| s.strings).expect("string samples");
+        assert_eq!(strings.len(), 2);
+    }
+
+    #[test]
+    fn spent_time_budget_yields_partial_results_with_notes() {
+        use std::io::Write;
+
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(&b"kernel32.dll GetProcAddress ".repeat(4096))
+            .unwrap();
+        let run = |max_time_ms| {
+            let config = TriageConfig {
+                max_time_ms,
+                ..Default::default()
+            };
+            analyze_path_with_config(file.path(), &IOLimits::default(), &config, None)
+                .expect("analyze_path_with_config")
+        };
+
+        let full = run(None);
+        assert!(full.strings.is_some());
+        assert!(!full
+            .errors
+            .iter()
+            .flatten()
+            .any(|e| e.kind == TriageErrorKind::BudgetExceeded));
+
+        let spent = run(Some(0));
+        assert!(spent.strings.is_none());
+        assert_eq!(spent.budgets.unwrap().limit_time_ms, Some(0));
+        let notes: Vec<_> = spent
+            .errors
+            .iter()
+            .flatten()
+            .filter(|e| e.kind == TriageErrorKind::BudgetExceeded)
+            .filter_map(|e| e.message.as_deref())
+            .collect();
+        assert!(notes.iter().any(|m| m.starts_with("entropy stopped early")));
+        assert!(notes.iter().any(|m| m.starts_with("strings stopped early")));
+    }
+
+    #[test]
+    fn strings_scan_window_is_independent_of_entropy_window() {
+        use std::io::Write;
+
+        // A string past the 1 MiB entropy prefix.
+        let mut data = vec![0u8; 3 << 19];
+        data.extend_from_slice(b"http://late.example.com/payload\0");
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(&data).unwrap();
+
+        let mut config = TriageConfig::default();
+        let art = analyze_path_with_config(file.path(), &IOLimits::default(), &config, None)
+            .expect("analyze_path_with_config");
+        assert!(art.strings.is_none());
+
+        config.heuristics.string_scan_bytes = 4 << 20;
+        // Debug builds scan far slower than the 10 ms/MiB release default.
+        config.heuristics.string_time_guard_ms = 1_000;
+        let art = analyze_path_with_config(file.path(), &IOLimits::default(), &config, None)
+            .expect("analyze_path_with_config");
+        assert!(art.strings.is_some_and(|s| s.ascii_count > 0));
+        // Entropy still sees only the all-zero first MiB.
+        assert_eq!(art.entropy.and_then(|e| e.overall), Some(0.0));
+        assert_eq!(art.budgets.unwrap().bytes_read, data.len() as u64);
+    }
+
+    #[test]
+    fn memory_dump_mode_reports_embedded_modules() {
+        use std::io::Write;
+
+        // An ELF at offset 0 and a PE image mapped further into the dump.
+        let mut dump = vec![0u8; 0x4000];
+        dump[..7].copy_from_slice(b"\x7FELF\x02\x01\x01");
+        dump[0x2000..0x2002].copy_from_slice(b"MZ");
+        dump[0x203C..0x2040].copy_from_slice(&0x80u32.to_le_bytes());
+        dump[0x2080..0x2084].copy_from_slice(b"PE\0\0");
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(&dump).unwrap();
+
+        let config = TriageConfig {
+            input_kind: InputKind::MemoryDump,
+            ..Default::default()
+        };
+        let art = analyze_path_with_config(file.path(), &IOLimits::default(), &config, None)
+            .expect("analyze_path_with_config");
+        assert!(!art
+            .verdicts
+            .iter()
+            .any(|v| matches!(v.format, Format::ELF | Format::PE)));
+        assert!(art.parse_status.is_none());
+        let kids: Vec<_> = art
+            .containers
+            .iter()
+            .flatten()
+            .map(|c| (c.type_name.as_str(), c.offset))
+            .collect();
+        assert_eq!(kids, vec![("elf", 0), ("pe", 0x2000)]);
+    }
+
     #[test]
     fn hit_byte_limit_is_false_when_limits_are_high() {
         let data = vec![0u8; 8 * 1024];
@@ -856,6 +1468,134 @@ mod tests_inner {
         assert!(!b.hit_byte_limit);
         assert_eq!(b.limit_bytes, Some(limits.max_read_bytes));
     }
+
+    #[test]
+    #[cfg(feature = "disasm")]
+    fn disasm_preview_starts_at_entry_point() {
+        let Ok(data) = std::fs::read("samples/adversarial/embedded/xor_url_in_elf.elf") else {
+            return;
+        };
+        if !data.starts_with(b"\x7fELF") {
+            return;
+        }
+        // e_entry of the sample is 0x1130 (`_start` in .text).
+        assert_eq!(preview_start(&data), Some((0x1130, 0x1130)));
+        assert_eq!(preview_start(&[0x90u8; 16]), Some((0, 0)));
+        let art = analyze_bytes(&data, &IOLimits::default()).expect("analyze_bytes");
+        if let Some(preview) = art.disasm_preview {
+            assert!(preview[0].starts_with("00001130:"), "got {:?}", preview[0]);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "disasm")]
+    fn disasm_preview_uses_verdict_byte_order() {
+        // addiu $sp, $sp, -32; jr $ra; nop -- big-endian MIPS
+        let code = [
+            0x27, 0xbd, 0xff, 0xe0, 0x03, 0xe0, 0x00, 0x08, 0x00, 0x00, 0x00, 0x00,
+        ];
+        let verdict =
+            TriageVerdict::try_new(Format::ELF, Arch::MIPS, 32, Endianness::Big, 0.9, None)
+                .unwrap();
+        let guesses = [(Arch::X86_64, 0.5)];
+        let preview =
+            compute_disasm_preview(&code, &[verdict], &guesses, Endianness::Little, 8, 64, 50)
+                .expect("preview");
+        assert!(preview[0].contains("addiu"), "got {:?}", preview);
+        assert!(preview[1].contains("jr"), "got {:?}", preview);
+    }
+
+    #[test]
+    fn headerless_code_gets_raw_verdict() {
+        // push rbp; mov rbp, rsp; sub rsp, i; call rel32; mov rax, [rbp-x]; leave; ret; nop
+        let mut code = Vec::new();
+        for i in 0..=255u8 {
+            code.extend_from_slice(&[0x55, 0x48, 0x89, 0xe5, 0x48, 0x83, 0xec, i, 0xe8, i]);
+            code.extend_from_slice(&[i.wrapping_mul(7), 0xff, 0xff, 0x48, 0x8b, 0x45]);
+            code.extend_from_slice(&[i.wrapping_mul(13), 0xc9, 0xc3, 0x90]);
+        }
+        let art = analyze_bytes(&code, &IOLimits::default()).expect("analyze_bytes");
+        let top = art.verdicts.first().expect("raw verdict");
+        assert_eq!(top.format, Format::Raw);
+        assert_eq!(top.arch, Arch::X86_64);
+        assert!(art.heuristic_arch.is_some());
+
+        // Zero padding has no code-like entropy: still no verdict.
+        let art = analyze_bytes(&[0u8; 4096], &IOLimits::default()).expect("analyze_bytes");
+        assert!(art.verdicts.is_empty());
+    }
+
+    #[test]
+    fn data_files_get_a_typed_verdict() {
+        let mut png = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+        png.resize(512, 0);
+        let art = analyze_bytes(&png, &IOLimits::default()).expect("analyze_bytes");
+        let top = art.verdicts.first().expect("data verdict");
+        assert_eq!(top.format, Format::Unknown);
+        assert_eq!(top.mime.as_deref(), Some("image/png"));
+        assert_eq!(top.label.as_deref(), Some("png"));
+    }
+
+    #[test]
+    fn analyze_range_triages_a_carved_region() {
+        use std::io::Write;
+
+        let mut png = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+        png.resize(512, 0);
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(&[0xAA; 1000]).unwrap();
+        file.write_all(&png).unwrap();
+
+        let limits = IOLimits::default();
+        let art = analyze_range(file.path(), 1000, 4096, &limits).expect("analyze_range");
+        assert_eq!(art.source_range, Some((1000, 512)));
+        assert_eq!(art.size_bytes, 512);
+        assert_eq!(art.path, file.path().to_string_lossy());
+        assert_eq!(art.sha256, Some(format!("{:x}", Sha256::digest(&png))));
+        let top = art.verdicts.first().expect("data verdict");
+        assert_eq!(top.mime.as_deref(), Some("image/png"));
+
+        assert!(analyze_range(file.path(), 4096, 16, &limits).is_err());
+        let tight = IOLimits {
+            max_file_size: 256,
+            ..limits
+        };
+        assert!(analyze_range(file.path(), 0, 1024, &tight).is_err());
+    }
+
+    #[test]
+    fn progress_reports_every_phase_in_order() {
+        let seen = std::cell::RefCell::new(Vec::new());
+        let cb = |phase: Phase, frac: f32| seen.borrow_mut().push((phase, frac));
+        let art = analyze_bytes_with_progress(&[0x90u8; 4096], &IOLimits::default(), Some(&cb))
+            .expect("analyze_bytes");
+        assert!(art.phase_timings.is_some());
+        let seen = seen.into_inner();
+        assert_eq!(seen.first(), Some(&(Phase::Sniffing, 0.0)));
+        assert_eq!(seen.last(), Some(&(Phase::Done, 1.0)));
+        assert_eq!(seen.len(), 9);
+        assert!(seen.windows(2).all(|w| w[0].1 < w[1].1));
+    }
+
+    #[test]
+    fn language_detection_runs_only_for_executables() {
+        let art = analyze_bytes(&[0u8; 4096], &IOLimits::default()).expect("analyze_bytes");
+        assert!(art.language.is_none());
+
+        let Ok(data) = std::fs::read("samples/adversarial/embedded/xor_url_in_elf.elf") else {
+            return;
+        };
+        if !data.starts_with(b"\x7fELF") {
+            return;
+        }
+        let art = analyze_bytes(&data, &IOLimits::default()).expect("analyze_bytes");
+        let lang = art.language.expect("language detection for ELF");
+        let compiler = lang.compiler.expect("compiler from .comment");
+        assert_eq!(
+            compiler.vendor,
+            crate::triage::compiler_detection::CompilerVendor::Gnu
+        );
+    }
 }
 
 #[cfg(feature = "python-ext")]
@@ -895,10 +1635,23 @@ pub fn analyze_path_py(
         max_file_size: _max_file_size,
     };
     let mut reader = SafeFileReader::open(p, limits.clone())
-        .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("{}", e)))?;
+        .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("{}", e)))?
+        .with_hashing(&[HashAlg::Sha256]);
     if reader.size() == 0 {
         return Err(pyo3::exceptions::PyValueError::new_err("Empty file"));
     }
+    let mut sha256 = None;
+    if let Some(cfg) = _config.as_ref().filter(|c| !c.known_good_sha256.is_empty()) {
+        let digest = reader
+            .finalize_hashes()
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("{}", e)))?
+            .sha256
+            .unwrap_or_default();
+        if cfg.known_good_sha256.contains(&digest) {
+            return Ok(known_good_artifact(path, reader.size(), digest));
+        }
+        sha256 = Some(digest);
+    }
     let sniff = reader
         .read_prefix(MAX_SNIFF_SIZE)
         .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("{}", e)))?;
@@ -908,7 +1661,19 @@ pub fn analyze_path_py(
     let heur = reader
         .read_prefix(MAX_ENTROPY_SIZE)
         .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("{}", e)))?;
-    let bytes_read = sniff.len() as u64 + header.len() as u64 + heur.len() as u64;
+    let string_scan_bytes = _config
+        .as_ref()
+        .map(|c| c.heuristics.string_scan_bytes)
+        .unwrap_or_else(|| crate::triage::config::HeuristicsConfig::default().string_scan_bytes);
+    let string_time_guard_ms = _config
+        .as_ref()
+        .map(|c| c.heuristics.string_time_guard_ms)
+        .unwrap_or_else(|| crate::triage::config::HeuristicsConfig::default().string_time_guard_ms);
+    let strings_buf = reader
+        .read_prefix(string_scan_bytes as u64)
+        .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("{}", e)))?;
+    // The prefixes overlap; count each file byte once.
+    let bytes_read = reader.bytes_read();
     // detect if any prefix was capped by byte limit
     let file_size = reader.size();
     let cap = limits.max_read_bytes;
@@ -916,16 +1681,22 @@ pub fn analyze_path_py(
         && (sniff.len() as u64 == cap
             || header.len() as u64 == cap
             || heur.len() as u64 == cap
+            || strings_buf.len() as u64 == cap
             || MAX_SNIFF_SIZE > cap
             || MAX_HEADER_SIZE > cap
-            || MAX_ENTROPY_SIZE > cap);
+            || MAX_ENTROPY_SIZE > cap
+            || string_scan_bytes as u64 > cap);
     let strings_cfg = StringsConfig {
         min_length: _min_string_length,
         max_samples: _max_string_samples,
-        max_scan_bytes: MAX_ENTROPY_SIZE as usize,
-        time_guard_ms: 10,
+        max_string_length: 4096,
+        dedup_strings: false,
+        include_only: None,
+        max_scan_bytes: string_scan_bytes,
+        time_guard_ms: string_time_guard_ms,
         enable_language: _enable_language,
         max_lang_detect: _max_lang_detect,
+        ordered_lang_budget: false,
         min_len_for_detect: 4,
         max_len_for_lingua: 32,
         min_lang_confidence: 0.5,
@@ -936,6 +1707,10 @@ pub fn analyze_path_py(
         max_classify: _max_classify,
         max_ioc_per_string: _max_ioc_per_string,
         max_ioc_samples: 50,
+        ioc_sample_max_len: 512,
+        parse_url_components: false,
+        defang_iocs: false,
+        thread_pool: None,
     };
     let packer_cfg: PackerConfig = _config
         .as_ref()
@@ -945,21 +1720,51 @@ pub fn analyze_path_py(
         .as_ref()
         .map(|c| c.similarity.clone())
         .unwrap_or_else(SimilarityConfig::default);
-    Ok(build_artifact_from_buffers(
+    let sniffer_cfg: SnifferConfig = _config
+        .as_ref()
+        .map(|c| c.sniffer.clone())
+        .unwrap_or_default();
+    let overlay_cfg: OverlayConfig = _config
+        .as_ref()
+        .map(|c| c.overlay.clone())
+        .unwrap_or_default();
+    let entropy_cfg: EntropyConfig = _config
+        .as_ref()
+        .map(|c| c.entropy.clone())
+        .unwrap_or_default();
+    let input_kind = _config.as_ref().map(|c| c.input_kind).unwrap_or_default();
+    let max_time_ms = _config.as_ref().and_then(|c| c.max_time_ms);
+    let hash_full_file = _config.as_ref().is_some_and(|c| c.hash_full_file);
+    if sha256.is_none() && (hash_full_file || bytes_read >= file_size) {
+        sha256 = reader
+            .finalize_hashes()
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("{}", e)))?
+            .sha256;
+    }
+    let mut art = build_artifact_from_buffers(
         path,
         reader.size() as usize,
         &sniff,
         &header,
         &heur,
+        &strings_buf,
         _max_recursion_depth,
         bytes_read,
         limits.max_read_bytes,
         _max_recursion_depth,
         hit_byte_limit,
+        &entropy_cfg,
         &strings_cfg,
         &packer_cfg,
         &sim_cfg,
-    ))
+        &sniffer_cfg,
+        &overlay_cfg,
+        input_kind,
+        max_time_ms,
+        None,
+    );
+    art.sha256 = sha256;
+    Ok(art)
 }
 
 #[cfg(feature = "python-ext")]
@@ -994,26 +1799,55 @@ pub fn analyze_bytes_py(
     if data.is_empty() {
         return Err(pyo3::exceptions::PyValueError::new_err("Empty data"));
     }
+    if let Some(cfg) = config.as_ref().filter(|c| !c.known_good_sha256.is_empty()) {
+        let digest = format!("{:x}", Sha256::digest(&data));
+        if cfg.known_good_sha256.contains(&digest) {
+            return Ok(known_good_artifact(
+                "<memory>".to_string(),
+                data.len() as u64,
+                digest,
+            ));
+        }
+    }
     let sniff_len = data.len().min(MAX_SNIFF_SIZE as usize);
     let header_len = data.len().min(MAX_HEADER_SIZE as usize);
     let ent_len = data.len().min(MAX_ENTROPY_SIZE as usize);
-    let bytes_read = (sniff_len + header_len + ent_len) as u64;
+    let string_scan_bytes = config
+        .as_ref()
+        .map(|c| c.heuristics.string_scan_bytes)
+        .unwrap_or_else(|| crate::triage::config::HeuristicsConfig::default().string_scan_bytes);
+    let string_time_guard_ms = config
+        .as_ref()
+        .map(|c| c.heuristics.string_time_guard_ms)
+        .unwrap_or_else(|| crate::triage::config::HeuristicsConfig::default().string_time_guard_ms);
+    let strings_len = data
+        .len()
+        .min(string_scan_bytes)
+        .min(usize::try_from(max_read_bytes).unwrap_or(usize::MAX));
+    // All windows are prefixes of `data`; count each byte once.
+    let bytes_read = sniff_len.max(header_len).max(ent_len).max(strings_len) as u64;
     let cap = max_read_bytes;
     let data_len = data.len() as u64;
     let hit_byte_limit = data_len > cap
         && (sniff_len as u64 == cap
             || header_len as u64 == cap
             || ent_len as u64 == cap
+            || strings_len as u64 == cap
             || MAX_SNIFF_SIZE > cap
             || MAX_HEADER_SIZE > cap
-            || MAX_ENTROPY_SIZE > cap);
+            || MAX_ENTROPY_SIZE > cap
+            || string_scan_bytes as u64 > cap);
     let strings_cfg = StringsConfig {
         min_length: min_string_length,
         max_samples: max_string_samples,
-        max_scan_bytes: ent_len,
-        time_guard_ms: 10,
+        max_string_length: 4096,
+        dedup_strings: false,
+        include_only: None,
+        max_scan_bytes: strings_len,
+        time_guard_ms: string_time_guard_ms,
         enable_language,
         max_lang_detect,
+        ordered_lang_budget: false,
         min_len_for_detect: 4,
         max_len_for_lingua: 32,
         min_lang_confidence: 0.5,
@@ -1024,6 +1858,10 @@ pub fn analyze_bytes_py(
         max_classify,
         max_ioc_per_string,
         max_ioc_samples: 50,
+        ioc_sample_max_len: 512,
+        parse_url_components: false,
+        defang_iocs: false,
+        thread_pool: None,
     };
     let packer_cfg: PackerConfig = config
         .as_ref()
@@ -1033,69 +1871,208 @@ pub fn analyze_bytes_py(
         .as_ref()
         .map(|c| c.similarity.clone())
         .unwrap_or_else(SimilarityConfig::default);
+    let sniffer_cfg: SnifferConfig = config
+        .as_ref()
+        .map(|c| c.sniffer.clone())
+        .unwrap_or_default();
+    let overlay_cfg: OverlayConfig = config
+        .as_ref()
+        .map(|c| c.overlay.clone())
+        .unwrap_or_default();
+    let entropy_cfg: EntropyConfig = config
+        .as_ref()
+        .map(|c| c.entropy.clone())
+        .unwrap_or_default();
+    let input_kind = config.as_ref().map(|c| c.input_kind).unwrap_or_default();
+    let max_time_ms = config.as_ref().and_then(|c| c.max_time_ms);
     Ok(build_artifact_from_buffers(
         "<memory>".to_string(),
         data.len(),
         &data[..sniff_len],
         &data[..header_len],
         &data[..ent_len],
+        &data[..strings_len],
         max_recursion_depth,
         bytes_read,
         max_read_bytes,
         max_recursion_depth,
         hit_byte_limit,
+        &entropy_cfg,
         &strings_cfg,
         &packer_cfg,
         &sim_cfg,
+        &sniffer_cfg,
+        &overlay_cfg,
+        input_kind,
+        max_time_ms,
+        None,
     ))
 }
 
 /// Pure Rust API: analyze a file path with I/O limits.
+///
+/// Parallel stages run on the current rayon pool; call this inside
+/// `pool.install(..)` to bound the threads a single file may use.
 pub fn analyze_path<P: AsRef<Path>>(
     path: P,
     limits: &IOLimits,
+) -> std::io::Result<TriagedArtifact> {
+    analyze_path_with_progress(path, limits, None)
+}
+
+/// Like [`analyze_path`], reporting each pipeline [`Phase`] to `progress`.
+pub fn analyze_path_with_progress<P: AsRef<Path>>(
+    path: P,
+    limits: &IOLimits,
+    progress: Option<ProgressFn<'_>>,
+) -> std::io::Result<TriagedArtifact> {
+    let mut config = TriageConfig::default();
+    config.io.max_read_bytes = usize::try_from(limits.max_read_bytes).unwrap_or(usize::MAX);
+    config.io.max_file_size = limits.max_file_size;
+    analyze_path_with_config(path, limits, &config, progress)
+}
+
+/// Like [`analyze_path_with_progress`], taking the read sizes, entropy,
+/// string, packer, similarity, sniffer and overlay settings from `config`.
+///
+/// `limits` and `config.io` both bound the run; the tighter of
+/// `max_read_bytes` and of `max_file_size` applies.
+///
+/// The prefix reads are hashed as they happen, so `sha256` is set when they
+/// cover the whole file. Larger files only get a `sha256` when
+/// `config.hash_full_file` is set, which reads the rest of the file outside
+/// `max_read_bytes`. When `config.known_good_sha256` is non-empty the file is
+/// hashed first, and an allowlisted file returns a minimal `known_good`
+/// artifact without running the pipeline.
+pub fn analyze_path_with_config<P: AsRef<Path>>(
+    path: P,
+    limits: &IOLimits,
+    config: &TriageConfig,
+    progress: Option<ProgressFn<'_>>,
 ) -> std::io::Result<TriagedArtifact> {
     let p = path.as_ref();
-    let mut reader = SafeFileReader::open(p, limits.clone())?;
+    let limits = IOLimits {
+        max_read_bytes: limits.max_read_bytes.min(config.io.max_read_bytes as u64),
+        max_file_size: limits.max_file_size.min(config.io.max_file_size),
+    };
+    let sniff_size = config.io.max_sniff_size as u64;
+    let header_size = config.io.max_header_size as u64;
+    let entropy_size = config.io.max_entropy_size as u64;
+    let mut reader = SafeFileReader::open(p, limits.clone())?.with_hashing(&[HashAlg::Sha256]);
     if reader.size() == 0 {
         return Err(std::io::Error::new(
             std::io::ErrorKind::InvalidData,
             "Empty file",
         ));
     }
-    let sniff = reader.read_prefix(MAX_SNIFF_SIZE)?;
-    let header = reader.read_prefix(MAX_HEADER_SIZE)?;
-    let heur = reader.read_prefix(MAX_ENTROPY_SIZE)?;
-    let bytes_read = sniff.len() as u64 + header.len() as u64 + heur.len() as u64;
+    let mut sha256 = None;
+    if !config.known_good_sha256.is_empty() {
+        let digest = reader.finalize_hashes()?.sha256.unwrap_or_default();
+        if config.known_good_sha256.contains(&digest) {
+            return Ok(known_good_artifact(
+                p.to_string_lossy().into_owned(),
+                reader.size(),
+                digest,
+            ));
+        }
+        sha256 = Some(digest);
+    }
+    let sniff = reader.read_prefix(sniff_size)?;
+    let header = reader.read_prefix(header_size)?;
+    let heur = reader.read_prefix(entropy_size)?;
+    let strings_cfg = StringsConfig {
+        min_length: config.heuristics.min_string_length,
+        max_samples: config.heuristics.string_sample_limit,
+        max_scan_bytes: config.heuristics.string_scan_bytes,
+        time_guard_ms: config.heuristics.string_time_guard_ms,
+        ..Default::default()
+    };
+    let strings_buf = reader.read_prefix(strings_cfg.max_scan_bytes as u64)?;
+    // The prefixes overlap; count each file byte once.
+    let bytes_read = reader.bytes_read();
     let cap = limits.max_read_bytes;
     let file_size = reader.size();
     let hit_byte_limit = file_size > cap
         && (sniff.len() as u64 == cap
             || header.len() as u64 == cap
             || heur.len() as u64 == cap
-            || MAX_SNIFF_SIZE > cap
-            || MAX_HEADER_SIZE > cap
-            || MAX_ENTROPY_SIZE > cap);
-    let strings_cfg = StringsConfig::default();
-    Ok(build_artifact_from_buffers(
+            || strings_buf.len() as u64 == cap
+            || sniff_size > cap
+            || header_size > cap
+            || entropy_size > cap
+            || strings_cfg.max_scan_bytes as u64 > cap);
+    // Finishing the hash reads nothing more once the prefixes cover the file.
+    if sha256.is_none() && (config.hash_full_file || bytes_read >= file_size) {
+        sha256 = reader.finalize_hashes()?.sha256;
+    }
+    let mut art = build_artifact_from_buffers(
         p.to_string_lossy().into_owned(),
         reader.size() as usize,
         &sniff,
         &header,
         &heur,
+        &strings_buf,
         1,
         bytes_read,
         limits.max_read_bytes,
         1,
         hit_byte_limit,
+        &config.entropy,
         &strings_cfg,
-        &PackerConfig::default(),
-        &SimilarityConfig::default(),
-    ))
+        &config.packers,
+        &config.similarity,
+        &config.sniffer,
+        &config.overlay,
+        config.input_kind,
+        config.max_time_ms,
+        progress,
+    );
+    art.sha256 = sha256;
+    Ok(art)
 }
 
 /// Pure Rust API: analyze raw bytes with I/O limits (only used for budgets; limits.max_read_bytes bounds processing).
 pub fn analyze_bytes(data: &[u8], limits: &IOLimits) -> std::io::Result<TriagedArtifact> {
+    analyze_bytes_with_progress(data, limits, None)
+}
+
+/// Like [`analyze_bytes`], reporting each pipeline [`Phase`] to `progress`.
+pub fn analyze_bytes_with_progress(
+    data: &[u8],
+    limits: &IOLimits,
+    progress: Option<ProgressFn<'_>>,
+) -> std::io::Result<TriagedArtifact> {
+    analyze_buffer("<memory>".to_string(), data, limits, progress)
+}
+
+/// Triage `len` bytes of `path` starting at `offset`, such as a carved
+/// payload or one segment of a memory dump, without extracting it first.
+///
+/// The range is clamped to the end of the file and must fit in
+/// `limits.max_file_size`; the file itself may be larger. The artifact
+/// keeps `path`, records the clamped range in `source_range` and carries the
+/// `sha256` of the range bytes, which are all read up front.
+pub fn analyze_range<P: AsRef<Path>>(
+    path: P,
+    offset: u64,
+    len: u64,
+    limits: &IOLimits,
+) -> std::io::Result<TriagedArtifact> {
+    let p = path.as_ref();
+    let data = IOUtils::read_file_range(p, offset, len, limits.max_file_size)?;
+    let mut artifact = analyze_buffer(p.to_string_lossy().into_owned(), &data, limits, None)?;
+    artifact.source_range = Some((offset, data.len() as u64));
+    artifact.sha256 = Some(format!("{:x}", Sha256::digest(&data)));
+    Ok(artifact)
+}
+
+/// Shared body of the in-memory entry points; `path` labels the artifact.
+fn analyze_buffer(
+    path: String,
+    data: &[u8],
+    limits: &IOLimits,
+    progress: Option<ProgressFn<'_>>,
+) -> std::io::Result<TriagedArtifact> {
     if data.is_empty() {
         return Err(std::io::Error::new(
             std::io::ErrorKind::InvalidData,
@@ -1106,30 +2083,43 @@ pub fn analyze_bytes(data: &[u8], limits: &IOLimits) -> std::io::Result<TriagedA
     let header_len = data.len().min(MAX_HEADER_SIZE as usize);
     let ent_bound = limits.max_read_bytes.min(MAX_ENTROPY_SIZE) as usize;
     let ent_len = data.len().min(ent_bound);
-    let bytes_read = (sniff_len + header_len + ent_len) as u64;
+    let strings_cfg = StringsConfig::default();
+    let strings_len = data
+        .len()
+        .min(strings_cfg.max_scan_bytes)
+        .min(usize::try_from(limits.max_read_bytes).unwrap_or(usize::MAX));
+    // All windows are prefixes of `data`; count each byte once.
+    let bytes_read = sniff_len.max(header_len).max(ent_len).max(strings_len) as u64;
     let cap = limits.max_read_bytes;
     let data_len = data.len() as u64;
     let hit_byte_limit = data_len > cap
         && (sniff_len as u64 == cap
             || header_len as u64 == cap
             || ent_len as u64 == cap
+            || strings_len as u64 == cap
             || ent_bound as u64 == cap
             || MAX_SNIFF_SIZE > cap
             || MAX_HEADER_SIZE > cap);
-    let strings_cfg = StringsConfig::default();
     Ok(build_artifact_from_buffers(
-        "<memory>".to_string(),
+        path,
         data.len(),
         &data[..sniff_len],
         &data[..header_len],
         &data[..ent_len],
+        &data[..strings_len],
         1,
         bytes_read,
         limits.max_read_bytes,
         1,
         hit_byte_limit,
+        &EntropyConfig::default(),
         &strings_cfg,
         &PackerConfig::default(),
         &SimilarityConfig::default(),
+        &SnifferConfig::default(),
+        &OverlayConfig::default(),
+        InputKind::File,
+        None,
+        progress,
     ))
 }
@@ -8,8 +8,10 @@
 //! - Code generation patterns
 //! - String and error message analysis
 
+use crate::triage::search;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::OnceLock;
 
 /// Programming languages that can be detected
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -59,7 +61,7 @@ pub enum CompilerVendor {
 
 /// Detailed compiler information
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[cfg_attr(feature = "python-ext", pyo3::pyclass)]
+#[cfg_attr(feature = "python-ext", pyo3::pyclass(get_all))]
 pub struct CompilerInfo {
     pub vendor: CompilerVendor,
     pub product_name: String,
@@ -80,6 +82,9 @@ pub struct LanguageEvidence {
     pub go_symbols: u32,
     pub swift_symbols: u32,
     pub objc_symbols: u32,
+    pub nim_symbols: u32,
+    pub zig_symbols: u32,
+    pub crystal_symbols: u32,
     pub plain_c_symbols: u32,
 
     // Runtime library evidence
@@ -93,6 +98,9 @@ pub struct LanguageEvidence {
     pub cpp_error_strings: u32,
     pub rust_panic_strings: u32,
     pub go_error_strings: u32,
+    pub nim_strings: u32,
+    pub zig_panic_strings: u32,
+    pub crystal_strings: u32,
 
     // Metadata evidence
     pub has_rich_header: bool,
@@ -103,8 +111,8 @@ pub struct LanguageEvidence {
 }
 
 /// Final detection result with confidence scoring
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[cfg_attr(feature = "python-ext", pyo3::pyclass)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "python-ext", pyo3::pyclass(get_all))]
 pub struct LanguageDetectionResult {
     pub language: SourceLanguage,
     pub compiler: Option<CompilerInfo>,
@@ -135,6 +143,33 @@ pub fn detect_language_from_symbols(symbols: &[String]) -> LanguageEvidence {
         } else if symbol.starts_with("?") || symbol.starts_with("@@") {
             evidence.cpp_msvc_symbols += 1;
         }
+        // Nim runtime entry points and module init procs ("..._DatInit000")
+        else if symbol.starts_with("NimMain")
+            || symbol.starts_with("nimGC_")
+            || symbol.starts_with("nimFrame")
+            || symbol.starts_with("nimRegisterGlobalMarker")
+            || symbol.ends_with("DatInit000")
+            || symbol.contains("dotnim_")
+        {
+            evidence.nim_symbols += 1;
+        }
+        // Zig standard library / panic handler (dotted, fully qualified)
+        else if symbol.starts_with("builtin.")
+            || symbol.starts_with("std.start.")
+            || symbol.starts_with("std.debug.")
+            || symbol.starts_with("std.os.")
+            || symbol.starts_with("std.heap.")
+            || symbol.starts_with("std.fmt.")
+        {
+            evidence.zig_symbols += 1;
+        }
+        // Crystal runtime hooks and mangled "*Crystal::..." procs
+        else if symbol.starts_with("__crystal_")
+            || symbol.starts_with("*Crystal::")
+            || symbol.starts_with("*GC::")
+        {
+            evidence.crystal_symbols += 1;
+        }
         // Go symbols (dot notation)
         else if (symbol.contains(".")
             && (symbol.starts_with("main.")
@@ -214,8 +249,33 @@ pub fn detect_language_from_strings(strings: &[String]) -> LanguageEvidence {
     let mut evidence = LanguageEvidence::default();
 
     for s in strings {
+        // Nim runtime: stdlib paths ("fatal.nim", "@m..nim" module names) and
+        // the default unhandled-exception/segfault handlers
+        if s.contains("fatal.nim")
+            || (s.starts_with("@m") && s.contains(".nim"))
+            || s.contains("Error: unhandled exception:")
+            || s.contains("Illegal storage access. (Attempt to read from nil?)")
+        {
+            evidence.nim_strings += 1;
+        }
+        // Zig default panic handler messages
+        else if s.contains("reached unreachable code")
+            || s.contains("attempt to unwrap error")
+            || s.contains("attempt to use null value")
+            || s.contains("integer cast truncated bits")
+            || s.contains("cast causes pointer to be null")
+        {
+            evidence.zig_panic_strings += 1;
+        }
+        // Crystal runtime
+        else if s.contains("CRYSTAL_LOAD_DEBUG_INFO")
+            || s.contains("Failed to raise an exception")
+            || s.contains("__crystal_")
+        {
+            evidence.crystal_strings += 1;
+        }
         // Rust panic messages
-        if s.contains("panicked at")
+        else if s.contains("panicked at")
             || s.contains("called `Option::unwrap()` on a `None` value")
             || s.contains("called `Result::unwrap()` on an `Err` value")
             || s.contains("attempt to ") && (s.contains("overflow") || s.contains("divide by zero"))
@@ -296,7 +356,7 @@ pub fn detect_from_elf_comment(comment: &str) -> Option<CompilerInfo> {
                 version_minor: version_parts.get(1).and_then(|s| s.parse().ok()),
                 version_patch: version_parts.get(2).and_then(|s| s.parse().ok()),
                 build_number: None,
-                target_triple: parts.get(1).map(|s| s.to_string()),
+                target_triple: triple_in_text(comment),
             });
         }
     }
@@ -351,14 +411,167 @@ pub fn detect_from_elf_comment(comment: &str) -> Option<CompilerInfo> {
     None
 }
 
+/// Target-triple shaped token: `<arch>-<vendor>-<os>[-<env>]`.
+static TRIPLE_RE: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
+
+fn triple_re() -> &'static regex::Regex {
+    TRIPLE_RE.get_or_init(|| {
+        regex::Regex::new(
+            r"\b(?:x86_64|i[3-6]86|aarch64(?:_be)?|arm64e?|armv?\d*\w*|thumbv\d+\w*|riscv(?:32|64)\w*|mips(?:64)?(?:el)?|powerpc(?:64)?(?:le)?|s390x|sparcv9|loongarch64|wasm(?:32|64))-[a-z0-9_.]+-[a-z0-9_.]+(?:-[a-z0-9_.]+)?\b",
+        )
+        .expect("valid triple regex")
+    })
+}
+
+/// OS components accepted in a triple; filters out look-alike tokens.
+const TRIPLE_OS: &[&str] = &[
+    "linux",
+    "windows",
+    "darwin",
+    "macosx",
+    "macos",
+    "ios",
+    "freebsd",
+    "netbsd",
+    "openbsd",
+    "android",
+    "androideabi",
+    "wasi",
+    "none",
+    "elf",
+    "eabi",
+    "eabihf",
+    "gnu",
+    "musl",
+    "msvc",
+    "uefi",
+    "fuchsia",
+    "solaris",
+    "illumos",
+];
+
+/// True if `s` is a plausible LLVM target triple.
+pub fn looks_like_triple(s: &str) -> bool {
+    triple_re().find(s).is_some_and(|m| m.as_str() == s)
+        && s.split('-')
+            .skip(1)
+            .any(|part| TRIPLE_OS.iter().any(|os| part.starts_with(os)))
+}
+
+/// Find a target triple in producer text, preferring explicit
+/// `--target=`/`-target`/`-triple` flags over bare triple-shaped tokens.
+fn triple_in_text(text: &str) -> Option<String> {
+    for flag in ["--target=", "-target ", "-triple ", "--target "] {
+        if let Some(pos) = text.find(flag) {
+            let rest = &text[pos + flag.len()..];
+            let cand: String = rest
+                .chars()
+                .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+                .collect();
+            if looks_like_triple(&cand) {
+                return Some(cand);
+            }
+        }
+    }
+    triple_re()
+        .find_iter(text)
+        .map(|m| m.as_str())
+        .find(|t| looks_like_triple(t))
+        .map(str::to_string)
+}
+
+/// Extract the compilation target triple (e.g. `aarch64-unknown-linux-musl`).
+///
+/// Sources, in order: DWARF producer strings (`.debug_str`,
+/// `.debug_line_str`), the `.comment` section (Clang/rustc idents) and note
+/// sections (`.note.*`). Only these sections are inspected, so stray
+/// triple-looking strings in `.rodata` are not picked up.
+pub fn extract_target_triple(data: &[u8]) -> Option<String> {
+    use object::read::{Object, ObjectSection};
+    let obj = object::read::File::parse(data).ok()?;
+    let section_text = |name: &str| -> Option<String> {
+        let sec = obj.section_by_name(name)?;
+        let bytes = sec.data().ok()?;
+        Some(String::from_utf8_lossy(bytes).replace('\0', "\n"))
+    };
+    for name in [".debug_str", ".debug_line_str", ".comment"] {
+        if let Some(t) = section_text(name).as_deref().and_then(triple_in_text) {
+            return Some(t);
+        }
+    }
+    obj.sections()
+        .filter(|s| s.name().is_ok_and(|n| n.starts_with(".note")))
+        .filter_map(|s| s.data().ok())
+        .find_map(|b| triple_in_text(&String::from_utf8_lossy(b).replace('\0', "\n")))
+}
+
+/// C runtime an ELF binary links against
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "python-ext", pyo3::pyclass)]
+pub enum Libc {
+    Glibc,
+    Musl,
+    Bionic,
+    UClibc,
+}
+
+/// Classify a `PT_INTERP` path.
+fn libc_from_interpreter(interp: &str) -> Option<Libc> {
+    let name = interp.rsplit('/').next().unwrap_or(interp);
+    if name.starts_with("ld-musl-") {
+        Some(Libc::Musl)
+    } else if name.starts_with("ld-uClibc") {
+        Some(Libc::UClibc)
+    } else if interp.starts_with("/system/bin/linker") || interp.starts_with("/apex/") {
+        Some(Libc::Bionic)
+    } else if name.starts_with("ld-linux")
+        || name == "ld.so.1"
+        || name == "ld64.so.1"
+        || name == "ld64.so.2"
+    {
+        Some(Libc::Glibc)
+    } else {
+        None
+    }
+}
+
+/// Classify a `DT_NEEDED` entry. Bare `libc.so` is shared by musl and bionic,
+/// so it is left to the interpreter to decide.
+fn libc_from_needed(lib: &str) -> Option<Libc> {
+    if lib.starts_with("libc.musl-") {
+        Some(Libc::Musl)
+    } else if lib == "libc.so.6" {
+        Some(Libc::Glibc)
+    } else if lib.starts_with("libuClibc") || lib == "libc.so.0" {
+        Some(Libc::UClibc)
+    } else {
+        None
+    }
+}
+
+/// Determine whether an ELF links against glibc, musl, bionic or uClibc.
+///
+/// The interpreter path (`/lib/ld-musl-*` vs `/lib64/ld-linux-*`) is checked
+/// first, then `DT_NEEDED` names. Static binaries without either yield `None`.
+pub fn detect_libc(parser: &crate::formats::elf::ElfParser) -> Option<Libc> {
+    if let Some(libc) = parser.interpreter().and_then(libc_from_interpreter) {
+        return Some(libc);
+    }
+    let dynamic = parser.dynamic().ok().flatten()?;
+    let needed = dynamic.needed_libraries();
+    needed.iter().find_map(|lib| libc_from_needed(lib))
+}
+
 /// Detect bytecode formats from magic numbers
 pub fn detect_bytecode_format(data: &[u8]) -> Option<SourceLanguage> {
     if data.len() < 4 {
         return None;
     }
 
-    // Java class file: 0xCAFEBABE
-    if data[0..4] == [0xCA, 0xFE, 0xBA, 0xBE] {
+    // Java class file: 0xCAFEBABE (shared with Mach-O FAT)
+    if crate::triage::format_detection::classify_cafebabe(data)
+        == Some(crate::triage::format_detection::CafeBabeKind::JavaClass)
+    {
         return Some(SourceLanguage::Java);
     }
 
@@ -377,12 +590,8 @@ pub fn detect_bytecode_format(data: &[u8]) -> Option<SourceLanguage> {
 
     // .NET/C# PE files often have specific CLI headers
     // Check for "BSJB" signature in CLR metadata
-    if data.len() > 0x100 {
-        for window in data.windows(4) {
-            if window == b"BSJB" {
-                return Some(SourceLanguage::CSharp);
-            }
-        }
+    if data.len() > 0x100 && search::contains(data, b"BSJB") {
+        return Some(SourceLanguage::CSharp);
     }
 
     None
@@ -401,44 +610,29 @@ pub enum PackerType {
 
 /// Detect if binary is packed and identify packer
 pub fn detect_packer(data: &[u8]) -> Option<PackerType> {
-    // UPX detection - look for "UPX!" signature
-    if data.windows(4).any(|w| w == b"UPX!") {
-        return Some(PackerType::UPX);
-    }
-
-    // UPX alternate signatures
-    if data.windows(3).any(|w| w == b"UPX") {
-        // Additional check for UPX0, UPX1, UPX2 section names
-        for upx_section in &[b"UPX0", b"UPX1", b"UPX2"] {
-            if data.windows(4).any(|w| w == *upx_section) {
-                return Some(PackerType::UPX);
-            }
-        }
-    }
-
-    // ASPack signature
-    if data.windows(8).any(|w| w == b".aspack\x00") {
-        return Some(PackerType::ASPack);
-    }
-
-    // PECompact signature
-    if data.windows(9).any(|w| w == b"PECompact") {
-        return Some(PackerType::PECompact);
-    }
-
-    // Themida/WinLicense signatures
-    if data.windows(7).any(|w| w == b"Themida") || data.windows(10).any(|w| w == b"WinLicense") {
-        return Some(PackerType::Themida);
-    }
-
-    // VMProtect signatures
-    if data.windows(8).any(|w| w == b".vmp0\x00\x00\x00")
-        || data.windows(8).any(|w| w == b".vmp1\x00\x00\x00")
-    {
-        return Some(PackerType::VMProtect);
-    }
-
-    None
+    // Signatures in priority order: UPX magic and section names, ASPack,
+    // PECompact, Themida/WinLicense, VMProtect section names.
+    const SIGNATURES: [(&[u8], PackerType); 10] = [
+        (b"UPX!", PackerType::UPX),
+        (b"UPX0", PackerType::UPX),
+        (b"UPX1", PackerType::UPX),
+        (b"UPX2", PackerType::UPX),
+        (b".aspack\x00", PackerType::ASPack),
+        (b"PECompact", PackerType::PECompact),
+        (b"Themida", PackerType::Themida),
+        (b"WinLicense", PackerType::Themida),
+        (b".vmp0\x00\x00\x00", PackerType::VMProtect),
+        (b".vmp1\x00\x00\x00", PackerType::VMProtect),
+    ];
+
+    static FINDERS: OnceLock<search::PatternSet<'static>> = OnceLock::new();
+    FINDERS
+        .get_or_init(|| {
+            let needles: Vec<&[u8]> = SIGNATURES.iter().map(|(sig, _)| *sig).collect();
+            search::PatternSet::new(&needles)
+        })
+        .first_match(data)
+        .map(|i| SIGNATURES[i].1)
 }
 
 /// Check if binary appears to be stripped (has minimal symbols)
@@ -496,24 +690,8 @@ pub fn is_shared_library(path: &str) -> bool {
 pub fn has_go_buildid(data: &[u8]) -> bool {
     // Look for Go build info markers
     // 1. "Go buildinf:" marker in the binary
-    let go_buildinf_marker = b"Go buildinf:";
-    if data
-        .windows(go_buildinf_marker.len())
-        .any(|window| window == go_buildinf_marker)
-    {
-        return true;
-    }
-
     // 2. .note.go.buildid ELF section (starts with "Go\0\0" in note name)
-    let go_note_marker = b"Go\x00";
-    if data
-        .windows(go_note_marker.len())
-        .any(|window| window == go_note_marker)
-    {
-        return true;
-    }
-
-    false
+    search::contains(data, b"Go buildinf:") || search::contains(data, b"Go\x00")
 }
 
 /// Extract Go version from binary if present
@@ -521,31 +699,28 @@ pub fn extract_go_version(data: &[u8]) -> Option<String> {
     // Look for "go1.XX.YY" pattern after "Go buildinf:" marker
     let go_buildinf = b"Go buildinf:";
 
-    for (i, window) in data.windows(go_buildinf.len()).enumerate() {
-        if window == go_buildinf {
-            // Look for go version pattern in next 100 bytes
-            let start = i + go_buildinf.len();
-            let end = (start + 100).min(data.len());
-            let search_area = &data[start..end];
-
-            // Find "go1." pattern
-            if let Some(go_pos) = search_area.windows(4).position(|w| &w[0..4] == b"go1.") {
-                // Extract version string (e.g., "go1.23.5")
-                let version_start = go_pos;
-                let mut version_end = version_start + 4;
-                while version_end < search_area.len() {
-                    let c = search_area[version_end];
-                    if c.is_ascii_digit() || c == b'.' {
-                        version_end += 1;
-                    } else {
-                        break;
-                    }
-                }
-                if version_end > version_start + 4 {
-                    return String::from_utf8(search_area[version_start..version_end].to_vec())
-                        .ok();
+    for i in search::find_all(data, go_buildinf) {
+        // Look for go version pattern in next 100 bytes
+        let start = i + go_buildinf.len();
+        let end = (start + 100).min(data.len());
+        let search_area = &data[start..end];
+
+        // Find "go1." pattern
+        if let Some(go_pos) = search::find(search_area, b"go1.") {
+            // Extract version string (e.g., "go1.23.5")
+            let version_start = go_pos;
+            let mut version_end = version_start + 4;
+            while version_end < search_area.len() {
+                let c = search_area[version_end];
+                if c.is_ascii_digit() || c == b'.' {
+                    version_end += 1;
+                } else {
+                    break;
                 }
             }
+            if version_end > version_start + 4 {
+                return String::from_utf8(search_area[version_start..version_end].to_vec()).ok();
+            }
         }
     }
     None
@@ -595,6 +770,9 @@ pub fn detect_language_and_compiler_with_path(
     evidence.go_symbols = symbol_evidence.go_symbols;
     evidence.swift_symbols = symbol_evidence.swift_symbols;
     evidence.objc_symbols = symbol_evidence.objc_symbols;
+    evidence.nim_symbols = symbol_evidence.nim_symbols;
+    evidence.zig_symbols = symbol_evidence.zig_symbols;
+    evidence.crystal_symbols = symbol_evidence.crystal_symbols;
     evidence.plain_c_symbols = symbol_evidence.plain_c_symbols;
 
     evidence.libstdcpp_imports = runtime_evidence.libstdcpp_imports;
@@ -605,6 +783,9 @@ pub fn detect_language_and_compiler_with_path(
     evidence.cpp_error_strings = string_evidence.cpp_error_strings;
     evidence.rust_panic_strings = string_evidence.rust_panic_strings;
     evidence.go_error_strings = string_evidence.go_error_strings;
+    evidence.nim_strings = string_evidence.nim_strings;
+    evidence.zig_panic_strings = string_evidence.zig_panic_strings;
+    evidence.crystal_strings = string_evidence.crystal_strings;
 
     // Check metadata
     evidence.has_rich_header = rich_header.is_some();
@@ -643,6 +824,12 @@ pub fn detect_language_and_compiler_with_path(
         compiler_info = detect_from_elf_comment(comment);
     }
 
+    if let Some(ci) = compiler_info.as_mut() {
+        if ci.target_triple.is_none() {
+            ci.target_triple = extract_target_triple(binary_data);
+        }
+    }
+
     // Calculate language scores
     let mut scores = HashMap::new();
 
@@ -699,6 +886,30 @@ pub fn detect_language_and_compiler_with_path(
         );
     }
 
+    // Nim / Zig / Crystal evidence (runtime symbols weigh more than strings)
+    for (lang, syms, strs) in [
+        (
+            SourceLanguage::Nim,
+            evidence.nim_symbols,
+            evidence.nim_strings,
+        ),
+        (
+            SourceLanguage::Zig,
+            evidence.zig_symbols,
+            evidence.zig_panic_strings,
+        ),
+        (
+            SourceLanguage::Crystal,
+            evidence.crystal_symbols,
+            evidence.crystal_strings,
+        ),
+    ] {
+        let score = syms as f32 * 2.0 + strs as f32 * 1.0;
+        if score > 0.0 {
+            scores.insert(lang, score);
+        }
+    }
+
     // Find the highest scoring language
     let (mut detected_language, mut max_score) = scores
         .iter()
@@ -798,6 +1009,24 @@ pub fn detect_language_and_compiler_with_path(
     if evidence.has_go_buildid {
         summary_parts.push("Go build ID present".to_string());
     }
+    if evidence.nim_symbols + evidence.nim_strings > 0 {
+        summary_parts.push(format!(
+            "{} Nim runtime indicators",
+            evidence.nim_symbols + evidence.nim_strings
+        ));
+    }
+    if evidence.zig_symbols + evidence.zig_panic_strings > 0 {
+        summary_parts.push(format!(
+            "{} Zig runtime indicators",
+            evidence.zig_symbols + evidence.zig_panic_strings
+        ));
+    }
+    if evidence.crystal_symbols + evidence.crystal_strings > 0 {
+        summary_parts.push(format!(
+            "{} Crystal runtime indicators",
+            evidence.crystal_symbols + evidence.crystal_strings
+        ));
+    }
     if evidence.has_rich_header {
         summary_parts.push("PE Rich Header (MSVC)".to_string());
     }
@@ -889,4 +1118,94 @@ mod tests {
         assert_eq!(info.version_minor, Some(0));
         assert_eq!(info.version_patch, Some(6));
     }
+
+    #[test]
+    fn test_nim_zig_crystal_detection() {
+        let nim = vec![
+            "NimMain".to_string(),
+            "NimMainModule".to_string(),
+            "atmdotdotatslibatssystemdotnim_DatInit000".to_string(),
+            "main".to_string(),
+        ];
+        let ev = detect_language_from_symbols(&nim);
+        assert_eq!(ev.nim_symbols, 3);
+        let r =
+            detect_language_and_compiler(&nim, &[], &["fatal.nim".to_string()], None, None, b"");
+        assert_eq!(r.language, SourceLanguage::Nim);
+
+        let zig_syms = vec![
+            "builtin.default_panic".to_string(),
+            "std.start.posixCallMainAndExit".to_string(),
+        ];
+        let zig_strs = vec!["reached unreachable code".to_string()];
+        assert_eq!(detect_language_from_symbols(&zig_syms).zig_symbols, 2);
+        assert_eq!(detect_language_from_strings(&zig_strs).zig_panic_strings, 1);
+        let r = detect_language_and_compiler(&zig_syms, &[], &zig_strs, None, None, b"");
+        assert_eq!(r.language, SourceLanguage::Zig);
+
+        let crystal = vec![
+            "__crystal_main".to_string(),
+            "__crystal_raise".to_string(),
+            "*Crystal::main<Int32, Pointer(Pointer(UInt8))>:Int32".to_string(),
+        ];
+        assert_eq!(detect_language_from_symbols(&crystal).crystal_symbols, 3);
+        let r = detect_language_and_compiler(&crystal, &[], &[], None, None, b"");
+        assert_eq!(r.language, SourceLanguage::Crystal);
+    }
+
+    #[test]
+    fn test_target_triple_from_producer_text() {
+        assert!(looks_like_triple("aarch64-unknown-linux-musl"));
+        assert!(looks_like_triple("x86_64-pc-windows-msvc"));
+        assert!(!looks_like_triple("x86_64-foo-bar"));
+        assert_eq!(
+            triple_in_text("clang version 17.0.6 --target=aarch64-unknown-linux-musl -O2")
+                .as_deref(),
+            Some("aarch64-unknown-linux-musl")
+        );
+        assert_eq!(
+            triple_in_text(
+                "Apple clang version 15.0.0 (clang-1500.0.40.1) arm64-apple-darwin23.1.0"
+            )
+            .as_deref(),
+            Some("arm64-apple-darwin23.1.0")
+        );
+        let info = detect_from_elf_comment(
+            "clang version 14.0.6 (https://github.com/llvm/llvm-project) x86_64-pc-linux-gnu",
+        )
+        .unwrap();
+        assert_eq!(info.target_triple.as_deref(), Some("x86_64-pc-linux-gnu"));
+        assert!(extract_target_triple(b"not an object").is_none());
+    }
+
+    #[test]
+    fn test_libc_classification() {
+        assert_eq!(
+            libc_from_interpreter("/lib/ld-musl-x86_64.so.1"),
+            Some(Libc::Musl)
+        );
+        assert_eq!(
+            libc_from_interpreter("/lib64/ld-linux-x86-64.so.2"),
+            Some(Libc::Glibc)
+        );
+        assert_eq!(
+            libc_from_interpreter("/lib/ld-linux-aarch64.so.1"),
+            Some(Libc::Glibc)
+        );
+        assert_eq!(
+            libc_from_interpreter("/system/bin/linker64"),
+            Some(Libc::Bionic)
+        );
+        assert_eq!(libc_from_interpreter("/opt/custom/ld.so"), None);
+        assert_eq!(libc_from_needed("libc.musl-aarch64.so.1"), Some(Libc::Musl));
+        assert_eq!(libc_from_needed("libc.so.6"), Some(Libc::Glibc));
+        assert_eq!(libc_from_needed("libc.so"), None);
+
+        let Ok(data) = std::fs::read("samples/adversarial/embedded/xor_url_in_elf.elf") else {
+            return;
+        };
+        if let Ok(parser) = crate::formats::elf::ElfParser::parse(&data) {
+            assert_eq!(detect_libc(&parser), Some(Libc::Glibc));
+        }
+    }
 }
@@ -3,8 +3,13 @@
 //! Provides centralized configuration for all triage components with
 //! sensible defaults and Python-accessible configuration.
 
+use crate::core::triage::Severity;
+use crate::triage::sniffers::SnifferRegistry;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 
+#[cfg(feature = "python-ext")]
+use crate::triage::sniffers::MagicSniffer;
 #[cfg(feature = "python-ext")]
 use pyo3::prelude::*;
 
@@ -28,6 +33,186 @@ pub struct TriageConfig {
     pub parsers: ParserConfig,
     /// Similarity (CTPH) configuration.
     pub similarity: SimilarityConfig,
+    /// Content/extension sniffer configuration.
+    #[serde(default)]
+    pub sniffer: SnifferConfig,
+    /// Overlay payload classification.
+    #[serde(default)]
+    pub overlay: OverlayConfig,
+    /// Lowercase hex SHA-256 digests of files known to be clean. A match
+    /// short-circuits triage with a minimal `known_good` artifact; when the
+    /// set is empty no up-front hash is computed. A non-empty set hashes the
+    /// whole file, past `io.max_read_bytes`.
+    #[serde(default)]
+    pub known_good_sha256: HashSet<String>,
+    /// Read the rest of the file after the prefix reads so path analysis can
+    /// report a whole-file `sha256`. These reads are not bounded by
+    /// `io.max_read_bytes`; when off, `sha256` is only set if the prefix
+    /// reads already covered the file.
+    #[serde(default)]
+    pub hash_full_file: bool,
+    /// What the input bytes are: a file on disk, a memory dump or raw code.
+    #[serde(default)]
+    pub input_kind: InputKind,
+    /// Wall-clock budget in milliseconds for the size-dependent phases
+    /// (entropy windows, strings, disassembly preview). They stop early with
+    /// partial results and a `BudgetExceeded` note once it is spent, so
+    /// format and symbol analysis still run. `None` is unlimited.
+    #[serde(default)]
+    pub max_time_ms: Option<u64>,
+}
+
+/// How triage interprets the input bytes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "python-ext", pyclass(eq, eq_int))]
+pub enum InputKind {
+    /// A file whose format header, if any, sits at offset 0.
+    #[default]
+    File,
+    /// Process or physical memory: no header is expected at offset 0 and
+    /// every embedded PE/ELF image is reported as a child.
+    MemoryDump,
+    /// Headerless code: format parsing is skipped and triage goes straight
+    /// to architecture inference and disassembly.
+    Shellcode,
+}
+
+impl TriageConfig {
+    /// Low-latency preset for bulk triage.
+    ///
+    /// Reads at most 1 MiB (entropy over the first 256 KiB, 64 windows),
+    /// scans 256 KiB for strings and keeps 20 samples, scans 128 KiB for
+    /// packers and skips CTPH.
+    pub fn fast() -> Self {
+        let mut cfg = Self::default();
+        cfg.io.max_read_bytes = 1 << 20;
+        cfg.io.max_entropy_size = 256 << 10;
+        cfg.entropy.max_windows = 64;
+        cfg.heuristics.string_sample_limit = 20;
+        cfg.heuristics.string_scan_bytes = 256 << 10;
+        cfg.packers.scan_limit = 128 << 10;
+        cfg.similarity.enable_ctph = false;
+        cfg
+    }
+
+    /// The default configuration, named for symmetry with the other presets.
+    pub fn balanced() -> Self {
+        Self::default()
+    }
+
+    /// Thorough preset for deep hunts on individual samples.
+    ///
+    /// Accepts files up to 1 GiB and reads up to 64 MiB, computes entropy
+    /// over 16 MiB in 4 KiB windows (up to 4096), scans 16 MiB for strings
+    /// and keeps 200 samples, and scans 4 MiB for packers.
+    pub fn deep() -> Self {
+        let mut cfg = Self::default();
+        cfg.io.max_read_bytes = 64 << 20;
+        cfg.io.max_file_size = 1 << 30;
+        cfg.io.max_entropy_size = 16 << 20;
+        cfg.entropy.window_size = 4096;
+        cfg.entropy.step = 4096;
+        cfg.entropy.max_windows = 4096;
+        cfg.heuristics.string_sample_limit = 200;
+        cfg.heuristics.string_scan_bytes = 16 << 20;
+        cfg.packers.scan_limit = 4 << 20;
+        cfg
+    }
+
+    /// Build a configuration from `GLAURUNG_*` environment variables.
+    ///
+    /// Every variable below is honored by
+    /// [`crate::triage::api::analyze_path_with_config`].
+    ///
+    /// `GLAURUNG_TRIAGE_PRESET` (`fast`, `balanced` or `deep`; default
+    /// `balanced`) selects the starting point, then these override it:
+    ///
+    /// | Variable | Field |
+    /// |---|---|
+    /// | `GLAURUNG_MAX_READ_BYTES` | `io.max_read_bytes` |
+    /// | `GLAURUNG_MAX_FILE_SIZE` | `io.max_file_size` |
+    /// | `GLAURUNG_MAX_SNIFF_SIZE` | `io.max_sniff_size` |
+    /// | `GLAURUNG_MAX_HEADER_SIZE` | `io.max_header_size` |
+    /// | `GLAURUNG_MAX_ENTROPY_SIZE` | `io.max_entropy_size` |
+    /// | `GLAURUNG_ENTROPY_WINDOW` | `entropy.window_size` and `entropy.step` |
+    /// | `GLAURUNG_ENTROPY_MAX_WINDOWS` | `entropy.max_windows` |
+    /// | `GLAURUNG_MIN_STRING_LENGTH` | `heuristics.min_string_length` |
+    /// | `GLAURUNG_STRING_SAMPLES` | `heuristics.string_sample_limit` |
+    /// | `GLAURUNG_STRING_SCAN_BYTES` | `heuristics.string_scan_bytes` |
+    /// | `GLAURUNG_PACKER_SCAN_LIMIT` | `packers.scan_limit` |
+    /// | `GLAURUNG_ENABLE_CTPH` | `similarity.enable_ctph` (`1/0`, `true/false`) |
+    /// | `GLAURUNG_MAX_TIME_MS` | `max_time_ms` |
+    ///
+    /// Unset variables keep the preset value; malformed ones are an error
+    /// naming the variable.
+    pub fn from_env() -> Result<Self, String> {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Self, String> {
+        let mut cfg = match lookup("GLAURUNG_TRIAGE_PRESET").as_deref() {
+            None | Some("balanced") => Self::balanced(),
+            Some("fast") => Self::fast(),
+            Some("deep") => Self::deep(),
+            Some(other) => {
+                return Err(format!(
+                    "GLAURUNG_TRIAGE_PRESET: unknown preset {:?}",
+                    other
+                ))
+            }
+        };
+        let num = |key: &str| -> Result<Option<u64>, String> {
+            lookup(key)
+                .map(|v| {
+                    v.trim()
+                        .parse::<u64>()
+                        .map_err(|e| format!("{}: {:?}: {}", key, v, e))
+                })
+                .transpose()
+        };
+        let set = |key: &str, slot: &mut usize| -> Result<(), String> {
+            if let Some(v) = num(key)? {
+                *slot = usize::try_from(v).map_err(|e| format!("{}: {}", key, e))?;
+            }
+            Ok(())
+        };
+        set("GLAURUNG_MAX_READ_BYTES", &mut cfg.io.max_read_bytes)?;
+        if let Some(v) = num("GLAURUNG_MAX_FILE_SIZE")? {
+            cfg.io.max_file_size = v;
+        }
+        set("GLAURUNG_MAX_SNIFF_SIZE", &mut cfg.io.max_sniff_size)?;
+        set("GLAURUNG_MAX_HEADER_SIZE", &mut cfg.io.max_header_size)?;
+        set("GLAURUNG_MAX_ENTROPY_SIZE", &mut cfg.io.max_entropy_size)?;
+        set("GLAURUNG_ENTROPY_WINDOW", &mut cfg.entropy.window_size)?;
+        if lookup("GLAURUNG_ENTROPY_WINDOW").is_some() {
+            cfg.entropy.step = cfg.entropy.window_size;
+        }
+        set("GLAURUNG_ENTROPY_MAX_WINDOWS", &mut cfg.entropy.max_windows)?;
+        set(
+            "GLAURUNG_MIN_STRING_LENGTH",
+            &mut cfg.heuristics.min_string_length,
+        )?;
+        set(
+            "GLAURUNG_STRING_SAMPLES",
+            &mut cfg.heuristics.string_sample_limit,
+        )?;
+        set(
+            "GLAURUNG_STRING_SCAN_BYTES",
+            &mut cfg.heuristics.string_scan_bytes,
+        )?;
+        set("GLAURUNG_PACKER_SCAN_LIMIT", &mut cfg.packers.scan_limit)?;
+        if let Some(v) = lookup("GLAURUNG_ENABLE_CTPH") {
+            cfg.similarity.enable_ctph = match v.trim().to_ascii_lowercase().as_str() {
+                "1" | "true" | "yes" | "on" => true,
+                "0" | "false" | "no" | "off" => false,
+                _ => return Err(format!("GLAURUNG_ENABLE_CTPH: {:?} is not a boolean", v)),
+            };
+        }
+        if let Some(v) = num("GLAURUNG_MAX_TIME_MS")? {
+            cfg.max_time_ms = Some(v);
+        }
+        Ok(cfg)
+    }
 }
 
 #[cfg(feature = "python-ext")]
@@ -38,6 +223,30 @@ impl TriageConfig {
         Self::default()
     }
 
+    #[staticmethod]
+    #[pyo3(name = "fast")]
+    fn py_fast() -> Self {
+        Self::fast()
+    }
+
+    #[staticmethod]
+    #[pyo3(name = "balanced")]
+    fn py_balanced() -> Self {
+        Self::balanced()
+    }
+
+    #[staticmethod]
+    #[pyo3(name = "deep")]
+    fn py_deep() -> Self {
+        Self::deep()
+    }
+
+    #[staticmethod]
+    #[pyo3(name = "from_env")]
+    fn py_from_env() -> PyResult<Self> {
+        Self::from_env().map_err(pyo3::exceptions::PyValueError::new_err)
+    }
+
     #[getter]
     pub fn get_io(&self) -> IOConfig {
         self.io.clone()
@@ -117,6 +326,213 @@ impl TriageConfig {
     pub fn set_similarity(&mut self, config: SimilarityConfig) {
         self.similarity = config;
     }
+
+    #[getter]
+    pub fn get_sniffer(&self) -> SnifferConfig {
+        self.sniffer.clone()
+    }
+
+    #[setter]
+    pub fn set_sniffer(&mut self, config: SnifferConfig) {
+        self.sniffer = config;
+    }
+
+    #[getter]
+    pub fn get_overlay(&self) -> OverlayConfig {
+        self.overlay.clone()
+    }
+
+    #[setter]
+    pub fn set_overlay(&mut self, config: OverlayConfig) {
+        self.overlay = config;
+    }
+
+    #[getter]
+    pub fn get_known_good_sha256(&self) -> HashSet<String> {
+        self.known_good_sha256.clone()
+    }
+
+    #[setter]
+    pub fn set_known_good_sha256(&mut self, digests: HashSet<String>) {
+        self.known_good_sha256 = digests.iter().map(|d| d.to_ascii_lowercase()).collect();
+    }
+
+    #[getter]
+    pub fn get_hash_full_file(&self) -> bool {
+        self.hash_full_file
+    }
+
+    #[setter]
+    pub fn set_hash_full_file(&mut self, enabled: bool) {
+        self.hash_full_file = enabled;
+    }
+
+    #[getter]
+    pub fn get_input_kind(&self) -> InputKind {
+        self.input_kind
+    }
+
+    #[setter]
+    pub fn set_input_kind(&mut self, kind: InputKind) {
+        self.input_kind = kind;
+    }
+
+    #[getter]
+    pub fn get_max_time_ms(&self) -> Option<u64> {
+        self.max_time_ms
+    }
+
+    #[setter]
+    pub fn set_max_time_ms(&mut self, ms: Option<u64>) {
+        self.max_time_ms = ms;
+    }
+}
+
+/// Content/extension sniffer configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+#[cfg_attr(feature = "python-ext", pyclass)]
+pub struct SnifferConfig {
+    /// Extension (without the dot, case-insensitive) to label overrides,
+    /// consulted before `mime_guess` (e.g. `"sys" -> "pe"`, `"bin" -> "firmware"`).
+    pub extension_overrides: HashMap<String, String>,
+    /// Severity assigned to sniffer/header and content/extension mismatches
+    /// (default: Warning; a renamed-but-valid binary is not a failure).
+    pub mismatch_severity: Severity,
+    /// Custom content sniffers run after the built-in ones (not serialized).
+    #[serde(skip)]
+    pub custom_sniffers: SnifferRegistry,
+}
+
+impl Default for SnifferConfig {
+    fn default() -> Self {
+        Self {
+            extension_overrides: HashMap::new(),
+            mismatch_severity: Severity::Warning,
+            custom_sniffers: SnifferRegistry::new(),
+        }
+    }
+}
+
+impl SnifferConfig {
+    /// Look up the override label for an extension, if any.
+    pub fn override_for(&self, extension: &str) -> Option<&str> {
+        let ext = extension.trim_start_matches('.');
+        self.extension_overrides
+            .iter()
+            .find(|(k, _)| k.trim_start_matches('.').eq_ignore_ascii_case(ext))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+#[cfg(feature = "python-ext")]
+#[pymethods]
+impl SnifferConfig {
+    #[new]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[getter]
+    pub fn get_extension_overrides(&self) -> HashMap<String, String> {
+        self.extension_overrides.clone()
+    }
+    #[setter]
+    pub fn set_extension_overrides(&mut self, v: HashMap<String, String>) {
+        self.extension_overrides = v;
+    }
+
+    #[getter]
+    pub fn get_mismatch_severity(&self) -> Severity {
+        self.mismatch_severity
+    }
+    #[setter]
+    pub fn set_mismatch_severity(&mut self, v: Severity) {
+        self.mismatch_severity = v;
+    }
+
+    /// Register a custom sniffer labelling inputs with `magic` at `offset`.
+    #[pyo3(signature = (label, magic, offset=0, mime=None))]
+    pub fn add_magic(
+        &mut self,
+        label: String,
+        magic: Vec<u8>,
+        offset: usize,
+        mime: Option<String>,
+    ) {
+        let mut sniffer = MagicSniffer::new(label, magic).at_offset(offset);
+        sniffer.mime = mime;
+        self.custom_sniffers.register(sniffer);
+    }
+}
+
+/// A user-defined overlay payload magic.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OverlaySignature {
+    /// Payload name reported in `OverlayAnalysis::payload`.
+    pub label: String,
+    pub magic: Vec<u8>,
+    /// Offset of `magic` from the start of the overlay.
+    #[serde(default)]
+    pub offset: usize,
+}
+
+/// Overlay payload classification configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+#[cfg_attr(feature = "python-ext", pyclass)]
+pub struct OverlayConfig {
+    /// Leading overlay bytes searched for installer markers (NSIS, Inno
+    /// Setup); magic at the overlay start is always checked.
+    pub marker_scan_bytes: usize,
+    /// Custom payload magics, checked in order before the built-in ones.
+    pub signatures: Vec<OverlaySignature>,
+}
+
+impl Default for OverlayConfig {
+    fn default() -> Self {
+        Self {
+            marker_scan_bytes: 64 * 1024,
+            signatures: Vec::new(),
+        }
+    }
+}
+
+#[cfg(feature = "python-ext")]
+#[pymethods]
+impl OverlayConfig {
+    #[new]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[getter]
+    pub fn get_marker_scan_bytes(&self) -> usize {
+        self.marker_scan_bytes
+    }
+    #[setter]
+    pub fn set_marker_scan_bytes(&mut self, v: usize) {
+        self.marker_scan_bytes = v;
+    }
+
+    /// Custom signatures as `(label, magic, offset)` tuples.
+    #[getter]
+    pub fn get_signatures(&self) -> Vec<(String, Vec<u8>, usize)> {
+        self.signatures
+            .iter()
+            .map(|s| (s.label.clone(), s.magic.clone(), s.offset))
+            .collect()
+    }
+
+    /// Register a payload `label` for overlays with `magic` at `offset`.
+    #[pyo3(signature = (label, magic, offset=0))]
+    pub fn add_signature(&mut self, label: String, magic: Vec<u8>, offset: usize) {
+        self.signatures.push(OverlaySignature {
+            label,
+            magic,
+            offset,
+        });
+    }
 }
 
 /// Similarity (CTPH) configuration.
@@ -131,6 +547,16 @@ pub struct SimilarityConfig {
     pub digest_size: usize,
     /// CTPH precision (8,16,32,64)
     pub precision: u8,
+    /// Also compute entropy and CTPH for each discovered container child
+    #[serde(default)]
+    pub fingerprint_children: bool,
+    /// Bytes of each child fingerprinted (prefix); bounds the per-child cost
+    #[serde(default = "default_max_child_fingerprint_bytes")]
+    pub max_child_fingerprint_bytes: usize,
+}
+
+fn default_max_child_fingerprint_bytes() -> usize {
+    1024 * 1024
 }
 
 impl Default for SimilarityConfig {
@@ -140,6 +566,33 @@ impl Default for SimilarityConfig {
             window_size: 8,
             digest_size: 4,
             precision: 8,
+            fingerprint_children: false,
+            max_child_fingerprint_bytes: default_max_child_fingerprint_bytes(),
+        }
+    }
+}
+
+impl SimilarityConfig {
+    /// CTPH parameters for an input of `len` bytes; a zero window or digest
+    /// size selects parameters scaled to the input size.
+    pub fn ctph_config_for(&self, len: usize) -> crate::similarity::CtphConfig {
+        let (window_size, digest_size, precision) =
+            if self.window_size == 0 || self.digest_size == 0 {
+                if len < 16 * 1024 {
+                    (8, 4, 8)
+                } else if len < 1024 * 1024 {
+                    (16, 5, 16)
+                } else {
+                    (32, 6, 16)
+                }
+            } else {
+                (self.window_size, self.digest_size, self.precision)
+            };
+        crate::similarity::CtphConfig {
+            window_size,
+            digest_size,
+            precision,
+            ..Default::default()
         }
     }
 }
@@ -187,6 +640,24 @@ impl SimilarityConfig {
     pub fn set_precision(&mut self, v: u8) {
         self.precision = v;
     }
+
+    #[getter]
+    pub fn get_fingerprint_children(&self) -> bool {
+        self.fingerprint_children
+    }
+    #[setter]
+    pub fn set_fingerprint_children(&mut self, v: bool) {
+        self.fingerprint_children = v;
+    }
+
+    #[getter]
+    pub fn get_max_child_fingerprint_bytes(&self) -> usize {
+        self.max_child_fingerprint_bytes
+    }
+    #[setter]
+    pub fn set_max_child_fingerprint_bytes(&mut self, v: usize) {
+        self.max_child_fingerprint_bytes = v;
+    }
 }
 
 /// I/O configuration for file reading and buffering.
@@ -436,6 +907,14 @@ pub struct EntropyThresholds {
     pub low_header: f64,
     /// High entropy body threshold.
     pub high_body: f64,
+    /// Windows whose entropy is more than this many standard deviations
+    /// from the mean are reported as outliers (default: 3.0).
+    #[serde(default = "default_outlier_zscore")]
+    pub outlier_zscore: f64,
+}
+
+fn default_outlier_zscore() -> f64 {
+    3.0
 }
 
 impl Default for EntropyThresholds {
@@ -448,6 +927,7 @@ impl Default for EntropyThresholds {
             cliff_delta: 1.0,
             low_header: 4.0,
             high_body: 7.0,
+            outlier_zscore: default_outlier_zscore(),
         }
     }
 }
@@ -523,6 +1003,15 @@ impl EntropyThresholds {
         self.high_body
     }
 
+    #[getter]
+    pub fn get_outlier_zscore(&self) -> f64 {
+        self.outlier_zscore
+    }
+    #[getter(outlier_zscore)]
+    pub fn p_get_outlier_zscore(&self) -> f64 {
+        self.outlier_zscore
+    }
+
     #[setter]
     pub fn set_text(&mut self, value: f64) {
         self.text = value;
@@ -585,6 +1074,15 @@ impl EntropyThresholds {
     pub fn p_set_high_body(&mut self, value: f64) {
         self.high_body = value;
     }
+
+    #[setter]
+    pub fn set_outlier_zscore(&mut self, value: f64) {
+        self.outlier_zscore = value;
+    }
+    #[setter(outlier_zscore)]
+    pub fn p_set_outlier_zscore(&mut self, value: f64) {
+        self.outlier_zscore = value;
+    }
 }
 
 /// Entropy scoring weights.
@@ -683,6 +1181,23 @@ pub struct HeuristicsConfig {
     pub endianness_threshold: f32,
     /// Weight for endianness confidence (default: 0.05).
     pub endianness_weight: f32,
+    /// Bytes from the start of the file scanned for strings, independent of
+    /// `io.max_entropy_size` and bounded only by the read limit; use
+    /// `usize::MAX` to cover the whole readable file (default: 1048576).
+    #[serde(default = "default_string_scan_bytes")]
+    pub string_scan_bytes: usize,
+    /// Time guard for the string scan per MiB of scanned bytes, still capped
+    /// by `max_time_ms` (default: 10).
+    #[serde(default = "default_string_time_guard_ms")]
+    pub string_time_guard_ms: u64,
+}
+
+fn default_string_scan_bytes() -> usize {
+    1 << 20
+}
+
+fn default_string_time_guard_ms() -> u64 {
+    10
 }
 
 impl Default for HeuristicsConfig {
@@ -693,6 +1208,8 @@ impl Default for HeuristicsConfig {
             string_sample_max_len: 120,
             endianness_threshold: 0.1,
             endianness_weight: 0.05,
+            string_scan_bytes: default_string_scan_bytes(),
+            string_time_guard_ms: default_string_time_guard_ms(),
         }
     }
 }
@@ -730,6 +1247,16 @@ impl HeuristicsConfig {
         self.endianness_weight
     }
 
+    #[getter]
+    pub fn get_string_scan_bytes(&self) -> usize {
+        self.string_scan_bytes
+    }
+
+    #[getter]
+    pub fn get_string_time_guard_ms(&self) -> u64 {
+        self.string_time_guard_ms
+    }
+
     #[setter]
     pub fn set_min_string_length(&mut self, length: usize) {
         self.min_string_length = length;
@@ -754,6 +1281,16 @@ impl HeuristicsConfig {
     pub fn set_endianness_weight(&mut self, weight: f32) {
         self.endianness_weight = weight;
     }
+
+    #[setter]
+    pub fn set_string_scan_bytes(&mut self, bytes: usize) {
+        self.string_scan_bytes = bytes;
+    }
+
+    #[setter]
+    pub fn set_string_time_guard_ms(&mut self, ms: u64) {
+        self.string_time_guard_ms = ms;
+    }
 }
 
 /// Scoring and confidence configuration.
@@ -1012,3 +1549,52 @@ impl ParserConfig {
         self.python_bytecode_confidence = confidence;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup<'a>(vars: &'a [(&'a str, &'a str)]) -> impl Fn(&str) -> Option<String> + 'a {
+        move |key| {
+            vars.iter()
+                .find(|(k, _)| *k == key)
+                .map(|(_, v)| v.to_string())
+        }
+    }
+
+    #[test]
+    fn presets_scale_budgets() {
+        let (fast, deep) = (TriageConfig::fast(), TriageConfig::deep());
+        let balanced = TriageConfig::balanced();
+        assert!(fast.io.max_read_bytes < balanced.io.max_read_bytes);
+        assert!(deep.io.max_read_bytes > balanced.io.max_read_bytes);
+        assert!(!fast.similarity.enable_ctph);
+        assert!(deep.entropy.max_windows > balanced.entropy.max_windows);
+        assert!(deep.heuristics.string_scan_bytes > balanced.heuristics.string_scan_bytes);
+    }
+
+    #[test]
+    fn from_env_applies_preset_then_overrides() {
+        let cfg = TriageConfig::from_lookup(lookup(&[])).unwrap();
+        assert_eq!(cfg.io.max_read_bytes, IOConfig::default().max_read_bytes);
+
+        let cfg = TriageConfig::from_lookup(lookup(&[
+            ("GLAURUNG_TRIAGE_PRESET", "fast"),
+            ("GLAURUNG_MAX_READ_BYTES", "2048"),
+            ("GLAURUNG_ENTROPY_WINDOW", "512"),
+            ("GLAURUNG_ENABLE_CTPH", "true"),
+            ("GLAURUNG_MAX_TIME_MS", "250"),
+        ]))
+        .unwrap();
+        assert_eq!(cfg.io.max_read_bytes, 2048);
+        assert_eq!((cfg.entropy.window_size, cfg.entropy.step), (512, 512));
+        assert_eq!(cfg.entropy.max_windows, 64);
+        assert!(cfg.similarity.enable_ctph);
+        assert_eq!(cfg.max_time_ms, Some(250));
+
+        let err =
+            TriageConfig::from_lookup(lookup(&[("GLAURUNG_STRING_SAMPLES", "lots")])).unwrap_err();
+        assert!(err.starts_with("GLAURUNG_STRING_SAMPLES"));
+        assert!(TriageConfig::from_lookup(lookup(&[("GLAURUNG_TRIAGE_PRESET", "max")])).is_err());
+    }
+}
@@ -1,8 +1,19 @@
 //! Container and archive format detection.
 //!
-//! Fast magic checks for zip, tar, 7z, ar, cpio, gzip, xz, bzip2, zstd, lz4
-//! with bounded metadata extraction.
+//! Fast magic checks for zip, tar, 7z, ar, cpio, gzip, xz, bzip2, zstd, lz4,
+//! cab with bounded metadata extraction, plus member enumeration for tar
+//! (including tar chained through gzip) and Microsoft Cabinet archives.
+//! ZIPs appended to other files (self-extractors, polyglots) are located
+//! from their End Of Central Directory record by [`detect_appended_zip`].
+//! Installer payloads (NSIS, Inno Setup, InstallShield, MSI) are reported
+//! under the installer family's label, and the streams of OLE compound files
+//! (MSI, legacy Office) are enumerated as members.
+use crate::core::binary::Endianness;
 use crate::core::triage::{ContainerChild, ContainerMetadata};
+use crate::formats::read::{bytes_at, u16_at, u32_at};
+use crate::triage::signatures::{
+    detect_installer_with_hits, InstallerFamily, MultiScanner, SignatureHits,
+};
 
 fn parse_zip_metadata(data: &[u8]) -> Option<ContainerMetadata> {
     // Search for End of Central Directory (EOCD) signature 0x06054b50 near the end
@@ -30,6 +41,89 @@ fn parse_zip_metadata(data: &[u8]) -> Option<ContainerMetadata> {
     None
 }
 
+/// End Of Central Directory record size without the trailing comment.
+const EOCD_LEN: usize = 22;
+/// EOCD comments are at most 64 KiB, bounding the backwards scan.
+const MAX_EOCD_SEARCH: usize = EOCD_LEN + u16::MAX as usize;
+
+/// Byte range of a ZIP archive located from its EOCD record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZipLocation {
+    /// Offset of the first local file header (or the EOCD for an empty archive).
+    pub start: usize,
+    /// End of the EOCD record including its comment.
+    pub end: usize,
+    pub entries: u16,
+}
+
+/// Locate a ZIP archive anywhere in `data` from the last valid EOCD record.
+///
+/// Handles both archive-relative central directory offsets (data prepended
+/// to a plain ZIP) and file-absolute ones (`zip -A` style self-extractors):
+/// the prefix length is recovered as `eocd - cd_size - cd_offset` and the
+/// first central directory entry must point at a `PK\x03\x04` local header.
+pub fn locate_zip_eocd(data: &[u8]) -> Option<ZipLocation> {
+    let floor = data.len().saturating_sub(MAX_EOCD_SEARCH);
+    let mut hi = data.len().checked_sub(EOCD_LEN)? + 4;
+    while let Some(rel) = memchr::memmem::rfind(&data[floor..hi], b"PK\x05\x06") {
+        let pos = floor + rel;
+        if let Some(loc) = zip_location_at(data, pos) {
+            return Some(loc);
+        }
+        // Keep searching below this candidate (e.g. a signature in a comment).
+        hi = pos + 3;
+    }
+    None
+}
+
+/// Validate the EOCD record at `pos` against its central directory.
+fn zip_location_at(data: &[u8], pos: usize) -> Option<ZipLocation> {
+    const LE: Endianness = Endianness::Little;
+    let comment_len = u16_at(data, pos + 20, LE).ok()? as usize;
+    let end = pos + EOCD_LEN + comment_len;
+    if end > data.len() {
+        return None;
+    }
+    let entries = u16_at(data, pos + 10, LE).ok()?;
+    let cd_size = u32_at(data, pos + 12, LE).ok()? as usize;
+    let cd_offset = u32_at(data, pos + 16, LE).ok()? as usize;
+    let start = if entries == 0 {
+        (cd_size == 0).then_some(pos)?
+    } else {
+        let cd_start = pos.checked_sub(cd_size)?;
+        let prefix = cd_start.checked_sub(cd_offset)?;
+        if bytes_at(data, cd_start, 46).ok()?[..4] != *b"PK\x01\x02" {
+            return None;
+        }
+        let lfh = prefix.checked_add(u32_at(data, cd_start + 42, LE).ok()? as usize)?;
+        if bytes_at(data, lfh, 4).ok()? != b"PK\x03\x04" {
+            return None;
+        }
+        lfh
+    };
+    Some(ZipLocation {
+        start,
+        end,
+        entries,
+    })
+}
+
+/// A ZIP that does not start at offset 0, found via [`locate_zip_eocd`].
+///
+/// Archives starting at offset 0 are already reported by
+/// [`detect_containers`] and yield `None` here.
+pub fn detect_appended_zip(data: &[u8]) -> Option<ContainerChild> {
+    let loc = locate_zip_eocd(data).filter(|l| l.start > 0)?;
+    let slice = &data[loc.start..loc.end];
+    let mut c = ContainerChild::new(
+        zip_subtype(slice).to_string(),
+        loc.start as u64,
+        slice.len() as u64,
+    );
+    c.metadata = parse_zip_metadata(slice);
+    Some(c)
+}
+
 fn parse_gzip_metadata(data: &[u8]) -> Option<ContainerMetadata> {
     if data.len() < 18 {
         return None;
@@ -99,6 +193,223 @@ fn parse_tar_metadata(data: &[u8]) -> Option<ContainerMetadata> {
     })
 }
 
+/// Upper bound on tar members enumerated from a single archive.
+pub const MAX_TAR_MEMBERS: usize = 4096;
+
+/// Upper bound on bytes inflated when probing a gzip stream for an inner tar.
+pub const MAX_GZIP_INFLATE: usize = 16 * 1024 * 1024;
+
+/// Parse a tar numeric field: NUL/space-terminated octal, or GNU base-256 when
+/// the high bit of the first byte is set.
+fn parse_tar_number(field: &[u8]) -> Option<u64> {
+    if field.first().is_some_and(|&b| b & 0x80 != 0) {
+        let mut v: u64 = u64::from(field[0] & 0x7F);
+        for &b in &field[1..] {
+            v = v.checked_mul(256)?.checked_add(u64::from(b))?;
+        }
+        return Some(v);
+    }
+    let digits: Vec<u8> = field
+        .iter()
+        .copied()
+        .skip_while(|&c| c == b' ')
+        .take_while(|&c| c != 0 && c != b' ')
+        .collect();
+    if digits.is_empty() {
+        return Some(0);
+    }
+    u64::from_str_radix(std::str::from_utf8(&digits).ok()?, 8).ok()
+}
+
+/// Read a NUL-terminated tar string field.
+fn tar_str(field: &[u8]) -> String {
+    let end = field.iter().position(|&c| c == 0).unwrap_or(field.len());
+    String::from_utf8_lossy(&field[..end]).into_owned()
+}
+
+/// Enumerate regular-file members of a ustar/GNU tar archive.
+///
+/// Each member becomes a `tar_member` child whose `offset`/`size` locate the
+/// member's content within `data`. GNU long names (`L` entries) and the ustar
+/// `prefix` field are honoured; directories, links and PAX headers are skipped.
+/// Members whose content runs past the end of `data` are truncated to what is
+/// available.
+pub fn enumerate_tar_members(data: &[u8]) -> Vec<ContainerChild> {
+    const BLOCK: usize = 512;
+    let mut out = Vec::new();
+    if data.len() < 262 || &data[257..262] != b"ustar" {
+        return out;
+    }
+    let mut off = 0usize;
+    let mut long_name: Option<String> = None;
+    while off + BLOCK <= data.len() && out.len() < MAX_TAR_MEMBERS {
+        let hdr = &data[off..off + BLOCK];
+        if hdr.iter().all(|&b| b == 0) {
+            break;
+        }
+        if &hdr[257..262] != b"ustar" {
+            break;
+        }
+        let Some(size) = parse_tar_number(&hdr[124..136]) else {
+            break;
+        };
+        let content_off = off + BLOCK;
+        let typeflag = hdr[156];
+        match typeflag {
+            b'L' => {
+                let end = content_off.saturating_add(size as usize).min(data.len());
+                long_name = Some(tar_str(&data[content_off..end]));
+            }
+            b'0' | 0 | b'7' => {
+                let name = long_name.take().unwrap_or_else(|| {
+                    let base = tar_str(&hdr[0..100]);
+                    // GNU headers ("ustar  ") reuse the prefix area for timestamps.
+                    let prefix = if &hdr[257..263] == b"ustar\0" {
+                        tar_str(&hdr[345..500])
+                    } else {
+                        String::new()
+                    };
+                    if prefix.is_empty() {
+                        base
+                    } else {
+                        format!("{}/{}", prefix, base)
+                    }
+                });
+                if content_off <= data.len() {
+                    let avail = (data.len() - content_off) as u64;
+                    out.push(ContainerChild::member(
+                        "tar_member".into(),
+                        name,
+                        content_off as u64,
+                        size.min(avail),
+                    ));
+                }
+            }
+            _ => {
+                long_name = None;
+            }
+        }
+        let Some(content_len) = usize::try_from(size.div_ceil(BLOCK as u64))
+            .ok()
+            .and_then(|blocks| blocks.checked_mul(BLOCK))
+        else {
+            break;
+        };
+        off = match content_off.checked_add(content_len) {
+            Some(next) => next,
+            None => break,
+        };
+    }
+    out
+}
+
+/// Inflate up to `max_out` bytes of a gzip stream.
+///
+/// Returns whatever was decoded before the bound or a stream error was hit, so
+/// truncated archives still yield their leading members.
+pub fn inflate_gzip_prefix(data: &[u8], max_out: usize) -> Option<Vec<u8>> {
+    use std::io::Read;
+    if data.len() < 2 || data[0] != 0x1F || data[1] != 0x8B {
+        return None;
+    }
+    let mut out = Vec::new();
+    let mut dec = flate2::read::GzDecoder::new(data).take(max_out as u64);
+    // Keep partial output on error: truncated downloads are common in triage.
+    let _ = dec.read_to_end(&mut out);
+    if out.is_empty() {
+        None
+    } else {
+        Some(out)
+    }
+}
+
+/// Enumerate the members of a gzip-compressed tar (`.tar.gz` / `.tgz`),
+/// inflating at most `max_inflate` bytes of it.
+pub fn enumerate_gzip_tar(data: &[u8], max_inflate: usize) -> Option<ContainerChild> {
+    enumerate_inflated_tar(&inflate_gzip_prefix(data, max_inflate)?)
+}
+
+/// Describe a tar inflated from a gzip stream as a single `tar` child with
+/// its members nested beneath it.
+///
+/// The tar and its members exist only in the decompressed stream, so they
+/// are flagged `in_decompressed_stream` and their offsets are relative to it.
+pub fn enumerate_inflated_tar(inner: &[u8]) -> Option<ContainerChild> {
+    let mut members = enumerate_tar_members(inner);
+    if members.is_empty() {
+        return None;
+    }
+    for m in &mut members {
+        m.in_decompressed_stream = true;
+    }
+    let mut tar = ContainerChild::new("tar".into(), 0, inner.len() as u64);
+    tar.in_decompressed_stream = true;
+    tar.metadata = parse_tar_metadata(inner);
+    tar.children = Some(members);
+    Some(tar)
+}
+
+/// Enumerate members of a Microsoft Cabinet as `cab_member` children.
+///
+/// Members whose bytes sit contiguously in `data` (stored folders, within
+/// one `CFDATA` block) are located exactly. Members of compressed folders
+/// are flagged `in_decompressed_stream`, with their offset within the
+/// folder's uncompressed data.
+pub fn enumerate_cab_members(data: &[u8]) -> Vec<ContainerChild> {
+    let Ok(cab) = crate::formats::cab::Cabinet::parse(data) else {
+        return Vec::new();
+    };
+    cab.files
+        .iter()
+        .map(|f| {
+            let offset = f.data_offset.unwrap_or(f.folder_offset as u64);
+            let mut child =
+                ContainerChild::member("cab_member".into(), f.name.clone(), offset, f.size as u64);
+            child.in_decompressed_stream = f.data_offset.is_none();
+            child
+        })
+        .collect()
+}
+
+fn parse_cab_metadata(data: &[u8]) -> Option<ContainerMetadata> {
+    let cab = crate::formats::cab::Cabinet::parse(data).ok()?;
+    Some(ContainerMetadata {
+        file_count: Some(cab.files.len() as u32),
+        total_uncompressed_size: Some(cab.total_uncompressed_size()),
+        total_compressed_size: Some(cab.declared_size as u64),
+    })
+}
+
+/// Enumerate the streams of an OLE compound file as `ole_stream` children.
+///
+/// Streams stored contiguously are located at their exact bytes; fragmented
+/// streams point at their first sector (or mini sector). MSI-encoded names
+/// are expanded.
+pub fn enumerate_ole_streams(data: &[u8]) -> Vec<ContainerChild> {
+    let Ok(ole) = crate::formats::ole::OleFile::parse(data) else {
+        return Vec::new();
+    };
+    ole.streams()
+        .map(|s| {
+            let offset = s
+                .data_offset
+                .or_else(|| ole.stream_start(data, s))
+                .unwrap_or(0);
+            let name = crate::formats::ole::decode_msi_name(&s.path);
+            ContainerChild::member("ole_stream".into(), name, offset, s.size)
+        })
+        .collect()
+}
+
+fn parse_ole_metadata(data: &[u8]) -> Option<ContainerMetadata> {
+    let ole = crate::formats::ole::OleFile::parse(data).ok()?;
+    Some(ContainerMetadata {
+        file_count: Some(ole.streams().count() as u32),
+        total_uncompressed_size: Some(ole.streams().map(|s| s.size).sum()),
+        total_compressed_size: None,
+    })
+}
+
 /// Classify a ZIP archive into a more specific Android/Java subtype by looking
 /// for well-known member paths, which ZIP stores as plaintext in both local and
 /// central-directory headers (so this works without decompressing anything).
@@ -127,6 +438,12 @@ fn zip_subtype(data: &[u8]) -> &'static str {
 }
 
 pub fn detect_containers(data: &[u8]) -> Vec<ContainerChild> {
+    detect_containers_with_hits(data, &MultiScanner::builtin().scan(data))
+}
+
+/// [`detect_containers`] reusing the caller's [`MultiScanner::builtin`] scan
+/// of `data` for the installer markers.
+pub fn detect_containers_with_hits(data: &[u8], hits: &SignatureHits<'_>) -> Vec<ContainerChild> {
     let mut containers = Vec::new();
 
     // ZIP/JAR/APK/AAB. All share the PK signature; the stored (uncompressed)
@@ -187,6 +504,31 @@ pub fn detect_containers(data: &[u8]) -> Vec<ContainerChild> {
         containers.push(ContainerChild::new("cpio".into(), 0, data.len() as u64));
     }
 
+    // Microsoft Cabinet ("MSCF")
+    if crate::formats::cab::is_cab(data) {
+        let mut c = ContainerChild::new("cab".into(), 0, data.len() as u64);
+        c.metadata = parse_cab_metadata(data);
+        containers.push(c);
+    }
+
+    // Installer payloads; MSI packages span the whole file
+    if let Some(m) = detect_installer_with_hits(data, hits) {
+        if let Some((offset, size)) = m.payload {
+            let mut c = ContainerChild::new(m.family.label().into(), offset, size);
+            if m.family == InstallerFamily::Msi {
+                c.metadata = parse_ole_metadata(data);
+            }
+            containers.push(c);
+        }
+    }
+
+    // Other OLE compound files (legacy Office documents and the like)
+    if crate::formats::ole::is_ole(data) && !containers.iter().any(|c| c.type_name == "msi") {
+        let mut c = ContainerChild::new("ole".into(), 0, data.len() as u64);
+        c.metadata = parse_ole_metadata(data);
+        containers.push(c);
+    }
+
     // RAR4/RAR5
     if data.len() >= 7 && data[..7] == [0x52, 0x61, 0x72, 0x21, 0x1A, 0x07, 0x00] {
         containers.push(ContainerChild::new("rar".into(), 0, data.len() as u64));
@@ -201,8 +543,173 @@ pub fn detect_containers(data: &[u8]) -> Vec<ContainerChild> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::formats::cab::tests::build_cab;
     use std::fs;
 
+    /// Build a minimal ustar header block for a regular file.
+    fn ustar_header(name: &str, size: usize, typeflag: u8) -> Vec<u8> {
+        let mut h = vec![0u8; 512];
+        h[..name.len()].copy_from_slice(name.as_bytes());
+        let sz = format!("{:011o}\0", size);
+        h[124..136].copy_from_slice(sz.as_bytes());
+        h[156] = typeflag;
+        h[257..263].copy_from_slice(b"ustar\0");
+        h[263..265].copy_from_slice(b"00");
+        h
+    }
+
+    fn build_tar(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut out = Vec::new();
+        for (name, body) in files {
+            out.extend(ustar_header(name, body.len(), b'0'));
+            out.extend_from_slice(body);
+            out.resize(out.len().div_ceil(512) * 512, 0);
+        }
+        out.extend(vec![0u8; 1024]);
+        out
+    }
+
+    /// One stored member after `prefix`; `absolute` writes file offsets
+    /// into the central directory as `zip -A` does for self-extractors.
+    fn build_prefixed_zip(prefix: &[u8], absolute: bool) -> Vec<u8> {
+        let base = if absolute { prefix.len() as u32 } else { 0 };
+        let (name, body) = (b"a.txt", b"hi");
+        let mut out = prefix.to_vec();
+        out.extend_from_slice(b"PK\x03\x04");
+        out.extend_from_slice(&[0; 14]);
+        out.extend_from_slice(&(body.len() as u32).to_le_bytes());
+        out.extend_from_slice(&(body.len() as u32).to_le_bytes());
+        out.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        out.extend_from_slice(&[0; 2]);
+        out.extend_from_slice(name);
+        out.extend_from_slice(body);
+        let cd_start = out.len();
+        out.extend_from_slice(b"PK\x01\x02");
+        out.extend_from_slice(&[0; 24]);
+        out.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        out.extend_from_slice(&[0; 12]);
+        out.extend_from_slice(&base.to_le_bytes());
+        out.extend_from_slice(name);
+        let cd_size = (out.len() - cd_start) as u32;
+        out.extend_from_slice(b"PK\x05\x06");
+        out.extend_from_slice(&[0; 4]);
+        out.extend_from_slice(&1u16.to_le_bytes());
+        out.extend_from_slice(&1u16.to_le_bytes());
+        out.extend_from_slice(&cd_size.to_le_bytes());
+        out.extend_from_slice(&(cd_start as u32 - prefix.len() as u32 + base).to_le_bytes());
+        out.extend_from_slice(&0u16.to_le_bytes());
+        out
+    }
+
+    #[test]
+    fn appended_zip_is_located_from_eocd() {
+        let stub = b"MZ\x90\0 self-extractor stub with a stray PK\x03\x04 inside";
+        for absolute in [false, true] {
+            let data = build_prefixed_zip(stub, absolute);
+            let loc = locate_zip_eocd(&data).expect("eocd");
+            assert_eq!(
+                (loc.start, loc.end, loc.entries),
+                (stub.len(), data.len(), 1)
+            );
+            let child = detect_appended_zip(&data).expect("appended zip");
+            assert_eq!(child.type_name, "zip");
+            assert_eq!(child.offset, stub.len() as u64);
+            assert_eq!(child.size, (data.len() - stub.len()) as u64);
+            assert_eq!(child.metadata.unwrap().file_count, Some(1));
+        }
+
+        // A ZIP at offset 0 is left to detect_containers.
+        let plain = build_prefixed_zip(b"", false);
+        assert_eq!(locate_zip_eocd(&plain).unwrap().start, 0);
+        assert!(detect_appended_zip(&plain).is_none());
+        // A bare EOCD signature without a central directory is not a ZIP.
+        assert!(
+            locate_zip_eocd(b"xxxxPK\x05\x06\0\0\0\0\x01\0\x01\0\x10\0\0\0\0\0\0\0\0\0").is_none()
+        );
+    }
+
+    #[test]
+    fn installer_payload_is_reported_as_container() {
+        use crate::triage::signatures::NSIS_FIRST_HEADER;
+        let mut exe = vec![0u8; 0x800];
+        exe[..2].copy_from_slice(b"MZ");
+        exe[0x604..0x604 + NSIS_FIRST_HEADER.len()].copy_from_slice(NSIS_FIRST_HEADER);
+        let v = detect_containers(&exe);
+        let nsis = v.iter().find(|c| c.type_name == "nsis").unwrap();
+        assert_eq!((nsis.offset, nsis.size), (0x600, 0x200));
+    }
+
+    #[test]
+    fn cab_members_of_compressed_folders_are_in_decompressed_stream() {
+        let mut cab = build_cab(&[("setup.exe", &b"MZ\x90\x00"[..])]);
+        let members = enumerate_cab_members(&cab);
+        assert_eq!(members.len(), 1);
+        assert!(!members[0].in_decompressed_stream);
+        let off = members[0].offset as usize;
+        assert_eq!(&cab[off..off + 4], b"MZ\x90\x00");
+
+        cab[42..44].copy_from_slice(&1u16.to_le_bytes()); // MSZIP
+        let members = enumerate_cab_members(&cab);
+        assert_eq!(members[0].name.as_deref(), Some("setup.exe"));
+        assert!(members[0].in_decompressed_stream);
+        assert_eq!((members[0].offset, members[0].size), (0, 4));
+    }
+
+    #[test]
+    fn enumerate_tar_members_reports_names_offsets_sizes() {
+        let tar = build_tar(&[
+            ("a.txt", &b"hello"[..]),
+            ("bin/payload", &[0x7F, b'E', b'L', b'F']),
+        ]);
+        let members = enumerate_tar_members(&tar);
+        assert_eq!(members.len(), 2);
+        assert_eq!(members[0].type_name, "tar_member");
+        assert_eq!(members[0].name.as_deref(), Some("a.txt"));
+        assert_eq!((members[0].offset, members[0].size), (512, 5));
+        assert_eq!(members[1].name.as_deref(), Some("bin/payload"));
+        assert_eq!((members[1].offset, members[1].size), (1536, 4));
+        let m = &members[1];
+        assert_eq!(
+            &tar[m.offset as usize..(m.offset + m.size) as usize],
+            b"\x7FELF"
+        );
+    }
+
+    #[test]
+    fn enumerate_tar_members_honours_gnu_long_name() {
+        let long = "d/".repeat(80) + "deep.bin";
+        let mut tar = ustar_header("././@LongLink", long.len() + 1, b'L');
+        tar.extend_from_slice(long.as_bytes());
+        tar.push(0);
+        tar.resize(tar.len().div_ceil(512) * 512, 0);
+        tar.extend(build_tar(&[("trunc", &b"x"[..])]));
+        let members = enumerate_tar_members(&tar);
+        assert_eq!(members.len(), 1);
+        assert_eq!(members[0].name.as_deref(), Some(long.as_str()));
+    }
+
+    #[test]
+    fn enumerate_gzip_tar_chains_decompression() {
+        use flate2::write::GzEncoder;
+        use std::io::Write;
+        let tar = build_tar(&[("dropper.sh", &b"#!/bin/sh\n"[..])]);
+        let mut enc = GzEncoder::new(Vec::new(), flate2::Compression::default());
+        enc.write_all(&tar).unwrap();
+        let gz = enc.finish().unwrap();
+        let inner = enumerate_gzip_tar(&gz, MAX_GZIP_INFLATE).expect("tar inside gzip");
+        assert_eq!(inner.type_name, "tar");
+        assert!(inner.in_decompressed_stream);
+        let members = inner.children.expect("members");
+        assert_eq!(members.len(), 1);
+        assert_eq!(members[0].name.as_deref(), Some("dropper.sh"));
+        assert!(members[0].in_decompressed_stream);
+        assert_eq!((members[0].offset, members[0].size), (512, 10));
+        // A plain gzip payload (not tar) yields nothing
+        let mut enc = GzEncoder::new(Vec::new(), flate2::Compression::default());
+        enc.write_all(b"not a tarball").unwrap();
+        assert!(enumerate_gzip_tar(&enc.finish().unwrap(), MAX_GZIP_INFLATE).is_none());
+    }
+
     #[test]
     fn detect_zip_and_gzip_and_tar_real_files() {
         let zip = "samples/containers/zip/hello-cpp-g++-O0.zip";
@@ -0,0 +1,109 @@
+//! Wall-clock budget shared by the triage phases.
+//!
+//! A [`Deadline`] is created once per artifact and handed to the phases
+//! whose cost grows with input size — entropy windows, string extraction
+//! and the disassembly preview. Each checks it cooperatively, stops early
+//! and keeps what it has, so a slow phase cannot starve the format and
+//! symbol analysis that runs after it.
+
+use crate::core::triage::{TriageError, TriageErrorKind};
+use std::time::{Duration, Instant};
+
+/// Point in time after which budgeted phases should stop.
+#[derive(Debug, Clone, Copy)]
+pub struct Deadline {
+    start: Instant,
+    limit: Option<Duration>,
+}
+
+impl Default for Deadline {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+impl Deadline {
+    /// A deadline that never expires.
+    pub fn none() -> Self {
+        Self {
+            start: Instant::now(),
+            limit: None,
+        }
+    }
+
+    /// A deadline `limit` from now.
+    pub fn after(limit: Duration) -> Self {
+        Self {
+            start: Instant::now(),
+            limit: Some(limit),
+        }
+    }
+
+    /// [`Deadline::after`] in milliseconds; `None` never expires.
+    pub fn from_ms(limit_ms: Option<u64>) -> Self {
+        limit_ms.map_or_else(Self::none, |ms| Self::after(Duration::from_millis(ms)))
+    }
+
+    /// The configured budget in milliseconds, if any.
+    pub fn limit_ms(&self) -> Option<u64> {
+        self.limit.map(|l| l.as_millis() as u64)
+    }
+
+    /// Time left before expiry; `None` when unlimited.
+    pub fn remaining(&self) -> Option<Duration> {
+        self.limit.map(|l| l.saturating_sub(self.start.elapsed()))
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.remaining().is_some_and(|r| r.is_zero())
+    }
+
+    /// `ms`, lowered to the time left when that is shorter. Used to bound
+    /// the per-phase time guards that predate the shared deadline.
+    pub fn cap_ms(&self, ms: u64) -> u64 {
+        match self.remaining() {
+            Some(r) => ms.min(r.as_millis() as u64),
+            None => ms,
+        }
+    }
+
+    /// Non-fatal error recording that `phase` stopped early and its
+    /// results are partial.
+    pub fn note(&self, phase: &str) -> TriageError {
+        TriageError::new(
+            TriageErrorKind::BudgetExceeded,
+            Some(format!(
+                "{} stopped early: time limit reached (limit_time_ms={}); results are partial",
+                phase,
+                self.limit_ms().unwrap_or(0)
+            )),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unlimited_and_expired_deadlines() {
+        let open = Deadline::none();
+        assert!(!open.is_expired());
+        assert_eq!(open.cap_ms(25), 25);
+        assert_eq!(Deadline::from_ms(None).limit_ms(), None);
+
+        let spent = Deadline::from_ms(Some(0));
+        assert!(spent.is_expired());
+        assert_eq!(spent.cap_ms(25), 0);
+        assert_eq!(spent.limit_ms(), Some(0));
+        assert!(spent
+            .note("strings")
+            .message
+            .as_deref()
+            .is_some_and(|m| m.starts_with("strings stopped early")));
+
+        let long = Deadline::after(Duration::from_secs(3600));
+        assert!(!long.is_expired());
+        assert_eq!(long.cap_ms(25), 25);
+    }
+}
@@ -1,14 +1,27 @@
 use crate::core::triage::{
-    EntropyAnalysis, EntropyAnomaly, EntropyClass, EntropySummary, PackedIndicators,
+    EntropyAnalysis, EntropyAnomaly, EntropyClass, EntropyOutlier, EntropySummary, PackedIndicators,
+};
+use crate::entropy::{
+    analyze_windows_while, detect_anomalies_zscore, shannon_entropy, WindowConfig,
 };
-use crate::entropy::{analyze_windows, shannon_entropy, WindowConfig};
 use crate::triage::config::EntropyConfig;
+use crate::triage::deadline::Deadline;
 
 #[cfg(feature = "python-ext")]
 use pyo3::prelude::*;
 
 /// Computes entropy summary using the optimized entropy module.
 pub fn compute_entropy(data: &[u8], cfg: &EntropyConfig) -> EntropySummary {
+    compute_entropy_with_deadline(data, cfg, &Deadline::none())
+}
+
+/// Like [`compute_entropy`], keeping only the windows computed before
+/// `deadline` expires.
+pub fn compute_entropy_with_deadline(
+    data: &[u8],
+    cfg: &EntropyConfig,
+    deadline: &Deadline,
+) -> EntropySummary {
     // Calculate overall entropy if requested
     let overall = if cfg.overall {
         Some(shannon_entropy(data))
@@ -23,42 +36,37 @@ pub fn compute_entropy(data: &[u8], cfg: &EntropyConfig) -> EntropySummary {
         max_windows: cfg.max_windows,
     };
 
-    let window_analysis = analyze_windows(data, &window_config);
+    let window_analysis = analyze_windows_while(data, &window_config, || !deadline.is_expired());
 
-    // Extract statistics
-    let (windows, window_size, mean, std_dev, min, max) = if window_analysis.is_empty() {
-        (None, None, None, None, None, None)
-    } else {
-        (
-            Some(window_analysis.entropies.clone()),
-            Some(window_analysis.window_size as u32),
-            window_analysis.mean(),
-            window_analysis.std_dev(),
-            window_analysis.min(),
-            window_analysis.max(),
-        )
-    };
-
-    EntropySummary {
-        overall,
-        window_size,
-        windows,
-        mean,
-        std_dev,
-        min,
-        max,
+    if window_analysis.is_empty() {
+        return EntropySummary::new(overall, None, None);
     }
+    EntropySummary::new(
+        overall,
+        Some(window_analysis.window_size as u32),
+        Some(window_analysis.entropies),
+    )
 }
 
 /// Analyzes entropy with classification and anomaly detection.
 ///
 /// This function performs high-level analysis including:
 /// - Classification into entropy categories (Text, Code, Compressed, etc.)
-/// - Detection of entropy anomalies (cliffs)
+/// - Detection of entropy anomalies (cliffs) and outlier windows (z-score)
 /// - Packed/encrypted indicator analysis
 pub fn analyze_entropy(data: &[u8], cfg: &EntropyConfig) -> EntropyAnalysis {
+    analyze_entropy_with_deadline(data, cfg, &Deadline::none())
+}
+
+/// Like [`analyze_entropy`], stopping the window scan at `deadline`; the
+/// classification and indicators then rest on the windows computed so far.
+pub fn analyze_entropy_with_deadline(
+    data: &[u8],
+    cfg: &EntropyConfig,
+    deadline: &Deadline,
+) -> EntropyAnalysis {
     // Get entropy summary
-    let summary = compute_entropy(data, cfg);
+    let summary = compute_entropy_with_deadline(data, cfg, deadline);
     let overall = summary.overall.unwrap_or_else(|| shannon_entropy(data));
 
     // Classification via thresholds
@@ -70,12 +78,14 @@ pub fn analyze_entropy(data: &[u8], cfg: &EntropyConfig) -> EntropyAnalysis {
 
     // Detect anomalies (entropy cliffs)
     let anomalies = detect_entropy_anomalies(&summary, t.cliff_delta);
+    let outliers = detect_entropy_outliers(&summary, t.outlier_zscore);
 
     EntropyAnalysis {
         summary,
         classification: class,
         packed_indicators: indicators,
         anomalies,
+        outliers,
     }
 }
 
@@ -172,6 +182,27 @@ fn detect_entropy_anomalies(summary: &EntropySummary, cliff_threshold: f64) -> V
     anomalies
 }
 
+/// Detects windows more than `threshold` standard deviations from the mean.
+///
+/// Complements the cliff scan: a single low-entropy window (e.g. a config
+/// blob) inside an otherwise packed file shows up here even when its
+/// neighbours differ from it by less than the cliff delta.
+fn detect_entropy_outliers(summary: &EntropySummary, threshold: f64) -> Vec<EntropyOutlier> {
+    let (Some(windows), Some(mean), Some(std_dev)) =
+        (&summary.windows, summary.mean, summary.std_dev)
+    else {
+        return Vec::new();
+    };
+    detect_anomalies_zscore(windows, threshold)
+        .into_iter()
+        .map(|index| EntropyOutlier {
+            index,
+            entropy: windows[index],
+            z_score: (windows[index] - mean) / std_dev,
+        })
+        .collect()
+}
+
 /// Finds the index of the first entropy cliff in window data.
 fn find_first_cliff(windows: &[f64], threshold: f64) -> Option<usize> {
     for i in 1..windows.len() {
@@ -231,6 +262,13 @@ pub fn analyze_entropy_bytes_py(
     Ok(analyze_entropy(&data, &cfg))
 }
 
+#[cfg(feature = "python-ext")]
+#[pyfunction]
+#[pyo3(name = "compare_entropy_profiles")]
+pub fn compare_entropy_profiles_py(a: Vec<f64>, b: Vec<f64>) -> PyResult<f64> {
+    Ok(crate::entropy::compare_profiles(&a, &b))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -340,4 +378,33 @@ mod tests {
         assert!(analysis.packed_indicators.has_high_entropy_body);
         assert!(analysis.packed_indicators.verdict > 0.5);
     }
+
+    #[test]
+    fn outlier_window_inside_packed_body_is_reported() {
+        // 16 random windows; window 7 draws from 150 byte values (~7.2 bits),
+        // a step too small for the cliff detector.
+        let mut rng = 7u64;
+        let mut data = Vec::new();
+        for w in 0..16 {
+            for _ in 0..4096 {
+                rng = rng.wrapping_mul(1664525).wrapping_add(1013904223);
+                let b = (rng >> 24) as u8;
+                data.push(if w == 7 { b % 150 } else { b });
+            }
+        }
+        let mut cfg = EntropyConfig {
+            window_size: 4096,
+            step: 4096,
+            ..Default::default()
+        };
+        let analysis = analyze_entropy(&data, &cfg);
+
+        assert!(analysis.anomalies.is_empty());
+        assert_eq!(analysis.outliers.len(), 1);
+        assert_eq!(analysis.outliers[0].index, 7);
+        assert!(analysis.outliers[0].z_score < -3.0);
+
+        cfg.thresholds.outlier_zscore = 10.0;
+        assert!(analyze_entropy(&data, &cfg).outliers.is_empty());
+    }
 }
@@ -2,9 +2,16 @@
 //!
 //! This module provides clean, performant logic to derive binary formats
 //! from triage hints including labels, file extensions, and MIME types.
+//! [`detect_format`] combines content sniffing and header validation into a
+//! single best guess without building a full artifact.
 
 use crate::core::binary::Format;
-use crate::core::triage::TriageHint;
+use crate::core::triage::{SnifferSource, TriageHint, TriageVerdict};
+use crate::triage::headers;
+use crate::triage::io::{MAX_HEADER_SIZE, MAX_SNIFF_SIZE};
+use crate::triage::sniffers::ContentSniffer;
+#[cfg(feature = "python-ext")]
+use pyo3::prelude::*;
 use std::collections::HashMap;
 
 /// Lazily-initialized lookup tables for format detection
@@ -130,6 +137,115 @@ pub fn derive_format_from_hint(hint: &TriageHint) -> Option<Format> {
     None
 }
 
+/// Default MIME type and label for a binary format, used when no content
+/// hint names the format more precisely.
+pub fn format_type(format: Format) -> Option<(&'static str, &'static str)> {
+    Some(match format {
+        Format::ELF => ("application/x-elf", "elf"),
+        Format::PE => ("application/vnd.microsoft.portable-executable", "pe"),
+        Format::MachO => ("application/x-mach-binary", "macho"),
+        Format::Wasm => ("application/wasm", "wasm"),
+        Format::PythonBytecode => ("application/x-python-bytecode", "pyc"),
+        Format::Dex => ("application/vnd.android.dex", "dex"),
+        Format::COFF => ("application/x-coff", "coff"),
+        Format::Raw | Format::Unknown => return None,
+    })
+}
+
+/// Fill in `mime`/`label` on verdicts that lack them.
+///
+/// A content hint mapping to the verdict's format supplies both; otherwise
+/// the [`format_type`] defaults are used.
+pub fn annotate_verdicts(verdicts: &mut [TriageVerdict], hints: &[TriageHint]) {
+    for v in verdicts.iter_mut().filter(|v| v.mime.is_none()) {
+        let hinted = hints
+            .iter()
+            .filter(|h| h.source != SnifferSource::MimeGuess)
+            .find(|h| derive_format_from_hint(h) == Some(v.format));
+        let (default_mime, default_label) = format_type(v.format).unzip();
+        v.mime = hinted
+            .and_then(|h| h.mime.clone())
+            .or(default_mime.map(String::from));
+        v.label = hinted
+            .and_then(|h| h.label.clone())
+            .or(default_label.map(String::from));
+    }
+}
+
+/// Confidence added to a header candidate that the content sniffer agrees with.
+const SNIFFER_AGREEMENT_BONUS: f32 = 0.1;
+
+/// Confidence for a format known only from a content sniffer hint; magic
+/// matched but the header did not validate.
+const SNIFFER_ONLY_CONFIDENCE: f32 = 0.4;
+
+/// Best-guess format of `data` with a confidence in `0.0..=1.0`.
+///
+/// Header validation candidates win, boosted when the content sniffer maps
+/// to the same format. Without a valid header the sniffer hint is used at
+/// reduced confidence. Unrecognized input yields `(Format::Raw, 0.0)`.
+pub fn detect_format(data: &[u8]) -> (Format, f32) {
+    let sniffed = ContentSniffer::sniff_bytes(&data[..data.len().min(MAX_SNIFF_SIZE as usize)])
+        .filter(|h| !is_container_hint(h))
+        .and_then(|h| derive_format_from_hint(&h));
+
+    let header_buf = &data[..data.len().min(MAX_HEADER_SIZE as usize)];
+    let best = headers::validate(header_buf)
+        .candidates
+        .into_iter()
+        .map(|v| {
+            let bonus = if Some(v.format) == sniffed {
+                SNIFFER_AGREEMENT_BONUS
+            } else {
+                0.0
+            };
+            (v.format, (v.confidence + bonus).min(1.0))
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1));
+
+    best.or_else(|| sniffed.map(|f| (f, SNIFFER_ONLY_CONFIDENCE)))
+        .unwrap_or((Format::Raw, 0.0))
+}
+
+#[cfg(feature = "python-ext")]
+#[pyfunction]
+#[pyo3(name = "detect_format")]
+pub fn detect_format_py(data: Vec<u8>) -> (Format, f32) {
+    detect_format(&data)
+}
+
+/// What a `CA FE BA BE` prefix actually introduces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CafeBabeKind {
+    /// Mach-O universal (FAT) binary.
+    MachOFat,
+    /// Java class file.
+    JavaClass,
+}
+
+/// Smallest Java class major version (JDK 1.1).
+const JAVA_MIN_MAJOR_VERSION: u16 = 45;
+
+/// Disambiguate the `0xCAFEBABE` magic shared by Mach-O FAT and Java classes.
+///
+/// The big-endian word after the magic is the FAT slice count in a universal
+/// binary (a handful of architectures) but `minor_version:major_version` in a
+/// class file, where the major version is at least 45. `0xCAFEBABF` (FAT64)
+/// has no Java counterpart. Returns `None` when the magic does not match or
+/// the version word fits neither layout.
+pub fn classify_cafebabe(data: &[u8]) -> Option<CafeBabeKind> {
+    if data.len() < 8 || data[..3] != [0xCA, 0xFE, 0xBA] {
+        return None;
+    }
+    let word = u32::from_be_bytes([data[4], data[5], data[6], data[7]]);
+    match data[3] {
+        0xBF if word > 0 => Some(CafeBabeKind::MachOFat),
+        0xBE if word > 0 && word < JAVA_MIN_MAJOR_VERSION as u32 => Some(CafeBabeKind::MachOFat),
+        0xBE if (word & 0xFFFF) as u16 >= JAVA_MIN_MAJOR_VERSION => Some(CafeBabeKind::JavaClass),
+        _ => None,
+    }
+}
+
 /// Check if a hint represents a container format that should not be mapped to a binary format.
 ///
 /// Container formats like ZIP, TAR, GZIP are intentionally excluded from binary format
@@ -152,6 +268,7 @@ pub fn is_container_hint(hint: &TriageHint) -> bool {
                 | "rar5"
                 | "ar"
                 | "cpio"
+                | "cab"
         )
     } else {
         false
@@ -171,6 +288,20 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_detect_format_combines_sniffer_and_headers() {
+        let (format, conf) = detect_format(b"\0asm\x01\0\0\0");
+        assert_eq!(format, Format::Wasm);
+        assert!((conf - 0.85).abs() < 1e-6, "{}", conf);
+
+        // Containers and plain text are not binary formats.
+        assert_eq!(
+            detect_format(b"\x1f\x8b\x08\0\0\0\0\0\0\x03"),
+            (Format::Raw, 0.0)
+        );
+        assert_eq!(detect_format(b"just some text"), (Format::Raw, 0.0));
+    }
+
     #[test]
     fn test_label_detection() {
         assert_eq!(
@@ -364,6 +495,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_cafebabe_disambiguation() {
+        // FAT with two slices
+        let fat = [0xCA, 0xFE, 0xBA, 0xBE, 0, 0, 0, 2];
+        assert_eq!(classify_cafebabe(&fat), Some(CafeBabeKind::MachOFat));
+        // FAT64
+        let fat64 = [0xCA, 0xFE, 0xBA, 0xBF, 0, 0, 0, 1];
+        assert_eq!(classify_cafebabe(&fat64), Some(CafeBabeKind::MachOFat));
+        // Java 8 class (major 52) and a preview class with a nonzero minor
+        let class = [0xCA, 0xFE, 0xBA, 0xBE, 0, 0, 0, 52];
+        assert_eq!(classify_cafebabe(&class), Some(CafeBabeKind::JavaClass));
+        let preview = [0xCA, 0xFE, 0xBA, 0xBE, 0xFF, 0xFF, 0, 65];
+        assert_eq!(classify_cafebabe(&preview), Some(CafeBabeKind::JavaClass));
+        // Neither layout, or no magic
+        assert_eq!(
+            classify_cafebabe(&[0xCA, 0xFE, 0xBA, 0xBE, 0, 0, 0, 0]),
+            None
+        );
+        assert_eq!(classify_cafebabe(&[0xCA, 0xFE, 0xBA, 0xBE]), None);
+        assert_eq!(classify_cafebabe(b"\x7fELF\x02\x01\x01\x00"), None);
+    }
+
     #[test]
     fn test_container_detection() {
         assert!(is_container_hint(&create_hint(Some("zip"), None, None)));
@@ -379,6 +532,7 @@ mod tests {
         assert!(is_container_hint(&create_hint(Some("rar5"), None, None)));
         assert!(is_container_hint(&create_hint(Some("ar"), None, None)));
         assert!(is_container_hint(&create_hint(Some("cpio"), None, None)));
+        assert!(is_container_hint(&create_hint(Some("cab"), None, None)));
 
         // Non-container formats should return false
         assert!(!is_container_hint(&create_hint(Some("elf"), None, None)));
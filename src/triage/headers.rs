@@ -1,9 +1,11 @@
 //! Header validation and minimal parsing.
 //!
-//! Fast magic checks and header validation for ELF, PE, Mach-O, Wasm
-//! with precise error reporting.
+//! Fast magic checks and header validation for ELF, PE, COFF, Mach-O
+//! (thin and FAT), Wasm with precise error reporting.
 use crate::core::binary::{Arch, Endianness, Format};
 use crate::core::triage::{TriageError, TriageErrorKind, TriageVerdict};
+use crate::triage::format_detection::{classify_cafebabe, CafeBabeKind};
+use crate::triage::signatures;
 
 pub struct HeaderResult {
     pub candidates: Vec<TriageVerdict>,
@@ -262,70 +264,30 @@ pub fn validate(data: &[u8]) -> HeaderResult {
         }
     }
 
-    // Mach-O (both endiannesses), not including FAT here
+    // Mach-O (both endiannesses): thin headers carry the CPU type at offset 4
     if data.len() >= 4 {
-        let m = u32::from_be_bytes([data[0], data[1], data[2], data[3]]);
-        // Big-endian view
-        match m {
-            0xFEEDFACE => {
-                if let Ok(v) = TriageVerdict::try_new(
-                    Format::MachO,
-                    Arch::Unknown,
-                    32,
-                    Endianness::Big,
-                    0.6,
-                    None,
-                ) {
-                    candidates.push(v);
-                }
-            }
-            0xFEEDFACF => {
-                if let Ok(v) = TriageVerdict::try_new(
-                    Format::MachO,
-                    Arch::Unknown,
-                    64,
-                    Endianness::Big,
-                    0.6,
-                    None,
-                ) {
-                    candidates.push(v);
-                }
-            }
-            0xCAFEBABE | 0xBEBAFECA => {
-                // FAT binaries are a future enhancement; surface as Mach-O container later
-            }
-            _ => {
-                // Little-endian view of magic
-                let ml = u32::from_le_bytes([data[0], data[1], data[2], data[3]]);
-                match ml {
-                    0xFEEDFACE => {
-                        if let Ok(v) = TriageVerdict::try_new(
-                            Format::MachO,
-                            Arch::Unknown,
-                            32,
-                            Endianness::Little,
-                            0.6,
-                            None,
-                        ) {
-                            candidates.push(v);
-                        }
-                    }
-                    0xFEEDFACF => {
-                        if let Ok(v) = TriageVerdict::try_new(
-                            Format::MachO,
-                            Arch::Unknown,
-                            64,
-                            Endianness::Little,
-                            0.6,
-                            None,
-                        ) {
-                            candidates.push(v);
-                        }
-                    }
-                    _ => {}
-                }
-            }
+        let be_magic = u32::from_be_bytes([data[0], data[1], data[2], data[3]]);
+        let le_magic = u32::from_le_bytes([data[0], data[1], data[2], data[3]]);
+        let thin = match (be_magic, le_magic) {
+            (0xFEEDFACE, _) => Some((32u8, Endianness::Big)),
+            (0xFEEDFACF, _) => Some((64, Endianness::Big)),
+            (_, 0xFEEDFACE) => Some((32, Endianness::Little)),
+            (_, 0xFEEDFACF) => Some((64, Endianness::Little)),
+            _ => None,
+        };
+        if let Some((bits, end)) = thin {
+            validate_thin_macho(data, bits, end, &mut candidates, &mut errors);
+        } else if be_magic == 0xCAFEBABE || be_magic == 0xCAFEBABF {
+            validate_fat_macho(data, be_magic == 0xCAFEBABF, &mut candidates, &mut errors);
         }
+    }
+
+    // Bare COFF object (no MZ stub): FileHeader at offset 0
+    if candidates.is_empty() && !data.starts_with(b"MZ") {
+        validate_coff(data, &mut candidates);
+    }
+
+    if data.len() >= 4 {
         // WebAssembly 0x00 61 73 6D + version
         if data.len() >= 8 && data[..4] == [0x00, b'a', b's', b'm'] {
             let version = u32::from_le_bytes([data[4], data[5], data[6], data[7]]);
@@ -450,6 +412,168 @@ pub fn validate(data: &[u8]) -> HeaderResult {
     HeaderResult { candidates, errors }
 }
 
+fn read_u32(data: &[u8], off: usize, end: Endianness) -> Option<u32> {
+    let b: [u8; 4] = data.get(off..off + 4)?.try_into().ok()?;
+    Some(match end {
+        Endianness::Little => u32::from_le_bytes(b),
+        Endianness::Big => u32::from_be_bytes(b),
+    })
+}
+
+/// Natural byte order of a Mach-O slice for the given CPU.
+fn macho_arch_endianness(arch: Arch) -> Endianness {
+    match arch {
+        Arch::PPC | Arch::PPC64 => Endianness::Big,
+        _ => Endianness::Little,
+    }
+}
+
+fn validate_thin_macho(
+    data: &[u8],
+    bits: u8,
+    end: Endianness,
+    candidates: &mut Vec<TriageVerdict>,
+    errors: &mut Vec<TriageError>,
+) {
+    let header_size = if bits == 64 { 32 } else { 28 };
+    let mut conf = 0.8f32;
+    let mut arch = Arch::Unknown;
+    match (
+        read_u32(data, 4, end),
+        read_u32(data, 16, end),
+        read_u32(data, 20, end),
+    ) {
+        (Some(cputype), Some(ncmds), Some(sizeofcmds)) if data.len() >= header_size => {
+            arch = signatures::macho_cpu_to_arch(cputype);
+            if arch == Arch::Unknown {
+                conf = 0.6;
+                errors.push(TriageError::new(
+                    TriageErrorKind::UnsupportedVariant,
+                    Some(format!("Mach-O cputype=0x{:x}", cputype)),
+                ));
+            }
+            if ncmds == 0 {
+                errors.push(TriageError::new(
+                    TriageErrorKind::IncoherentFields,
+                    Some("Mach-O has no load commands".into()),
+                ));
+                conf = conf.min(0.6);
+            } else if header_size.saturating_add(sizeofcmds as usize) > data.len() {
+                errors.push(TriageError::new(
+                    TriageErrorKind::Truncated,
+                    Some("Mach-O load commands truncated".into()),
+                ));
+                conf = conf.min(0.6);
+            }
+        }
+        _ => {
+            errors.push(TriageError::new(
+                TriageErrorKind::ShortRead,
+                Some("Mach-O header too short".into()),
+            ));
+            conf = 0.6;
+        }
+    }
+    if let Ok(v) = TriageVerdict::try_new(Format::MachO, arch, bits, end, conf, None) {
+        candidates.push(v);
+    }
+}
+
+/// Universal (FAT) Mach-O: one candidate per distinct slice architecture.
+///
+/// The FAT magic is shared with Java class files; see
+/// [`classify_cafebabe`] for how the two are told apart.
+fn validate_fat_macho(
+    data: &[u8],
+    fat64: bool,
+    candidates: &mut Vec<TriageVerdict>,
+    errors: &mut Vec<TriageError>,
+) {
+    if classify_cafebabe(data) != Some(CafeBabeKind::MachOFat) {
+        return;
+    }
+    let Some(nfat) = read_u32(data, 4, Endianness::Big) else {
+        return;
+    };
+    let entry_size = if fat64 { 32 } else { 20 };
+    let mut archs: Vec<Arch> = Vec::new();
+    for i in 0..nfat as usize {
+        let Some(cputype) = read_u32(data, 8 + i * entry_size, Endianness::Big) else {
+            errors.push(TriageError::new(
+                TriageErrorKind::Truncated,
+                Some("Mach-O FAT arch table truncated".into()),
+            ));
+            break;
+        };
+        let arch = signatures::macho_cpu_to_arch(cputype);
+        if !archs.contains(&arch) {
+            archs.push(arch);
+        }
+    }
+    if archs.len() > 1 {
+        archs.retain(|a| *a != Arch::Unknown);
+    }
+    for arch in archs {
+        let bits = if arch == Arch::Unknown {
+            32
+        } else {
+            arch.bits()
+        };
+        let conf = if arch == Arch::Unknown { 0.6 } else { 0.75 };
+        if let Ok(v) = TriageVerdict::try_new(
+            Format::MachO,
+            arch,
+            bits,
+            macho_arch_endianness(arch),
+            conf,
+            None,
+        ) {
+            candidates.push(v);
+        }
+    }
+}
+
+/// Bare COFF object file (e.g. MSVC `.obj`).
+///
+/// COFF has no magic, so require a known machine, no optional header, a
+/// plausible section count and a section table whose first name looks like
+/// a section name.
+fn validate_coff(data: &[u8], candidates: &mut Vec<TriageVerdict>) {
+    if data.len() < 20 + 40 {
+        return;
+    }
+    let machine = u16::from_le_bytes([data[0], data[1]]);
+    let num_sections = u16::from_le_bytes([data[2], data[3]]) as usize;
+    let size_opt = u16::from_le_bytes([data[16], data[17]]);
+    let arch = signatures::pe_machine_to_arch(machine);
+    if arch == Arch::Unknown || size_opt != 0 || num_sections == 0 || num_sections > 96 {
+        return;
+    }
+    let name = &data[20..28];
+    let name_len = name.iter().position(|&b| b == 0).unwrap_or(name.len());
+    let plausible_name = name_len > 0
+        && matches!(name[0], b'.' | b'/')
+        && name[..name_len].iter().all(|b| b.is_ascii_graphic())
+        && name[name_len..].iter().all(|&b| b == 0);
+    if !plausible_name {
+        return;
+    }
+    let mut conf = 0.7f32;
+    if 20 + num_sections * 40 > data.len() {
+        conf = 0.6;
+    }
+    if let Ok(v) = TriageVerdict::try_new(
+        Format::COFF,
+        arch,
+        arch.bits(),
+        Endianness::Little,
+        conf,
+        None,
+    ) {
+        candidates.push(v);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -473,4 +597,63 @@ mod tests {
                 .any(|v| v.format == Format::PythonBytecode));
         }
     }
+
+    #[test]
+    fn macho_thin_fat_and_coff_headers() {
+        // Thin 64-bit little-endian x86_64 Mach-O with one load command
+        let mut thin = vec![0u8; 64];
+        thin[..4].copy_from_slice(&0xFEEDFACFu32.to_le_bytes());
+        thin[4..8].copy_from_slice(&0x0100_0007u32.to_le_bytes());
+        thin[16..20].copy_from_slice(&1u32.to_le_bytes());
+        thin[20..24].copy_from_slice(&16u32.to_le_bytes());
+        let hr = validate(&thin);
+        let v = &hr.candidates[0];
+        assert_eq!(
+            (v.format, v.arch, v.bits, v.endianness),
+            (Format::MachO, Arch::X86_64, 64, Endianness::Little)
+        );
+        assert!(hr.errors.is_empty(), "{:?}", hr.errors);
+
+        // FAT with x86_64 + arm64 slices
+        let mut fat = vec![0u8; 8 + 2 * 20];
+        fat[..4].copy_from_slice(&0xCAFEBABEu32.to_be_bytes());
+        fat[4..8].copy_from_slice(&2u32.to_be_bytes());
+        fat[8..12].copy_from_slice(&0x0100_0007u32.to_be_bytes());
+        fat[28..32].copy_from_slice(&0x0100_000Cu32.to_be_bytes());
+        let archs: Vec<Arch> = validate(&fat)
+            .candidates
+            .iter()
+            .filter(|v| v.format == Format::MachO)
+            .map(|v| v.arch)
+            .collect();
+        assert_eq!(archs, vec![Arch::X86_64, Arch::AArch64]);
+
+        // Java class files share the FAT magic (version 52 in bytes 4..8)
+        let class = [0xCA, 0xFE, 0xBA, 0xBE, 0x00, 0x00, 0x00, 0x34];
+        assert!(validate(&class).candidates.is_empty());
+
+        // Bare AMD64 COFF object with a single .text section
+        let mut coff = vec![0u8; 20 + 40];
+        coff[..2].copy_from_slice(&0x8664u16.to_le_bytes());
+        coff[2..4].copy_from_slice(&1u16.to_le_bytes());
+        coff[20..25].copy_from_slice(b".text");
+        let hr = validate(&coff);
+        assert_eq!(hr.candidates.len(), 1);
+        assert_eq!(
+            (hr.candidates[0].format, hr.candidates[0].arch),
+            (Format::COFF, Arch::X86_64)
+        );
+        // Same header without a section-like name is not claimed
+        coff[20..25].copy_from_slice(b"\x01\x02\x03\x04\x05");
+        assert!(validate(&coff).candidates.is_empty());
+
+        let macho_path = "samples/binaries/platforms/darwin/amd64/export/native/multi_import-macho";
+        if let Ok(d) = fs::read(macho_path) {
+            let hr = validate(&d);
+            assert!(hr
+                .candidates
+                .iter()
+                .any(|v| v.format == Format::MachO && v.arch == Arch::X86_64));
+        }
+    }
 }
@@ -0,0 +1,109 @@
+//! Flattened risk indicators for quick routing decisions.
+//!
+//! [`summarize`] folds the nested packer, entropy, signing, overlay and
+//! symbol results of a [`TriagedArtifact`] into one flat [`Indicators`]
+//! record. Fields whose source analysis did not run read as `false`/`0`.
+
+use crate::core::triage::{EntropyClass, TriagedArtifact};
+#[cfg(feature = "python-ext")]
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// `PackedIndicators::verdict` at or above which the file counts as packed
+/// without a named packer match (low-entropy header + high-entropy body).
+const PACKED_VERDICT_THRESHOLD: f32 = 0.8;
+
+/// Overall entropy treated as high when no classification is available;
+/// matches the default `EntropyThresholds::compressed`.
+const HIGH_ENTROPY_FALLBACK: f64 = 7.0;
+
+/// Boolean/count summary of a triaged artifact.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "python-ext", pyclass(get_all))]
+pub struct Indicators {
+    /// A packer signature matched or the entropy profile looks packed.
+    pub is_packed: bool,
+    /// Authenticode, a Mach-O code signature, or a signature in the overlay.
+    pub is_signed: bool,
+    pub is_stripped: bool,
+    /// Non-empty data appended past the end of the image.
+    pub has_overlay: bool,
+    /// Whole-file entropy classified as compressed, encrypted or random.
+    pub high_entropy: bool,
+    /// Number of imports flagged as suspicious.
+    pub suspicious_imports: u32,
+    /// Number of sections mapped both writable and executable.
+    pub wx_sections: u32,
+}
+
+/// Summarize `artifact` into [`Indicators`].
+pub fn summarize(artifact: &TriagedArtifact) -> Indicators {
+    let packed_verdict = artifact
+        .entropy_analysis
+        .as_ref()
+        .map_or(0.0, |e| e.packed_indicators.verdict);
+    let is_packed = artifact.packers.as_ref().is_some_and(|p| !p.is_empty())
+        || packed_verdict >= PACKED_VERDICT_THRESHOLD;
+
+    let is_signed = artifact.signing.as_ref().is_some_and(|s| {
+        s.pe_authenticode_present || s.macho_code_signature_present || s.overlay_has_signature
+    });
+
+    let high_entropy = match &artifact.entropy_analysis {
+        Some(analysis) => !matches!(
+            analysis.classification,
+            EntropyClass::Text(_) | EntropyClass::Code(_)
+        ),
+        None => artifact
+            .entropy
+            .as_ref()
+            .and_then(|e| e.overall)
+            .is_some_and(|h| h > HIGH_ENTROPY_FALLBACK),
+    };
+
+    let symbols = artifact.symbols.as_ref();
+    let count = |v: Option<&Vec<String>>| v.map_or(0, |v| v.len() as u32);
+
+    Indicators {
+        is_packed,
+        is_signed,
+        is_stripped: artifact.is_stripped().unwrap_or(false),
+        has_overlay: artifact.overlay.as_ref().is_some_and(|o| o.size > 0),
+        high_entropy,
+        suspicious_imports: count(symbols.and_then(|s| s.suspicious_imports.as_ref())),
+        wx_sections: count(symbols.and_then(|s| s.wx_sections.as_ref())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::triage::{EntropySummary, PackerMatch};
+    use crate::symbols::SymbolSummary;
+
+    #[test]
+    fn summarize_flattens_nested_results() {
+        let mut artifact = TriagedArtifact::builder()
+            .with_id("a")
+            .with_path("a.bin")
+            .with_size_bytes(16)
+            .build()
+            .unwrap();
+        assert_eq!(summarize(&artifact), Indicators::default());
+
+        artifact.packers = Some(vec![PackerMatch::new("UPX".into(), 0.9)]);
+        artifact.entropy = Some(EntropySummary::new(Some(7.6), None, None));
+        artifact.symbols = Some(SymbolSummary {
+            stripped: true,
+            suspicious_imports: Some(vec!["VirtualAlloc".into(), "WriteProcessMemory".into()]),
+            wx_sections: Some(vec!["UPX1".into()]),
+            ..Default::default()
+        });
+
+        let ind = summarize(&artifact);
+        assert!(ind.is_packed && ind.is_stripped && ind.high_entropy);
+        assert!(!ind.is_signed && !ind.has_overlay);
+        assert_eq!(ind.suspicious_imports, 2);
+        assert_eq!(ind.wx_sections, 1);
+    }
+}
@@ -3,6 +3,8 @@
 //! Provides prefix caching, bounded readers, and safe file access
 //! with resource limits to prevent DoS attacks.
 
+use crate::core::binary::Hashes;
+use crate::io::{HashAlg, InlineHashing};
 use std::fs::File;
 use std::io::{self, Read, Seek, SeekFrom};
 use std::path::Path;
@@ -138,10 +140,16 @@ impl PrefixCache {
 }
 
 /// Safe file reader with resource limits.
+///
+/// The largest prefix read so far is cached: shorter `read_prefix` calls are
+/// served from it and `bytes_read` counts each byte of the file once.
 pub struct SafeFileReader {
     file: File,
     size: u64,
     limits: IOLimits,
+    prefix: Vec<u8>,
+    bytes_read: u64,
+    hashing: Option<InlineHashing>,
 }
 
 impl SafeFileReader {
@@ -175,13 +183,64 @@ impl SafeFileReader {
         }
 
         info!("Successfully opened file: {:?} ({} bytes)", path, size);
-        Ok(Self { file, size, limits })
+        Ok(Self {
+            file,
+            size,
+            limits,
+            prefix: Vec::new(),
+            bytes_read: 0,
+            hashing: None,
+        })
+    }
+
+    /// Hash the bytes returned by [`Self::read_prefix`] and
+    /// [`Self::read_all`] with `algs` as they are read.
+    ///
+    /// [`Self::finalize_hashes`] then only reads the part of the file past
+    /// the longest prefix instead of making a second full pass.
+    pub fn with_hashing(mut self, algs: &[HashAlg]) -> Self {
+        self.hashing = Some(InlineHashing::new(algs));
+        self
+    }
+
+    /// Whole-file digests for the algorithms passed to
+    /// [`Self::with_hashing`]; all fields are `None` without it.
+    ///
+    /// The rest of the file is streamed in chunks; like [`Self::sha256`] it
+    /// is bounded by `max_file_size` and does not count toward `bytes_read`.
+    /// Hashing stops after this call.
+    pub fn finalize_hashes(&mut self) -> io::Result<Hashes> {
+        let Some(mut hashing) = self.hashing.take() else {
+            return Ok(InlineHashing::new(&[]).finish());
+        };
+        self.hash_rest(&mut hashing)?;
+        Ok(hashing.finish())
+    }
+
+    /// Feed `hashing` the file from its current position to the end.
+    fn hash_rest(&mut self, hashing: &mut InlineHashing) -> io::Result<()> {
+        self.file.seek(SeekFrom::Start(hashing.position))?;
+        let mut reader = (&mut self.file).take(self.size.saturating_sub(hashing.position));
+        let mut buf = vec![0u8; 64 * 1024];
+        loop {
+            let n = reader.read(&mut buf)?;
+            if n == 0 {
+                return Ok(());
+            }
+            hashing.observe(hashing.position, &buf[..n]);
+        }
     }
 
     pub fn size(&self) -> u64 {
         self.size
     }
 
+    /// Unique bytes read from the file so far (overlapping prefix reads are
+    /// counted once).
+    pub fn bytes_read(&self) -> u64 {
+        self.bytes_read
+    }
+
     pub fn limits(&self) -> &IOLimits {
         &self.limits
     }
@@ -192,20 +251,47 @@ impl SafeFileReader {
         let mut reader = BoundedReader::new(&mut self.file, self.limits.max_read_bytes);
         let mut data = Vec::new();
         reader.read_to_end(&mut data)?;
+        self.bytes_read = self.bytes_read.max(data.len() as u64);
+        if let Some(hashing) = &mut self.hashing {
+            hashing.observe(0, &data);
+        }
         Ok(data)
     }
 
-    /// Read a prefix of the file.
+    /// Read a prefix of the file, served from the prefix cache when a read
+    /// at least as long (or covering the whole file) has already been done.
     pub fn read_prefix(&mut self, size: u64) -> io::Result<Vec<u8>> {
         let read_size = std::cmp::min(size, self.limits.max_read_bytes);
+        let cached = self.prefix.len() as u64;
+        if read_size <= cached || cached == self.size {
+            let end = std::cmp::min(read_size, cached) as usize;
+            return Ok(self.prefix[..end].to_vec());
+        }
         self.file.seek(SeekFrom::Start(0))?;
         let mut reader = BoundedReader::new(&mut self.file, read_size);
         let mut data = vec![0u8; read_size as usize];
         let n = reader.read(&mut data)?;
         data.truncate(n);
+        self.bytes_read = self.bytes_read.max(n as u64);
+        if let Some(hashing) = &mut self.hashing {
+            hashing.observe(0, &data);
+        }
+        if data.len() > self.prefix.len() {
+            self.prefix.clone_from(&data);
+        }
         Ok(data)
     }
 
+    /// SHA-256 of the whole file as lowercase hex, streamed in chunks.
+    ///
+    /// The file is already bounded by `max_file_size`; this pass does not
+    /// count toward `bytes_read` or touch the prefix cache.
+    pub fn sha256(&mut self) -> io::Result<String> {
+        let mut hashing = InlineHashing::new(&[HashAlg::Sha256]);
+        self.hash_rest(&mut hashing)?;
+        Ok(hashing.finish().sha256.unwrap_or_default())
+    }
+
     /// Create a bounded reader from the current position.
     pub fn bounded_reader(&mut self, limit: u64) -> BoundedReader<&mut File> {
         let effective_limit = std::cmp::min(limit, self.limits.max_read_bytes);
@@ -253,6 +339,37 @@ impl IOUtils {
         reader.read_prefix(prefix_size)
     }
 
+    /// Read `len` bytes at `offset`, clamped to the end of the file.
+    ///
+    /// Only the range is read, so the file itself may exceed `max_len`;
+    /// the clamped range may not. An `offset` past the end is an error.
+    pub fn read_file_range<P: AsRef<Path>>(
+        path: P,
+        offset: u64,
+        len: u64,
+        max_len: u64,
+    ) -> io::Result<Vec<u8>> {
+        let mut file = File::open(path)?;
+        let size = file.metadata()?.len();
+        if offset > size {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Range offset {} is past end of file ({} bytes)", offset, size),
+            ));
+        }
+        let len = len.min(size - offset);
+        if len > max_len {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Range too large: {} bytes (limit: {})", len, max_len),
+            ));
+        }
+        file.seek(SeekFrom::Start(offset))?;
+        let mut data = Vec::with_capacity(len as usize);
+        file.take(len).read_to_end(&mut data)?;
+        Ok(data)
+    }
+
     /// Check if a path exists and is a regular file.
     pub fn is_regular_file<P: AsRef<Path>>(path: P) -> bool {
         path.as_ref()
@@ -339,6 +456,46 @@ mod tests {
         assert_eq!(prefix, &test_data[..10]);
     }
 
+    #[test]
+    fn test_overlapping_prefixes_counted_once() {
+        let test_data = vec![0xabu8; 300];
+        let temp_file = NamedTempFile::new().unwrap();
+        temp_file.as_file().write_all(&test_data).unwrap();
+
+        let mut reader = SafeFileReader::open(temp_file.path(), IOLimits::default()).unwrap();
+        assert_eq!(reader.read_prefix(16).unwrap().len(), 16);
+        assert_eq!(reader.read_prefix(128).unwrap().len(), 128);
+        assert_eq!(reader.read_prefix(64).unwrap(), &test_data[..64]);
+        assert_eq!(reader.bytes_read(), 128);
+        // Requests past EOF are served from the whole-file cache.
+        assert_eq!(reader.read_prefix(4096).unwrap().len(), 300);
+        assert_eq!(reader.read_prefix(8192).unwrap().len(), 300);
+        assert_eq!(reader.bytes_read(), 300);
+    }
+
+    #[test]
+    fn test_inline_hashing_reads_only_the_tail() {
+        let temp_file = NamedTempFile::new().unwrap();
+        temp_file.as_file().write_all(b"hello world").unwrap();
+
+        let mut reader = SafeFileReader::open(temp_file.path(), IOLimits::default())
+            .unwrap()
+            .with_hashing(&[HashAlg::Sha256, HashAlg::Md5]);
+        assert_eq!(reader.read_prefix(5).unwrap(), b"hello");
+        assert_eq!(reader.read_prefix(3).unwrap(), b"hel");
+        let hashes = reader.finalize_hashes().unwrap();
+        assert_eq!(
+            hashes.sha256.as_deref(),
+            Some("b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9")
+        );
+        assert_eq!(
+            hashes.md5.as_deref(),
+            Some("5eb63bbbe01eeed093cb22bb8f5acdc3")
+        );
+        assert_eq!(reader.bytes_read(), 5);
+        assert_eq!(reader.finalize_hashes().unwrap().sha256, None);
+    }
+
     #[test]
     fn test_file_size_limit() {
         let test_data = vec![0u8; 100]; // 100 bytes
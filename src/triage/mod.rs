@@ -7,19 +7,23 @@ pub mod api;
 pub mod compiler_detection;
 pub mod config;
 pub mod containers;
+pub mod deadline;
 pub mod disasm_mini;
 pub mod entropy;
 pub mod format_detection;
 pub mod headers;
 pub mod heuristics;
+pub mod indicators;
 pub mod io;
 pub mod languages;
 pub mod overlay;
 pub mod packers;
 pub mod parsers;
+pub mod progress;
 pub mod recurse;
 pub mod rich_header;
 pub mod score;
+pub mod search;
 pub mod signatures;
 pub mod signing;
 pub mod sniffers;
@@ -27,6 +31,6 @@ pub mod sniffers;
 // Re-export key types from core for convenience
 pub use crate::core::triage::{
     Budgets, ConfidenceSignal, ContainerChild, EntropySummary, PackerMatch, ParserKind,
-    ParserResult, SnifferSource, StringsSummary, TriageError, TriageErrorKind, TriageHint,
-    TriageVerdict, TriagedArtifact,
+    ParserResult, Severity, SnifferSource, StringsSummary, TriageError, TriageErrorKind,
+    TriageHint, TriageVerdict, TriagedArtifact,
 };
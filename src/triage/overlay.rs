@@ -2,9 +2,17 @@
 //!
 //! Overlays are data appended after the official end of a binary file,
 //! commonly used for self-extracting archives, installers, and digital signatures.
+//! The payload is classified by leading magic, installer markers (NSIS,
+//! Inno Setup) within [`OverlayConfig::marker_scan_bytes`], an embedded
+//! executable header or a ZIP End Of Central Directory record, with
+//! user-supplied [`OverlayConfig::signatures`] checked first.
 
 use crate::core::binary::Format;
 use crate::entropy::shannon_entropy;
+use crate::triage::config::OverlayConfig;
+use crate::triage::containers::locate_zip_eocd;
+use crate::triage::search;
+use crate::triage::signatures::NSIS_FIRST_HEADER;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 
@@ -27,6 +35,11 @@ pub struct OverlayAnalysis {
     /// Detected format of overlay (if recognizable)
     pub detected_format: Option<OverlayFormat>,
 
+    /// Short payload name (e.g. `nsis`, `zip`, `pe`, or a custom signature
+    /// label); `None` when the format is unknown.
+    #[serde(default)]
+    pub payload: Option<String>,
+
     /// If overlay contains or appears to be a digital signature
     pub has_signature: bool,
 
@@ -61,13 +74,38 @@ pub enum OverlayFormat {
     SquashFS,
     /// ISO 9660 filesystem
     ISO9660,
+    /// Embedded PE executable
+    PE,
+    /// Embedded ELF executable
+    ELF,
     /// Unknown or unrecognized format
     Unknown,
 }
 
+impl OverlayFormat {
+    /// Lowercase payload name used for [`OverlayAnalysis::payload`].
+    pub fn label(&self) -> Option<&'static str> {
+        Some(match self {
+            OverlayFormat::ZIP => "zip",
+            OverlayFormat::CAB => "cab",
+            OverlayFormat::SevenZip => "7z",
+            OverlayFormat::RAR => "rar",
+            OverlayFormat::NSIS => "nsis",
+            OverlayFormat::InnoSetup => "inno-setup",
+            OverlayFormat::Certificate => "certificate",
+            OverlayFormat::AppImage => "appimage",
+            OverlayFormat::SquashFS => "squashfs",
+            OverlayFormat::ISO9660 => "iso9660",
+            OverlayFormat::PE => "pe",
+            OverlayFormat::ELF => "elf",
+            OverlayFormat::Unknown => return None,
+        })
+    }
+}
+
 impl OverlayAnalysis {
     /// Create a new OverlayAnalysis from raw overlay data
-    fn from_data(offset: u64, data: &[u8]) -> Self {
+    fn from_data(offset: u64, data: &[u8], cfg: &OverlayConfig) -> Self {
         let size = data.len() as u64;
         let entropy = if !data.is_empty() {
             shannon_entropy(data) as f32
@@ -79,8 +117,15 @@ impl OverlayAnalysis {
         let header_size = data.len().min(256);
         let header = data[..header_size].to_vec();
 
-        // Detect format
-        let detected_format = detect_overlay_format(data);
+        // Detect format; custom signatures name the payload themselves
+        let (detected_format, payload) = match custom_signature(data, cfg) {
+            Some(label) => (Some(OverlayFormat::Unknown), Some(label.to_string())),
+            None => {
+                let format = detect_overlay_format_with(data, cfg);
+                let payload = format.as_ref().and_then(|f| f.label()).map(String::from);
+                (format, payload)
+            }
+        };
         let has_signature = matches!(detected_format, Some(OverlayFormat::Certificate))
             || check_for_signature(data);
         let is_archive = matches!(
@@ -100,6 +145,7 @@ impl OverlayAnalysis {
             entropy,
             header,
             detected_format,
+            payload,
             has_signature,
             is_archive,
             sha256,
@@ -109,41 +155,56 @@ impl OverlayAnalysis {
 
 /// Detect overlay data in a binary file based on its format.
 pub fn detect_overlay(data: &[u8], format: Format) -> Option<OverlayAnalysis> {
-    match format {
-        Format::PE => detect_pe_overlay(data),
-        Format::ELF => detect_elf_overlay(data),
-        Format::MachO => detect_macho_overlay(data),
+    detect_overlay_with_config(data, format, &OverlayConfig::default())
+}
+
+/// Like [`detect_overlay`], classifying the payload with `cfg`.
+pub fn detect_overlay_with_config(
+    data: &[u8],
+    format: Format,
+    cfg: &OverlayConfig,
+) -> Option<OverlayAnalysis> {
+    let offset = match format {
+        Format::PE => pe_overlay_offset(data),
+        Format::ELF => elf_overlay_offset(data),
+        Format::MachO => macho_overlay_offset(data),
         _ => None,
+    }?;
+    let overlay_data = &data[offset as usize..];
+    // Skip if overlay is too small to be meaningful
+    if overlay_data.len() < 8 {
+        return None;
     }
+    Some(OverlayAnalysis::from_data(offset, overlay_data, cfg))
 }
 
-/// Detect overlay in PE files using the object crate.
+/// Start of the PE overlay, found using the object crate.
 /// Based on LIEF's approach: find max(section_offset + section_size).
 ///
 /// Security considerations:
 /// - MS13-098: Attackers can modify overlays without breaking Authenticode signatures
 /// - Certificate directory size can be manipulated to cover overlay data
 /// - WinVerifyTrust doesn't hash all bytes (not a flat file hash)
-fn detect_pe_overlay(data: &[u8]) -> Option<OverlayAnalysis> {
+fn pe_overlay_offset(data: &[u8]) -> Option<u64> {
     // Parse PE file
     let pe = match object::read::pe::PeFile32::parse(data) {
         Ok(pe) => pe,
         Err(_) => {
             // Try 64-bit
             match object::read::pe::PeFile64::parse(data) {
-                Ok(pe) => return detect_pe_overlay_impl(data, pe),
+                Ok(pe) => return pe_overlay_offset_impl(data, pe),
                 Err(_) => return None,
             }
         }
     };
-    detect_pe_overlay_impl(data, pe)
+    pe_overlay_offset_impl(data, pe)
 }
 
-/// Internal implementation for PE overlay detection.
-fn detect_pe_overlay_impl<Pe: object::read::pe::ImageNtHeaders>(
+/// Internal implementation for PE overlay offset detection.
+fn pe_overlay_offset_impl<Pe: object::read::pe::ImageNtHeaders>(
     data: &[u8],
     pe: object::read::pe::PeFile<Pe>,
-) -> Option<OverlayAnalysis> {
+) -> Option<u64> {
     // Find the end of the last section
     // This follows LIEF's approach: max(section.offset + section.size)
     let last_section_end = pe
@@ -163,20 +224,11 @@ fn detect_pe_overlay_impl<Pe: object::read::pe::ImageNtHeaders>(
         return None;
     }
 
-    // Extract overlay data
-    let overlay_offset = last_section_end;
-    let overlay_data = &data[overlay_offset as usize..];
-
-    // Skip if overlay is too small to be meaningful
-    if overlay_data.len() < 8 {
-        return None;
-    }
-
-    Some(OverlayAnalysis::from_data(overlay_offset, overlay_data))
+    Some(last_section_end)
 }
 
-/// Detect overlay in ELF files using the object crate.
-fn detect_elf_overlay(data: &[u8]) -> Option<OverlayAnalysis> {
+/// Start of the ELF overlay, found using the object crate.
+fn elf_overlay_offset(data: &[u8]) -> Option<u64> {
     use object::read::elf::{ElfFile32, ElfFile64};
     use object::{Object, ObjectSection};
 
@@ -215,20 +267,11 @@ fn detect_elf_overlay(data: &[u8]) -> Option<OverlayAnalysis> {
         return None;
     }
 
-    // Extract overlay data
-    let overlay_offset = last_section_end;
-    let overlay_data = &data[overlay_offset as usize..];
-
-    // Skip if overlay is too small
-    if overlay_data.len() < 8 {
-        return None;
-    }
-
-    Some(OverlayAnalysis::from_data(overlay_offset, overlay_data))
+    Some(last_section_end)
 }
 
-/// Detect overlay in Mach-O files using the object crate.
-fn detect_macho_overlay(data: &[u8]) -> Option<OverlayAnalysis> {
+/// Start of the Mach-O overlay, found using the object crate.
+fn macho_overlay_offset(data: &[u8]) -> Option<u64> {
     use object::read::macho::{MachOFile32, MachOFile64};
     use object::{Object, ObjectSegment};
 
@@ -263,23 +306,47 @@ fn detect_macho_overlay(data: &[u8]) -> Option<OverlayAnalysis> {
         return None;
     }
 
-    // Extract overlay data
-    let overlay_offset = last_segment_end;
-    let overlay_data = &data[overlay_offset as usize..];
+    Some(last_segment_end)
+}
 
-    // Skip if overlay is too small
-    if overlay_data.len() < 8 {
-        return None;
-    }
+/// Label of the first [`OverlayConfig::signatures`] entry matching `data`.
+fn custom_signature<'c>(data: &[u8], cfg: &'c OverlayConfig) -> Option<&'c str> {
+    cfg.signatures
+        .iter()
+        .find(|sig| {
+            let end = sig.offset.saturating_add(sig.magic.len());
+            !sig.magic.is_empty() && data.get(sig.offset..end) == Some(sig.magic.as_slice())
+        })
+        .map(|sig| sig.label.as_str())
+}
+
+/// Inno Setup setup-loader and setup-data markers.
+const INNO_MARKERS: [&[u8]; 3] = [b"rDlPtS", b"Inno Setup Setup Data", b"Inno Setup"];
 
-    Some(OverlayAnalysis::from_data(overlay_offset, overlay_data))
+/// Whether `data` starts with an MZ header whose `e_lfanew` points at `PE\0\0`.
+fn is_embedded_pe(data: &[u8]) -> bool {
+    if !data.starts_with(b"MZ") || data.len() < 0x40 {
+        return false;
+    }
+    let e_lfanew = u32::from_le_bytes([data[0x3C], data[0x3D], data[0x3E], data[0x3F]]) as usize;
+    e_lfanew
+        .checked_add(4)
+        .and_then(|end| data.get(e_lfanew..end))
+        == Some(b"PE\0\0".as_slice())
 }
 
 /// Detect the format of overlay data based on magic bytes and patterns.
+#[cfg(test)]
 fn detect_overlay_format(data: &[u8]) -> Option<OverlayFormat> {
+    detect_overlay_format_with(data, &OverlayConfig::default())
+}
+
+/// Built-in overlay classification, bounding marker searches by `cfg`.
+fn detect_overlay_format_with(data: &[u8], cfg: &OverlayConfig) -> Option<OverlayFormat> {
     if data.len() < 4 {
         return None;
     }
+    let marker_window = &data[..data.len().min(cfg.marker_scan_bytes)];
 
     // Check magic bytes at the beginning
     match &data[..4.min(data.len())] {
@@ -312,15 +379,29 @@ fn detect_overlay_format(data: &[u8]) -> Option<OverlayFormat> {
         return Some(OverlayFormat::SevenZip);
     }
 
-    // Check for NSIS installer patterns
-    if data.windows(4).any(|w| w == b"NSIS") {
+    // Embedded executables (droppers, bundled installers)
+    if is_embedded_pe(data) {
+        return Some(OverlayFormat::PE);
+    }
+    if data.starts_with(b"\x7FELF") {
+        return Some(OverlayFormat::ELF);
+    }
+
+    // Check for NSIS installer patterns: the first header follows a
+    // 4-byte flags field, usually at the overlay start
+    if search::contains(marker_window, NSIS_FIRST_HEADER)
+        || search::contains(marker_window, b"NSIS")
+    {
         return Some(OverlayFormat::NSIS);
     }
 
     // Check for Inno Setup patterns
     if data.len() >= 64 {
         // Inno Setup has various signatures, check common ones
-        if data.windows(12).any(|w| w == b"Inno Setup") {
+        if INNO_MARKERS
+            .iter()
+            .any(|m| search::contains(marker_window, m))
+        {
             return Some(OverlayFormat::InnoSetup);
         }
         // Check for "zlb" compressed Inno Setup
@@ -329,6 +410,11 @@ fn detect_overlay_format(data: &[u8]) -> Option<OverlayFormat> {
         }
     }
 
+    // ZIP behind a stub (self-extractors): located from its EOCD record
+    if locate_zip_eocd(data).is_some() {
+        return Some(OverlayFormat::ZIP);
+    }
+
     // Check for certificate/signature patterns
     // Look for PKCS#7 SignedData OID
     if data.len() >= 32 {
@@ -391,9 +477,7 @@ fn check_for_pkcs7_signature(data: &[u8]) -> bool {
 
     // Search for the OID in the first 256 bytes
     let search_len = data.len().min(256);
-    data[..search_len]
-        .windows(PKCS7_SIGNED_DATA_OID.len())
-        .any(|w| w == PKCS7_SIGNED_DATA_OID)
+    search::contains(&data[..search_len], PKCS7_SIGNED_DATA_OID)
 }
 
 /// Calculate SHA256 hash of data.
@@ -435,6 +519,11 @@ mod python {
             self.detected_format.clone()
         }
 
+        #[getter]
+        fn payload(&self) -> Option<String> {
+            self.payload.clone()
+        }
+
         #[getter]
         fn has_signature(&self) -> bool {
             self.has_signature
@@ -473,6 +562,7 @@ mod python {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::triage::config::OverlaySignature;
 
     #[test]
     fn test_overlay_format_detection() {
@@ -496,6 +586,48 @@ mod tests {
         assert_eq!(detect_overlay_format(rar_data), Some(OverlayFormat::RAR));
     }
 
+    #[test]
+    fn test_installer_and_executable_payloads() {
+        let mut nsis = vec![0u8; 64];
+        nsis[..4].copy_from_slice(&[0; 4]);
+        nsis[4..20].copy_from_slice(NSIS_FIRST_HEADER);
+        assert_eq!(detect_overlay_format(&nsis), Some(OverlayFormat::NSIS));
+
+        let mut inno = vec![0u8; 128];
+        inno[16..24].copy_from_slice(b"rDlPtS02");
+        assert_eq!(detect_overlay_format(&inno), Some(OverlayFormat::InnoSetup));
+
+        let mut pe = vec![0u8; 0x100];
+        pe[..2].copy_from_slice(b"MZ");
+        pe[0x3C] = 0x80;
+        pe[0x80..0x84].copy_from_slice(b"PE\0\0");
+        assert_eq!(detect_overlay_format(&pe), Some(OverlayFormat::PE));
+        // MZ without a PE header is not an executable
+        pe[0x80] = 0;
+        assert_eq!(detect_overlay_format(&pe), Some(OverlayFormat::Unknown));
+
+        // Markers past the scan window are ignored
+        let cfg = OverlayConfig {
+            marker_scan_bytes: 8,
+            ..Default::default()
+        };
+        assert_eq!(
+            detect_overlay_format_with(&inno, &cfg),
+            Some(OverlayFormat::Unknown)
+        );
+
+        // Custom signatures name the payload
+        let mut cfg = OverlayConfig::default();
+        cfg.signatures.push(OverlaySignature {
+            label: "acme-bundle".into(),
+            magic: b"ACME".to_vec(),
+            offset: 2,
+        });
+        let analysis = OverlayAnalysis::from_data(0, b"..ACMEpayload", &cfg);
+        assert_eq!(analysis.detected_format, Some(OverlayFormat::Unknown));
+        assert_eq!(analysis.payload.as_deref(), Some("acme-bundle"));
+    }
+
     #[test]
     fn test_sha256_calculation() {
         let data = b"test data";
@@ -509,12 +641,13 @@ mod tests {
     #[test]
     fn test_overlay_analysis_creation() {
         let data = b"PK\x03\x04test overlay data with some content";
-        let analysis = OverlayAnalysis::from_data(1000, data);
+        let analysis = OverlayAnalysis::from_data(1000, data, &OverlayConfig::default());
 
         assert_eq!(analysis.offset, 1000);
         assert_eq!(analysis.size, data.len() as u64);
         assert!(analysis.entropy > 0.0);
         assert_eq!(analysis.detected_format, Some(OverlayFormat::ZIP));
+        assert_eq!(analysis.payload.as_deref(), Some("zip"));
         assert!(analysis.is_archive);
         assert!(!analysis.has_signature);
     }
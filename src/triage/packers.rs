@@ -1,7 +1,9 @@
 use crate::core::triage::PackerMatch;
 use crate::entropy::shannon_entropy;
+use crate::formats::pe::PeParser;
 use crate::triage::config::{EntropyConfig, PackerConfig};
 use crate::triage::entropy::analyze_entropy;
+use crate::triage::signatures::{MultiScanner, SignatureHits};
 
 fn bump_match(out: &mut Vec<PackerMatch>, name: &str, base_if_absent: f32, delta: f32) {
     if let Some(m) = out.iter_mut().find(|m| m.name.eq_ignore_ascii_case(name)) {
@@ -14,7 +16,83 @@ fn bump_match(out: &mut Vec<PackerMatch>, name: &str, base_if_absent: f32, delta
     }
 }
 
+/// Score a PE for UPX's section layout when its strings have been scrubbed.
+///
+/// Scramblers rewrite `UPX!`/`UPX0`/`UPX1` but keep the shape: an empty
+/// first section reserving the unpacked image, a high-entropy section holding
+/// the compressed data and the entry stub, a `pushad`/`push rbx..rbp`
+/// prologue, and a tail `jmp` back into the empty section (the OEP).
+fn modified_upx_score(data: &[u8]) -> f32 {
+    let Ok(pe) = PeParser::new(data) else {
+        return 0.0;
+    };
+    let sections = pe.sections();
+    if !(2..=3).contains(&sections.len()) {
+        return 0.0;
+    }
+    let first = &sections[0].header;
+    let image_start = first.virtual_address;
+    let image_end = image_start.saturating_add(first.virtual_size);
+
+    let mut score = 0.0f32;
+    if first.size_of_raw_data == 0 && first.virtual_size > 0 {
+        score += 0.3;
+    }
+
+    let entry = pe.entry_point();
+    let Some(stub) = sections[1..].iter().find(|s| {
+        let h = &s.header;
+        entry >= h.virtual_address && entry < h.virtual_address.saturating_add(h.virtual_size)
+    }) else {
+        return score;
+    };
+    let Some(bytes) = data.get(stub.data.clone()) else {
+        return score;
+    };
+    if bytes.len() >= 1024 && shannon_entropy(bytes) > 7.0 {
+        score += 0.3;
+    }
+
+    let entry_off = (entry - stub.header.virtual_address) as usize;
+    let code = bytes.get(entry_off..).unwrap_or(&[]);
+    const PROLOGUES: [&[u8]; 2] = [
+        &[0x60, 0xBE],                               // pushad; mov esi, imm32
+        &[0x53, 0x56, 0x57, 0x55, 0x48, 0x8D, 0x35], // push rbx/rsi/rdi/rbp; lea rsi, [rip+..]
+    ];
+    if PROLOGUES.iter().any(|p| code.starts_with(p)) {
+        score += 0.2;
+    }
+
+    // jmp rel32 from the stub into the reserved image
+    let window = &code[..code.len().min(4096)];
+    let jumps_to_oep = window.windows(5).enumerate().any(|(i, w)| {
+        if w[0] != 0xE9 {
+            return false;
+        }
+        let rel = i32::from_le_bytes([w[1], w[2], w[3], w[4]]);
+        let next = entry.wrapping_add(i as u32 + 5);
+        let target = next.wrapping_add(rel as u32);
+        target >= image_start && target < image_end
+    });
+    if jumps_to_oep {
+        score += 0.2;
+    }
+    score
+}
+
 pub fn detect_packers(data: &[u8], cfg: &PackerConfig) -> Vec<PackerMatch> {
+    let hay = &data[..data.len().min(cfg.scan_limit)];
+    detect_packers_with_hits(data, &MultiScanner::builtin().scan(hay), cfg)
+}
+
+/// [`detect_packers`] reusing the caller's [`MultiScanner::builtin`] scan of
+/// `data`, shared with the other literal-signature detectors; only markers
+/// inside the first `cfg.scan_limit` bytes count.
+pub fn detect_packers_with_hits(
+    data: &[u8],
+    hits: &SignatureHits<'_>,
+    cfg: &PackerConfig,
+) -> Vec<PackerMatch> {
     let mut out = Vec::new();
     // Respect scan_limit from PackerConfig (default) to bound scanning cost
     let scan_limit = cfg.scan_limit;
@@ -23,22 +101,23 @@ pub fn detect_packers(data: &[u8], cfg: &PackerConfig) -> Vec<PackerMatch> {
     } else {
         data
     };
+    // Hits record first occurrences, so a marker is inside `hay` iff its
+    // first occurrence is
+    let has = |p: &[u8]| hits.find(p).is_some_and(|o| o + p.len() <= hay.len());
 
     // UPX
     let mut upx = 0.0f32;
-    if memchr::memmem::find(hay, b"UPX!").is_some() {
+    if has(b"UPX!") {
         upx += 0.4;
     }
-    if memchr::memmem::find(hay, b"UPX0").is_some() {
+    if has(b"UPX0") {
         upx += 0.3;
     }
-    if memchr::memmem::find(hay, b"UPX1").is_some() {
+    if has(b"UPX1") {
         upx += 0.3;
     }
     // Version/signature hints increase confidence
-    if memchr::memmem::find(hay, b"$Id: UPX ").is_some()
-        || memchr::memmem::find(hay, b"UPX ").is_some()
-    {
+    if has(b"$Id: UPX ") || has(b"UPX ") {
         upx += 0.2;
     }
     if upx > 0.0 {
@@ -47,44 +126,46 @@ pub fn detect_packers(data: &[u8], cfg: &PackerConfig) -> Vec<PackerMatch> {
         out.push(PackerMatch::new("UPX".to_string(), conf));
     }
 
+    // UPX with scrubbed strings, recognized by layout
+    if upx == 0.0 {
+        let score = modified_upx_score(hay);
+        if score >= 0.6 {
+            out.push(PackerMatch::new("UPX (modified)".into(), score.min(0.9)));
+        }
+    }
+
     // ASPack
-    if memchr::memmem::find(hay, b"ASPack").is_some() {
+    if has(b"ASPack") {
         out.push(PackerMatch::new("ASPack".into(), 0.9));
     }
 
     // PECompact
-    if memchr::memmem::find(hay, b"PECompact").is_some()
-        || memchr::memmem::find(hay, b"PEC2").is_some()
-    {
+    if has(b"PECompact") || has(b"PEC2") {
         out.push(PackerMatch::new("PECompact".into(), 0.85));
     }
 
     // Petite
-    if memchr::memmem::find(hay, b"Petite").is_some() {
+    if has(b"Petite") {
         out.push(PackerMatch::new("Petite".into(), 0.8));
     }
 
     // FSG
-    if memchr::memmem::find(hay, b"FSG!").is_some() {
+    if has(b"FSG!") {
         out.push(PackerMatch::new("FSG".into(), 0.85));
     }
 
     // MPRESS
-    if memchr::memmem::find(hay, b"MPRESS").is_some() {
+    if has(b"MPRESS") {
         out.push(PackerMatch::new("MPRESS".into(), 0.85));
     }
 
     // Themida/WinLicense
-    if memchr::memmem::find(hay, b"Themida").is_some()
-        || memchr::memmem::find(hay, b"WinLicense").is_some()
-    {
+    if has(b"Themida") || has(b"WinLicense") {
         out.push(PackerMatch::new("Themida/WinLicense".into(), 0.9));
     }
 
     // VMProtect (section names often .vmp0/.vmp1 in PE, but scan raw too)
-    if memchr::memmem::find(hay, b".vmp0").is_some()
-        || memchr::memmem::find(hay, b".vmp1").is_some()
-    {
+    if has(b".vmp0") || has(b".vmp1") {
         out.push(PackerMatch::new("VMProtect".into(), 0.75));
     }
 
@@ -185,6 +266,72 @@ mod tests {
         }
     }
 
+    /// PE32 with an empty first section and a high-entropy stub section whose
+    /// entry code ends in a jump back into the first section.
+    fn scrubbed_upx_pe() -> Vec<u8> {
+        let mut d = vec![0u8; 0x200];
+        d[0..2].copy_from_slice(b"MZ");
+        d[0x3c] = 0x40;
+        d[0x40..0x44].copy_from_slice(b"PE\0\0");
+        d[0x44..0x46].copy_from_slice(&0x14cu16.to_le_bytes());
+        d[0x46] = 2; // sections
+        d[0x54] = 0xe0; // SizeOfOptionalHeader
+        d[0x56] = 0x02;
+        let opt = 0x58;
+        d[opt..opt + 2].copy_from_slice(&0x10bu16.to_le_bytes());
+        d[opt + 16..opt + 20].copy_from_slice(&0x12f00u32.to_le_bytes()); // entry RVA
+        d[opt + 28..opt + 32].copy_from_slice(&0x400000u32.to_le_bytes());
+        d[opt + 32..opt + 36].copy_from_slice(&0x1000u32.to_le_bytes());
+        d[opt + 36..opt + 40].copy_from_slice(&0x200u32.to_le_bytes());
+        d[opt + 56..opt + 60].copy_from_slice(&0x13000u32.to_le_bytes());
+        d[opt + 60..opt + 64].copy_from_slice(&0x200u32.to_le_bytes());
+        d[opt + 68] = 2;
+        d[opt + 92] = 16;
+        // (name, vsize, va, raw size, raw ptr)
+        let secs = [
+            (b"abc0\0\0\0\0", 0x10000u32, 0x1000u32, 0u32, 0u32),
+            (b"abc1\0\0\0\0", 0x2000, 0x11000, 0x2000, 0x200),
+        ];
+        for (i, (name, vsize, va, raw, ptr)) in secs.iter().enumerate() {
+            let h = opt + 0xe0 + i * 40;
+            d[h..h + 8].copy_from_slice(*name);
+            d[h + 8..h + 12].copy_from_slice(&vsize.to_le_bytes());
+            d[h + 12..h + 16].copy_from_slice(&va.to_le_bytes());
+            d[h + 16..h + 20].copy_from_slice(&raw.to_le_bytes());
+            d[h + 20..h + 24].copy_from_slice(&ptr.to_le_bytes());
+            d[h + 36..h + 40].copy_from_slice(&0xe000_0060u32.to_le_bytes());
+        }
+        let mut rng: u64 = 0x1234_5678_9abc_def0;
+        for _ in 0..0x1f00 {
+            rng = rng.wrapping_mul(6364136223846793005).wrapping_add(1);
+            d.push((rng >> 32) as u8);
+        }
+        // entry at RVA 0x12f00: pushad; mov esi, imm32; ...; popad; jmp 0x1000
+        let stub_start = d.len();
+        d.extend_from_slice(&[0x60, 0xBE, 0x00, 0x10, 0x41, 0x00, 0x90, 0x61, 0xE9]);
+        let jmp_next = 0x12f00 + (d.len() - stub_start) as u32 + 4;
+        d.extend_from_slice(&0x1000u32.wrapping_sub(jmp_next).to_le_bytes());
+        d.resize(0x200 + 0x2000, 0xcc);
+        d
+    }
+
+    #[test]
+    fn detect_upx_with_scrubbed_magic_by_layout() {
+        let data = scrubbed_upx_pe();
+        assert!(memchr::memmem::find(&data, b"UPX").is_none());
+        let v = detect_packers(&data, &PackerConfig::default());
+        let m = v
+            .iter()
+            .find(|m| m.name == "UPX (modified)")
+            .expect("modified UPX match");
+        assert!(m.confidence >= 0.9);
+
+        // Same layout without the compressed payload and stub
+        let mut plain = data;
+        plain[0x200..].fill(0);
+        assert!(modified_upx_score(&plain) < 0.6);
+    }
+
     #[test]
     fn detect_packed_by_entropy_cliff() {
         // Construct a buffer with low-entropy header and high-entropy body
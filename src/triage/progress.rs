@@ -0,0 +1,65 @@
+//! Opt-in progress reporting for triage.
+//!
+//! Callers that want feedback during long analyses pass a callback to
+//! [`analyze_path_with_progress`](super::api::analyze_path_with_progress) or
+//! [`analyze_bytes_with_progress`](super::api::analyze_bytes_with_progress).
+//! It is invoked on the calling thread as each phase starts, with the
+//! fraction of the pipeline already completed, and once more with
+//! [`Phase::Done`] and `1.0`.
+
+/// Triage pipeline phases, in execution order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+    Sniffing,
+    Headers,
+    Entropy,
+    Strings,
+    Parsers,
+    Disasm,
+    FormatAnalysis,
+    Scoring,
+    Done,
+}
+
+impl Phase {
+    const ALL: [Phase; 9] = [
+        Phase::Sniffing,
+        Phase::Headers,
+        Phase::Entropy,
+        Phase::Strings,
+        Phase::Parsers,
+        Phase::Disasm,
+        Phase::FormatAnalysis,
+        Phase::Scoring,
+        Phase::Done,
+    ];
+
+    /// Fraction of the pipeline completed when this phase starts.
+    pub fn fraction(self) -> f32 {
+        let idx = Self::ALL.iter().position(|&p| p == self).unwrap_or(0);
+        idx as f32 / (Self::ALL.len() - 1) as f32
+    }
+}
+
+/// Progress callback accepted by the `*_with_progress` entry points.
+pub type ProgressFn<'a> = &'a dyn Fn(Phase, f32);
+
+/// Invoke `progress`, if any, for the start of `phase`.
+pub(crate) fn report(progress: Option<ProgressFn<'_>>, phase: Phase) {
+    if let Some(cb) = progress {
+        cb(phase, phase.fraction());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fractions_are_monotonic_and_end_at_one() {
+        let fractions: Vec<f32> = Phase::ALL.iter().map(|p| p.fraction()).collect();
+        assert_eq!(fractions[0], 0.0);
+        assert_eq!(*fractions.last().unwrap(), 1.0);
+        assert!(fractions.windows(2).all(|w| w[0] < w[1]));
+    }
+}
@@ -1,191 +1,89 @@
 //! Recursive discovery of nested artifacts with budget control.
 
+use crate::analysis::carve::{self, Carved};
+use crate::core::binary::Format;
 use crate::core::triage::{Budgets, ContainerChild};
-use crate::triage::containers::detect_containers;
+use crate::formats::macho;
+use crate::triage::containers::{
+    detect_appended_zip, detect_containers_with_hits, enumerate_cab_members,
+    enumerate_inflated_tar, enumerate_ole_streams, enumerate_tar_members, inflate_gzip_prefix,
+    locate_zip_eocd, MAX_GZIP_INFLATE,
+};
+use crate::triage::format_detection::{classify_cafebabe, format_type, CafeBabeKind};
+use crate::triage::search;
+use crate::triage::signatures::{MultiScanner, SignatureHits};
 use serde::{Deserialize, Serialize};
 
 /// Recursion engine for discovering nested payloads with depth accounting.
 pub struct RecursionEngine {
     pub max_depth: usize,
+    /// Report each architecture of a universal Mach-O as a child.
+    pub fat_slices: bool,
 }
 
 impl Default for RecursionEngine {
     fn default() -> Self {
-        Self { max_depth: 1 }
+        Self::new(1)
     }
 }
 
 impl RecursionEngine {
     pub fn new(max_depth: usize) -> Self {
-        Self { max_depth }
+        Self {
+            max_depth,
+            fat_slices: true,
+        }
+    }
+
+    /// Toggle splitting universal Mach-O binaries into per-arch slices.
+    pub fn with_fat_slices(mut self, enabled: bool) -> Self {
+        self.fat_slices = enabled;
+        self
     }
 
-    /// Detect FAT Mach-O and yield child slices for each arch.
+    /// Detect FAT Mach-O and yield one `macho_slice_<arch>` child per slice.
     fn detect_fat_macho(&self, data: &[u8]) -> Vec<ContainerChild> {
-        if data.len() < 8 {
+        // A big-endian CAFEBABE may be a Java class file rather than FAT
+        if data.starts_with(&[0xCA, 0xFE, 0xBA])
+            && classify_cafebabe(data) != Some(CafeBabeKind::MachOFat)
+        {
             return Vec::new();
         }
-        let magic = u32::from_be_bytes([data[0], data[1], data[2], data[3]]);
-        let swapped_magic = u32::from_le_bytes([data[0], data[1], data[2], data[3]]);
-        // FAT magic values (32/64, swapped variants)
-        let is_fat_be = magic == 0xCAFEBABE || magic == 0xCAFEBABF;
-        let is_fat_le = swapped_magic == 0xCAFEBABE || swapped_magic == 0xCAFEBABF;
-        let mut out = Vec::new();
-        if !is_fat_be && !is_fat_le {
-            return out;
-        }
-        // Use big-endian by default (standard FAT), fall back to little-endian swapped
-        let be = is_fat_be;
-        let nfat = if be {
-            u32::from_be_bytes([data[4], data[5], data[6], data[7]]) as usize
-        } else {
-            u32::from_le_bytes([data[4], data[5], data[6], data[7]]) as usize
-        };
-        // Header sizes: 32-bit: 20 bytes per arch; 64-bit: 24 bytes per arch (we accept either)
-        let header32_size = 20usize;
-        let header64_size = 24usize;
-        let after_header = 8usize;
-        // Try 64 then 32 (tolerant parsing); bound by data length
-        for entry_size in [header64_size, header32_size] {
-            if after_header + nfat.saturating_mul(entry_size) > data.len() {
-                continue;
-            }
-            for i in 0..nfat {
-                let base = after_header + i * entry_size;
-                if base + entry_size > data.len() {
-                    break;
-                }
-                // fields: offset (u32/u64), size (u32/u64) at positions:
-                // For 32-bit: offset @8, size @12
-                // For 64-bit: offset @8 (u64), size @16 (u64)
-                if entry_size == header32_size {
-                    let off = if be {
-                        u32::from_be_bytes([
-                            data[base + 8],
-                            data[base + 9],
-                            data[base + 10],
-                            data[base + 11],
-                        ])
-                    } else {
-                        u32::from_le_bytes([
-                            data[base + 8],
-                            data[base + 9],
-                            data[base + 10],
-                            data[base + 11],
-                        ])
-                    } as u64;
-                    let sz = if be {
-                        u32::from_be_bytes([
-                            data[base + 12],
-                            data[base + 13],
-                            data[base + 14],
-                            data[base + 15],
-                        ])
-                    } else {
-                        u32::from_le_bytes([
-                            data[base + 12],
-                            data[base + 13],
-                            data[base + 14],
-                            data[base + 15],
-                        ])
-                    } as u64;
-                    if off == 0 || sz == 0 {
-                        continue;
-                    }
-                    if (off as usize) < data.len()
-                        && (off as usize).saturating_add(sz as usize) <= data.len()
-                    {
-                        out.push(ContainerChild::new("macho-thin".into(), off, sz));
-                    }
-                } else {
-                    // 64-bit
-                    if base + 24 > data.len() {
-                        continue;
-                    }
-                    let off = if be {
-                        u64::from_be_bytes([
-                            data[base + 8],
-                            data[base + 9],
-                            data[base + 10],
-                            data[base + 11],
-                            data[base + 12],
-                            data[base + 13],
-                            data[base + 14],
-                            data[base + 15],
-                        ])
-                    } else {
-                        u64::from_le_bytes([
-                            data[base + 8],
-                            data[base + 9],
-                            data[base + 10],
-                            data[base + 11],
-                            data[base + 12],
-                            data[base + 13],
-                            data[base + 14],
-                            data[base + 15],
-                        ])
-                    };
-                    let sz = if be {
-                        u64::from_be_bytes([
-                            data[base + 16],
-                            data[base + 17],
-                            data[base + 18],
-                            data[base + 19],
-                            data[base + 20],
-                            data[base + 21],
-                            data[base + 22],
-                            data[base + 23],
-                        ])
-                    } else {
-                        u64::from_le_bytes([
-                            data[base + 16],
-                            data[base + 17],
-                            data[base + 18],
-                            data[base + 19],
-                            data[base + 20],
-                            data[base + 21],
-                            data[base + 22],
-                            data[base + 23],
-                        ])
-                    };
-                    if off == 0 || sz == 0 {
-                        continue;
-                    }
-                    if (off as usize) < data.len()
-                        && (off as usize).saturating_add(sz as usize) <= data.len()
-                    {
-                        out.push(ContainerChild::new("macho-thin".into(), off, sz));
-                    }
-                }
-            }
-            if !out.is_empty() {
-                break;
-            }
-        }
-        out
+        macho::parse_fat(data)
+            .unwrap_or_default()
+            .iter()
+            .map(|s| {
+                ContainerChild::new(format!("macho_slice_{}", s.arch_name()), s.offset, s.size)
+            })
+            .collect()
     }
 
     /// Detect embedded container signatures at non-zero offsets (simple overlay heuristic).
     fn detect_embedded_containers(&self, data: &[u8]) -> Vec<ContainerChild> {
         let mut out = Vec::new();
-        // ZIP local header signature anywhere
-        let sig_zip = b"PK\x03\x04";
-        let mut start = 1usize; // skip offset 0 (handled by detect_containers)
-        while let Some(pos) = memchr::memmem::find(&data[start..], sig_zip) {
-            let off = start + pos;
-            if off > 0 {
-                out.push(ContainerChild::new(
-                    "zip".into(),
-                    off as u64,
-                    (data.len() - off) as u64,
-                ));
-                break; // first hit is enough for triage
+        // ZIP located from its EOCD record (self-extractors, polyglots);
+        // without one, fall back to the first local header signature.
+        if locate_zip_eocd(data).is_some() {
+            out.extend(detect_appended_zip(data));
+        } else {
+            let sig_zip = b"PK\x03\x04";
+            let mut start = 1usize; // skip offset 0 (handled by detect_containers)
+            while let Some(pos) = memchr::memmem::find(&data[start..], sig_zip) {
+                let off = start + pos;
+                if off > 0 {
+                    out.push(ContainerChild::new(
+                        "zip".into(),
+                        off as u64,
+                        (data.len() - off) as u64,
+                    ));
+                    break; // first hit is enough for triage
+                }
+                start = off + 4;
             }
-            start = off + 4;
         }
         // GZIP signature 1F 8B at non-zero offset
         let sig_gz = [0x1F, 0x8B];
-        if let Some(i) = data.windows(2).position(|w| w == sig_gz) {
+        if let Some(i) = search::find(data, &sig_gz) {
             if i > 0 {
                 out.push(ContainerChild::new(
                     "gzip".into(),
@@ -196,7 +94,7 @@ impl RecursionEngine {
         }
         // XZ signature FD 37 7A 58 5A 00
         let sig_xz = [0xFD, 0x37, 0x7A, 0x58, 0x5A, 0x00];
-        if let Some(i) = data.windows(sig_xz.len()).position(|w| w == sig_xz) {
+        if let Some(i) = search::find(data, &sig_xz) {
             if i > 0 {
                 out.push(ContainerChild::new(
                     "xz".into(),
@@ -217,7 +115,7 @@ impl RecursionEngine {
         }
         // ZSTD signature 28 B5 2F FD
         let sig_zstd = [0x28, 0xB5, 0x2F, 0xFD];
-        if let Some(i) = data.windows(sig_zstd.len()).position(|w| w == sig_zstd) {
+        if let Some(i) = search::find(data, &sig_zstd) {
             if i > 0 {
                 out.push(ContainerChild::new(
                     "zstd".into(),
@@ -226,6 +124,19 @@ impl RecursionEngine {
                 ));
             }
         }
+        // CAB: "MSCF" with a zero reserved field (dropper payloads in overlays)
+        if data.len() > 1 {
+            if let Some(pos) = memchr::memmem::find(&data[1..], crate::formats::cab::CAB_MAGIC) {
+                let off = pos + 1;
+                if crate::formats::cab::is_cab(&data[off..]) {
+                    out.push(ContainerChild::new(
+                        "cab".into(),
+                        off as u64,
+                        (data.len() - off) as u64,
+                    ));
+                }
+            }
+        }
         // TAR: look for "ustar" at offset +257 within a 512-byte header block
         if let Some(pos) = memchr::memmem::find(&data[1..], b"ustar") {
             let abs = pos + 1;
@@ -243,12 +154,56 @@ impl RecursionEngine {
         out
     }
 
+    /// Inflate a gzip child and enumerate the tar inside it.
+    ///
+    /// Inflated bytes are charged to `budgets.bytes_read`; each stream is
+    /// bounded by [`MAX_GZIP_INFLATE`] and by what is left of
+    /// `budgets.limit_bytes`, and running out sets `hit_byte_limit`.
+    fn inflate_gzip_members(&self, data: &[u8], budgets: &mut Budgets) -> Vec<ContainerChild> {
+        let remaining = budgets
+            .limit_bytes
+            .map_or(u64::MAX, |l| l.saturating_sub(budgets.bytes_read));
+        let allowance = remaining.min(MAX_GZIP_INFLATE as u64) as usize;
+        if allowance == 0 {
+            budgets.hit_byte_limit = true;
+            return Vec::new();
+        }
+        let Some(inner) = inflate_gzip_prefix(data, allowance) else {
+            return Vec::new();
+        };
+        budgets.bytes_read = budgets.bytes_read.saturating_add(inner.len() as u64);
+        if inner.len() == allowance && remaining < MAX_GZIP_INFLATE as u64 {
+            budgets.hit_byte_limit = true;
+        }
+        enumerate_inflated_tar(&inner).into_iter().collect()
+    }
+
     /// Discover immediate children; enforce max_depth.
+    ///
+    /// Offsets at every level of the returned tree are relative to `data`,
+    /// except for children flagged `in_decompressed_stream`.
     pub fn discover_children(
         &self,
         data: &[u8],
         budgets: &mut Budgets,
         depth: usize,
+    ) -> Vec<ContainerChild> {
+        if depth >= self.max_depth {
+            return Vec::new();
+        }
+        let hits = MultiScanner::builtin().scan(data);
+        self.discover_children_with_hits(data, &hits, budgets, depth)
+    }
+
+    /// [`Self::discover_children`] reusing the caller's
+    /// [`MultiScanner::builtin`] scan of `data`; nested levels scan their
+    /// own slices.
+    pub fn discover_children_with_hits(
+        &self,
+        data: &[u8],
+        hits: &SignatureHits<'_>,
+        budgets: &mut Budgets,
+        depth: usize,
     ) -> Vec<ContainerChild> {
         if depth >= self.max_depth {
             return Vec::new();
@@ -257,31 +212,63 @@ impl RecursionEngine {
         budgets.recursion_depth = budgets.recursion_depth.saturating_add(1);
         let mut children = Vec::new();
         // Top-level container magic
-        children.extend(detect_containers(data));
+        children.extend(detect_containers_with_hits(data, hits));
         // Fat Mach-O slicing
-        children.extend(self.detect_fat_macho(data));
+        if self.fat_slices {
+            children.extend(self.detect_fat_macho(data));
+        }
         // Embedded container (overlay) heuristics
         children.extend(self.detect_embedded_containers(data));
+        // Executables stacked past the start of the buffer
+        children.extend(
+            carve::embedded_executables(data)
+                .iter()
+                .filter(|c| c.offset > 0)
+                .filter_map(|c| carved_child(c, data.len())),
+        );
         // Deterministic ordering: by offset, then type_name
         children.sort_by(|a, b| a.offset.cmp(&b.offset).then(a.type_name.cmp(&b.type_name)));
+        // Archive member enumeration (tar, tar chained through gzip, cab, OLE)
+        for ch in children.iter_mut() {
+            let Some(slice) = child_bytes(ch, data) else {
+                continue;
+            };
+            let off = ch.offset;
+            let mut members: Vec<ContainerChild> = match ch.type_name.as_str() {
+                "tar" => enumerate_tar_members(slice),
+                "gzip" => self.inflate_gzip_members(slice, budgets),
+                "cab" => enumerate_cab_members(slice),
+                "ole" | "msi" => enumerate_ole_streams(slice),
+                _ => Vec::new(),
+            };
+            if !members.is_empty() {
+                rebase(&mut members, off);
+                ch.children = Some(members);
+            }
+        }
         // If allowed, recurse into each child's slice to build a tree
         if depth + 1 < self.max_depth {
             for ch in children.iter_mut() {
-                let off = ch.offset as usize;
-                let sz = ch.size as usize;
-                if off >= data.len() {
+                // Archives already described by their members
+                if ch.children.is_some() {
                     continue;
                 }
-                let end = off.saturating_add(sz).min(data.len());
-                if end <= off {
+                let Some(slice) = child_bytes(ch, data).filter(|s| !s.is_empty()) else {
                     continue;
-                }
-                let slice = &data[off..end];
-                let mut sub_b = Budgets::new(slice.len() as u64, 0, 0);
+                };
+                let off = ch.offset;
+                // Share the byte budget; depth is tracked by `depth`
+                let mut sub_b = Budgets {
+                    recursion_depth: 0,
+                    ..budgets.clone()
+                };
                 let mut grandkids = self.discover_children(slice, &mut sub_b, depth + 1);
+                budgets.bytes_read = sub_b.bytes_read;
+                budgets.hit_byte_limit |= sub_b.hit_byte_limit;
                 if !grandkids.is_empty() {
                     // children already sorted deterministically by inner call
-                    ch.children = Some(std::mem::take(&mut grandkids));
+                    rebase(&mut grandkids, off);
+                    ch.children = Some(grandkids);
                 }
             }
         }
@@ -289,6 +276,103 @@ impl RecursionEngine {
     }
 }
 
+/// The bytes of `ch` within `data`, clamped to its end; `None` for children
+/// in a decompressed stream or starting past the end.
+fn child_bytes<'a>(ch: &ContainerChild, data: &'a [u8]) -> Option<&'a [u8]> {
+    if ch.in_decompressed_stream {
+        return None;
+    }
+    let off = usize::try_from(ch.offset).ok()?;
+    if off >= data.len() {
+        return None;
+    }
+    let end = off.saturating_add(ch.size as usize).min(data.len());
+    Some(&data[off..end])
+}
+
+/// Shift the offsets of `children` and their descendants by `base`; those
+/// in a decompressed stream stay relative to it.
+fn rebase(children: &mut [ContainerChild], base: u64) {
+    for ch in children {
+        if ch.in_decompressed_stream {
+            continue;
+        }
+        ch.offset = ch.offset.saturating_add(base);
+        if let Some(grandkids) = ch.children.as_mut() {
+            rebase(grandkids, base);
+        }
+    }
+}
+
+/// Fill in `entropy` and `ctph` for `children` and their descendants.
+///
+/// Offsets at every level are relative to `data`, as produced by
+/// [`RecursionEngine::discover_children`]. Only the first
+/// `max_child_fingerprint_bytes` of each child are read. Children in a
+/// decompressed stream (e.g. `.tar.gz` members) are skipped.
+pub fn fingerprint_children(
+    children: &mut [ContainerChild],
+    data: &[u8],
+    cfg: &crate::triage::config::SimilarityConfig,
+) {
+    for ch in children.iter_mut() {
+        let Some(slice) = child_bytes(ch, data) else {
+            continue;
+        };
+        let sample = &slice[..slice.len().min(cfg.max_child_fingerprint_bytes)];
+        if !sample.is_empty() {
+            ch.entropy = Some(crate::entropy::shannon_entropy(sample));
+            if cfg.enable_ctph {
+                let ctph_cfg = cfg.ctph_config_for(sample.len());
+                ch.ctph = Some(crate::similarity::ctph_hash(sample, &ctph_cfg));
+            }
+        }
+        if let Some(grandkids) = ch.children.as_mut() {
+            fingerprint_children(grandkids, data, cfg);
+        }
+    }
+}
+
+/// Upper bound on modules reported by [`scan_embedded_modules`].
+pub const MAX_DUMP_MODULES: usize = 256;
+
+/// Locate PE and ELF images at arbitrary offsets of a memory dump.
+///
+/// An `MZ` counts only when its `e_lfanew` reaches a `PE\0\0` signature and
+/// an ELF magic only when its class, data and version bytes are valid. Each
+/// module is reported as a `pe`/`elf` child running to the next module or
+/// the end of `data`; at most `max_modules` are returned.
+pub fn scan_embedded_modules(data: &[u8], max_modules: usize) -> Vec<ContainerChild> {
+    let mut hits = carve::embedded_executables(data);
+    hits.retain(|c| matches!(c.format, Format::PE | Format::ELF));
+    hits.truncate(max_modules);
+    hits.iter()
+        .enumerate()
+        .filter_map(|(i, c)| {
+            // Mapped images do not match their on-disk layout, so a module
+            // runs to the next one rather than to its header size estimate.
+            let end = hits.get(i + 1).map_or(data.len(), |next| next.offset);
+            let (_, label) = format_type(c.format)?;
+            Some(ContainerChild::new(
+                label.to_string(),
+                c.offset as u64,
+                (end - c.offset) as u64,
+            ))
+        })
+        .collect()
+}
+
+/// Child for a carved image, its header size estimate clamped to `end`.
+fn carved_child(carved: &Carved, end: usize) -> Option<ContainerChild> {
+    let (_, label) = format_type(carved.format)?;
+    let size = carved.size_estimate.min(end - carved.offset);
+    Some(ContainerChild::new(
+        label.to_string(),
+        carved.offset as u64,
+        size as u64,
+    ))
+}
+
 /// Rollup summary for recursion/children stats
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(feature = "python-ext", pyo3::pyclass)]
@@ -330,12 +414,14 @@ mod tests {
         // magic CAFEBABE
         data[0..4].copy_from_slice(&0xCAFEBABEu32.to_be_bytes());
         data[4..8].copy_from_slice(&(2u32).to_be_bytes());
-        // entry 0: offset 100, size 50
+        // entry 0: x86_64 at offset 100, size 50
         let base0 = 8;
+        data[base0..base0 + 4].copy_from_slice(&0x0100_0007u32.to_be_bytes());
         data[base0 + 8..base0 + 12].copy_from_slice(&(100u32).to_be_bytes());
         data[base0 + 12..base0 + 16].copy_from_slice(&(50u32).to_be_bytes());
-        // entry 1: offset 150, size 30
+        // entry 1: arm64 at offset 150, size 30
         let base1 = 8 + 20;
+        data[base1..base1 + 4].copy_from_slice(&0x0100_000Cu32.to_be_bytes());
         data[base1 + 8..base1 + 12].copy_from_slice(&(150u32).to_be_bytes());
         data[base1 + 12..base1 + 16].copy_from_slice(&(30u32).to_be_bytes());
 
@@ -344,10 +430,129 @@ mod tests {
         let kids = eng.discover_children(&data, &mut b, 0);
         assert!(kids
             .iter()
-            .any(|c| c.type_name == "macho-thin" && c.offset == 100 && c.size == 50));
+            .any(|c| c.type_name == "macho_slice_x86_64" && c.offset == 100 && c.size == 50));
         assert!(kids
             .iter()
-            .any(|c| c.type_name == "macho-thin" && c.offset == 150 && c.size == 30));
+            .any(|c| c.type_name == "macho_slice_arm64" && c.offset == 150 && c.size == 30));
+
+        let eng = RecursionEngine::new(2).with_fat_slices(false);
+        let kids = eng.discover_children(&data, &mut b, 0);
+        assert!(!kids.iter().any(|c| c.type_name.starts_with("macho_slice")));
+    }
+
+    #[test]
+    fn java_class_is_not_split_as_fat() {
+        // CAFEBABE followed by minor 0 / major 52 (Java 8)
+        let mut data = vec![0u8; 256];
+        data[0..4].copy_from_slice(&0xCAFEBABEu32.to_be_bytes());
+        data[6..8].copy_from_slice(&52u16.to_be_bytes());
+        let eng = RecursionEngine::new(2);
+        assert!(eng.detect_fat_macho(&data).is_empty());
+    }
+
+    #[test]
+    fn tar_children_carry_members() {
+        let mut data = vec![0u8; 512 * 4];
+        data[..7].copy_from_slice(b"payload");
+        data[124..136].copy_from_slice(b"00000000004\0");
+        data[156] = b'0';
+        data[257..263].copy_from_slice(b"ustar\0");
+        data[512..516].copy_from_slice(b"MZ\x90\x00");
+
+        let eng = RecursionEngine::new(1);
+        let mut b = Budgets::new(data.len() as u64, 0, 0);
+        let kids = eng.discover_children(&data, &mut b, 0);
+        let tar = kids
+            .iter()
+            .find(|c| c.type_name == "tar" && c.offset == 0)
+            .expect("tar child");
+        let members = tar.children.as_ref().expect("tar members");
+        assert_eq!(members.len(), 1);
+        assert_eq!(members[0].type_name, "tar_member");
+        assert_eq!(members[0].name.as_deref(), Some("payload"));
+        assert_eq!((members[0].offset, members[0].size), (512, 4));
+    }
+
+    fn one_member_tar() -> Vec<u8> {
+        let mut tar = vec![0u8; 512 * 4];
+        tar[..7].copy_from_slice(b"payload");
+        tar[124..136].copy_from_slice(b"00000000004\0");
+        tar[156] = b'0';
+        tar[257..263].copy_from_slice(b"ustar\0");
+        tar[512..516].copy_from_slice(b"MZ\x90\x00");
+        tar
+    }
+
+    #[test]
+    fn embedded_tar_members_have_absolute_offsets() {
+        let mut data = vec![0u8; 1024];
+        data.extend(one_member_tar());
+        let mut b = Budgets::new(data.len() as u64, 0, 0);
+        let kids = RecursionEngine::new(1).discover_children(&data, &mut b, 0);
+        let tar = kids
+            .iter()
+            .find(|c| c.type_name == "tar" && c.offset == 1024)
+            .expect("tar child");
+        let member = &tar.children.as_ref().unwrap()[0];
+        assert_eq!((member.offset, member.size), (1024 + 512, 4));
+        let off = member.offset as usize;
+        assert_eq!(&data[off..off + 4], b"MZ\x90\x00");
+    }
+
+    #[test]
+    fn gzip_members_are_in_decompressed_stream_and_charged() {
+        use flate2::write::GzEncoder;
+        use std::io::Write;
+        let tar = one_member_tar();
+        let mut enc = GzEncoder::new(Vec::new(), flate2::Compression::default());
+        enc.write_all(&tar).unwrap();
+        let mut data = vec![0u8; 64];
+        data.extend(enc.finish().unwrap());
+
+        let mut b = Budgets::new(0, 0, 0);
+        let kids = RecursionEngine::new(1).discover_children(&data, &mut b, 0);
+        let gz = kids.iter().find(|c| c.type_name == "gzip").unwrap();
+        let inner = &gz.children.as_ref().expect("inflated tar")[0];
+        assert!(inner.in_decompressed_stream);
+        assert_eq!((inner.offset, inner.size), (0, tar.len() as u64));
+        let member = &inner.children.as_ref().unwrap()[0];
+        assert!(member.in_decompressed_stream);
+        assert_eq!((member.offset, member.size), (512, 4));
+        assert_eq!(b.bytes_read, tar.len() as u64);
+        assert!(!b.hit_byte_limit);
+
+        // An exhausted byte budget stops inflation.
+        let mut b = Budgets::new(0, 0, 0);
+        b.limit_bytes = Some(0);
+        let kids = RecursionEngine::new(1).discover_children(&data, &mut b, 0);
+        let gz = kids.iter().find(|c| c.type_name == "gzip").unwrap();
+        assert!(gz.children.is_none());
+        assert!(b.hit_byte_limit);
+    }
+
+    #[test]
+    fn fingerprints_children_and_members_when_enabled() {
+        let mut data = vec![0u8; 512 * 4];
+        data[..7].copy_from_slice(b"payload");
+        data[124..136].copy_from_slice(b"00000000004\0");
+        data[156] = b'0';
+        data[257..263].copy_from_slice(b"ustar\0");
+        data[512..516].copy_from_slice(b"MZ\x90\x00");
+
+        let mut b = Budgets::new(data.len() as u64, 0, 0);
+        let mut kids = RecursionEngine::new(1).discover_children(&data, &mut b, 0);
+        assert!(kids.iter().all(|c| c.entropy.is_none() && c.ctph.is_none()));
+
+        let cfg = crate::triage::config::SimilarityConfig::default();
+        fingerprint_children(&mut kids, &data, &cfg);
+        let tar = kids.iter().find(|c| c.type_name == "tar").unwrap();
+        assert!(tar.entropy.is_some() && tar.ctph.is_some());
+        let member = &tar.children.as_ref().unwrap()[0];
+        assert_eq!(
+            member.entropy,
+            Some(crate::entropy::shannon_entropy(b"MZ\x90\x00"))
+        );
+        assert!(member.ctph.is_some());
     }
 
     #[test]
@@ -383,4 +588,39 @@ mod tests {
             .iter()
             .any(|c| c.type_name == "tar" && c.offset == off_tar as u64));
     }
+
+    #[test]
+    fn carved_executables_become_children() {
+        let mut data = vec![0u8; 0x400];
+        data[..2].copy_from_slice(b"MZ");
+        data[0x200..0x207].copy_from_slice(b"\x7FELF\x01\x01\x01");
+        let eng = RecursionEngine::new(1);
+        let mut b = Budgets::new(data.len() as u64, 0, 0);
+        let kids = eng.discover_children(&data, &mut b, 0);
+        assert!(kids
+            .iter()
+            .any(|c| c.type_name == "elf" && c.offset == 0x200 && c.size == 0x34));
+    }
+
+    #[test]
+    fn scans_dump_for_pe_and_elf_modules() {
+        let mut dump = vec![0u8; 0x3000];
+        // PE image at 0x1000 with e_lfanew = 0x80
+        dump[0x1000..0x1002].copy_from_slice(b"MZ");
+        dump[0x103C..0x1040].copy_from_slice(&0x80u32.to_le_bytes());
+        dump[0x1080..0x1084].copy_from_slice(b"PE\0\0");
+        // ELF64 LE image at 0x2000
+        dump[0x2000..0x2007].copy_from_slice(b"\x7FELF\x02\x01\x01");
+        // Stray magics without valid headers
+        dump[0x500..0x502].copy_from_slice(b"MZ");
+        dump[0x2800..0x2807].copy_from_slice(b"\x7FELF\x07\x00\x00");
+
+        let mods = scan_embedded_modules(&dump, MAX_DUMP_MODULES);
+        let found: Vec<_> = mods
+            .iter()
+            .map(|c| (c.type_name.as_str(), c.offset, c.size))
+            .collect();
+        assert_eq!(found, vec![("pe", 0x1000, 0x1000), ("elf", 0x2000, 0x1000)]);
+        assert_eq!(scan_embedded_modules(&dump, 1).len(), 1);
+    }
 }
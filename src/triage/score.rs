@@ -1,7 +1,9 @@
 //! Confidence scoring and verdict ranking.
 
+use crate::core::binary::{Arch, Endianness, Format};
 use crate::core::triage::{
-    ConfidenceSignal, TriageError, TriageErrorKind, TriageVerdict, TriagedArtifact,
+    ConfidenceSignal, SnifferSource, TriageError, TriageErrorKind, TriageHint, TriageVerdict,
+    TriagedArtifact,
 };
 use std::collections::HashMap;
 
@@ -130,7 +132,6 @@ impl ScoreEngine {
                     score = (*conf).clamp(0.0, 1.0);
                 } else {
                     // family equivalence: x86 <-> x86_64
-                    use crate::core::binary::Arch;
                     let fam_match = (verdict.arch == Arch::X86 && *top_arch == Arch::X86_64)
                         || (verdict.arch == Arch::X86_64 && *top_arch == Arch::X86);
                     if fam_match {
@@ -203,6 +204,9 @@ impl ScoreEngine {
                 }
             }
         }
+        if verdict.format == Format::PE {
+            signals.extend(pe_timestamp_signal(artifact));
+        }
         (penalty.clamp(0.0, 0.25), signals)
     }
 
@@ -227,16 +231,117 @@ impl ScoreEngine {
     }
 }
 
+/// Borland Delphi/C++Builder linkers write this fixed stamp (1992-06-19).
+const DELPHI_FIXED_STAMP: u32 = 0x2A42_5E19;
+/// "MZ\x90\0": the first DOS header bytes pasted over the stamp.
+const DOS_MAGIC_STAMP: u32 = 0x0090_5A4D;
+/// 1980-01-01, the DOS/ZIP epoch many reproducible-build setups pin to.
+const DOS_EPOCH_STAMP: u32 = 315_532_800;
+
+/// Explain a PE `TimeDateStamp` that is zeroed, pinned or a known forgery.
+///
+/// Informational only (no penalty): modern toolchains zero or hash the
+/// stamp, while forged stamps are a hint worth surfacing. ELF has no link
+/// timestamp, so only PE is covered.
+fn pe_timestamp_signal(artifact: &TriagedArtifact) -> Option<ConfidenceSignal> {
+    let pe = artifact.format_specific.as_ref()?.pe.as_ref()?;
+    let note = match pe.time_date_stamp? {
+        _ if pe.reproducible => "timestamp is a reproducible-build hash (REPRO debug entry)",
+        0 => "timestamp zeroed",
+        1 | DOS_EPOCH_STAMP => "timestamp pinned to a reproducible-build epoch",
+        DELPHI_FIXED_STAMP => "fixed Delphi/C++Builder timestamp",
+        DOS_MAGIC_STAMP => "timestamp copied from DOS header magic",
+        u32::MAX => "timestamp set to all ones",
+        _ => return None,
+    };
+    Some(ConfidenceSignal::new(
+        "build_timestamp".into(),
+        0.0,
+        Some(note.into()),
+    ))
+}
+
 /// Public API: Score verdicts for an artifact and return ranked list.
 pub fn score(artifact: &TriagedArtifact) -> Vec<TriageVerdict> {
     let engine = ScoreEngine::default();
     engine.score_artifact(artifact)
 }
 
+/// Minimum share of the summed per-family architecture scores that the top
+/// family must hold before headerless data is reported as raw code.
+const RAW_ARCH_MIN_SHARE: f32 = 0.5;
+/// Minimum absolute opcode-profile score for the top architecture.
+const RAW_ARCH_MIN_SCORE: f32 = 0.01;
+/// Overall entropy band (bits/byte) typical of machine code: below it is
+/// text or padding, above it is compressed or encrypted data.
+const RAW_CODE_ENTROPY: std::ops::RangeInclusive<f64> = 4.5..=7.2;
+
+/// Build a `Format::Raw` verdict for headerless data that looks like code.
+///
+/// Used when no header validates (e.g. dumped shellcode): if the top
+/// architecture guess clearly dominates the other families and the overall
+/// entropy is code-like, the guess is surfaced as a low-confidence raw
+/// verdict instead of leaving the verdict list empty.
+pub fn raw_code_verdict(
+    arch_guesses: &[(Arch, f32)],
+    entropy: Option<f64>,
+    endianness: Endianness,
+) -> Option<TriageVerdict> {
+    if !RAW_CODE_ENTROPY.contains(&entropy?) {
+        return None;
+    }
+    // x86 and x86_64 share an opcode profile; count them as one family.
+    let family = |a: Arch| if a == Arch::X86 { Arch::X86_64 } else { a };
+    let mut per_family: Vec<(Arch, f32)> = Vec::new();
+    for &(arch, conf) in arch_guesses {
+        if arch == Arch::Unknown {
+            continue;
+        }
+        let fam = family(arch);
+        match per_family.iter_mut().find(|(a, _)| *a == fam) {
+            Some((_, c)) => *c = c.max(conf),
+            None => per_family.push((fam, conf)),
+        }
+    }
+    let (arch, top) = per_family
+        .iter()
+        .copied()
+        .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap())?;
+    let total: f32 = per_family.iter().map(|(_, c)| c).sum();
+    if top < RAW_ARCH_MIN_SCORE || top / total < RAW_ARCH_MIN_SHARE {
+        return None;
+    }
+    let confidence = (top / total * 0.5).clamp(0.0, 1.0);
+    TriageVerdict::try_new(Format::Raw, arch, arch.bits(), endianness, confidence, None).ok()
+}
+
+/// Confidence of a verdict built from a content sniffer hint alone.
+const DATA_HINT_CONFIDENCE: f32 = 0.5;
+
+/// Build a `Format::Unknown` verdict for recognized non-executable content.
+///
+/// Images, documents and archives produce no header verdicts; the first
+/// content (not extension) hint carrying a MIME type or label is surfaced as
+/// a typed verdict so the ranked list covers every file type.
+pub fn data_verdict(hints: &[TriageHint]) -> Option<TriageVerdict> {
+    let hint = hints.iter().find(|h| {
+        h.source != SnifferSource::MimeGuess && (h.mime.is_some() || h.label.is_some())
+    })?;
+    TriageVerdict::try_new(
+        Format::Unknown,
+        Arch::Unknown,
+        Arch::Unknown.bits(),
+        Endianness::Little,
+        DATA_HINT_CONFIDENCE,
+        None,
+    )
+    .ok()
+    .map(|v| v.with_type(hint.mime.clone(), hint.label.clone()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::binary::{Arch, Endianness, Format};
     use crate::core::triage::{
         Budgets, EntropySummary, TriageHint, TriageVerdict, TriagedArtifact,
     };
@@ -281,4 +386,88 @@ mod tests {
         assert!(sigs.iter().any(|n| n == "architecture_match"));
         assert!(sigs.iter().any(|n| n == "endianness_match"));
     }
+
+    #[test]
+    fn pe_timestamp_signal_flags_zeroed_and_fake_stamps() {
+        use crate::core::triage::formats::{FormatSpecificTriage, PeTriageInfo};
+
+        let notes = |stamp: u32, reproducible: bool| {
+            let verdict =
+                TriageVerdict::try_new(Format::PE, Arch::X86, 32, Endianness::Little, 0.6, None)
+                    .unwrap();
+            let pe = PeTriageInfo {
+                rich_header: None,
+                time_date_stamp: Some(stamp),
+                compile_time: None,
+                reproducible,
+            };
+            let artifact = TriagedArtifact::new(
+                "id".into(),
+                "<mem>".into(),
+                128,
+                None,
+                vec![],
+                vec![verdict],
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some(FormatSpecificTriage {
+                    pe: Some(pe),
+                    ..Default::default()
+                }),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            );
+            score(&artifact)[0]
+                .signals
+                .clone()
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|s| s.name == "build_timestamp")
+                .filter_map(|s| s.notes)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(notes(0, false), vec!["timestamp zeroed"]);
+        assert_eq!(
+            notes(0x2A42_5E19, false),
+            vec!["fixed Delphi/C++Builder timestamp"]
+        );
+        assert!(notes(0x9C3F_12AB, true)[0].contains("REPRO"));
+        assert!(notes(1_577_836_800, false).is_empty());
+    }
+
+    #[test]
+    fn raw_code_verdict_requires_dominant_arch_and_code_entropy() {
+        let guesses = vec![
+            (Arch::X86_64, 0.04),
+            (Arch::X86, 0.04),
+            (Arch::AArch64, 0.005),
+        ];
+        let v = raw_code_verdict(&guesses, Some(5.8), Endianness::Little).expect("verdict");
+        assert_eq!(v.format, Format::Raw);
+        assert_eq!(v.arch, Arch::X86_64);
+        assert_eq!(v.bits, 64);
+        assert!(v.confidence > 0.0 && v.confidence <= 0.5);
+
+        // Encrypted-looking or text-like entropy: no verdict.
+        assert!(raw_code_verdict(&guesses, Some(7.9), Endianness::Little).is_none());
+        assert!(raw_code_verdict(&guesses, Some(3.0), Endianness::Little).is_none());
+        assert!(raw_code_verdict(&guesses, None, Endianness::Little).is_none());
+
+        // No clear winner across families.
+        let flat = vec![
+            (Arch::RISCV64, 0.005),
+            (Arch::X86_64, 0.004),
+            (Arch::MIPS, 0.004),
+        ];
+        assert!(raw_code_verdict(&flat, Some(5.8), Endianness::Little).is_none());
+    }
 }
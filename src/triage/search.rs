@@ -0,0 +1,87 @@
+//! Fast byte-pattern search shared by the triage detectors.
+//!
+//! Thin wrappers over `memchr::memmem`, which uses SIMD substring search
+//! where available, replacing `data.windows(n).any(..)` scans that cost
+//! O(len * n). [`PatternSet`] builds its searchers once so scanning a
+//! buffer for a dozen signatures does not redo the per-needle setup.
+
+use memchr::memmem;
+
+/// Whether `needle` occurs anywhere in `data`. An empty needle never matches.
+#[inline]
+pub fn contains(data: &[u8], needle: &[u8]) -> bool {
+    find(data, needle).is_some()
+}
+
+/// Offset of the first occurrence of `needle` in `data`.
+#[inline]
+pub fn find(data: &[u8], needle: &[u8]) -> Option<usize> {
+    if needle.is_empty() {
+        return None;
+    }
+    memmem::find(data, needle)
+}
+
+/// Offsets of every non-overlapping occurrence of `needle`, in order.
+pub fn find_all(data: &[u8], needle: &[u8]) -> Vec<usize> {
+    find_bounded(data, needle, usize::MAX)
+}
+
+/// Like [`find_all`], stopping after `max_hits` offsets.
+pub fn find_bounded(data: &[u8], needle: &[u8], max_hits: usize) -> Vec<usize> {
+    if needle.is_empty() {
+        return Vec::new();
+    }
+    memmem::find_iter(data, needle).take(max_hits).collect()
+}
+
+/// A fixed set of needles searched together.
+pub struct PatternSet<'n> {
+    finders: Vec<memmem::Finder<'n>>,
+}
+
+impl<'n> PatternSet<'n> {
+    /// Build searchers for `needles`; empty needles never match.
+    pub fn new(needles: &[&'n [u8]]) -> Self {
+        Self {
+            finders: needles.iter().map(|n| memmem::Finder::new(*n)).collect(),
+        }
+    }
+
+    /// Index of the first needle (in construction order) present in `data`.
+    pub fn first_match(&self, data: &[u8]) -> Option<usize> {
+        self.finders
+            .iter()
+            .position(|f| !f.needle().is_empty() && f.find(data).is_some())
+    }
+
+    /// Indices of all needles present in `data`.
+    pub fn matches(&self, data: &[u8]) -> Vec<usize> {
+        self.finders
+            .iter()
+            .enumerate()
+            .filter(|(_, f)| !f.needle().is_empty() && f.find(data).is_some())
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_offsets_and_pattern_sets() {
+        let data = b"xxUPX!..UPX0..UPX!";
+        assert_eq!(find_all(data, b"UPX!"), vec![2, 14]);
+        assert_eq!(find_bounded(data, b"UPX", 2), vec![2, 8]);
+        assert_eq!(find(data, b"UPX0"), Some(8));
+        assert!(!contains(data, b""));
+        assert_eq!(find_all(b"aaaa", b"aa"), vec![0, 2]);
+
+        let set = PatternSet::new(&[b"Themida", b"UPX0", b"", b"UPX!"]);
+        assert_eq!(set.first_match(data), Some(1));
+        assert_eq!(set.matches(data), vec![1, 3]);
+        assert_eq!(set.first_match(b"clean"), None);
+    }
+}
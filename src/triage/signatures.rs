@@ -4,6 +4,10 @@
 //! architecture mappings used throughout the triage module.
 
 use crate::core::binary::{Arch, Format};
+use crate::formats::ole::OleFile;
+pub use crate::formats::ole::OLE_MAGIC;
+use aho_corasick::AhoCorasick;
+use once_cell::sync::Lazy;
 
 /// Python bytecode magic numbers for different versions.
 pub const PYTHON_MAGIC_NUMBERS: &[(u32, &str)] = &[
@@ -179,6 +183,276 @@ pub fn detect_format_from_magic(data: &[u8]) -> Option<Format> {
     None
 }
 
+/// NSIS first header: flags, `0xDEADBEEF`, then `NullsoftInst`.
+pub const NSIS_FIRST_HEADER: &[u8] = b"\xEF\xBE\xAD\xDENullsoftInst";
+/// Inno Setup setup-loader offset table ID.
+pub const INNO_LOADER_ID: &[u8] = b"rDlPtS";
+/// Inno Setup setup-data (setup-0) ID prefix.
+pub const INNO_SETUP_DATA: &[u8] = b"Inno Setup Setup Data";
+/// InstallShield setup stream marker (InstallShield 12 and later).
+pub const INSTALLSHIELD_STREAM: &[u8] = b"ISSetupStream";
+/// Inno Setup zlib block header, used when the setup-data ID is absent.
+const INNO_ZLIB_BLOCK: &[u8] = b"zlb\x1A";
+/// Root storage CLSIDs of Windows Installer packages (`.msi`) and patches
+/// (`.msp`), in on-disk byte order.
+const MSI_CLSIDS: [[u8; 16]; 2] = [
+    [
+        0x84, 0x10, 0x0C, 0x00, 0x00, 0x00, 0x00, 0x00, 0xC0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x46,
+    ],
+    [
+        0x86, 0x10, 0x0C, 0x00, 0x00, 0x00, 0x00, 0x00, 0xC0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x46,
+    ],
+];
+
+/// Installer toolkit that produced a setup program or package.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstallerFamily {
+    Nsis,
+    InnoSetup,
+    InstallShield,
+    Msi,
+}
+
+impl InstallerFamily {
+    /// Lowercase label used for hints and container children.
+    pub fn label(&self) -> &'static str {
+        match self {
+            InstallerFamily::Nsis => "nsis",
+            InstallerFamily::InnoSetup => "inno-setup",
+            InstallerFamily::InstallShield => "installshield",
+            InstallerFamily::Msi => "msi",
+        }
+    }
+}
+
+/// An installer recognized by [`detect_installer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InstallerMatch {
+    pub family: InstallerFamily,
+    /// `(offset, size)` of the installer's compressed payload when it could
+    /// be located; the whole file for MSI packages.
+    pub payload: Option<(u64, u64)>,
+}
+
+/// Recognize NSIS, Inno Setup and InstallShield setup executables and
+/// Windows Installer packages.
+///
+/// Setup executables are PE stubs with their payload appended, so the
+/// markers are searched for across all of `data`, which callers bound.
+pub fn detect_installer(data: &[u8]) -> Option<InstallerMatch> {
+    detect_installer_with_hits(data, &MultiScanner::builtin().scan(data))
+}
+
+/// [`detect_installer`] reusing the caller's [`MultiScanner::builtin`] scan
+/// of `data`, shared with the other literal-signature detectors.
+pub fn detect_installer_with_hits(data: &[u8], hits: &SignatureHits<'_>) -> Option<InstallerMatch> {
+    if data.starts_with(&OLE_MAGIC) {
+        return is_msi(data).then_some(InstallerMatch {
+            family: InstallerFamily::Msi,
+            payload: Some((0, data.len() as u64)),
+        });
+    }
+    if !data.starts_with(b"MZ") {
+        return None;
+    }
+    let to_end = |off: usize| Some((off as u64, (data.len() - off) as u64));
+
+    if let Some(sig) = hits.find(NSIS_FIRST_HEADER).filter(|&p| p >= 4) {
+        // firstheader: flags, siginfo, "NullsoftInst", header length, then
+        // the length of all data including the firstheader itself.
+        let start = sig - 4;
+        let size = data
+            .get(start + 24..start + 28)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as usize)
+            .filter(|&n| n > 0 && n <= data.len() - start)
+            .unwrap_or(data.len() - start);
+        return Some(InstallerMatch {
+            family: InstallerFamily::Nsis,
+            payload: Some((start as u64, size as u64)),
+        });
+    }
+
+    let setup_data = hits.find(INNO_SETUP_DATA);
+    if setup_data.is_some() || (hits.contains(b"Inno Setup") && hits.contains(INNO_LOADER_ID)) {
+        let payload = setup_data
+            .or_else(|| hits.find(INNO_ZLIB_BLOCK))
+            .and_then(to_end);
+        return Some(InstallerMatch {
+            family: InstallerFamily::InnoSetup,
+            payload,
+        });
+    }
+
+    let stream = hits.find(INSTALLSHIELD_STREAM);
+    if stream.is_some() || hits.contains(b"InstallShield") {
+        return Some(InstallerMatch {
+            family: InstallerFamily::InstallShield,
+            payload: stream.and_then(to_end),
+        });
+    }
+    None
+}
+
+/// Whether an OLE compound file's root storage carries an MSI CLSID.
+fn is_msi(data: &[u8]) -> bool {
+    OleFile::parse(data).is_ok_and(|o| MSI_CLSIDS.contains(&o.root_clsid))
+}
+
+/// Literal strings behind [`crate::triage::packers::detect_packers`].
+pub const PACKER_LITERALS: &[&[u8]] = &[
+    b"UPX!",
+    b"UPX0",
+    b"UPX1",
+    b"$Id: UPX ",
+    b"UPX ",
+    b"ASPack",
+    b"PECompact",
+    b"PEC2",
+    b"Petite",
+    b"FSG!",
+    b"MPRESS",
+    b"Themida",
+    b"WinLicense",
+    b".vmp0",
+    b".vmp1",
+];
+
+/// Literal markers behind [`detect_installer`].
+pub const INSTALLER_LITERALS: &[&[u8]] = &[
+    NSIS_FIRST_HEADER,
+    INNO_SETUP_DATA,
+    b"Inno Setup",
+    INNO_LOADER_ID,
+    INNO_ZLIB_BLOCK,
+    INSTALLSHIELD_STREAM,
+    b"InstallShield",
+];
+
+/// Detector a literal signature belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SignatureKind {
+    Packer,
+    Installer,
+    Crypto,
+}
+
+static BUILTIN_SCANNER: Lazy<MultiScanner> = Lazy::new(|| {
+    let packers = PACKER_LITERALS
+        .iter()
+        .map(|p| (SignatureKind::Packer, p.to_vec()));
+    let installers = INSTALLER_LITERALS
+        .iter()
+        .map(|p| (SignatureKind::Installer, p.to_vec()));
+    let crypto = crate::analysis::crypto::literal_patterns()
+        .into_iter()
+        .map(|p| (SignatureKind::Crypto, p));
+    MultiScanner::new(packers.chain(installers).chain(crypto))
+        .expect("builtin signature literals fit default automaton limits")
+});
+
+/// Aho-Corasick automaton over a fixed set of literal signatures.
+///
+/// Detectors that each looked for a dozen markers with separate substring
+/// searches share one pass over the buffer instead; [`MultiScanner::builtin`]
+/// covers every packer, installer and crypto-constant literal.
+pub struct MultiScanner {
+    automaton: AhoCorasick,
+    patterns: Vec<(SignatureKind, Vec<u8>)>,
+}
+
+impl MultiScanner {
+    /// Build an automaton over `patterns`. Empty patterns and repeats of an
+    /// earlier pattern are dropped; fails when the automaton exceeds
+    /// aho-corasick's default size limits.
+    pub fn new(
+        patterns: impl IntoIterator<Item = (SignatureKind, Vec<u8>)>,
+    ) -> Result<Self, aho_corasick::BuildError> {
+        let mut kept: Vec<(SignatureKind, Vec<u8>)> = Vec::new();
+        for (kind, p) in patterns {
+            if !p.is_empty() && !kept.iter().any(|(_, q)| *q == p) {
+                kept.push((kind, p));
+            }
+        }
+        let automaton = AhoCorasick::new(kept.iter().map(|(_, p)| p))?;
+        Ok(Self {
+            automaton,
+            patterns: kept,
+        })
+    }
+
+    /// Shared scanner over [`PACKER_LITERALS`], [`INSTALLER_LITERALS`] and
+    /// the crypto constants of [`crate::analysis::crypto`].
+    pub fn builtin() -> &'static MultiScanner {
+        &BUILTIN_SCANNER
+    }
+
+    /// Number of distinct patterns.
+    pub fn len(&self) -> usize {
+        self.patterns.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// First offset of every pattern in `data`, found in a single pass that
+    /// ends early once all patterns have been seen.
+    pub fn scan(&self, data: &[u8]) -> SignatureHits<'_> {
+        let mut first = vec![None; self.patterns.len()];
+        let mut remaining = first.len();
+        // Overlapping matches arrive ordered by end offset, which for a
+        // fixed-length pattern is also start order.
+        for m in self.automaton.find_overlapping_iter(data) {
+            let slot = &mut first[m.pattern().as_usize()];
+            if slot.is_none() {
+                *slot = Some(m.start());
+                remaining -= 1;
+                if remaining == 0 {
+                    break;
+                }
+            }
+        }
+        SignatureHits {
+            scanner: self,
+            first,
+        }
+    }
+}
+
+/// Result of [`MultiScanner::scan`].
+pub struct SignatureHits<'s> {
+    scanner: &'s MultiScanner,
+    first: Vec<Option<usize>>,
+}
+
+impl SignatureHits<'_> {
+    /// Offset of the first occurrence of `pattern`; `None` when it does not
+    /// occur or is not one of the scanner's patterns.
+    pub fn find(&self, pattern: &[u8]) -> Option<usize> {
+        let idx = self
+            .scanner
+            .patterns
+            .iter()
+            .position(|(_, p)| p == pattern)?;
+        self.first[idx]
+    }
+
+    pub fn contains(&self, pattern: &[u8]) -> bool {
+        self.find(pattern).is_some()
+    }
+
+    /// Matched patterns with their first offsets, in pattern order.
+    pub fn iter(&self) -> impl Iterator<Item = (SignatureKind, &[u8], usize)> + '_ {
+        self.scanner
+            .patterns
+            .iter()
+            .zip(&self.first)
+            .filter_map(|((kind, p), off)| off.map(|o| (*kind, p.as_slice(), o)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -235,4 +509,93 @@ mod tests {
         let unknown_data = &[0x00, 0x00];
         assert_eq!(detect_format_from_magic(unknown_data), None);
     }
+
+    #[test]
+    fn test_detect_installer() {
+        let mut stub = vec![0u8; 0x400];
+        stub[..2].copy_from_slice(b"MZ");
+
+        // NSIS firstheader at 0x200 declaring 0x100 bytes of data
+        let mut nsis = stub.clone();
+        nsis[0x204..0x204 + NSIS_FIRST_HEADER.len()].copy_from_slice(NSIS_FIRST_HEADER);
+        nsis[0x218..0x21C].copy_from_slice(&0x100u32.to_le_bytes());
+        assert_eq!(
+            detect_installer(&nsis),
+            Some(InstallerMatch {
+                family: InstallerFamily::Nsis,
+                payload: Some((0x200, 0x100)),
+            })
+        );
+
+        let mut inno = stub.clone();
+        inno[0x30..0x36].copy_from_slice(INNO_LOADER_ID);
+        inno[0x100..0x10A].copy_from_slice(b"Inno Setup");
+        inno[0x300..0x304].copy_from_slice(INNO_ZLIB_BLOCK);
+        let m = detect_installer(&inno).unwrap();
+        assert_eq!(m.family, InstallerFamily::InnoSetup);
+        assert_eq!(m.payload, Some((0x300, 0x100)));
+
+        let mut is = stub.clone();
+        is[0x100..0x10D].copy_from_slice(b"InstallShield");
+        let m = detect_installer(&is).unwrap();
+        assert_eq!(
+            (m.family, m.payload),
+            (InstallerFamily::InstallShield, None)
+        );
+
+        // v3 compound file: FAT at sector 0, directory at sector 1 holding
+        // only a root entry with the MSI CLSID
+        let mut msi = vec![0u8; 0x600];
+        msi[..8].copy_from_slice(&OLE_MAGIC);
+        msi[0x1A] = 3;
+        msi[0x1E] = 9;
+        msi[0x20] = 6;
+        msi[0x2C..0x30].copy_from_slice(&1u32.to_le_bytes());
+        msi[0x30..0x34].copy_from_slice(&1u32.to_le_bytes());
+        msi[0x200..0x204].copy_from_slice(&0xFFFF_FFFDu32.to_le_bytes());
+        msi[0x204..0x208].copy_from_slice(&0xFFFF_FFFEu32.to_le_bytes());
+        msi[0x442] = 5;
+        msi[0x444..0x450].fill(0xFF);
+        msi[0x450..0x460].copy_from_slice(&MSI_CLSIDS[0]);
+        let m = detect_installer(&msi).unwrap();
+        assert_eq!((m.family.label(), m.payload), ("msi", Some((0, 0x600))));
+        // Other compound files (e.g. Word documents) are not installers
+        msi[0x450] = 0;
+        assert_eq!(detect_installer(&msi), None);
+
+        assert_eq!(detect_installer(&stub), None);
+    }
+
+    #[test]
+    fn multi_scanner_reports_first_offset_per_pattern() {
+        let scanner = MultiScanner::new([
+            (SignatureKind::Packer, b"UPX!".to_vec()),
+            (SignatureKind::Packer, b"UPX".to_vec()),
+            (SignatureKind::Installer, b"UPX!".to_vec()),
+            (SignatureKind::Crypto, Vec::new()),
+            (SignatureKind::Crypto, b"expand 32-byte k".to_vec()),
+        ])
+        .unwrap();
+        assert_eq!(scanner.len(), 3);
+
+        let data = b"..UPX0..UPX!..UPX!..";
+        let hits = scanner.scan(data);
+        assert_eq!(hits.find(b"UPX"), Some(2));
+        assert_eq!(hits.find(b"UPX!"), Some(8));
+        assert_eq!(hits.find(b"expand 32-byte k"), None);
+        assert_eq!(hits.find(b"ASPack"), None);
+        let kinds: Vec<_> = hits.iter().map(|(k, p, o)| (k, p.len(), o)).collect();
+        assert_eq!(
+            kinds,
+            vec![(SignatureKind::Packer, 4, 8), (SignatureKind::Packer, 3, 2)]
+        );
+
+        let builtin = MultiScanner::builtin();
+        assert!(builtin.len() > PACKER_LITERALS.len() + INSTALLER_LITERALS.len());
+        let hits = builtin.scan(b"MZ...ASPack...expand 32-byte k");
+        assert!(hits.contains(b"ASPack"));
+        assert!(hits
+            .iter()
+            .any(|(k, _, o)| k == SignatureKind::Crypto && o == 14));
+    }
 }
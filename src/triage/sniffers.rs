@@ -1,10 +1,17 @@
 //! Content and extension sniffers for initial classification.
 //!
 //! Uses `infer` for content-based detection and `mime_guess` for
-//! extension-based hints, with conflict detection.
-
-use crate::core::triage::{SnifferSource, TriageError, TriageErrorKind, TriageHint};
+//! extension-based hints, with conflict detection. Extension hints can be
+//! overridden per-extension via [`SnifferConfig::extension_overrides`].
+//! Additional content detectors implementing [`Sniffer`] can be registered
+//! in [`SnifferConfig::custom_sniffers`].
+
+use crate::core::triage::{Severity, SnifferSource, TriageError, TriageErrorKind, TriageHint};
+use crate::triage::config::SnifferConfig;
+use crate::triage::format_detection::{classify_cafebabe, CafeBabeKind};
+use std::fmt;
 use std::path::Path;
+use std::sync::Arc;
 use tracing::{debug, info};
 
 /// Result of a sniffer operation.
@@ -38,6 +45,35 @@ impl ContentSniffer {
             }
         }
 
+        // Recognized-but-unextractable archives; checked before infer so RAR4
+        // and RAR5 are told apart and labels match container detection.
+        // These come from our own magic checks, not infer.
+        if let Some((mime, label)) = unsupported_archive_magic(data) {
+            debug!("Detected {} archive magic", label);
+            return Some(TriageHint::new(
+                SnifferSource::Other,
+                Some(mime.to_string()),
+                None,
+                Some(label.to_string()),
+            ));
+        }
+
+        // Mach-O FAT and Java class share CA FE BA BE; infer labels both
+        // as Mach-O, so decide here from the word after the magic.
+        if let Some(kind) = classify_cafebabe(data) {
+            let (mime, label) = match kind {
+                CafeBabeKind::MachOFat => ("application/x-mach-binary", "macho"),
+                CafeBabeKind::JavaClass => ("application/java-vm", "class"),
+            };
+            debug!("Detected {} from CAFEBABE magic", label);
+            return Some(TriageHint::new(
+                SnifferSource::Other,
+                Some(mime.to_string()),
+                None,
+                Some(label.to_string()),
+            ));
+        }
+
         // Use infer to detect file type from content
         if let Some(kind) = infer::get(data) {
             let mime = Some(kind.mime_type().to_string());
@@ -62,6 +98,134 @@ impl ContentSniffer {
     }
 }
 
+/// A user-supplied content detector, run after the built-in sniffers.
+///
+/// Implementations see the same bounded prefix as [`ContentSniffer`] and
+/// should return hints with [`SnifferSource::Other`] so they are kept out of
+/// content/extension conflict checks.
+pub trait Sniffer: Send + Sync {
+    /// Short identifier used in logs.
+    fn name(&self) -> &str;
+
+    /// Return a hint if `data` is recognized.
+    fn sniff(&self, data: &[u8]) -> Option<TriageHint>;
+}
+
+/// Matches fixed magic bytes at a fixed offset.
+#[derive(Debug, Clone)]
+pub struct MagicSniffer {
+    pub label: String,
+    pub magic: Vec<u8>,
+    pub offset: usize,
+    pub mime: Option<String>,
+}
+
+impl MagicSniffer {
+    pub fn new(label: impl Into<String>, magic: impl Into<Vec<u8>>) -> Self {
+        Self {
+            label: label.into(),
+            magic: magic.into(),
+            offset: 0,
+            mime: None,
+        }
+    }
+
+    pub fn at_offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub fn with_mime(mut self, mime: impl Into<String>) -> Self {
+        self.mime = Some(mime.into());
+        self
+    }
+}
+
+impl Sniffer for MagicSniffer {
+    fn name(&self) -> &str {
+        &self.label
+    }
+
+    fn sniff(&self, data: &[u8]) -> Option<TriageHint> {
+        let end = self.offset.checked_add(self.magic.len())?;
+        (!self.magic.is_empty() && data.get(self.offset..end)? == self.magic.as_slice()).then(
+            || {
+                TriageHint::new(
+                    SnifferSource::Other,
+                    self.mime.clone(),
+                    None,
+                    Some(self.label.clone()),
+                )
+            },
+        )
+    }
+}
+
+/// Ordered collection of custom [`Sniffer`]s.
+#[derive(Clone, Default)]
+pub struct SnifferRegistry {
+    sniffers: Vec<Arc<dyn Sniffer>>,
+}
+
+impl SnifferRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a sniffer; sniffers run in registration order.
+    pub fn register<S: Sniffer + 'static>(&mut self, sniffer: S) -> &mut Self {
+        self.sniffers.push(Arc::new(sniffer));
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.sniffers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sniffers.is_empty()
+    }
+
+    /// Hints from every registered sniffer that recognized `data`.
+    pub fn sniff(&self, data: &[u8]) -> Vec<TriageHint> {
+        self.sniffers
+            .iter()
+            .filter_map(|s| {
+                let hint = s.sniff(data)?;
+                debug!("Custom sniffer {} matched", s.name());
+                Some(hint)
+            })
+            .collect()
+    }
+}
+
+impl fmt::Debug for SnifferRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.sniffers.iter().map(|s| s.name()))
+            .finish()
+    }
+}
+
+/// Archive labels that are recognized by magic but whose members cannot be
+/// enumerated or extracted.
+pub const UNSUPPORTED_ARCHIVE_LABELS: &[&str] = &["7z", "rar", "rar5"];
+
+/// Match 7z and RAR (v4/v5) magic, returning `(mime, label)`.
+pub fn unsupported_archive_magic(data: &[u8]) -> Option<(&'static str, &'static str)> {
+    if data.starts_with(&[0x37, 0x7A, 0xBC, 0xAF, 0x27, 0x1C]) {
+        return Some(("application/x-7z-compressed", "7z"));
+    }
+    if data.starts_with(b"Rar!\x1a\x07") {
+        return match data.get(6) {
+            Some(0x00) => Some(("application/vnd.rar", "rar")),
+            Some(0x01) => Some(("application/vnd.rar", "rar5")),
+            _ => None,
+        };
+    }
+    None
+}
+
 /// Sniffer for extension-based file type detection using `mime_guess`.
 pub struct ExtensionSniffer;
 
@@ -94,6 +258,26 @@ impl ExtensionSniffer {
         }
     }
 
+    /// Sniff file type from extension, consulting configured overrides first.
+    ///
+    /// An override yields a [`SnifferSource::Other`] hint carrying the
+    /// configured label; otherwise this falls back to [`Self::sniff_path`].
+    pub fn sniff_path_with_config(path: &Path, cfg: &SnifferConfig) -> Option<TriageHint> {
+        let extension_str = path.extension().and_then(|e| e.to_str());
+        if let Some(ext) = extension_str {
+            if let Some(label) = cfg.override_for(ext) {
+                debug!("Extension override: {} -> {}", ext, label);
+                return Some(TriageHint::new(
+                    SnifferSource::Other,
+                    None,
+                    Some(ext.to_string()),
+                    Some(label.to_string()),
+                ));
+            }
+        }
+        Self::sniff_path(path)
+    }
+
     /// Convert MIME type to a simple label.
     fn mime_to_label(mime: &str) -> Option<String> {
         if mime.starts_with("application/") {
@@ -107,6 +291,8 @@ impl ExtensionSniffer {
                 Some("tar".to_string())
             } else if mime.contains("x-7z") {
                 Some("7z".to_string())
+            } else if mime.contains("vnd.rar") || mime.contains("x-rar") {
+                Some("rar".to_string())
             } else if mime.contains("x-elf") {
                 Some("elf".to_string())
             } else if mime.contains("x-executable") || mime.contains("x-sharedlib") {
@@ -132,6 +318,16 @@ pub struct CombinedSniffer;
 impl CombinedSniffer {
     /// Sniff both content and extension, detecting conflicts.
     pub fn sniff(data: &[u8], path: Option<&Path>) -> SnifferResult {
+        Self::sniff_with_config(data, path, &SnifferConfig::default())
+    }
+
+    /// Like [`Self::sniff`], applying the extension overrides and custom
+    /// sniffers in `cfg`.
+    pub fn sniff_with_config(
+        data: &[u8],
+        path: Option<&Path>,
+        cfg: &SnifferConfig,
+    ) -> SnifferResult {
         let mut hints = Vec::new();
         let mut errors = Vec::new();
 
@@ -139,22 +335,42 @@ impl CombinedSniffer {
         if let Some(content_hint) = ContentSniffer::sniff_bytes(data) {
             hints.push(content_hint);
         }
+        hints.extend(cfg.custom_sniffers.sniff(data));
 
         // Extension-based sniffing
         if let Some(path) = path {
-            if let Some(extension_hint) = ExtensionSniffer::sniff_path(path) {
+            if let Some(extension_hint) = ExtensionSniffer::sniff_path_with_config(path, cfg) {
                 hints.push(extension_hint);
             }
         }
 
         // Check for conflicts between content and extension hints
-        Self::detect_conflicts(&hints, &mut errors);
+        Self::detect_conflicts(&hints, &mut errors, cfg.mismatch_severity);
+
+        // Note archives we recognize by content but cannot look inside
+        if let Some(label) = hints
+            .iter()
+            .filter(|h| h.source == SnifferSource::Other)
+            .filter_map(|h| h.label.as_deref())
+            .find(|l| UNSUPPORTED_ARCHIVE_LABELS.contains(l))
+        {
+            errors.push(
+                TriageError::new(
+                    TriageErrorKind::UnsupportedVariant,
+                    Some(format!(
+                        "{} archive recognized; member extraction is not supported",
+                        label
+                    )),
+                )
+                .with_severity(Severity::Info),
+            );
+        }
 
         SnifferResult { hints, errors }
     }
 
     /// Detect conflicts between different sniffer sources.
-    fn detect_conflicts(hints: &[TriageHint], errors: &mut Vec<TriageError>) {
+    fn detect_conflicts(hints: &[TriageHint], errors: &mut Vec<TriageError>, severity: Severity) {
         if hints.len() < 2 {
             return;
         }
@@ -176,13 +392,16 @@ impl CombinedSniffer {
             for content_hint in &content_hints {
                 for extension_hint in &extension_hints {
                     if Self::hints_conflict(content_hint, extension_hint) {
-                        errors.push(TriageError::new(
-                            TriageErrorKind::SnifferMismatch,
-                            Some(format!(
-                                "Content suggests {:?} but extension suggests {:?}",
-                                content_hint.label, extension_hint.label
-                            )),
-                        ));
+                        errors.push(
+                            TriageError::new(
+                                TriageErrorKind::SnifferMismatch,
+                                Some(format!(
+                                    "Content suggests {:?} but extension suggests {:?}",
+                                    content_hint.label, extension_hint.label
+                                )),
+                            )
+                            .with_severity(severity),
+                        );
                     }
                 }
             }
@@ -239,6 +458,49 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_7z_and_rar_magic_recognized() {
+        let sz = b"7z\xBC\xAF\x27\x1C\x00\x04";
+        let hint = ContentSniffer::sniff_bytes(sz).expect("7z hint");
+        assert_eq!(hint.label.as_deref(), Some("7z"));
+        assert_eq!(hint.source, SnifferSource::Other);
+
+        let rar4 = b"Rar!\x1a\x07\x00\xcf\x90\x73\x00";
+        let hint = ContentSniffer::sniff_bytes(rar4).expect("rar hint");
+        assert_eq!(hint.label.as_deref(), Some("rar"));
+
+        let rar5 = b"Rar!\x1a\x07\x01\x00\x33\x92\xb5\xe5";
+        let result = CombinedSniffer::sniff(rar5, None);
+        assert_eq!(result.hints[0].label.as_deref(), Some("rar5"));
+        assert!(crate::triage::format_detection::is_container_hint(
+            &result.hints[0]
+        ));
+        assert!(result
+            .errors
+            .iter()
+            .any(|e| e.kind == TriageErrorKind::UnsupportedVariant));
+    }
+
+    #[test]
+    fn test_cafebabe_fat_vs_java_class() {
+        let fat = [0xCA, 0xFE, 0xBA, 0xBE, 0, 0, 0, 2, 0x01, 0, 0, 0x07];
+        let hint = ContentSniffer::sniff_bytes(&fat).expect("fat hint");
+        assert_eq!(hint.label.as_deref(), Some("macho"));
+        assert_eq!(hint.source, SnifferSource::Other);
+        assert_eq!(
+            crate::triage::format_detection::derive_format_from_hint(&hint),
+            Some(crate::core::binary::Format::MachO)
+        );
+
+        let class = [0xCA, 0xFE, 0xBA, 0xBE, 0, 0, 0, 61, 0, 0x1d];
+        let hint = ContentSniffer::sniff_bytes(&class).expect("class hint");
+        assert_eq!(hint.label.as_deref(), Some("class"));
+        assert_eq!(
+            crate::triage::format_detection::derive_format_from_hint(&hint),
+            None
+        );
+    }
+
     #[test]
     fn test_extension_sniffer() {
         let path = PathBuf::from("test.exe");
@@ -249,6 +511,75 @@ mod tests {
         assert!(hint.extension.as_ref().unwrap() == "exe");
     }
 
+    #[test]
+    fn test_extension_overrides_take_precedence() {
+        let mut cfg = SnifferConfig::default();
+        cfg.extension_overrides
+            .insert("SYS".to_string(), "pe".to_string());
+        cfg.extension_overrides
+            .insert("bin".to_string(), "firmware".to_string());
+
+        let hint = ExtensionSniffer::sniff_path_with_config(Path::new("drv.sys"), &cfg)
+            .expect("override hint");
+        assert_eq!(hint.source, SnifferSource::Other);
+        assert_eq!(hint.label.as_deref(), Some("pe"));
+        assert_eq!(hint.extension.as_deref(), Some("sys"));
+
+        let result =
+            CombinedSniffer::sniff_with_config(b"\x00\x01", Some(Path::new("fw.bin")), &cfg);
+        assert!(result
+            .hints
+            .iter()
+            .any(|h| h.label.as_deref() == Some("firmware")));
+
+        // Extensions without an override still go through mime_guess
+        let hint = ExtensionSniffer::sniff_path_with_config(Path::new("a.exe"), &cfg).unwrap();
+        assert_eq!(hint.source, SnifferSource::MimeGuess);
+    }
+
+    #[test]
+    fn test_custom_sniffers_contribute_hints() {
+        let mut cfg = SnifferConfig::default();
+        cfg.custom_sniffers
+            .register(MagicSniffer::new("acme-pkg", *b"ACPK"))
+            .register(MagicSniffer::new("acme-fw", *b"FW").at_offset(4));
+
+        let result = CombinedSniffer::sniff_with_config(b"ACPK\x01\x00", None, &cfg);
+        assert_eq!(result.hints.len(), 1);
+        assert_eq!(result.hints[0].source, SnifferSource::Other);
+        assert_eq!(result.hints[0].label.as_deref(), Some("acme-pkg"));
+
+        let result = CombinedSniffer::sniff_with_config(b"\0\0\0\0FW", None, &cfg);
+        assert_eq!(result.hints[0].label.as_deref(), Some("acme-fw"));
+        assert!(cfg.custom_sniffers.sniff(b"FW").is_empty());
+        assert_eq!(
+            format!("{:?}", cfg.custom_sniffers),
+            r#"["acme-pkg", "acme-fw"]"#
+        );
+    }
+
+    #[test]
+    fn test_conflict_severity_is_configurable() {
+        let elf_data = b"\x7fELF\x02\x01\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x02\x00\x3e\x00";
+        let path = PathBuf::from("renamed.zip");
+        let result = CombinedSniffer::sniff(elf_data, Some(&path));
+        assert!(result
+            .errors
+            .iter()
+            .all(|e| e.severity == Severity::Warning));
+
+        let cfg = SnifferConfig {
+            mismatch_severity: Severity::Error,
+            ..Default::default()
+        };
+        let result = CombinedSniffer::sniff_with_config(elf_data, Some(&path), &cfg);
+        assert!(result
+            .errors
+            .iter()
+            .filter(|e| e.kind == TriageErrorKind::SnifferMismatch)
+            .all(|e| e.severity == Severity::Error));
+    }
+
     #[test]
     fn test_combined_sniffer_no_conflict() {
         let elf_data = b"\x7fELF\x01\x01\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00";
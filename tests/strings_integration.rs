@@ -77,6 +77,7 @@ fn strings_extract_summary_on_sample_binaries() {
         max_classify: 64,
         max_ioc_per_string: 8,
         max_ioc_samples: 32,
+        ..StringsConfig::default()
     };
 
     for path in files {
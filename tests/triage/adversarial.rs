@@ -2,7 +2,7 @@ use std::fs;
 use std::path::Path;
 
 use glaurung::core::binary::Format;
-use glaurung::core::triage::{Budgets, TriageErrorKind};
+use glaurung::core::triage::{Budgets, Severity, TriageErrorKind};
 use glaurung::triage::api::compute_sniffer_header_mismatches;
 use glaurung::triage::containers::detect_containers;
 use glaurung::triage::headers;
@@ -47,7 +47,8 @@ fn adversarial_zip_masquerade_exe_is_exempt_from_mismatch() {
     let header_formats: Vec<Format> = hdr.candidates.iter().map(|v| v.format).collect();
     let containers = detect_containers(&d);
     let labels: Vec<String> = containers.iter().map(|c| c.type_name.clone()).collect();
-    let errs = compute_sniffer_header_mismatches(&sn.hints, &header_formats, &labels);
+    let errs =
+        compute_sniffer_header_mismatches(&sn.hints, &header_formats, &labels, Severity::Warning);
     assert!(errs.is_empty());
 }
 